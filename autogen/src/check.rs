@@ -0,0 +1,142 @@
+// Copyright (c) 2026 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Checks the structure type constants and the function pointers of vk-sys against the
+//! registry.
+
+use std::collections::HashMap;
+
+use crate::extensions::DEVICE_EXTENSIONS;
+use crate::extensions::INSTANCE_EXTENSIONS;
+use crate::registry::Registry;
+use crate::vk_sys;
+
+/// Compares the source code of vk-sys with the registry, and returns a description of each
+/// problem.
+///
+/// For each extension exposed by vulkano, the `STRUCTURE_TYPE_*` constants must exist and have
+/// the right value, and the commands must be part of a function pointers table with the right
+/// signature. The code to use in vk-sys is part of the description of the problem.
+pub fn check_vk_sys(registry: &Registry, source: &str) -> Vec<String> {
+    let mut constants: HashMap<String, Option<i64>> = HashMap::new();
+    let mut functions = HashMap::new();
+
+    for line in source.lines().map(|l| l.trim()) {
+        if let Some(rest) = line.strip_prefix("pub const STRUCTURE_TYPE_") {
+            let (name, value) = match rest.split_once(':') {
+                Some((name, ty_and_value)) => (name, ty_and_value.split_once('=')),
+                None => continue,
+            };
+            let value = value.and_then(|(_, v)| v.trim().trim_end_matches(';').parse().ok());
+            constants.insert(format!("VK_STRUCTURE_TYPE_{}", name.trim()), value);
+        } else if let Some(pos) = line.find(" => (") {
+            functions.insert(line[..pos].to_owned(), line.to_owned());
+        }
+    }
+
+    let mut problems = Vec::new();
+
+    for name in INSTANCE_EXTENSIONS.iter().chain(DEVICE_EXTENSIONS.iter()) {
+        let ext = match registry.extension(name) {
+            Some(ext) => ext,
+            None => {
+                problems.push(format!("{} isn't in the registry", name));
+                continue;
+            }
+        };
+
+        for stype in ext.structure_types.iter() {
+            let expected = registry.structure_types.get(stype).cloned();
+            let fix = match expected {
+                Some(value) => format!(", it should be `{}`", vk_sys::structure_type(stype, value)),
+                None => String::new(),
+            };
+            match constants.get(stype) {
+                None => problems.push(format!(
+                    "{}: {} is missing{}",
+                    name,
+                    stype.trim_start_matches("VK_"),
+                    fix
+                )),
+                Some(value) if *value != expected => problems.push(format!(
+                    "{}: {} is {:?} instead of {:?}{}",
+                    name,
+                    stype.trim_start_matches("VK_"),
+                    value,
+                    expected,
+                    fix
+                )),
+                Some(_) => (),
+            }
+        }
+
+        for command in ext.commands.iter() {
+            let code = vk_sys::function_pointer(registry, command);
+            match (functions.get(command.trim_start_matches("vk")), code) {
+                (Some(line), Some(code)) if *line != code => problems.push(format!(
+                    "{}: {} is `{}` instead of `{}`",
+                    name, command, line, code
+                )),
+                (Some(_), _) => (),
+                (None, Some(code)) => problems.push(format!(
+                    "{}: {} is missing, add `{}` to `{}`",
+                    name,
+                    command,
+                    code,
+                    vk_sys::table(registry, command).unwrap()
+                )),
+                (None, None) => problems.push(format!("{}: {} is missing", name, command)),
+            }
+        }
+    }
+
+    problems
+}
+
+#[cfg(test)]
+mod tests {
+    use super::check_vk_sys;
+    use crate::registry::tests::registry;
+
+    #[test]
+    fn problems() {
+        let source = "
+            pub const STRUCTURE_TYPE_SWAPCHAIN_CREATE_INFO_KHR: u32 = 1000001001;
+            ptrs!(DevicePointers, {
+                CreateSwapchainKHR => (device: Device, pCreateInfo: *const SwapchainCreateInfoKHR, pAllocator: *const AllocationCallbacks, pSwapchain: *mut SwapchainKHR) -> Result,
+            });
+        ";
+
+        let problems = check_vk_sys(&registry(), source);
+        assert!(problems.iter().any(|p| p.contains("SWAPCHAIN_CREATE_INFO_KHR is Some(1000001001)")));
+        assert!(problems.iter().any(|p| p.contains(
+            "it should be `pub const STRUCTURE_TYPE_SWAPCHAIN_CREATE_INFO_KHR: u32 = 1000001000;`"
+        )));
+        assert!(problems.iter().any(|p| p.contains(
+            "vkDestroySurfaceKHR is missing, add `DestroySurfaceKHR => (instance: Instance, \
+             surface: SurfaceKHR, pAllocator: *const AllocationCallbacks) -> (),` to \
+             `InstancePointers`"
+        )));
+        assert!(!problems.iter().any(|p| p.contains("vkCreateSwapchainKHR")));
+    }
+
+    #[test]
+    fn wrong_signature() {
+        let source = "
+            ptrs!(DevicePointers, {
+                CreateSwapchainKHR => (device: Device) -> Result,
+            });
+        ";
+
+        let problems = check_vk_sys(&registry(), source);
+        assert!(problems.iter().any(|p| p.contains(
+            "vkCreateSwapchainKHR is `CreateSwapchainKHR => (device: Device) -> Result,` instead of"
+        )));
+    }
+}
@@ -0,0 +1,225 @@
+// Copyright (c) 2026 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Generates the invocations of `instance_extensions!` and `device_extensions!`.
+
+use std::fmt::Write;
+
+use crate::registry::ExtensionType;
+use crate::registry::Registry;
+
+/// The instance extensions that are exposed by `InstanceExtensions`, in order.
+pub const INSTANCE_EXTENSIONS: &[&str] = &[
+    "VK_KHR_surface",
+    "VK_KHR_display",
+    "VK_KHR_xlib_surface",
+    "VK_KHR_xcb_surface",
+    "VK_KHR_wayland_surface",
+    "VK_KHR_android_surface",
+    "VK_KHR_win32_surface",
+    "VK_EXT_debug_utils",
+    "VK_MVK_ios_surface",
+    "VK_MVK_macos_surface",
+    "VK_MVK_moltenvk",
+    "VK_NN_vi_surface",
+    "VK_EXT_swapchain_colorspace",
+    "VK_KHR_get_physical_device_properties2",
+    "VK_KHR_get_surface_capabilities2",
+    "VK_EXT_headless_surface",
+    "VK_KHR_external_memory_capabilities",
+];
+
+/// The device extensions that are exposed by `DeviceExtensions`, in order.
+pub const DEVICE_EXTENSIONS: &[&str] = &[
+    "VK_KHR_swapchain",
+    "VK_KHR_display_swapchain",
+    "VK_KHR_sampler_mirror_clamp_to_edge",
+    "VK_KHR_maintenance1",
+    "VK_KHR_get_memory_requirements2",
+    "VK_KHR_dedicated_allocation",
+    "VK_KHR_incremental_present",
+    "VK_KHR_16bit_storage",
+    "VK_KHR_storage_buffer_storage_class",
+    "VK_KHR_multiview",
+    "VK_KHR_maintenance2",
+    "VK_KHR_create_renderpass2",
+    "VK_KHR_depth_stencil_resolve",
+    "VK_EXT_full_screen_exclusive",
+    "VK_EXT_extended_dynamic_state",
+    "VK_KHR_maintenance3",
+    "VK_EXT_descriptor_indexing",
+    "VK_KHR_descriptor_update_template",
+    "VK_KHR_push_descriptor",
+    "VK_KHR_timeline_semaphore",
+    "VK_KHR_buffer_device_address",
+    "VK_EXT_scalar_block_layout",
+    "VK_KHR_driver_properties",
+    "VK_AMD_negative_viewport_height",
+];
+
+/// Pairs of device extensions that can't be enabled at the same time.
+///
+/// The registry doesn't describe these, they come from the valid usage of `VkDeviceCreateInfo`.
+/// An extension also conflicts with the version of Vulkan that the other extension was promoted
+/// to.
+pub const CONFLICTS: &[(&str, &str)] = &[
+    ("VK_AMD_negative_viewport_height", "VK_KHR_maintenance1"),
+];
+
+/// Returns the name of the member of `InstanceExtensions` or `DeviceExtensions` that
+/// corresponds to an extension, for example `khr_swapchain` for `VK_KHR_swapchain`.
+pub fn member_name(extension: &str) -> String {
+    extension.trim_start_matches("VK_").to_lowercase()
+}
+
+/// Returns the expression that vulkano uses for the version of Vulkan named `name` in the
+/// registry, or `None` if vulkano doesn't know about it.
+pub fn version_expr(name: Option<&str>) -> &'static str {
+    match name {
+        Some("VK_VERSION_1_1") => "Some(Version::V1_1)",
+        Some("VK_VERSION_1_2") => "Some(Version::V1_2)",
+        _ => "None",
+    }
+}
+
+/// Generates the invocation of `instance_extensions!` or `device_extensions!`.
+pub fn generate(registry: &Registry, ty: ExtensionType) -> Result<String, String> {
+    generate_with(registry, ty, INSTANCE_EXTENSIONS, DEVICE_EXTENSIONS)
+}
+
+// Same as `generate`, but with the given lists of exposed extensions.
+fn generate_with(
+    registry: &Registry,
+    ty: ExtensionType,
+    instance_extensions: &[&str],
+    device_extensions: &[&str],
+) -> Result<String, String> {
+    let (list, macro_name, sname, rawname) = match ty {
+        ExtensionType::Instance => (
+            instance_extensions,
+            "instance_extensions",
+            "InstanceExtensions",
+            "RawInstanceExtensions",
+        ),
+        ExtensionType::Device => (
+            device_extensions,
+            "device_extensions",
+            "DeviceExtensions",
+            "RawDeviceExtensions",
+        ),
+    };
+
+    let mut out = String::new();
+    writeln!(out, "{}! {{", macro_name).unwrap();
+    writeln!(out, "    {},", sname).unwrap();
+    writeln!(out, "    {},", rawname).unwrap();
+
+    for &name in list {
+        let ext = registry
+            .extension(name)
+            .ok_or_else(|| format!("{} isn't in the registry", name))?;
+        if ext.ty != Some(ty) {
+            return Err(format!("{} isn't a {:?} extension", name, ty));
+        }
+
+        let mut requires = Vec::new();
+        let mut requires_instance = Vec::new();
+        for required in ext.requires.iter() {
+            let required_ty = registry.extension(required).and_then(|e| e.ty);
+            let exposed = match required_ty {
+                Some(ExtensionType::Instance) => instance_extensions.contains(&required.as_str()),
+                Some(ExtensionType::Device) => device_extensions.contains(&required.as_str()),
+                None => false,
+            };
+            if !exposed {
+                return Err(format!(
+                    "{} requires {}, which isn't exposed by vulkano",
+                    name, required
+                ));
+            }
+
+            if required_ty == Some(ty) {
+                requires.push(member_name(required));
+            } else {
+                requires_instance.push(member_name(required));
+            }
+        }
+
+        let conflicts: Vec<_> = CONFLICTS
+            .iter()
+            .filter_map(|&(a, b)| match (a == name, b == name) {
+                (true, _) => Some(b),
+                (_, true) => Some(a),
+                _ => None,
+            })
+            .filter(|other| list.contains(other))
+            .map(member_name)
+            .collect();
+
+        writeln!(out, "    {} => b\"{}\" {{", member_name(name), name).unwrap();
+        let promoted_to = version_expr(ext.promoted_to.as_deref());
+        writeln!(out, "        promoted_to: {},", promoted_to).unwrap();
+        writeln!(out, "        requires: [{}],", requires.join(", ")).unwrap();
+        if ty == ExtensionType::Device {
+            writeln!(out, "        requires_instance: [{}],", requires_instance.join(", "))
+                .unwrap();
+            writeln!(out, "        conflicts: [{}],", conflicts.join(", ")).unwrap();
+        }
+        writeln!(out, "    }},").unwrap();
+    }
+
+    writeln!(out, "}}").unwrap();
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::generate_with;
+    use super::member_name;
+    use super::version_expr;
+    use crate::registry::tests::registry;
+    use crate::registry::ExtensionType;
+
+    #[test]
+    fn names() {
+        assert_eq!(member_name("VK_KHR_16bit_storage"), "khr_16bit_storage");
+        assert_eq!(version_expr(Some("VK_VERSION_1_1")), "Some(Version::V1_1)");
+        assert_eq!(version_expr(Some("VK_KHR_maintenance1")), "None");
+    }
+
+    #[test]
+    fn requirements() {
+        let registry = registry();
+        let instance = &["VK_KHR_surface", "VK_KHR_get_physical_device_properties2"];
+        let device = &["VK_KHR_swapchain", "VK_KHR_multiview"];
+
+        let out = generate_with(&registry, ExtensionType::Device, instance, device).unwrap();
+        assert!(out.contains("    khr_swapchain => b\"VK_KHR_swapchain\" {\n        \
+                              promoted_to: None,\n        \
+                              requires: [],\n        \
+                              requires_instance: [khr_surface],\n        \
+                              conflicts: [],\n    },"));
+        assert!(out.contains("promoted_to: Some(Version::V1_1),"));
+
+        // Conflicts are only listed if both extensions are exposed.
+        let conflicting = &["VK_KHR_maintenance1", "VK_AMD_negative_viewport_height"];
+        let out = generate_with(&registry, ExtensionType::Device, instance, conflicting).unwrap();
+        assert!(out.contains("        requires_instance: [],\n        \
+                              conflicts: [amd_negative_viewport_height],\n"));
+        assert!(out.contains("conflicts: [khr_maintenance1],"));
+        let out = generate_with(&registry, ExtensionType::Device, instance, &conflicting[..1])
+            .unwrap();
+        assert!(out.contains("conflicts: [],"));
+
+        // Extensions can't require extensions that aren't exposed.
+        assert!(generate_with(&registry, ExtensionType::Device, &[], device).is_err());
+        // Instance extensions can't be listed as device extensions.
+        assert!(generate_with(&registry, ExtensionType::Device, instance, instance).is_err());
+    }
+}
@@ -0,0 +1,189 @@
+// Copyright (c) 2026 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Generates the invocation of `features!`.
+
+use std::fmt::Write;
+
+use crate::extensions::version_expr;
+use crate::extensions::DEVICE_EXTENSIONS;
+use crate::registry::Registry;
+
+// Members of `Features` whose name isn't the snake case version of the Vulkan name.
+const MEMBER_NAMES: &[(&str, &str)] = &[
+    ("shaderFloat64", "shader_f3264"),
+    ("sparseResidency2Samples", "sparse_residency2_samples"),
+    ("sparseResidency4Samples", "sparse_residency4_samples"),
+    ("sparseResidency8Samples", "sparse_residency8_samples"),
+    ("sparseResidency16Samples", "sparse_residency16_samples"),
+    ("storageBuffer16BitAccess", "storage_buffer_16bit_access"),
+    ("uniformAndStorageBuffer16BitAccess", "uniform_and_storage_buffer_16bit_access"),
+    ("storagePushConstant16", "storage_push_constant_16"),
+    ("storageInputOutput16", "storage_input_output_16"),
+];
+
+// Fields of the vk-sys structs whose name isn't the Vulkan name.
+const VK_SYS_MEMBER_NAMES: &[(&str, &str)] = &[("shaderFloat64", "shaderf3264")];
+
+// Groups of chained features whose name isn't derived from the name of the struct.
+const GROUP_NAMES: &[(&str, &str)] = &[("16BitStorage", "storage_16bit")];
+
+/// Converts a Vulkan name such as `sparseResidencyImage2D` to snake case.
+pub fn snake_case(name: &str) -> String {
+    let mut out = String::with_capacity(name.len() + 8);
+    let mut previous_lowercase = false;
+    for c in name.chars() {
+        if c.is_ascii_uppercase() && previous_lowercase {
+            out.push('_');
+        }
+        previous_lowercase = c.is_ascii_lowercase();
+        out.push(c.to_ascii_lowercase());
+    }
+    out
+}
+
+fn member_name(vk_name: &str) -> String {
+    MEMBER_NAMES
+        .iter()
+        .find(|(vk, _)| *vk == vk_name)
+        .map(|(_, name)| (*name).to_owned())
+        .unwrap_or_else(|| snake_case(vk_name))
+}
+
+fn vk_sys_member_name(vk_name: &str) -> &str {
+    VK_SYS_MEMBER_NAMES
+        .iter()
+        .find(|(vk, _)| *vk == vk_name)
+        .map(|(_, name)| *name)
+        .unwrap_or(vk_name)
+}
+
+// `VkPhysicalDevice16BitStorageFeaturesKHR` becomes `storage_16bit`, and
+// `VkPhysicalDeviceMultiviewFeaturesKHR` becomes `multiview`.
+fn group_name(struct_name: &str) -> String {
+    let base = struct_name
+        .trim_start_matches("VkPhysicalDevice")
+        .trim_end_matches(|c: char| c.is_ascii_uppercase())
+        .trim_end_matches("Features");
+
+    GROUP_NAMES
+        .iter()
+        .find(|(n, _)| *n == base)
+        .map(|(_, name)| (*name).to_owned())
+        .unwrap_or_else(|| snake_case(base))
+}
+
+/// Generates the invocation of `features!`.
+pub fn generate(registry: &Registry) -> Result<String, String> {
+    generate_with(registry, DEVICE_EXTENSIONS)
+}
+
+// Same as `generate`, but with the given list of exposed device extensions.
+fn generate_with(registry: &Registry, device_extensions: &[&str]) -> Result<String, String> {
+    let core = registry
+        .structs
+        .get("VkPhysicalDeviceFeatures")
+        .ok_or("VkPhysicalDeviceFeatures isn't in the registry")?;
+
+    let mut out = String::new();
+    writeln!(out, "features! {{").unwrap();
+    writeln!(out, "    core {{").unwrap();
+    for member in core.members.iter() {
+        writeln!(
+            out,
+            "        {} => {},",
+            member_name(&member.name),
+            vk_sys_member_name(&member.name)
+        )
+        .unwrap();
+    }
+    writeln!(out, "    }},").unwrap();
+
+    writeln!(out, "    chained {{").unwrap();
+    for &ext_name in device_extensions {
+        let ext = registry
+            .extension(ext_name)
+            .ok_or_else(|| format!("{} isn't in the registry", ext_name))?;
+
+        for ty in ext.types.iter() {
+            let s = match registry.resolved_struct(ty) {
+                Some(s) if s.extends.iter().any(|e| e == "VkPhysicalDeviceFeatures2") => s,
+                _ => continue,
+            };
+
+            // The structure type whose name matches the name of the struct, such as
+            // `VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_16BIT_STORAGE_FEATURES_KHR` for
+            // `VkPhysicalDevice16BitStorageFeaturesKHR`.
+            let normalized = ty.trim_start_matches("Vk").to_uppercase();
+            let stype = ext
+                .structure_types
+                .iter()
+                .find(|st| {
+                    st.trim_start_matches("VK_STRUCTURE_TYPE_").replace('_', "") == normalized
+                })
+                .ok_or_else(|| format!("no structure type found for {}", ty))?;
+
+            writeln!(
+                out,
+                "        {} => {} [{}, b\"{}\", {}] {{",
+                group_name(ty),
+                ty.trim_start_matches("Vk"),
+                stype.trim_start_matches("VK_"),
+                ext_name,
+                version_expr(ext.promoted_to.as_deref()),
+            )
+            .unwrap();
+            for member in s.members.iter().filter(|m| m.ty == "VkBool32") {
+                writeln!(
+                    out,
+                    "            {} => {},",
+                    member_name(&member.name),
+                    vk_sys_member_name(&member.name)
+                )
+                .unwrap();
+            }
+            writeln!(out, "        }},").unwrap();
+        }
+    }
+    writeln!(out, "    }},").unwrap();
+    writeln!(out, "}}").unwrap();
+
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::generate_with;
+    use super::group_name;
+    use super::member_name;
+    use super::snake_case;
+    use crate::registry::tests::registry;
+
+    #[test]
+    fn names() {
+        assert_eq!(snake_case("robustBufferAccess"), "robust_buffer_access");
+        assert_eq!(snake_case("textureCompressionASTC_LDR"), "texture_compression_astc_ldr");
+        assert_eq!(snake_case("sparseResidencyImage2D"), "sparse_residency_image2d");
+        assert_eq!(member_name("storagePushConstant16"), "storage_push_constant_16");
+        assert_eq!(group_name("VkPhysicalDevice16BitStorageFeaturesKHR"), "storage_16bit");
+        assert_eq!(group_name("VkPhysicalDeviceDescriptorIndexingFeaturesEXT"),
+                   "descriptor_indexing");
+    }
+
+    #[test]
+    fn chained_structs() {
+        let out = generate_with(&registry(), &["VK_KHR_swapchain", "VK_KHR_multiview"]).unwrap();
+        assert!(out.contains("        shader_f3264 => shaderf3264,\n"));
+        assert!(out.contains("        multiview => PhysicalDeviceMultiviewFeaturesKHR \
+                              [STRUCTURE_TYPE_PHYSICAL_DEVICE_MULTIVIEW_FEATURES_KHR, \
+                              b\"VK_KHR_multiview\", Some(Version::V1_1)] {\n            \
+                              multiview => multiview,\n            \
+                              multiview_geometry_shader => multiviewGeometryShader,\n        },"));
+    }
+}
@@ -0,0 +1,119 @@
+// Copyright (c) 2026 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Generates parts of vulkano from the Vulkan registry.
+//!
+//! Usage: `cargo run -p autogen -- path/to/vk.xml`
+//!
+//! The registry can be found in the `Vulkan-Headers` repository, under `registry/vk.xml`. The
+//! following files are generated:
+//!
+//! - `vulkano/src/autogen/instance_extensions.rs` and
+//!   `vulkano/src/autogen/device_extensions.rs` contain the members of `InstanceExtensions` and
+//!   `DeviceExtensions`, along with the extensions that each of them requires and the version of
+//!   Vulkan it was promoted to.
+//! - `vulkano/src/autogen/features.rs` contains the members of `Features`, which are those of
+//!   `VkPhysicalDeviceFeatures` and of the structs that the device extensions chain to
+//!   `VkPhysicalDeviceFeatures2`.
+//!
+//! Only the extensions listed in `extensions::INSTANCE_EXTENSIONS` and
+//! `extensions::DEVICE_EXTENSIONS` are exposed, since the rest of vulkano must know about an
+//! extension in order to use it. To add an extension, add it to one of these lists and run the
+//! generator again.
+//!
+//! The generator also produces the structure type constants and the entries of the function
+//! pointer tables of vk-sys for these extensions. vk-sys itself is still maintained by hand,
+//! since these tables also contain the core functions, which aren't generated yet. Its source
+//! code is compared with the generated code, and the generator prints each constant or function
+//! pointer that is missing or wrong along with the code to use instead, then exits with a
+//! non-zero status so that a stale vk-sys fails CI.
+
+use std::env;
+use std::fs;
+use std::fs::File;
+use std::path::Path;
+use std::process;
+
+mod check;
+mod extensions;
+mod features;
+mod registry;
+mod vk_sys;
+
+use crate::registry::ExtensionType;
+use crate::registry::Registry;
+
+const HEADER: &str = "\
+// Copyright (c) 2026 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+// This file is generated from the Vulkan registry by the `autogen` crate. Don't edit it by hand.
+
+";
+
+fn main() {
+    let vk_xml = match env::args().nth(1) {
+        Some(path) => path,
+        None => {
+            eprintln!("Usage: autogen <path to vk.xml>");
+            process::exit(1);
+        }
+    };
+
+    let file = File::open(&vk_xml).unwrap_or_else(|err| {
+        eprintln!("Failed to open {}: {}", vk_xml, err);
+        process::exit(1);
+    });
+    let root = registry::parse_xml(file).unwrap_or_else(|err| {
+        eprintln!("Failed to parse {}: {}", vk_xml, err);
+        process::exit(1);
+    });
+    let registry = Registry::from_xml(&root);
+
+    let root_dir = Path::new(env!("CARGO_MANIFEST_DIR")).join("..");
+    let out_dir = root_dir.join("vulkano/src/autogen");
+
+    let outputs = vec![
+        (
+            "instance_extensions.rs",
+            extensions::generate(&registry, ExtensionType::Instance),
+        ),
+        (
+            "device_extensions.rs",
+            extensions::generate(&registry, ExtensionType::Device),
+        ),
+        ("features.rs", features::generate(&registry)),
+    ];
+
+    for (file_name, output) in outputs {
+        let output = output.unwrap_or_else(|err| {
+            eprintln!("Failed to generate {}: {}", file_name, err);
+            process::exit(1);
+        });
+        fs::write(out_dir.join(file_name), format!("{}{}", HEADER, output))
+            .expect("failed to write the generated file");
+    }
+
+    let vk_sys = fs::read_to_string(root_dir.join("vk-sys/src/lib.rs"))
+        .expect("failed to read the source code of vk-sys");
+    let problems = check::check_vk_sys(&registry, &vk_sys);
+    if !problems.is_empty() {
+        eprintln!("vk-sys doesn't match the registry:");
+        for problem in problems {
+            eprintln!("    {}", problem);
+        }
+        process::exit(1);
+    }
+}
@@ -0,0 +1,622 @@
+// Copyright (c) 2026 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Loads the parts of `vk.xml` that the generators need.
+
+use std::collections::HashMap;
+use std::io::Read;
+
+use xml::reader::EventReader;
+use xml::reader::XmlEvent;
+
+/// An element of an XML document.
+#[derive(Debug, Clone, Default)]
+pub struct Element {
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<Element>,
+    /// Text directly contained by the element.
+    pub text: String,
+}
+
+impl Element {
+    /// Returns the value of an attribute.
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Returns the children with the given name.
+    pub fn children<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> + 'a {
+        self.children.iter().filter(move |c| c.name == name)
+    }
+
+    /// Returns the text of the first child with the given name.
+    pub fn child_text(&self, name: &str) -> Option<&str> {
+        self.children
+            .iter()
+            .find(|c| c.name == name)
+            .map(|c| c.text.as_str())
+    }
+
+    // Elements that only apply to Vulkan SC are ignored.
+    fn is_vulkan(&self) -> bool {
+        match self.attribute("api") {
+            Some(api) => api.split(',').any(|api| api == "vulkan"),
+            None => true,
+        }
+    }
+}
+
+/// Parses an XML document and returns its root element.
+pub fn parse_xml<R: Read>(source: R) -> Result<Element, xml::reader::Error> {
+    let mut stack = vec![Element::default()];
+
+    for event in EventReader::new(source) {
+        match event? {
+            XmlEvent::StartElement { name, attributes, .. } => {
+                stack.push(Element {
+                    name: name.local_name,
+                    attributes: attributes
+                        .into_iter()
+                        .map(|a| (a.name.local_name, a.value))
+                        .collect(),
+                    children: Vec::new(),
+                    text: String::new(),
+                });
+            }
+            XmlEvent::EndElement { .. } => {
+                let element = stack.pop().unwrap();
+                stack.last_mut().unwrap().children.push(element);
+            }
+            XmlEvent::Characters(text) | XmlEvent::CData(text) => {
+                stack.last_mut().unwrap().text.push_str(&text);
+            }
+            _ => (),
+        }
+    }
+
+    let document = stack.pop().unwrap();
+    Ok(document.children.into_iter().next().unwrap_or_default())
+}
+
+/// Whether an extension is an instance or a device extension.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ExtensionType {
+    Instance,
+    Device,
+}
+
+/// An extension of the registry.
+#[derive(Debug, Clone)]
+pub struct Extension {
+    pub name: String,
+    pub ty: Option<ExtensionType>,
+    /// Extensions that must be enabled in order to enable this one.
+    pub requires: Vec<String>,
+    /// Name of the version of Vulkan or of the extension that this extension was promoted to,
+    /// such as `VK_VERSION_1_1`.
+    pub promoted_to: Option<String>,
+    /// Types added by the extension.
+    pub types: Vec<String>,
+    /// Commands added by the extension.
+    pub commands: Vec<String>,
+    /// Values of `VkStructureType` added by the extension.
+    pub structure_types: Vec<String>,
+}
+
+/// A struct of the registry.
+#[derive(Debug, Clone)]
+pub struct Struct {
+    /// Name of the struct that this one is an alias of.
+    pub alias: Option<String>,
+    /// Structs whose `pNext` chain this struct can be part of.
+    pub extends: Vec<String>,
+    pub members: Vec<Member>,
+}
+
+/// A member of a struct.
+#[derive(Debug, Clone)]
+pub struct Member {
+    pub name: String,
+    pub ty: String,
+}
+
+/// A command of the registry.
+#[derive(Debug, Clone)]
+pub struct Command {
+    /// Name of the command that this one is an alias of.
+    pub alias: Option<String>,
+    /// Return type, such as `VkResult` or `void`.
+    pub ret: String,
+    pub params: Vec<Param>,
+}
+
+/// A parameter of a command.
+#[derive(Debug, Clone)]
+pub struct Param {
+    pub name: String,
+    pub ty: String,
+    /// Text around the type and the name, such as `const *` or `[4]`, in order.
+    pub decoration: String,
+}
+
+/// The parts of the registry that the generators need.
+#[derive(Debug, Clone, Default)]
+pub struct Registry {
+    pub extensions: Vec<Extension>,
+    pub structs: HashMap<String, Struct>,
+    pub commands: HashMap<String, Command>,
+    /// Values of the `VkStructureType` enum, including the aliases.
+    pub structure_types: HashMap<String, i64>,
+}
+
+// Value of an enum, before aliases are resolved.
+enum EnumValue {
+    Value(i64),
+    Alias(String),
+}
+
+impl Registry {
+    /// Builds the registry from the root element of `vk.xml`.
+    pub fn from_xml(root: &Element) -> Registry {
+        let mut registry = Registry::default();
+        let mut structure_types = HashMap::new();
+
+        for ty in root.children("types").flat_map(|t| t.children("type")) {
+            if ty.attribute("category") != Some("struct") || !ty.is_vulkan() {
+                continue;
+            }
+
+            let name = match ty.attribute("name") {
+                Some(name) => name.to_owned(),
+                None => continue,
+            };
+
+            let members = ty
+                .children("member")
+                .filter(|m| m.is_vulkan())
+                .filter_map(|m| {
+                    Some(Member {
+                        name: m.child_text("name")?.to_owned(),
+                        ty: m.child_text("type")?.to_owned(),
+                    })
+                })
+                .collect();
+
+            registry.structs.insert(
+                name,
+                Struct {
+                    alias: ty.attribute("alias").map(|a| a.to_owned()),
+                    extends: ty
+                        .attribute("structextends")
+                        .map(|e| e.split(',').map(|s| s.to_owned()).collect())
+                        .unwrap_or_default(),
+                    members,
+                },
+            );
+        }
+
+        for command in root
+            .children("commands")
+            .flat_map(|c| c.children("command"))
+            .filter(|c| c.is_vulkan())
+        {
+            if let (Some(name), Some(alias)) =
+                (command.attribute("name"), command.attribute("alias"))
+            {
+                registry.commands.insert(
+                    name.to_owned(),
+                    Command {
+                        alias: Some(alias.to_owned()),
+                        ret: String::new(),
+                        params: Vec::new(),
+                    },
+                );
+                continue;
+            }
+
+            let proto = match command.children("proto").next() {
+                Some(proto) => proto,
+                None => continue,
+            };
+            let (name, ret) = match (proto.child_text("name"), proto.child_text("type")) {
+                (Some(name), Some(ret)) => (name.to_owned(), ret.to_owned()),
+                _ => continue,
+            };
+
+            let params = command
+                .children("param")
+                .filter(|p| p.is_vulkan())
+                .filter_map(|p| {
+                    Some(Param {
+                        name: p.child_text("name")?.to_owned(),
+                        ty: p.child_text("type")?.to_owned(),
+                        decoration: p.text.clone(),
+                    })
+                })
+                .collect();
+
+            registry.commands.insert(
+                name,
+                Command {
+                    alias: None,
+                    ret,
+                    params,
+                },
+            );
+        }
+
+        for enums in root.children("enums") {
+            if enums.attribute("name") != Some("VkStructureType") {
+                continue;
+            }
+
+            for e in enums.children("enum") {
+                if let (Some(name), Some(value)) = (e.attribute("name"), enum_value(e, None)) {
+                    structure_types.insert(name.to_owned(), value);
+                }
+            }
+        }
+
+        for require in root
+            .children("feature")
+            .filter(|f| f.is_vulkan())
+            .flat_map(|f| f.children("require"))
+        {
+            for e in require.children("enum") {
+                if e.attribute("extends") != Some("VkStructureType") {
+                    continue;
+                }
+                if let (Some(name), Some(value)) = (e.attribute("name"), enum_value(e, None)) {
+                    structure_types.insert(name.to_owned(), value);
+                }
+            }
+        }
+
+        for ext in root.children("extensions").flat_map(|e| e.children("extension")) {
+            let name = match ext.attribute("name") {
+                Some(name) => name.to_owned(),
+                None => continue,
+            };
+            let number = ext
+                .attribute("number")
+                .and_then(|n| n.parse().ok())
+                .unwrap_or(0);
+
+            let requires = if let Some(requires) = ext.attribute("requires") {
+                requires.split(',').map(|s| s.to_owned()).collect()
+            } else if let Some(depends) = ext.attribute("depends") {
+                parse_depends(depends)
+            } else {
+                Vec::new()
+            };
+
+            let mut extension = Extension {
+                name,
+                ty: match ext.attribute("type") {
+                    Some("instance") => Some(ExtensionType::Instance),
+                    Some("device") => Some(ExtensionType::Device),
+                    _ => None,
+                },
+                requires,
+                promoted_to: ext.attribute("promotedto").map(|p| p.to_owned()),
+                types: Vec::new(),
+                commands: Vec::new(),
+                structure_types: Vec::new(),
+            };
+
+            for require in ext.children("require").filter(|r| r.is_vulkan()) {
+                for child in require.children.iter() {
+                    let name = match child.attribute("name") {
+                        Some(name) => name.to_owned(),
+                        None => continue,
+                    };
+
+                    match child.name.as_str() {
+                        "type" => extension.types.push(name),
+                        "command" => extension.commands.push(name),
+                        "enum" if child.attribute("extends") == Some("VkStructureType") => {
+                            if let Some(value) = enum_value(child, Some(number)) {
+                                structure_types.insert(name.clone(), value);
+                            }
+                            extension.structure_types.push(name);
+                        }
+                        _ => (),
+                    }
+                }
+            }
+
+            registry.extensions.push(extension);
+        }
+
+        // Resolving the aliases. Aliases can point to other aliases, so this is repeated until
+        // nothing changes.
+        loop {
+            let mut changed = false;
+            let resolved: Vec<(String, i64)> = structure_types
+                .iter()
+                .filter_map(|(name, value)| match value {
+                    EnumValue::Alias(alias) => match structure_types.get(alias) {
+                        Some(EnumValue::Value(v)) => Some((name.clone(), *v)),
+                        _ => None,
+                    },
+                    EnumValue::Value(_) => None,
+                })
+                .collect();
+            for (name, value) in resolved {
+                structure_types.insert(name, EnumValue::Value(value));
+                changed = true;
+            }
+            if !changed {
+                break;
+            }
+        }
+
+        registry.structure_types = structure_types
+            .into_iter()
+            .filter_map(|(name, value)| match value {
+                EnumValue::Value(v) => Some((name, v)),
+                EnumValue::Alias(_) => None,
+            })
+            .collect();
+
+        registry
+    }
+
+    /// Returns the extension with the given name.
+    pub fn extension(&self, name: &str) -> Option<&Extension> {
+        self.extensions.iter().find(|e| e.name == name)
+    }
+
+    /// Returns the struct with the given name, after following the aliases.
+    pub fn resolved_struct(&self, name: &str) -> Option<&Struct> {
+        let mut s = self.structs.get(name)?;
+        while let Some(ref alias) = s.alias {
+            s = self.structs.get(alias)?;
+        }
+        Some(s)
+    }
+
+    /// Returns the command with the given name, after following the aliases.
+    pub fn resolved_command(&self, name: &str) -> Option<&Command> {
+        let mut c = self.commands.get(name)?;
+        while let Some(ref alias) = c.alias {
+            c = self.commands.get(alias)?;
+        }
+        Some(c)
+    }
+}
+
+// Computes the value of an `<enum>` element. `ext_number` is the number of the extension the
+// element belongs to, if any.
+fn enum_value(e: &Element, ext_number: Option<u32>) -> Option<EnumValue> {
+    if let Some(alias) = e.attribute("alias") {
+        return Some(EnumValue::Alias(alias.to_owned()));
+    }
+
+    if let Some(value) = e.attribute("value") {
+        return value.parse().ok().map(EnumValue::Value);
+    }
+
+    let offset: i64 = e.attribute("offset")?.parse().ok()?;
+    let ext_number: i64 = match e.attribute("extnumber") {
+        Some(n) => n.parse().ok()?,
+        None => ext_number? as i64,
+    };
+    let value = 1_000_000_000 + (ext_number - 1) * 1000 + offset;
+
+    Some(EnumValue::Value(if e.attribute("dir") == Some("-") {
+        -value
+    } else {
+        value
+    }))
+}
+
+// Extracts the extensions required by a `depends` expression, such as
+// `(VK_KHR_get_physical_device_properties2,VK_VERSION_1_1)+VK_KHR_swapchain`.
+//
+// `+` means that both sides are required and `,` that either side is. When there is a choice,
+// the first extension is picked, since an extension that has been promoted to core is also
+// satisfied by the corresponding version of Vulkan. Versions of Vulkan aren't returned.
+fn parse_depends(depends: &str) -> Vec<String> {
+    fn parse_or(chars: &mut std::iter::Peekable<std::str::Chars>) -> Vec<String> {
+        let mut alternatives = vec![parse_and(chars)];
+        while chars.peek() == Some(&',') {
+            chars.next();
+            alternatives.push(parse_and(chars));
+        }
+        alternatives
+            .into_iter()
+            .find(|a| !a.is_empty())
+            .unwrap_or_default()
+    }
+
+    fn parse_and(chars: &mut std::iter::Peekable<std::str::Chars>) -> Vec<String> {
+        let mut all = parse_term(chars);
+        while chars.peek() == Some(&'+') {
+            chars.next();
+            all.extend(parse_term(chars));
+        }
+        all
+    }
+
+    fn parse_term(chars: &mut std::iter::Peekable<std::str::Chars>) -> Vec<String> {
+        if chars.peek() == Some(&'(') {
+            chars.next();
+            let inner = parse_or(chars);
+            if chars.peek() == Some(&')') {
+                chars.next();
+            }
+            return inner;
+        }
+
+        let mut name = String::new();
+        while let Some(&c) = chars.peek() {
+            if c == '+' || c == ',' || c == '(' || c == ')' {
+                break;
+            }
+            name.push(c);
+            chars.next();
+        }
+
+        if name.starts_with("VK_VERSION_") || name.is_empty() {
+            Vec::new()
+        } else {
+            vec![name]
+        }
+    }
+
+    parse_or(&mut depends.chars().peekable())
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::parse_depends;
+    use super::parse_xml;
+    use super::ExtensionType;
+    use super::Registry;
+
+    /// A small subset of `vk.xml`, with the same structure.
+    pub const REGISTRY: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<registry>
+    <types>
+        <type category="struct" name="VkPhysicalDeviceFeatures">
+            <member><type>VkBool32</type> <name>robustBufferAccess</name></member>
+            <member><type>VkBool32</type> <name>shaderFloat64</name></member>
+            <member><type>VkBool32</type> <name>sparseResidencyImage2D</name></member>
+        </type>
+        <type category="struct" name="VkPhysicalDeviceMultiviewFeatures" structextends="VkPhysicalDeviceFeatures2,VkDeviceCreateInfo">
+            <member values="VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_MULTIVIEW_FEATURES"><type>VkStructureType</type> <name>sType</name></member>
+            <member><type>void</type>* <name>pNext</name></member>
+            <member><type>VkBool32</type> <name>multiview</name></member>
+            <member><type>VkBool32</type> <name>multiviewGeometryShader</name></member>
+        </type>
+        <type category="struct" name="VkPhysicalDeviceMultiviewFeaturesKHR" alias="VkPhysicalDeviceMultiviewFeatures"/>
+        <type category="struct" name="VkPhysicalDeviceMultiviewProperties" structextends="VkPhysicalDeviceProperties2">
+            <member><type>uint32_t</type> <name>maxMultiviewViewCount</name></member>
+        </type>
+    </types>
+    <commands>
+        <command>
+            <proto><type>void</type> <name>vkDestroySurfaceKHR</name></proto>
+            <param><type>VkInstance</type> <name>instance</name></param>
+            <param optional="true"><type>VkSurfaceKHR</type> <name>surface</name></param>
+            <param optional="true">const <type>VkAllocationCallbacks</type>* <name>pAllocator</name></param>
+        </command>
+        <command>
+            <proto><type>VkResult</type> <name>vkCreateSwapchainKHR</name></proto>
+            <param><type>VkDevice</type> <name>device</name></param>
+            <param>const <type>VkSwapchainCreateInfoKHR</type>* <name>pCreateInfo</name></param>
+            <param optional="true">const <type>VkAllocationCallbacks</type>* <name>pAllocator</name></param>
+            <param><type>VkSwapchainKHR</type>* <name>pSwapchain</name></param>
+        </command>
+        <command>
+            <proto><type>void</type> <name>vkCmdSetBlendConstants</name></proto>
+            <param><type>VkCommandBuffer</type> <name>commandBuffer</name></param>
+            <param>const <type>float</type> <name>blendConstants</name>[4]</param>
+        </command>
+        <command>
+            <proto><type>VkResult</type> <name>vkMapMemory</name></proto>
+            <param><type>VkDevice</type> <name>device</name></param>
+            <param><type>VkDeviceMemory</type> <name>memory</name></param>
+            <param><type>void</type>** <name>ppData</name></param>
+        </command>
+        <command name="vkMapMemoryKHR" alias="vkMapMemory"/>
+    </commands>
+    <enums name="VkStructureType" type="enum">
+        <enum value="0" name="VK_STRUCTURE_TYPE_APPLICATION_INFO"/>
+    </enums>
+    <feature api="vulkan" name="VK_VERSION_1_1" number="1.1">
+        <require>
+            <enum extends="VkStructureType" extnumber="54" offset="1" name="VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_MULTIVIEW_FEATURES"/>
+        </require>
+    </feature>
+    <extensions>
+        <extension name="VK_KHR_surface" number="1" type="instance" supported="vulkan">
+            <require>
+                <command name="vkDestroySurfaceKHR"/>
+            </require>
+        </extension>
+        <extension name="VK_KHR_swapchain" number="2" type="device" requires="VK_KHR_surface" supported="vulkan">
+            <require>
+                <enum offset="0" extends="VkStructureType" name="VK_STRUCTURE_TYPE_SWAPCHAIN_CREATE_INFO_KHR"/>
+                <command name="vkCreateSwapchainKHR"/>
+            </require>
+        </extension>
+        <extension name="VK_KHR_maintenance1" number="70" type="device" promotedto="VK_VERSION_1_1" supported="vulkan"/>
+        <extension name="VK_AMD_negative_viewport_height" number="36" type="device" supported="vulkan"/>
+        <extension name="VK_KHR_get_physical_device_properties2" number="60" type="instance" promotedto="VK_VERSION_1_1" supported="vulkan"/>
+        <extension name="VK_KHR_multiview" number="54" type="device" depends="VK_KHR_get_physical_device_properties2,VK_VERSION_1_1" promotedto="VK_VERSION_1_1" supported="vulkan">
+            <require>
+                <enum extends="VkStructureType" name="VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_MULTIVIEW_FEATURES_KHR" alias="VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_MULTIVIEW_FEATURES"/>
+                <type name="VkPhysicalDeviceMultiviewFeaturesKHR"/>
+                <type name="VkPhysicalDeviceMultiviewPropertiesKHR"/>
+            </require>
+        </extension>
+    </extensions>
+</registry>
+"#;
+
+    pub fn registry() -> Registry {
+        Registry::from_xml(&parse_xml(REGISTRY.as_bytes()).unwrap())
+    }
+
+    #[test]
+    fn extensions() {
+        let registry = registry();
+
+        let swapchain = registry.extension("VK_KHR_swapchain").unwrap();
+        assert_eq!(swapchain.ty, Some(ExtensionType::Device));
+        assert_eq!(swapchain.requires, vec!["VK_KHR_surface".to_owned()]);
+        assert_eq!(swapchain.commands, vec!["vkCreateSwapchainKHR".to_owned()]);
+
+        let multiview = registry.extension("VK_KHR_multiview").unwrap();
+        assert_eq!(multiview.requires, vec!["VK_KHR_get_physical_device_properties2".to_owned()]);
+        assert_eq!(multiview.promoted_to.as_deref(), Some("VK_VERSION_1_1"));
+    }
+
+    #[test]
+    fn structure_types() {
+        let registry = registry();
+        assert_eq!(registry.structure_types["VK_STRUCTURE_TYPE_SWAPCHAIN_CREATE_INFO_KHR"],
+                   1000001000);
+        assert_eq!(registry.structure_types["VK_STRUCTURE_TYPE_PHYSICAL_DEVICE_MULTIVIEW_FEATURES_KHR"],
+                   1000053001);
+    }
+
+    #[test]
+    fn struct_aliases() {
+        let registry = registry();
+        let s = registry.resolved_struct("VkPhysicalDeviceMultiviewFeaturesKHR").unwrap();
+        assert_eq!(s.members.len(), 4);
+        assert_eq!(s.members[2].name, "multiview");
+    }
+
+    #[test]
+    fn commands() {
+        let registry = registry();
+        let c = registry.resolved_command("vkMapMemoryKHR").unwrap();
+        assert_eq!(c.ret, "VkResult");
+        assert_eq!(c.params.len(), 3);
+        assert_eq!(c.params[2].ty, "void");
+        assert_eq!(c.params[2].decoration.trim(), "**");
+    }
+
+    #[test]
+    fn depends_expressions() {
+        assert_eq!(parse_depends("VK_KHR_a+VK_KHR_b"), vec!["VK_KHR_a", "VK_KHR_b"]);
+        assert_eq!(parse_depends("(VK_KHR_a,VK_VERSION_1_1)+VK_KHR_b"),
+                   vec!["VK_KHR_a", "VK_KHR_b"]);
+        assert_eq!(parse_depends("VK_VERSION_1_1,VK_KHR_a"), vec!["VK_KHR_a"]);
+        assert!(parse_depends("VK_VERSION_1_2").is_empty());
+    }
+}
@@ -0,0 +1,155 @@
+// Copyright (c) 2026 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Generates the code of the structure type constants and of the function pointers of vk-sys.
+
+use crate::registry::Param;
+use crate::registry::Registry;
+
+// C types and the corresponding Rust types.
+const C_TYPES: &[(&str, &str)] = &[
+    ("void", "c_void"),
+    ("char", "c_char"),
+    ("int", "c_int"),
+    ("float", "f32"),
+    ("double", "f64"),
+    ("size_t", "usize"),
+    ("uint8_t", "u8"),
+    ("uint16_t", "u16"),
+    ("uint32_t", "u32"),
+    ("uint64_t", "u64"),
+    ("int32_t", "i32"),
+    ("int64_t", "i64"),
+];
+
+// Parameters whose name is a keyword in Rust.
+const PARAM_NAMES: &[(&str, &str)] = &[("type", "ty")];
+
+/// Returns the declaration of the vk-sys constant of a `VkStructureType` value, such as
+/// `pub const STRUCTURE_TYPE_SWAPCHAIN_CREATE_INFO_KHR: u32 = 1000001000;`.
+pub fn structure_type(name: &str, value: i64) -> String {
+    format!("pub const {}: u32 = {};", name.trim_start_matches("VK_"), value)
+}
+
+/// Returns the name of the vk-sys table that the pointer to a command belongs to.
+///
+/// Commands whose first parameter is a device or one of its children are loaded with
+/// `vkGetDeviceProcAddr`, and the others with `vkGetInstanceProcAddr`.
+pub fn table(registry: &Registry, command: &str) -> Option<&'static str> {
+    let command = registry.resolved_command(command)?;
+    match command.params.first().map(|p| p.ty.as_str()) {
+        Some("VkDevice") | Some("VkQueue") | Some("VkCommandBuffer") => Some("DevicePointers"),
+        _ => Some("InstancePointers"),
+    }
+}
+
+/// Returns the entry of a command in the `ptrs!` table of vk-sys, such as
+/// `CreateSwapchainKHR => (device: Device, ...) -> Result,`.
+pub fn function_pointer(registry: &Registry, command: &str) -> Option<String> {
+    let resolved = registry.resolved_command(command)?;
+    let params: Vec<String> = resolved
+        .params
+        .iter()
+        .map(|p| format!("{}: {}", param_name(&p.name), param_type(p)))
+        .collect();
+
+    let ret = match resolved.ret.as_str() {
+        "void" => "()".to_owned(),
+        ret => rust_type(ret),
+    };
+
+    Some(format!(
+        "{} => ({}) -> {},",
+        command.trim_start_matches("vk"),
+        params.join(", "),
+        ret
+    ))
+}
+
+fn param_name(name: &str) -> &str {
+    PARAM_NAMES
+        .iter()
+        .find(|(vk, _)| *vk == name)
+        .map(|(_, name)| *name)
+        .unwrap_or(name)
+}
+
+fn rust_type(ty: &str) -> String {
+    if let Some((_, rust)) = C_TYPES.iter().find(|(c, _)| *c == ty) {
+        return (*rust).to_owned();
+    }
+    if ty.starts_with("PFN_") {
+        return ty.to_owned();
+    }
+    ty.trim_start_matches("Vk").to_owned()
+}
+
+// Builds the Rust type of a parameter from its type and the `const`, `*` and `[N]` around it.
+// A `const` applies to what the next pointer points to. Arrays are passed as pointers.
+fn param_type(param: &Param) -> String {
+    let mut ty = rust_type(&param.ty);
+    let mut is_const = false;
+
+    let decoration = param.decoration.replace('*', " * ").replace('[', " [");
+    for token in decoration.split_whitespace() {
+        if token == "const" {
+            is_const = true;
+        } else if token == "*" || token.starts_with('[') {
+            ty = format!("*{} {}", if is_const { "const" } else { "mut" }, ty);
+            is_const = false;
+        }
+    }
+
+    ty
+}
+
+#[cfg(test)]
+mod tests {
+    use super::function_pointer;
+    use super::structure_type;
+    use super::table;
+    use crate::registry::tests::registry;
+
+    #[test]
+    fn structure_types() {
+        assert_eq!(
+            structure_type("VK_STRUCTURE_TYPE_SWAPCHAIN_CREATE_INFO_KHR", 1000001000),
+            "pub const STRUCTURE_TYPE_SWAPCHAIN_CREATE_INFO_KHR: u32 = 1000001000;"
+        );
+    }
+
+    #[test]
+    fn function_pointers() {
+        let registry = registry();
+
+        assert_eq!(
+            function_pointer(&registry, "vkDestroySurfaceKHR").unwrap(),
+            "DestroySurfaceKHR => (instance: Instance, surface: SurfaceKHR, \
+             pAllocator: *const AllocationCallbacks) -> (),"
+        );
+        assert_eq!(
+            function_pointer(&registry, "vkCreateSwapchainKHR").unwrap(),
+            "CreateSwapchainKHR => (device: Device, pCreateInfo: *const SwapchainCreateInfoKHR, \
+             pAllocator: *const AllocationCallbacks, pSwapchain: *mut SwapchainKHR) -> Result,"
+        );
+        assert_eq!(
+            function_pointer(&registry, "vkCmdSetBlendConstants").unwrap(),
+            "CmdSetBlendConstants => (commandBuffer: CommandBuffer, \
+             blendConstants: *const f32) -> (),"
+        );
+        assert_eq!(
+            function_pointer(&registry, "vkMapMemoryKHR").unwrap(),
+            "MapMemoryKHR => (device: Device, memory: DeviceMemory, \
+             ppData: *mut *mut c_void) -> Result,"
+        );
+
+        assert_eq!(table(&registry, "vkDestroySurfaceKHR"), Some("InstancePointers"));
+        assert_eq!(table(&registry, "vkCreateSwapchainKHR"), Some("DevicePointers"));
+    }
+}
@@ -18,7 +18,7 @@ use vulkano::command_buffer::AutoCommandBufferBuilder;
 use vulkano::descriptor::descriptor_set::PersistentDescriptorSet;
 use vulkano::descriptor::PipelineLayoutAbstract;
 use vulkano::device::{Device, DeviceExtensions};
-use vulkano::instance::{Instance, InstanceExtensions, PhysicalDevice};
+use vulkano::instance::{DedicatedQueue, Instance, InstanceExtensions, PhysicalDeviceSelector};
 use vulkano::pipeline::ComputePipeline;
 use vulkano::sync::GpuFuture;
 use vulkano::sync;
@@ -29,19 +29,30 @@ fn main() {
     // As with other examples, the first step is to create an instance.
     let instance = Instance::new(None, &InstanceExtensions::none(), None).unwrap();
 
-    // Choose which physical device to use.
-    let physical = PhysicalDevice::enumerate(&instance).next().unwrap();
-
-    // Choose the queue of the physical device which is going to run our compute operation.
+    // Choose which physical device to use. The selector rejects the devices that don't support
+    // what we need and ranks the others, the most suitable first.
     //
-    // The Vulkan specs guarantee that a compliant implementation must provide at least one queue
-    // that supports compute operations.
-    let queue_family = physical.queue_families().find(|&q| q.supports_compute()).unwrap();
+    // We ask for a queue family dedicated to compute operations. If the device doesn't have one,
+    // the selector falls back to a family that supports graphics as well. The Vulkan specs
+    // guarantee that a compliant implementation must provide at least one queue that supports
+    // compute operations.
+    let extensions = DeviceExtensions {
+        khr_storage_buffer_storage_class: true,
+        ..DeviceExtensions::none()
+    };
+    let selection = PhysicalDeviceSelector::new()
+        .extensions(&extensions)
+        .dedicated_compute(DedicatedQueue::Preferred)
+        .select(&instance);
+    for rejection in selection.rejections.iter() {
+        println!("Skipping {}: {:?}", rejection.name, rejection.reasons);
+    }
+    let candidate = selection.best().expect("no suitable physical device");
+    let physical = candidate.physical_device(&instance);
 
     // Now initializing the device.
-    let (device, mut queues) = Device::new(physical, physical.supported_features(),
-        &DeviceExtensions{khr_storage_buffer_storage_class:true, ..DeviceExtensions::none()},
-        [(queue_family, 0.5)].iter().cloned()).unwrap();
+    let (device, mut queues) = Device::new(physical, physical.supported_features(), &extensions,
+        candidate.queue_families(physical)).unwrap();
 
     // Since we can request multiple queues, the `queues` variable is in fact an iterator. In this
     // example we use only one queue, so we just retrieve the first and only element of the
@@ -143,7 +143,7 @@ fn main() {
         .build().unwrap()
     );
 
-    let mut dynamic_state = DynamicState { line_width: None, viewports: None, scissors: None, compare_mask: None, write_mask: None, reference: None };
+    let mut dynamic_state = DynamicState::none();
     let mut framebuffers = window_size_dependent_setup(&images, render_pass.clone(), &mut dynamic_state);
 
     let mut recreate_swapchain = false;
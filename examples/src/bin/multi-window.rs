@@ -160,7 +160,7 @@ fn main() {
         .build(device.clone())
         .unwrap());
 
-    let mut dynamic_state = DynamicState { line_width: None, viewports: None, scissors: None, compare_mask: None, write_mask: None, reference: None };
+    let mut dynamic_state = DynamicState::none();
 
     window_surfaces.insert(window_id, WindowSurface {
         surface,
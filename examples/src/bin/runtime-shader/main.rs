@@ -34,7 +34,7 @@ use vulkano::format::Format;
 use vulkano::framebuffer::{Framebuffer, FramebufferAbstract, Subpass, RenderPassAbstract};
 use vulkano::image::SwapchainImage;
 use vulkano::pipeline::GraphicsPipeline;
-use vulkano::pipeline::shader::{GraphicsShaderType, ShaderInterfaceDef, ShaderInterfaceDefEntry, ShaderModule};
+use vulkano::pipeline::shader::{GraphicsShaderType, ShaderInterfaceDef, ShaderInterfaceDefEntry, ShaderInterfaceInterpolation, ShaderModule};
 use vulkano::pipeline::vertex::SingleBufferDefinition;
 use vulkano::pipeline::viewport::Viewport;
 use vulkano::swapchain::{AcquireError, PresentMode, SurfaceTransform, Swapchain, SwapchainCreationError, ColorSpace, FullscreenExclusive};
@@ -156,7 +156,11 @@ fn main() {
                 return Some(ShaderInterfaceDefEntry {
                     location: 1..2,
                     format: Format::R32G32B32Sfloat,
-                    name: Some(Cow::Borrowed("color"))
+                    name: Some(Cow::Borrowed("color")),
+                    component: 0,
+                    interpolation: ShaderInterfaceInterpolation::Smooth,
+                    is_patch: false,
+                    builtin: None,
                 })
             }
             if self.0 == 1 {
@@ -164,7 +168,11 @@ fn main() {
                 return Some(ShaderInterfaceDefEntry {
                     location: 0..1,
                     format: Format::R32G32Sfloat,
-                    name: Some(Cow::Borrowed("position"))
+                    name: Some(Cow::Borrowed("position")),
+                    component: 0,
+                    interpolation: ShaderInterfaceInterpolation::Smooth,
+                    is_patch: false,
+                    builtin: None,
                 })
             }
             None
@@ -206,7 +214,11 @@ fn main() {
                 return Some(ShaderInterfaceDefEntry {
                     location: 0..1,
                     format: Format::R32G32B32Sfloat,
-                    name: Some(Cow::Borrowed("v_color"))
+                    name: Some(Cow::Borrowed("v_color")),
+                    component: 0,
+                    interpolation: ShaderInterfaceInterpolation::Smooth,
+                    is_patch: false,
+                    builtin: None,
                 })
             }
             None
@@ -260,7 +272,11 @@ fn main() {
                 return Some(ShaderInterfaceDefEntry {
                     location: 0..1,
                     format: Format::R32G32B32Sfloat,
-                    name: Some(Cow::Borrowed("v_color"))
+                    name: Some(Cow::Borrowed("v_color")),
+                    component: 0,
+                    interpolation: ShaderInterfaceInterpolation::Smooth,
+                    is_patch: false,
+                    builtin: None,
                 })
             }
             None
@@ -300,7 +316,11 @@ fn main() {
                 return Some(ShaderInterfaceDefEntry {
                     location: 0..1,
                     format: Format::R32G32B32A32Sfloat,
-                    name: Some(Cow::Borrowed("f_color"))
+                    name: Some(Cow::Borrowed("f_color")),
+                    component: 0,
+                    interpolation: ShaderInterfaceInterpolation::Smooth,
+                    is_patch: false,
+                    builtin: None,
                 })
             }
             None
@@ -380,7 +400,7 @@ fn main() {
     // note that passing wrong types, providing sets at wrong indexes will cause
     // descriptor set builder to return Err!
 
-    let mut dynamic_state = DynamicState { line_width: None, viewports: None, scissors: None, compare_mask: None, write_mask: None, reference: None };
+    let mut dynamic_state = DynamicState::none();
     let mut framebuffers = window_size_dependent_setup(&images, render_pass.clone(), &mut dynamic_state);
     let mut previous_frame_end = Some(Box::new(sync::now(device.clone())) as Box<dyn GpuFuture>);
 
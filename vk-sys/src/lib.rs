@@ -15,6 +15,7 @@
 
 use std::mem;
 use std::ffi::CStr;
+use std::ffi::CString;
 use std::fmt;
 use std::os::raw::c_char;
 use std::os::raw::c_void;
@@ -73,6 +74,9 @@ pub const MAX_MEMORY_TYPES: u32 = 32;
 pub const MAX_MEMORY_HEAPS: u32 = 16;
 pub const MAX_EXTENSION_NAME_SIZE: u32 = 256;
 pub const MAX_DESCRIPTION_SIZE: u32 = 256;
+pub const LUID_SIZE_KHR: u32 = 8;
+pub const MAX_DRIVER_NAME_SIZE_KHR: u32 = 256;
+pub const MAX_DRIVER_INFO_SIZE_KHR: u32 = 256;
 pub const NULL_HANDLE: u64 = 0;
 
 pub type PipelineCacheHeaderVersion = u32;
@@ -182,11 +186,27 @@ pub const STRUCTURE_TYPE_QUEUE_FAMILY_PROPERTIES_2_KHR: u32 = 1000059005;
 pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_MEMORY_PROPERTIES_2_KHR: u32 = 1000059006;
 pub const STRUCTURE_TYPE_SPARSE_IMAGE_FORMAT_PROPERTIES_2_KHR: u32 = 1000059007;
 pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_SPARSE_IMAGE_FORMAT_INFO_2_KHR: u32 = 1000059008;
+pub const STRUCTURE_TYPE_RENDER_PASS_MULTIVIEW_CREATE_INFO_KHR: u32 = 1000053000;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_MULTIVIEW_FEATURES_KHR: u32 = 1000053001;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_MULTIVIEW_PROPERTIES_KHR: u32 = 1000053002;
 pub const STRUCTURE_TYPE_VI_SURFACE_CREATE_INFO_NN: u32 = 1000062000;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_ID_PROPERTIES_KHR: u32 = 1000071004;
 pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_PUSH_DESCRIPTOR_PROPERTIES_KHR: u32 = 1000080000;
 pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_16BIT_STORAGE_FEATURES_KHR: u32 = 1000083000;
 pub const STRUCTURE_TYPE_PRESENT_REGIONS_KHR: u32 = 1000084000;
 pub const STRUCTURE_TYPE_DESCRIPTOR_UPDATE_TEMPLATE_CREATE_INFO_KHR: u32 = 1000085000;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_SUBGROUP_PROPERTIES: u32 = 1000094000;
+pub const STRUCTURE_TYPE_ATTACHMENT_DESCRIPTION_2_KHR: u32 = 1000109000;
+pub const STRUCTURE_TYPE_ATTACHMENT_REFERENCE_2_KHR: u32 = 1000109001;
+pub const STRUCTURE_TYPE_SUBPASS_DESCRIPTION_2_KHR: u32 = 1000109002;
+pub const STRUCTURE_TYPE_SUBPASS_DEPENDENCY_2_KHR: u32 = 1000109003;
+pub const STRUCTURE_TYPE_RENDER_PASS_CREATE_INFO_2_KHR: u32 = 1000109004;
+pub const STRUCTURE_TYPE_SUBPASS_BEGIN_INFO_KHR: u32 = 1000109005;
+pub const STRUCTURE_TYPE_SUBPASS_END_INFO_KHR: u32 = 1000109006;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_POINT_CLIPPING_PROPERTIES_KHR: u32 = 1000117000;
+pub const STRUCTURE_TYPE_RENDER_PASS_INPUT_ATTACHMENT_ASPECT_CREATE_INFO_KHR: u32 = 1000117001;
+pub const STRUCTURE_TYPE_IMAGE_VIEW_USAGE_CREATE_INFO_KHR: u32 = 1000117002;
+pub const STRUCTURE_TYPE_PIPELINE_TESSELLATION_DOMAIN_ORIGIN_STATE_CREATE_INFO_KHR: u32 = 1000117003;
 pub const STRUCTURE_TYPE_MEMORY_DEDICATED_REQUIREMENTS_KHR: u32 = 1000127000;
 pub const STRUCTURE_TYPE_MEMORY_DEDICATED_ALLOCATE_INFO_KHR: u32 = 1000127001;
 pub const STRUCTURE_TYPE_BUFFER_MEMORY_REQUIREMENTS_INFO_2_KHR: u32 = 1000146000;
@@ -194,7 +214,26 @@ pub const STRUCTURE_TYPE_IMAGE_MEMORY_REQUIREMENTS_INFO_2_KHR: u32 = 1000146001;
 pub const STRUCTURE_TYPE_IMAGE_SPARSE_MEMORY_REQUIREMENTS_INFO_2_KHR: u32 = 1000146002;
 pub const STRUCTURE_TYPE_MEMORY_REQUIREMENTS_2_KHR: u32 = 1000146003;
 pub const STRUCTURE_TYPE_SPARSE_IMAGE_MEMORY_REQUIREMENTS_2_KHR: u32 = 1000146004;
+pub const STRUCTURE_TYPE_DESCRIPTOR_SET_LAYOUT_BINDING_FLAGS_CREATE_INFO_EXT: u32 = 1000161000;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_DESCRIPTOR_INDEXING_FEATURES_EXT: u32 = 1000161001;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_DESCRIPTOR_INDEXING_PROPERTIES_EXT: u32 = 1000161002;
+pub const STRUCTURE_TYPE_DESCRIPTOR_SET_VARIABLE_DESCRIPTOR_COUNT_ALLOCATE_INFO_EXT: u32 = 1000161003;
+pub const STRUCTURE_TYPE_DESCRIPTOR_SET_VARIABLE_DESCRIPTOR_COUNT_LAYOUT_SUPPORT_EXT: u32 = 1000161004;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_MAINTENANCE_3_PROPERTIES_KHR: u32 = 1000168000;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_DRIVER_PROPERTIES_KHR: u32 = 1000196000;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_DEPTH_STENCIL_RESOLVE_PROPERTIES_KHR: u32 = 1000199000;
+pub const STRUCTURE_TYPE_SUBPASS_DESCRIPTION_DEPTH_STENCIL_RESOLVE_KHR: u32 = 1000199001;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_TIMELINE_SEMAPHORE_FEATURES_KHR: u32 = 1000207000;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_TIMELINE_SEMAPHORE_PROPERTIES_KHR: u32 = 1000207001;
+pub const STRUCTURE_TYPE_SEMAPHORE_TYPE_CREATE_INFO_KHR: u32 = 1000207002;
+pub const STRUCTURE_TYPE_TIMELINE_SEMAPHORE_SUBMIT_INFO_KHR: u32 = 1000207003;
+pub const STRUCTURE_TYPE_SEMAPHORE_WAIT_INFO_KHR: u32 = 1000207004;
+pub const STRUCTURE_TYPE_SEMAPHORE_SIGNAL_INFO_KHR: u32 = 1000207005;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_SCALAR_BLOCK_LAYOUT_FEATURES_EXT: u32 = 1000221000;
 pub const STRUCTURE_TYPE_SURFACE_FULL_SCREEN_EXCLUSIVE_INFO_EXT: u32 = 1000255000;
+pub const STRUCTURE_TYPE_HEADLESS_SURFACE_CREATE_INFO_EXT: u32 = 1000256000;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_BUFFER_DEVICE_ADDRESS_FEATURES_KHR: u32 = 1000257000;
+pub const STRUCTURE_TYPE_PHYSICAL_DEVICE_EXTENDED_DYNAMIC_STATE_FEATURES_EXT: u32 = 1000267000;
 
 pub type SystemAllocationScope = u32;
 pub const SYSTEM_ALLOCATION_SCOPE_COMMAND: u32 = 0;
@@ -550,6 +589,18 @@ pub const DYNAMIC_STATE_DEPTH_BOUNDS: u32 = 5;
 pub const DYNAMIC_STATE_STENCIL_COMPARE_MASK: u32 = 6;
 pub const DYNAMIC_STATE_STENCIL_WRITE_MASK: u32 = 7;
 pub const DYNAMIC_STATE_STENCIL_REFERENCE: u32 = 8;
+pub const DYNAMIC_STATE_CULL_MODE_EXT: u32 = 1000267000;
+pub const DYNAMIC_STATE_FRONT_FACE_EXT: u32 = 1000267001;
+pub const DYNAMIC_STATE_PRIMITIVE_TOPOLOGY_EXT: u32 = 1000267002;
+pub const DYNAMIC_STATE_VIEWPORT_WITH_COUNT_EXT: u32 = 1000267003;
+pub const DYNAMIC_STATE_SCISSOR_WITH_COUNT_EXT: u32 = 1000267004;
+pub const DYNAMIC_STATE_VERTEX_INPUT_BINDING_STRIDE_EXT: u32 = 1000267005;
+pub const DYNAMIC_STATE_DEPTH_TEST_ENABLE_EXT: u32 = 1000267006;
+pub const DYNAMIC_STATE_DEPTH_WRITE_ENABLE_EXT: u32 = 1000267007;
+pub const DYNAMIC_STATE_DEPTH_COMPARE_OP_EXT: u32 = 1000267008;
+pub const DYNAMIC_STATE_DEPTH_BOUNDS_TEST_ENABLE_EXT: u32 = 1000267009;
+pub const DYNAMIC_STATE_STENCIL_TEST_ENABLE_EXT: u32 = 1000267010;
+pub const DYNAMIC_STATE_STENCIL_OP_EXT: u32 = 1000267011;
 
 pub type Filter = u32;
 pub const FILTER_NEAREST: u32 = 0;
@@ -837,6 +888,7 @@ pub type DescriptorSetLayoutCreateFlags = Flags;
 
 pub type DescriptorPoolCreateFlagBits = u32;
 pub const DESCRIPTOR_POOL_CREATE_FREE_DESCRIPTOR_SET_BIT: u32 = 0x00000001;
+pub const DESCRIPTOR_POOL_CREATE_UPDATE_AFTER_BIND_BIT_EXT: u32 = 0x00000002;
 pub type DescriptorPoolCreateFlags = Flags;
 pub type DescriptorPoolResetFlags = Flags;
 pub type FramebufferCreateFlags = Flags;
@@ -872,6 +924,7 @@ pub type AccessFlags = Flags;
 
 pub type DependencyFlagBits = u32;
 pub const DEPENDENCY_BY_REGION_BIT: u32 = 0x00000001;
+pub const DEPENDENCY_VIEW_LOCAL_BIT_KHR: u32 = 0x00000002;
 pub type DependencyFlags = Flags;
 
 
@@ -921,6 +974,7 @@ pub const DISPLAY_PLANE_ALPHA_PER_PIXEL_PREMULTIPLIED_BIT_KHR: u32 = 0x00000008;
 pub type DisplayModeCreateFlagsKHR = Flags;
 pub type DisplayPlaneAlphaFlagsKHR = Flags;
 pub type DisplaySurfaceCreateFlagsKHR = Flags;
+pub type HeadlessSurfaceCreateFlagsEXT = Flags;
 
 pub type ColorSpaceKHR = u32;
 pub const COLOR_SPACE_SRGB_NONLINEAR_KHR: u32 = 0;
@@ -1032,6 +1086,14 @@ pub type IOSSurfaceCreateFlagsMVK = u32;
 
 pub type DescriptorSetLayoutCreateFlagBits = u32;
 pub const DESCRIPTOR_SET_LAYOUT_CREATE_PUSH_DESCRIPTOR_BIT_KHR: u32 = 0x00000001;
+pub const DESCRIPTOR_SET_LAYOUT_CREATE_UPDATE_AFTER_BIND_POOL_BIT_EXT: u32 = 0x00000002;
+
+pub type DescriptorBindingFlagBitsEXT = u32;
+pub const DESCRIPTOR_BINDING_UPDATE_AFTER_BIND_BIT_EXT: u32 = 0x00000001;
+pub const DESCRIPTOR_BINDING_UPDATE_UNUSED_WHILE_PENDING_BIT_EXT: u32 = 0x00000002;
+pub const DESCRIPTOR_BINDING_PARTIALLY_BOUND_BIT_EXT: u32 = 0x00000004;
+pub const DESCRIPTOR_BINDING_VARIABLE_DESCRIPTOR_COUNT_BIT_EXT: u32 = 0x00000008;
+pub type DescriptorBindingFlagsEXT = Flags;
 
 pub type DescriptorUpdateTemplateTypeKHR = u32;
 pub const DESCRIPTOR_UPDATE_TEMPLATE_TYPE_DESCRIPTOR_SET_KHR: u32 = 0;
@@ -1041,6 +1103,14 @@ pub const DESCRIPTOR_UPDATE_TEMPLATE_TYPE_END_RANGE_KHR: u32 = DESCRIPTOR_UPDATE
 pub const DESCRIPTOR_UPDATE_TEMPLATE_TYPE_RANGE_SIZE_KHR: u32 = (DESCRIPTOR_UPDATE_TEMPLATE_TYPE_PUSH_DESCRIPTORS_KHR - DESCRIPTOR_UPDATE_TEMPLATE_TYPE_DESCRIPTOR_SET_KHR + 1);
 pub type DescriptorUpdateTemplateCreateFlagsKHR = Flags;
 
+pub type SemaphoreTypeKHR = u32;
+pub const SEMAPHORE_TYPE_BINARY_KHR: u32 = 0;
+pub const SEMAPHORE_TYPE_TIMELINE_KHR: u32 = 1;
+
+pub type SemaphoreWaitFlagBitsKHR = u32;
+pub const SEMAPHORE_WAIT_ANY_BIT_KHR: u32 = 0x00000001;
+pub type SemaphoreWaitFlagsKHR = Flags;
+
 pub type PFN_vkAllocationFunction = extern "system" fn(*mut c_void, usize, usize, SystemAllocationScope) -> *mut c_void;
 pub type PFN_vkReallocationFunction = extern "system" fn(*mut c_void, *mut c_void, usize, usize, SystemAllocationScope) -> *mut c_void;
 pub type PFN_vkFreeFunction = extern "system" fn(*mut c_void, *mut c_void);
@@ -2348,6 +2418,13 @@ pub struct DisplayPlanePropertiesKHR {
     pub currentStackIndex: u32,
 }
 
+#[repr(C)]
+pub struct HeadlessSurfaceCreateInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub flags: HeadlessSurfaceCreateFlagsEXT,
+}
+
 #[repr(C)]
 pub struct DisplaySurfaceCreateInfoKHR {
     pub sType: StructureType,
@@ -2473,6 +2550,120 @@ pub struct MVKSwapchainPerformance {
     pub averageFramesPerSecond: c_double,
 }
 
+#[repr(C)]
+pub struct PhysicalDeviceDescriptorIndexingFeaturesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub shaderInputAttachmentArrayDynamicIndexing: Bool32,
+    pub shaderUniformTexelBufferArrayDynamicIndexing: Bool32,
+    pub shaderStorageTexelBufferArrayDynamicIndexing: Bool32,
+    pub shaderUniformBufferArrayNonUniformIndexing: Bool32,
+    pub shaderSampledImageArrayNonUniformIndexing: Bool32,
+    pub shaderStorageBufferArrayNonUniformIndexing: Bool32,
+    pub shaderStorageImageArrayNonUniformIndexing: Bool32,
+    pub shaderInputAttachmentArrayNonUniformIndexing: Bool32,
+    pub shaderUniformTexelBufferArrayNonUniformIndexing: Bool32,
+    pub shaderStorageTexelBufferArrayNonUniformIndexing: Bool32,
+    pub descriptorBindingUniformBufferUpdateAfterBind: Bool32,
+    pub descriptorBindingSampledImageUpdateAfterBind: Bool32,
+    pub descriptorBindingStorageImageUpdateAfterBind: Bool32,
+    pub descriptorBindingStorageBufferUpdateAfterBind: Bool32,
+    pub descriptorBindingUniformTexelBufferUpdateAfterBind: Bool32,
+    pub descriptorBindingStorageTexelBufferUpdateAfterBind: Bool32,
+    pub descriptorBindingUpdateUnusedWhilePending: Bool32,
+    pub descriptorBindingPartiallyBound: Bool32,
+    pub descriptorBindingVariableDescriptorCount: Bool32,
+    pub runtimeDescriptorArray: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceDescriptorIndexingPropertiesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub maxUpdateAfterBindDescriptorsInAllPools: u32,
+    pub shaderUniformBufferArrayNonUniformIndexingNative: Bool32,
+    pub shaderSampledImageArrayNonUniformIndexingNative: Bool32,
+    pub shaderStorageBufferArrayNonUniformIndexingNative: Bool32,
+    pub shaderStorageImageArrayNonUniformIndexingNative: Bool32,
+    pub shaderInputAttachmentArrayNonUniformIndexingNative: Bool32,
+    pub robustBufferAccessUpdateAfterBind: Bool32,
+    pub quadDivergentImplicitLod: Bool32,
+    pub maxPerStageDescriptorUpdateAfterBindSamplers: u32,
+    pub maxPerStageDescriptorUpdateAfterBindUniformBuffers: u32,
+    pub maxPerStageDescriptorUpdateAfterBindStorageBuffers: u32,
+    pub maxPerStageDescriptorUpdateAfterBindSampledImages: u32,
+    pub maxPerStageDescriptorUpdateAfterBindStorageImages: u32,
+    pub maxPerStageDescriptorUpdateAfterBindInputAttachments: u32,
+    pub maxPerStageUpdateAfterBindResources: u32,
+    pub maxDescriptorSetUpdateAfterBindSamplers: u32,
+    pub maxDescriptorSetUpdateAfterBindUniformBuffers: u32,
+    pub maxDescriptorSetUpdateAfterBindUniformBuffersDynamic: u32,
+    pub maxDescriptorSetUpdateAfterBindStorageBuffers: u32,
+    pub maxDescriptorSetUpdateAfterBindStorageBuffersDynamic: u32,
+    pub maxDescriptorSetUpdateAfterBindSampledImages: u32,
+    pub maxDescriptorSetUpdateAfterBindStorageImages: u32,
+    pub maxDescriptorSetUpdateAfterBindInputAttachments: u32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceTimelineSemaphoreFeaturesKHR {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub timelineSemaphore: Bool32,
+}
+
+#[repr(C)]
+pub struct SemaphoreTypeCreateInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub semaphoreType: SemaphoreTypeKHR,
+    pub initialValue: u64,
+}
+
+#[repr(C)]
+pub struct TimelineSemaphoreSubmitInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub waitSemaphoreValueCount: u32,
+    pub pWaitSemaphoreValues: *const u64,
+    pub signalSemaphoreValueCount: u32,
+    pub pSignalSemaphoreValues: *const u64,
+}
+
+#[repr(C)]
+pub struct SemaphoreWaitInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub flags: SemaphoreWaitFlagsKHR,
+    pub semaphoreCount: u32,
+    pub pSemaphores: *const Semaphore,
+    pub pValues: *const u64,
+}
+
+#[repr(C)]
+pub struct SemaphoreSignalInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub semaphore: Semaphore,
+    pub value: u64,
+}
+
+#[repr(C)]
+pub struct DescriptorSetLayoutBindingFlagsCreateInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub bindingCount: u32,
+    pub pBindingFlags: *const DescriptorBindingFlagsEXT,
+}
+
+#[repr(C)]
+pub struct DescriptorSetVariableDescriptorCountAllocateInfoEXT {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub descriptorSetCount: u32,
+    pub pDescriptorCounts: *const u32,
+}
+
 #[repr(C)]
 pub struct PhysicalDeviceFeatures2KHR {
     pub sType: StructureType,
@@ -2681,13 +2872,248 @@ pub struct DebugUtilsLabelEXT {
 #[repr(C)]
 pub struct PhysicalDevice16BitStorageFeaturesKHR {
     pub sType: StructureType,
-    pub pNext: *const c_void,
+    pub pNext: *mut c_void,
     pub storageBuffer16BitAccess: Bool32,
     pub uniformAndStorageBuffer16BitAccess: Bool32,
     pub storagePushConstant16: Bool32,
     pub storageInputOutput16: Bool32,
 }
 
+pub type SubgroupFeatureFlags = Flags;
+pub type SubgroupFeatureFlagBits = u32;
+pub const SUBGROUP_FEATURE_BASIC_BIT: u32 = 0x00000001;
+pub const SUBGROUP_FEATURE_VOTE_BIT: u32 = 0x00000002;
+pub const SUBGROUP_FEATURE_ARITHMETIC_BIT: u32 = 0x00000004;
+pub const SUBGROUP_FEATURE_BALLOT_BIT: u32 = 0x00000008;
+pub const SUBGROUP_FEATURE_SHUFFLE_BIT: u32 = 0x00000010;
+pub const SUBGROUP_FEATURE_SHUFFLE_RELATIVE_BIT: u32 = 0x00000020;
+pub const SUBGROUP_FEATURE_CLUSTERED_BIT: u32 = 0x00000040;
+pub const SUBGROUP_FEATURE_QUAD_BIT: u32 = 0x00000080;
+
+#[repr(C)]
+pub struct PhysicalDeviceSubgroupProperties {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub subgroupSize: u32,
+    pub supportedStages: ShaderStageFlags,
+    pub supportedOperations: SubgroupFeatureFlags,
+    pub quadOperationsInAllStages: Bool32,
+}
+
+pub type DriverIdKHR = u32;
+pub const DRIVER_ID_AMD_PROPRIETARY_KHR: u32 = 1;
+pub const DRIVER_ID_AMD_OPEN_SOURCE_KHR: u32 = 2;
+pub const DRIVER_ID_MESA_RADV_KHR: u32 = 3;
+pub const DRIVER_ID_NVIDIA_PROPRIETARY_KHR: u32 = 4;
+pub const DRIVER_ID_INTEL_PROPRIETARY_WINDOWS_KHR: u32 = 5;
+pub const DRIVER_ID_INTEL_OPEN_SOURCE_MESA_KHR: u32 = 6;
+pub const DRIVER_ID_IMAGINATION_PROPRIETARY_KHR: u32 = 7;
+pub const DRIVER_ID_QUALCOMM_PROPRIETARY_KHR: u32 = 8;
+pub const DRIVER_ID_ARM_PROPRIETARY_KHR: u32 = 9;
+pub const DRIVER_ID_GOOGLE_SWIFTSHADER_KHR: u32 = 10;
+pub const DRIVER_ID_GGP_PROPRIETARY_KHR: u32 = 11;
+pub const DRIVER_ID_BROADCOM_PROPRIETARY_KHR: u32 = 12;
+
+#[repr(C)]
+#[derive(Copy, Clone)]
+pub struct ConformanceVersionKHR {
+    pub major: u8,
+    pub minor: u8,
+    pub subminor: u8,
+    pub patch: u8,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceDriverPropertiesKHR {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub driverID: DriverIdKHR,
+    pub driverName: [c_char; MAX_DRIVER_NAME_SIZE_KHR as usize],
+    pub driverInfo: [c_char; MAX_DRIVER_INFO_SIZE_KHR as usize],
+    pub conformanceVersion: ConformanceVersionKHR,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceIDPropertiesKHR {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub deviceUUID: [u8; UUID_SIZE as usize],
+    pub driverUUID: [u8; UUID_SIZE as usize],
+    pub deviceLUID: [u8; LUID_SIZE_KHR as usize],
+    pub deviceNodeMask: u32,
+    pub deviceLUIDValid: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceMaintenance3PropertiesKHR {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub maxPerSetDescriptors: u32,
+    pub maxMemoryAllocationSize: DeviceSize,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceMultiviewFeaturesKHR {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub multiview: Bool32,
+    pub multiviewGeometryShader: Bool32,
+    pub multiviewTessellationShader: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceMultiviewPropertiesKHR {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub maxMultiviewViewCount: u32,
+    pub maxMultiviewInstanceIndex: u32,
+}
+
+#[repr(C)]
+pub struct RenderPassMultiviewCreateInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub subpassCount: u32,
+    pub pViewMasks: *const u32,
+    pub dependencyCount: u32,
+    pub pViewOffsets: *const i32,
+    pub correlationMaskCount: u32,
+    pub pCorrelationMasks: *const u32,
+}
+
+#[repr(C)]
+pub struct AttachmentDescription2KHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub flags: AttachmentDescriptionFlags,
+    pub format: Format,
+    pub samples: SampleCountFlagBits,
+    pub loadOp: AttachmentLoadOp,
+    pub storeOp: AttachmentStoreOp,
+    pub stencilLoadOp: AttachmentLoadOp,
+    pub stencilStoreOp: AttachmentStoreOp,
+    pub initialLayout: ImageLayout,
+    pub finalLayout: ImageLayout,
+}
+
+#[repr(C)]
+pub struct AttachmentReference2KHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub attachment: u32,
+    pub layout: ImageLayout,
+    pub aspectMask: ImageAspectFlags,
+}
+
+#[repr(C)]
+pub struct SubpassDescription2KHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub flags: SubpassDescriptionFlags,
+    pub pipelineBindPoint: PipelineBindPoint,
+    pub viewMask: u32,
+    pub inputAttachmentCount: u32,
+    pub pInputAttachments: *const AttachmentReference2KHR,
+    pub colorAttachmentCount: u32,
+    pub pColorAttachments: *const AttachmentReference2KHR,
+    pub pResolveAttachments: *const AttachmentReference2KHR,
+    pub pDepthStencilAttachment: *const AttachmentReference2KHR,
+    pub preserveAttachmentCount: u32,
+    pub pPreserveAttachments: *const u32,
+}
+
+#[repr(C)]
+pub struct SubpassDependency2KHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub srcSubpass: u32,
+    pub dstSubpass: u32,
+    pub srcStageMask: PipelineStageFlags,
+    pub dstStageMask: PipelineStageFlags,
+    pub srcAccessMask: AccessFlags,
+    pub dstAccessMask: AccessFlags,
+    pub dependencyFlags: DependencyFlags,
+    pub viewOffset: i32,
+}
+
+#[repr(C)]
+pub struct RenderPassCreateInfo2KHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub flags: RenderPassCreateFlags,
+    pub attachmentCount: u32,
+    pub pAttachments: *const AttachmentDescription2KHR,
+    pub subpassCount: u32,
+    pub pSubpasses: *const SubpassDescription2KHR,
+    pub dependencyCount: u32,
+    pub pDependencies: *const SubpassDependency2KHR,
+    pub correlatedViewMaskCount: u32,
+    pub pCorrelatedViewMasks: *const u32,
+}
+
+#[repr(C)]
+pub struct SubpassBeginInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub contents: SubpassContents,
+}
+
+#[repr(C)]
+pub struct SubpassEndInfoKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+}
+
+pub type ResolveModeFlagBitsKHR = u32;
+pub const RESOLVE_MODE_NONE_KHR: u32 = 0;
+pub const RESOLVE_MODE_SAMPLE_ZERO_BIT_KHR: u32 = 0x00000001;
+pub const RESOLVE_MODE_AVERAGE_BIT_KHR: u32 = 0x00000002;
+pub const RESOLVE_MODE_MIN_BIT_KHR: u32 = 0x00000004;
+pub const RESOLVE_MODE_MAX_BIT_KHR: u32 = 0x00000008;
+pub type ResolveModeFlagsKHR = Flags;
+
+#[repr(C)]
+pub struct PhysicalDeviceDepthStencilResolvePropertiesKHR {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub supportedDepthResolveModes: ResolveModeFlagsKHR,
+    pub supportedStencilResolveModes: ResolveModeFlagsKHR,
+    pub independentResolveNone: Bool32,
+    pub independentResolve: Bool32,
+}
+
+#[repr(C)]
+pub struct SubpassDescriptionDepthStencilResolveKHR {
+    pub sType: StructureType,
+    pub pNext: *const c_void,
+    pub depthResolveMode: ResolveModeFlagBitsKHR,
+    pub stencilResolveMode: ResolveModeFlagBitsKHR,
+    pub pDepthStencilResolveAttachment: *const AttachmentReference2KHR,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceBufferDeviceAddressFeaturesKHR {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub bufferDeviceAddress: Bool32,
+    pub bufferDeviceAddressCaptureReplay: Bool32,
+    pub bufferDeviceAddressMultiDevice: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceScalarBlockLayoutFeaturesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub scalarBlockLayout: Bool32,
+}
+
+#[repr(C)]
+pub struct PhysicalDeviceExtendedDynamicStateFeaturesEXT {
+    pub sType: StructureType,
+    pub pNext: *mut c_void,
+    pub extendedDynamicState: Bool32,
+}
+
 #[repr(C)]
 pub struct DebugUtilsObjectNameInfoEXT {
     pub sType: StructureType,
@@ -2731,7 +3157,14 @@ macro_rules! ptrs {
                         $name: unsafe {
                             extern "system" fn $name($(_: $param_ty),*) { panic!("function pointer `{}` not loaded", stringify!($name)) }
                             let name = CStr::from_bytes_with_nul_unchecked(concat!("vk", stringify!($name), "\0").as_bytes());
-                            let val = f(name);
+                            let mut val = f(name);
+                            if val.is_null() && stringify!($name).ends_with("KHR") {
+                                // Functions that have been promoted to core are also available
+                                // under their name without the `KHR` suffix.
+                                let core_name = concat!("vk", stringify!($name));
+                                let core_name = CString::new(&core_name[.. core_name.len() - 3]).unwrap();
+                                val = f(&core_name);
+                            }
                             if val.is_null() { mem::transmute($name as *const ()) } else { mem::transmute(val) }
                         },
                     )+
@@ -2757,6 +3190,7 @@ ptrs!(EntryPoints, {
     CreateInstance => (pCreateInfo: *const InstanceCreateInfo, pAllocator: *const AllocationCallbacks, pInstance: *mut Instance) -> Result,
     EnumerateInstanceExtensionProperties => (pLayerName: *const c_char, pPropertyCount: *mut u32, pProperties: *mut ExtensionProperties) -> Result,
     EnumerateInstanceLayerProperties => (pPropertyCount: *mut u32, pProperties: *mut LayerProperties) -> Result,
+    EnumerateInstanceVersion => (pApiVersion: *mut u32) -> Result,
 });
 
 ptrs!(InstancePointers, {
@@ -2790,6 +3224,7 @@ ptrs!(InstancePointers, {
     CreateDisplayModeKHR => (physicalDevice: PhysicalDevice, display: DisplayKHR, pCreateInfo: *const DisplayModeCreateInfoKHR, pAllocator: *const AllocationCallbacks, pMode: *mut DisplayModeKHR) -> Result,
     GetDisplayPlaneCapabilitiesKHR => (physicalDevice: PhysicalDevice, mode: DisplayModeKHR, planeIndex: u32, pCapabilities: *mut DisplayPlaneCapabilitiesKHR) -> Result,
     CreateDisplayPlaneSurfaceKHR => (instance: Instance, pCreateInfo: *const DisplaySurfaceCreateInfoKHR, pAllocator: *const AllocationCallbacks, pSurface: *mut SurfaceKHR) -> Result,
+    CreateHeadlessSurfaceEXT => (instance: Instance, pCreateInfo: *const HeadlessSurfaceCreateInfoEXT, pAllocator: *const AllocationCallbacks, pSurface: *mut SurfaceKHR) -> Result,
     GetPhysicalDeviceSurfaceSupportKHR => (physicalDevice: PhysicalDevice, queueFamilyIndex: u32, surface: SurfaceKHR, pSupported: *mut Bool32) -> Result,
     GetPhysicalDeviceSurfaceCapabilitiesKHR => (physicalDevice: PhysicalDevice, surface: SurfaceKHR, pSurfaceCapabilities: *mut SurfaceCapabilitiesKHR) -> Result,
     GetPhysicalDeviceSurfaceFormatsKHR => (physicalDevice: PhysicalDevice, surface: SurfaceKHR, pSurfaceFormatCount: *mut u32, pSurfaceFormats: *mut SurfaceFormatKHR) -> Result,
@@ -2897,7 +3332,7 @@ ptrs!(DevicePointers, {
     CmdSetScissor => (commandBuffer: CommandBuffer, firstScissor: u32, scissorCount: u32, pScissors: *const Rect2D) -> (),
     CmdSetLineWidth => (commandBuffer: CommandBuffer, lineWidth: f32) -> (),
     CmdSetDepthBias => (commandBuffer: CommandBuffer, depthBiasConstantFactor: f32, depthBiasClamp: f32, depthBiasSlopeFactor: f32) -> (),
-    CmdSetBlendConstants => (commandBuffer: CommandBuffer, blendConstants: [f32; 4]) -> (),
+    CmdSetBlendConstants => (commandBuffer: CommandBuffer, blendConstants: *const f32) -> (),
     CmdSetDepthBounds => (commandBuffer: CommandBuffer, minDepthBounds: f32, maxDepthBounds: f32) -> (),
     CmdSetStencilCompareMask => (commandBuffer: CommandBuffer, faceMask: StencilFaceFlags, compareMask: u32) -> (),
     CmdSetStencilWriteMask => (commandBuffer: CommandBuffer, faceMask: StencilFaceFlags, writeMask: u32) -> (),
@@ -2953,6 +3388,22 @@ ptrs!(DevicePointers, {
     CmdBeginDebugUtilsLabelEXT => (commandBuffer: CommandBuffer, pLabelInfo: *const DebugUtilsLabelEXT) -> Result,
     CmdEndDebugUtilsLabelEXT => (commandBuffer: CommandBuffer) -> Result,
     CmdInsertDebugUtilsLabelEXT => (commandBuffer: CommandBuffer, pLabelInfo: *const DebugUtilsLabelEXT) -> Result,
+    GetSemaphoreCounterValueKHR => (device: Device, semaphore: Semaphore, pValue: *mut u64) -> Result,
+    WaitSemaphoresKHR => (device: Device, pWaitInfo: *const SemaphoreWaitInfoKHR, timeout: u64) -> Result,
+    SignalSemaphoreKHR => (device: Device, pSignalInfo: *const SemaphoreSignalInfoKHR) -> Result,
     AcquireFullScreenExclusiveModeEXT => (device: Device, swapchain: SwapchainKHR) -> Result,
     ReleaseFullScreenExclusiveModeEXT => (device: Device, swapchain: SwapchainKHR) -> Result,
+    CmdSetCullModeEXT => (commandBuffer: CommandBuffer, cullMode: CullModeFlags) -> (),
+    CmdSetFrontFaceEXT => (commandBuffer: CommandBuffer, frontFace: FrontFace) -> (),
+    CmdSetPrimitiveTopologyEXT => (commandBuffer: CommandBuffer, primitiveTopology: PrimitiveTopology) -> (),
+    CmdSetDepthTestEnableEXT => (commandBuffer: CommandBuffer, depthTestEnable: Bool32) -> (),
+    CmdSetDepthWriteEnableEXT => (commandBuffer: CommandBuffer, depthWriteEnable: Bool32) -> (),
+    CmdSetDepthCompareOpEXT => (commandBuffer: CommandBuffer, depthCompareOp: CompareOp) -> (),
+    CmdSetDepthBoundsTestEnableEXT => (commandBuffer: CommandBuffer, depthBoundsTestEnable: Bool32) -> (),
+    CmdSetStencilTestEnableEXT => (commandBuffer: CommandBuffer, stencilTestEnable: Bool32) -> (),
+    CmdSetStencilOpEXT => (commandBuffer: CommandBuffer, faceMask: StencilFaceFlags, failOp: StencilOp, passOp: StencilOp, depthFailOp: StencilOp, compareOp: CompareOp) -> (),
+    CreateRenderPass2KHR => (device: Device, pCreateInfo: *const RenderPassCreateInfo2KHR, pAllocator: *const AllocationCallbacks, pRenderPass: *mut RenderPass) -> Result,
+    CmdBeginRenderPass2KHR => (commandBuffer: CommandBuffer, pRenderPassBegin: *const RenderPassBeginInfo, pSubpassBeginInfo: *const SubpassBeginInfoKHR) -> (),
+    CmdNextSubpass2KHR => (commandBuffer: CommandBuffer, pSubpassBeginInfo: *const SubpassBeginInfoKHR, pSubpassEndInfo: *const SubpassEndInfoKHR) -> (),
+    CmdEndRenderPass2KHR => (commandBuffer: CommandBuffer, pSubpassEndInfo: *const SubpassEndInfoKHR) -> (),
 });
@@ -9,6 +9,7 @@
 
 use std::io::Error as IoError;
 use std::path::Path;
+use std::path::PathBuf;
 
 use syn::Ident;
 use proc_macro2::{Span, TokenStream};
@@ -141,9 +142,18 @@ pub fn compile(path: Option<String>, base_path: &impl AsRef<Path>, code: &str, t
     Ok(content)
 }
 
-pub fn reflect(name: &str, spirv: &[u32], dump: bool) -> Result<TokenStream, Error> {
+/// Parameters used to generate the code that recompiles a shader at runtime.
+pub struct HotReload {
+    pub source_path: PathBuf,
+    pub shader_kind: ShaderKind,
+    pub include_directories: Vec<PathBuf>,
+    pub macro_defines: Vec<(String, String)>,
+}
+
+pub fn reflect(name: &str, spirv: &[u32], hot_reload: Option<HotReload>, dump: bool) -> Result<TokenStream, Error> {
     let struct_name = Ident::new(&name, Span::call_site());
     let doc = parse::parse_spirv(spirv)?;
+    let words_len = spirv.len();
 
     // checking whether each required capability is enabled in the Vulkan device
     let mut cap_checks: Vec<TokenStream> = vec!();
@@ -198,6 +208,10 @@ pub fn reflect(name: &str, spirv: &[u32], dump: bool) -> Result<TokenStream, Err
     let structs = structs::write_structs(&doc);
     let descriptor_sets = descriptor_sets::write_descriptor_sets(&doc);
     let specialization_constants = spec_consts::write_specialization_constants(&doc);
+    let reload = match hot_reload {
+        Some(hot_reload) => write_reload(&struct_name, &hot_reload),
+        None => TokenStream::new(),
+    };
     let ast = quote!{
         #[allow(unused_imports)]
         use std::sync::Arc;
@@ -249,15 +263,17 @@ pub fn reflect(name: &str, spirv: &[u32], dump: bool) -> Result<TokenStream, Err
                         -> Result<#struct_name, ::vulkano::OomError>
             {
                 #( #cap_checks )*
-                let words = [ #( #spirv ),* ];
 
                 unsafe {
                     Ok(#struct_name {
-                        shader: ::vulkano::pipeline::shader::ShaderModule::from_words(device, &words)?
+                        shader: ::vulkano::pipeline::shader::ShaderModule::from_words(device, &Self::WORDS)?
                     })
                 }
             }
 
+            /// The SPIR-V code that was generated at compile time.
+            const WORDS: [u32; #words_len] = [ #( #spirv ),* ];
+
             /// Returns the module that was created.
             #[allow(dead_code)]
             #[inline]
@@ -265,6 +281,8 @@ pub fn reflect(name: &str, spirv: &[u32], dump: bool) -> Result<TokenStream, Err
                 &self.shader
             }
 
+            #reload
+
             #( #entry_points_inside_impl )*
         }
 
@@ -286,6 +304,95 @@ pub fn reflect(name: &str, spirv: &[u32], dump: bool) -> Result<TokenStream, Err
     Ok(ast)
 }
 
+/// Writes the `reload` method of the shader struct.
+fn write_reload(struct_name: &Ident, hot_reload: &HotReload) -> TokenStream {
+    let source_path = hot_reload.source_path.to_str()
+        .expect("Could not stringify the shader source path. Make sure the path consists of \
+                 valid unicode characters.");
+    let include_directories = hot_reload.include_directories.iter().map(|dir| {
+        dir.to_str()
+            .expect("Could not stringify an include directory. Make sure the path consists of \
+                     valid unicode characters.")
+    });
+    let macro_defines = hot_reload.macro_defines.iter().map(|(name, value)| {
+        quote!{ compile_options.add_macro_definition(#name, Some(#value)); }
+    });
+    let shader_kind = match hot_reload.shader_kind {
+        ShaderKind::Vertex => quote!{ ::shaderc::ShaderKind::Vertex },
+        ShaderKind::Fragment => quote!{ ::shaderc::ShaderKind::Fragment },
+        ShaderKind::Geometry => quote!{ ::shaderc::ShaderKind::Geometry },
+        ShaderKind::TessControl => quote!{ ::shaderc::ShaderKind::TessControl },
+        ShaderKind::TessEvaluation => quote!{ ::shaderc::ShaderKind::TessEvaluation },
+        ShaderKind::Compute => quote!{ ::shaderc::ShaderKind::Compute },
+        _ => unreachable!(),
+    };
+
+    quote!{
+        /// Path of the GLSL source that `reload` reads.
+        #[allow(dead_code)]
+        pub const SOURCE_PATH: &'static str = #source_path;
+
+        /// Reads and compiles the GLSL source again, and loads the result in Vulkan.
+        ///
+        /// Returns an error if the source can't be compiled, or if the layout of the new
+        /// module doesn't match the types that were generated at compile time.
+        #[allow(dead_code)]
+        #[allow(unsafe_code)]
+        pub fn reload(&self, device: ::std::sync::Arc<::vulkano::device::Device>)
+                      -> Result<#struct_name, ::vulkano::pipeline::shader_reload::ShaderReloadError>
+        {
+            use ::vulkano::pipeline::shader_reload::ShaderLayoutSignature;
+            use ::vulkano::pipeline::shader_reload::ShaderReloadError;
+
+            let source = ::std::fs::read_to_string(Self::SOURCE_PATH)?;
+
+            let mut compiler = ::shaderc::Compiler::new()
+                .ok_or_else(|| ShaderReloadError::CompilationError("failed to create GLSL compiler".into()))?;
+            let mut compile_options = ::shaderc::CompileOptions::new()
+                .ok_or_else(|| ShaderReloadError::CompilationError("failed to initialize compile option".into()))?;
+            compile_options.set_target_env(::shaderc::TargetEnv::Vulkan, (1 << 22) | (1 << 12));
+            compile_options.set_include_callback(|requested, directive_type, contained_within, _| {
+                let include_directories: &[&str] = &[ #( #include_directories ),* ];
+                let resolved = match directive_type {
+                    ::shaderc::IncludeType::Relative => {
+                        ::std::path::Path::new(contained_within)
+                            .parent()
+                            .map(|parent| parent.join(requested))
+                            .filter(|path| path.is_file())
+                    },
+                    ::shaderc::IncludeType::Standard => {
+                        include_directories.iter()
+                            .map(|dir| ::std::path::Path::new(dir).join(requested))
+                            .find(|path| path.is_file())
+                    },
+                }.ok_or_else(|| format!("Could not resolve the included file `{}`.", requested))?;
+                let content = ::std::fs::read_to_string(&resolved)
+                    .map_err(|_| format!("Could not read the included file `{}`.", requested))?;
+                Ok(::shaderc::ResolvedInclude {
+                    resolved_name: resolved.to_string_lossy().into_owned(),
+                    content,
+                })
+            });
+            #( #macro_defines )*
+
+            let artifact = compiler
+                .compile_into_spirv(&source, #shader_kind, Self::SOURCE_PATH, "main",
+                                    Some(&compile_options))
+                .map_err(|e| ShaderReloadError::CompilationError(e.to_string()))?;
+
+            let expected = ShaderLayoutSignature::from_words(&Self::WORDS)?;
+            let actual = ShaderLayoutSignature::from_words(artifact.as_binary())?;
+            expected.check_compatible(&actual)?;
+
+            unsafe {
+                Ok(#struct_name {
+                    shader: ::vulkano::pipeline::shader::ShaderModule::from_words(device, artifact.as_binary())?
+                })
+            }
+        }
+    }
+}
+
 #[derive(Debug)]
 pub enum Error {
     IoError(IoError),
@@ -498,6 +605,54 @@ mod tests {
         structs::write_structs(&doc);
     }
 
+    #[test]
+    fn test_layout_spec_constants() {
+        // `LEN` determines the layout of the storage buffer, so it must not be specializable,
+        // while `LOCAL` only sizes a local array.
+        let includes: [PathBuf;0] = [];
+        let defines: [(String, String);0] = [];
+        let comp = compile(None, &Path::new(""), "
+        #version 450
+        layout(constant_id = 0) const int LEN = 4;
+        layout(constant_id = 1) const int LOCAL = 8;
+        layout(binding = 0) buffer Data {
+            float values[LEN];
+        };
+        void main() {
+            float tmp[LOCAL];
+            tmp[0] = 1.0;
+            values[0] = tmp[0];
+        }
+        ", ShaderKind::Compute, &includes, &defines).unwrap();
+        let doc = parse::parse_spirv(comp.as_binary()).unwrap();
+        assert_eq!(spec_consts::layout_spec_constants(&doc).len(), 1);
+        let spec_consts = spec_consts::write_specialization_constants(&doc).to_string();
+        assert!(!spec_consts.contains("LEN"));
+        assert!(spec_consts.contains("LOCAL"));
+    }
+
+    #[test]
+    fn test_descriptor_array_of_arrays() {
+        // Arrays of arrays of descriptors can't be described by a `DescriptorDesc`, which must be
+        // reported as an error in the generated code instead of a panic.
+        let includes: [PathBuf;0] = [];
+        let defines: [(String, String);0] = [];
+        let comp = compile(None, &Path::new(""), "
+        #version 450
+        layout(set = 0, binding = 0) uniform sampler2D textures[2][3];
+        layout(set = 0, binding = 1) uniform sampler2D others[4];
+        layout(location = 0) out vec4 color;
+        void main() {
+            color = texture(textures[1][2], vec2(0.0)) + texture(others[3], vec2(0.0));
+        }
+        ", ShaderKind::Fragment, &includes, &defines).unwrap();
+        let doc = parse::parse_spirv(comp.as_binary()).unwrap();
+        let layout = descriptor_sets::write_descriptor_sets(&doc).to_string();
+        assert!(layout.contains("compile_error"));
+        assert!(layout.contains("textures"));
+        assert!(layout.contains("array_count : 4u32"));
+    }
+
     #[test]
     fn test_include_resolution() {
         let root_path = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
@@ -20,6 +20,8 @@ pub fn write_descriptor_sets(doc: &Spirv) -> TokenStream {
 
     // Finding all the descriptors.
     let mut descriptors = Vec::new();
+    // Descriptors that can't be described, reported as compile errors in the generated code.
+    let mut errors = Vec::new();
     struct Descriptor {
         set: u32,
         binding: u32,
@@ -43,12 +45,18 @@ pub fn write_descriptor_sets(doc: &Spirv) -> TokenStream {
         let binding = doc.get_decoration_params(variable_id, Decoration::DecorationBinding).unwrap()[0];
 
         // Find information about the kind of binding for this descriptor.
-        let (desc_ty, readonly, array_count) = descriptor_infos(doc, pointed_ty, storage_class, false)
-            .expect(&format!(
+        let (desc_ty, readonly, array_count) = match descriptor_infos(doc, pointed_ty, storage_class, false) {
+            Ok(Some(infos)) => infos,
+            Ok(None) => panic!(
                 "Couldn't find relevant type for uniform `{}` (type {}, maybe unimplemented)",
                 name,
                 pointed_ty
-            ));
+            ),
+            Err(err) => {
+                errors.push(format!("Unsupported descriptor `{}`: {}", name, err));
+                continue;
+            }
+        };
         descriptors.push(Descriptor { desc_ty, set, binding, array_count, readonly });
     }
 
@@ -116,6 +124,8 @@ pub fn write_descriptor_sets(doc: &Spirv) -> TokenStream {
     );
 
     quote!{
+        #( compile_error!(#errors); )*
+
         #[derive(Debug, Clone)]
         pub struct Layout(pub ShaderStages);
 
@@ -177,9 +187,11 @@ fn pointer_variable_ty(doc: &Spirv, variable: u32) -> (u32, StorageClass) {
 /// Returns a `DescriptorDescTy` constructor, a bool indicating whether the descriptor is
 /// read-only, and the number of array elements.
 ///
+/// Returns an error if the type is valid SPIR-V but can't be described by a `DescriptorDesc`.
+///
 /// See also section 14.5.2 of the Vulkan specs: Descriptor Set Interface
 fn descriptor_infos(doc: &Spirv, pointed_ty: u32, pointer_storage: StorageClass, force_combined_image_sampled: bool)
-    -> Option<(TokenStream, bool, u64)>
+    -> Result<Option<(TokenStream, bool, u64)>, String>
 {
     doc.instructions.iter().filter_map(|i| {
         match i {
@@ -198,7 +210,7 @@ fn descriptor_infos(doc: &Spirv, pointed_ty: u32, pointer_storage: StorageClass,
                     })
                 };
 
-                Some((desc, true, 1))
+                Some(Ok((desc, true, 1)))
             }
             &Instruction::TypeImage { result_id, ref dim, arrayed, ms, sampled, ref format, .. }
                 if result_id == pointed_ty =>
@@ -228,7 +240,7 @@ fn descriptor_infos(doc: &Spirv, pointed_ty: u32, pointer_storage: StorageClass,
                             }
                         };
 
-                        Some((desc, true, 1))
+                        Some(Ok((desc, true, 1)))
                     }
                     Dim::DimBuffer => {
                         // We are a texel buffer.
@@ -240,7 +252,7 @@ fn descriptor_infos(doc: &Spirv, pointed_ty: u32, pointer_storage: StorageClass,
                             }
                         };
 
-                        Some((desc, true, 1))
+                        Some(Ok((desc, true, 1)))
                     }
                     _ => {
                         // We are a sampled or storage image.
@@ -267,35 +279,45 @@ fn descriptor_infos(doc: &Spirv, pointed_ty: u32, pointer_storage: StorageClass,
                             })
                         };
 
-                        Some((desc, true, 1))
+                        Some(Ok((desc, true, 1)))
                     }
                 }
             }
 
             &Instruction::TypeSampledImage { result_id, image_type_id } if result_id == pointed_ty
-                => descriptor_infos(doc, image_type_id, pointer_storage.clone(), true),
+                => descriptor_infos(doc, image_type_id, pointer_storage.clone(), true).transpose(),
 
             &Instruction::TypeSampler { result_id } if result_id == pointed_ty => {
                 let desc = quote!{ DescriptorDescTy::Sampler };
-                Some((desc, true, 1))
+                Some(Ok((desc, true, 1)))
             }
             &Instruction::TypeArray { result_id, type_id, length_id } if result_id == pointed_ty => {
                 let (desc, readonly, arr) = match descriptor_infos(doc, type_id, pointer_storage.clone(), false) {
-                    None => return None,
-                    Some(v) => v,
+                    Ok(None) => return None,
+                    Ok(Some(v)) => v,
+                    Err(err) => return Some(Err(err)),
                 };
-                assert_eq!(arr, 1);     // TODO: implement?
-                let len = doc.instructions.iter().filter_map(|e| {
-                    match e {
-                        &Instruction::Constant { result_id, ref data, .. }
-                            if result_id == length_id => Some(data.clone()),
-                        _ => None
-                    }
-                }).next().expect("failed to find array length");
-                let len = len.iter().rev().fold(0, |a, &b| (a << 32) | b as u64);
-                Some((desc, readonly, len))
+                if arr != 1 {
+                    return Some(Err("arrays of arrays of descriptors aren't supported".to_owned()));
+                }
+                let len = spirv_search::array_length(doc, length_id);
+                Some(Ok((desc, readonly, len)))
+            }
+            &Instruction::TypeRuntimeArray { result_id, type_id } if result_id == pointed_ty => {
+                // The size of runtime arrays (used with descriptor indexing) is only known when
+                // the descriptor set layout is created. We report a single element, and the real
+                // layout is expected to be provided with `PipelineLayoutDescTweaks::with_set_layout`.
+                let (desc, readonly, arr) = match descriptor_infos(doc, type_id, pointer_storage.clone(), false) {
+                    Ok(None) => return None,
+                    Ok(Some(v)) => v,
+                    Err(err) => return Some(Err(err)),
+                };
+                if arr != 1 {
+                    return Some(Err("arrays of arrays of descriptors aren't supported".to_owned()));
+                }
+                Some(Ok((desc, readonly, 1)))
             }
             _ => None, // TODO: other types
         }
-    }).next()
+    }).next().transpose()
 }
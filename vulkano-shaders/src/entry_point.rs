@@ -10,8 +10,9 @@
 use syn::Ident;
 use proc_macro2::{Span, TokenStream};
 
-use crate::enums::{StorageClass, ExecutionModel, ExecutionMode, Decoration};
+use crate::enums::{StorageClass, ExecutionModel, ExecutionMode, Decoration, BuiltIn};
 use crate::parse::{Instruction, Spirv};
+use crate::spec_consts;
 use crate::spirv_search;
 
 pub fn write_entry_point(doc: &Spirv, instruction: &Instruction) -> (TokenStream, TokenStream) {
@@ -55,12 +56,23 @@ pub fn write_entry_point(doc: &Spirv, instruction: &Instruction) -> (TokenStream
 
     let (ty, f_call) = {
         if let ExecutionModel::ExecutionModelGLCompute = *execution {
+            let workgroup_size = match workgroup_size(doc, id) {
+                Some([x, y, z]) => quote!{
+                    .with_workgroup_size(::vulkano::pipeline::shader::WorkgroupSize {
+                        x: #x,
+                        y: #y,
+                        z: #z,
+                    })
+                },
+                None => quote!{},
+            };
+
             (
                 quote!{ ::vulkano::pipeline::shader::ComputeEntryPoint<#spec_consts_struct, Layout> },
                 quote!{ compute_entry_point(
                     ::std::ffi::CStr::from_ptr(NAME.as_ptr() as *const _),
                     Layout(ShaderStages { compute: true, .. ShaderStages::none() })
-                )}
+                ) #workgroup_size }
             )
         } else {
             let entry_ty = match *execution {
@@ -181,11 +193,60 @@ pub fn write_entry_point(doc: &Spirv, instruction: &Instruction) -> (TokenStream
     (interface_structs, entry_point)
 }
 
+/// Returns the tokens of the three `WorkgroupSizeDimension`s of the compute entry point `id`.
+///
+/// The `WorkgroupSize` built-in takes precedence over the `LocalSize` execution mode, as
+/// required by the SPIR-V specification.
+fn workgroup_size(doc: &Spirv, id: u32) -> Option<[TokenStream; 3]> {
+    for instruction in doc.instructions.iter() {
+        let (result_id, constituents) = match instruction {
+            &Instruction::ConstantComposite { result_id, ref constituents, .. } =>
+                (result_id, constituents),
+            &Instruction::SpecConstantComposite { result_id, ref data, .. } => (result_id, data),
+            _ => continue,
+        };
+
+        match doc.get_decoration_params(result_id, Decoration::DecorationBuiltIn) {
+            Some(params) => match BuiltIn::from_num(params[0]) {
+                Ok(BuiltIn::BuiltInWorkgroupSize) => (),
+                _ => continue,
+            },
+            None => continue,
+        }
+
+        let dimension = |n: usize| spec_consts::workgroup_size_dimension(doc, constituents[n]);
+        return Some([dimension(0), dimension(1), dimension(2)]);
+    }
+
+    for instruction in doc.instructions.iter() {
+        match instruction {
+            &Instruction::ExecutionMode {
+                target_id,
+                mode: ExecutionMode::ExecutionModeLocalSize,
+                ref optional_literals,
+            } if target_id == id => {
+                let dimension = |n: usize| {
+                    let value = optional_literals[n];
+                    quote!{ ::vulkano::pipeline::shader::WorkgroupSizeDimension::Constant(#value) }
+                };
+                return Some([dimension(0), dimension(1), dimension(2)]);
+            },
+            _ => (),
+        }
+    }
+
+    None
+}
+
 struct Element {
     location: u32,
     name: String,
     format: String,
     location_len: usize,
+    component: u32,
+    interpolation: &'static str,
+    is_patch: bool,
+    builtin: Option<&'static str>,
 }
 
 fn write_interface_structs(doc: &Spirv, capitalized_ep_name: &str, interface: &[u32],
@@ -204,10 +265,6 @@ fn write_interface_structs(doc: &Spirv, capitalized_ep_name: &str, interface: &[
                     ref storage_class,
                     ..
                 } if &result_id == interface => {
-                    if spirv_search::is_builtin(doc, result_id) {
-                        continue;
-                    }
-
                     let (to_write, ignore_first_array) = match storage_class {
                         &StorageClass::StorageClassInput =>
                             (&mut input_elements, ignore_first_array_in),
@@ -216,6 +273,23 @@ fn write_interface_structs(doc: &Spirv, capitalized_ep_name: &str, interface: &[
                         _ => continue,
                     };
 
+                    // Per-patch variables of tessellation shaders are not arrayed.
+                    let is_patch = doc.get_decoration_params(result_id, Decoration::DecorationPatch).is_some();
+                    let ignore_first_array = ignore_first_array && !is_patch;
+
+                    let mut type_id = pointed_type(doc, result_type_id);
+                    if ignore_first_array {
+                        type_id = match array_type(doc, type_id) {
+                            Some((element_type_id, _)) => element_type_id,
+                            None => panic!("Expected an array for the interface variable (id {})", result_id),
+                        };
+                    }
+
+                    if spirv_search::is_builtin(doc, result_id) {
+                        write_builtin_elements(doc, result_id, type_id, is_patch, to_write);
+                        continue;
+                    }
+
                     let name = spirv_search::name_from_id(doc, result_id);
                     if name == "__unnamed" {
                         continue;
@@ -225,9 +299,62 @@ fn write_interface_structs(doc: &Spirv, capitalized_ep_name: &str, interface: &[
                         Some(l) => l[0],
                         None => panic!("Attribute `{}` (id {}) is missing a location", name, result_id),
                     };
+                    let component = doc.get_decoration_params(result_id, Decoration::DecorationComponent)
+                        .map(|c| c[0])
+                        .unwrap_or(0);
+                    let interpolation = interpolation(doc, result_id).unwrap_or("Smooth");
+
+                    // Structs and arrays of structs occupy consecutive locations, one member after
+                    // the other.
+                    let (struct_id, array_len) = match array_type(doc, type_id) {
+                        Some((element_type_id, len)) if struct_members(doc, element_type_id).is_some() =>
+                            (element_type_id, Some(len)),
+                        _ => (type_id, None),
+                    };
+
+                    if let Some(members) = struct_members(doc, struct_id) {
+                        let mut next_location = location;
+                        for index in 0 .. array_len.unwrap_or(1) {
+                            for (member, &member_type_id) in members.iter().enumerate() {
+                                let member = member as u32;
+                                let member_name = spirv_search::member_name_from_id(doc, struct_id, member);
+                                let name = match array_len {
+                                    Some(_) => format!("{}[{}].{}", name, index, member_name),
+                                    None => format!("{}.{}", name, member_name),
+                                };
+                                let location = match array_len {
+                                    Some(_) => next_location,
+                                    None => doc
+                                        .get_member_decoration_params(struct_id, member, Decoration::DecorationLocation)
+                                        .map(|l| l[0])
+                                        .unwrap_or(next_location),
+                                };
+                                let component = doc
+                                    .get_member_decoration_params(struct_id, member, Decoration::DecorationComponent)
+                                    .map(|c| c[0])
+                                    .unwrap_or(0);
+                                let interpolation = member_interpolation(doc, struct_id, member)
+                                    .unwrap_or(interpolation);
+                                let is_patch = is_patch || doc
+                                    .get_member_decoration_params(struct_id, member, Decoration::DecorationPatch)
+                                    .is_some();
+
+                                let (format, location_len) = spirv_search::format_from_id(doc, member_type_id, false);
+                                next_location = location + location_len as u32;
+                                to_write.push(Element {
+                                    location, name, format, location_len, component, interpolation,
+                                    is_patch, builtin: None,
+                                });
+                            }
+                        }
+                        continue;
+                    }
 
-                    let (format, location_len) = spirv_search::format_from_id(doc, result_type_id, ignore_first_array);
-                    to_write.push(Element { location, name, format, location_len });
+                    let (format, location_len) = spirv_search::format_from_id(doc, type_id, false);
+                    to_write.push(Element {
+                        location, name, format, location_len, component, interpolation, is_patch,
+                        builtin: None,
+                    });
                 },
                 _ => (),
             }
@@ -239,10 +366,141 @@ fn write_interface_structs(doc: &Spirv, capitalized_ep_name: &str, interface: &[
     quote!{ #input #output }
 }
 
+/// Adds the elements of a built-in variable or of a built-in block (such as `gl_PerVertex`) to
+/// `to_write`.
+///
+/// Only the built-ins that are passed from one stage to the next are added.
+fn write_builtin_elements(doc: &Spirv, variable_id: u32, type_id: u32, is_patch: bool,
+                          to_write: &mut Vec<Element>) {
+    if let Some(params) = doc.get_decoration_params(variable_id, Decoration::DecorationBuiltIn) {
+        if let Some(builtin) = interface_builtin(params[0]) {
+            let (format, _) = spirv_search::format_from_id(doc, type_id, false);
+            to_write.push(Element {
+                location: 0,
+                name: spirv_search::name_from_id(doc, variable_id),
+                format,
+                location_len: 0,
+                component: 0,
+                interpolation: "Smooth",
+                is_patch,
+                builtin: Some(builtin),
+            });
+        }
+        return;
+    }
+
+    let members = match struct_members(doc, type_id) {
+        Some(members) => members,
+        None => return,
+    };
+
+    for (member, &member_type_id) in members.iter().enumerate() {
+        let member = member as u32;
+        let builtin = doc
+            .get_member_decoration_params(type_id, member, Decoration::DecorationBuiltIn)
+            .and_then(|params| interface_builtin(params[0]));
+
+        if let Some(builtin) = builtin {
+            let (format, _) = spirv_search::format_from_id(doc, member_type_id, false);
+            to_write.push(Element {
+                location: 0,
+                name: spirv_search::member_name_from_id(doc, type_id, member),
+                format,
+                location_len: 0,
+                component: 0,
+                interpolation: "Smooth",
+                is_patch,
+                builtin: Some(builtin),
+            });
+        }
+    }
+}
+
+/// Returns the name of the `ShaderInterfaceBuiltIn` variant corresponding to a SPIR-V `BuiltIn`,
+/// or `None` if it isn't part of the interface of a stage.
+fn interface_builtin(builtin: u32) -> Option<&'static str> {
+    match BuiltIn::from_num(builtin) {
+        Ok(BuiltIn::BuiltInPosition) => Some("Position"),
+        Ok(BuiltIn::BuiltInPointSize) => Some("PointSize"),
+        Ok(BuiltIn::BuiltInClipDistance) => Some("ClipDistance"),
+        Ok(BuiltIn::BuiltInCullDistance) => Some("CullDistance"),
+        Ok(BuiltIn::BuiltInViewIndex) => Some("ViewIndex"),
+        _ => None,
+    }
+}
+
+/// Returns the name of the `ShaderInterfaceInterpolation` variant of an id, or `None` if it has
+/// no interpolation decoration.
+fn interpolation(doc: &Spirv, id: u32) -> Option<&'static str> {
+    if doc.get_decoration_params(id, Decoration::DecorationFlat).is_some() {
+        Some("Flat")
+    } else if doc.get_decoration_params(id, Decoration::DecorationNoPerspective).is_some() {
+        Some("NoPerspective")
+    } else {
+        None
+    }
+}
+
+/// Same as `interpolation`, but for a member of a struct.
+fn member_interpolation(doc: &Spirv, struct_id: u32, member: u32) -> Option<&'static str> {
+    if doc.get_member_decoration_params(struct_id, member, Decoration::DecorationFlat).is_some() {
+        Some("Flat")
+    } else if doc.get_member_decoration_params(struct_id, member, Decoration::DecorationNoPerspective).is_some() {
+        Some("NoPerspective")
+    } else {
+        None
+    }
+}
+
+/// Returns the type pointed to by `id` if it is a pointer, or `id` otherwise.
+fn pointed_type(doc: &Spirv, id: u32) -> u32 {
+    for instruction in doc.instructions.iter() {
+        match instruction {
+            &Instruction::TypePointer { result_id, type_id, .. } if result_id == id => return type_id,
+            _ => (),
+        }
+    }
+
+    id
+}
+
+/// Returns the element type and the length of an array type, or `None` if `id` is not an array.
+fn array_type(doc: &Spirv, id: u32) -> Option<(u32, u64)> {
+    for instruction in doc.instructions.iter() {
+        match instruction {
+            &Instruction::TypeArray { result_id, type_id, length_id } if result_id == id => {
+                return Some((type_id, spirv_search::array_length(doc, length_id)));
+            },
+            _ => (),
+        }
+    }
+
+    None
+}
+
+/// Returns the types of the members of a struct, or `None` if `id` is not a struct.
+fn struct_members(doc: &Spirv, id: u32) -> Option<&[u32]> {
+    for instruction in doc.instructions.iter() {
+        match instruction {
+            &Instruction::TypeStruct { result_id, ref member_types } if result_id == id => {
+                return Some(member_types);
+            },
+            _ => (),
+        }
+    }
+
+    None
+}
+
 fn write_interface_struct(struct_name_str: &str, attributes: &[Element]) -> TokenStream {
     // Checking for overlapping elements.
-    for (offset, element1) in attributes.iter().enumerate() {
-        for element2 in attributes.iter().skip(offset + 1) {
+    let located = attributes.iter().filter(|e| e.builtin.is_none()).collect::<Vec<_>>();
+    for (offset, element1) in located.iter().enumerate() {
+        for element2 in located.iter().skip(offset + 1) {
+            if element1.component != element2.component {
+                continue;
+            }
+
             if element1.location == element2.location ||
                 (element1.location < element2.location && element1.location + element1.location_len as u32 > element2.location) ||
                 (element2.location < element1.location && element2.location + element2.location_len as u32 > element1.location)
@@ -263,11 +521,21 @@ fn write_interface_struct(struct_name_str: &str, attributes: &[Element]) -> Toke
         .iter()
         .enumerate()
         .map(|(num, element)| {
-            assert!(element.location_len >= 1);
+            assert!(element.location_len >= 1 || element.builtin.is_some());
             let loc = element.location;
             let loc_end = element.location + element.location_len as u32;
             let format = Ident::new(&element.format, Span::call_site());
             let name = &element.name;
+            let component = element.component;
+            let interpolation = Ident::new(element.interpolation, Span::call_site());
+            let is_patch = element.is_patch;
+            let builtin = match element.builtin {
+                Some(builtin) => {
+                    let builtin = Ident::new(builtin, Span::call_site());
+                    quote!{ Some(::vulkano::pipeline::shader::ShaderInterfaceBuiltIn::#builtin) }
+                },
+                None => quote!{ None },
+            };
             let num = num as u16;
 
             quote!{
@@ -277,7 +545,11 @@ fn write_interface_struct(struct_name_str: &str, attributes: &[Element]) -> Toke
                     return Some(::vulkano::pipeline::shader::ShaderInterfaceDefEntry {
                         location: #loc .. #loc_end,
                         format: ::vulkano::format::Format::#format,
-                        name: Some(::std::borrow::Cow::Borrowed(#name))
+                        name: Some(::std::borrow::Cow::Borrowed(#name)),
+                        component: #component,
+                        interpolation: ::vulkano::pipeline::shader::ShaderInterfaceInterpolation::#interpolation,
+                        is_patch: #is_patch,
+                        builtin: #builtin,
                     });
                 }
             }
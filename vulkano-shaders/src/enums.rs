@@ -382,6 +382,7 @@ enumeration! {
         BuiltInSubgroupLocalInvocationId = 41,
         BuiltInVertexIndex = 42,
         BuiltInInstanceIndex = 43,
+        BuiltInViewIndex = 4440,
     } BuiltIn;
 
     typedef enum SelectionControlShift_ {
@@ -58,6 +58,15 @@
 //! specialization constant found in the shader data. Implementations of
 //! `Default` and [`SpecializationConstants`][SpecializationConstants] are also
 //! generated for the struct.
+//! Arrays whose length is a specialization constant are given the default value
+//! of that constant as their length in the generated types. Since specializing
+//! such a constant would change the layout of these types, it is left out of the
+//! struct and always keeps its default value.
+//! Composite specialization constants of vector type, such as `const ivec2 SIZE =
+//! ivec2(WIDTH, 4);`, are exposed as methods of the struct that return their value.
+//! * For compute shaders, the workgroup size declared with `local_size_x` (or with
+//! `local_size_x_id` for specialization constants) is attached to the entry point and can
+//! be retrieved with `ComputeEntryPoint::workgroup_size`.
 //!
 //! All of these generated items will be accessed through the module specified
 //! by `mod_name: foo` If you wanted to store the `Shader` in a struct of your own,
@@ -140,10 +149,22 @@
 //! Adds the given macro definitions to the pre-processor. This is equivalent to passing `-DNAME=VALUE`
 //! on the command line.
 //!
+//! ## `hot_reload: true`
+//!
+//! Generates a `Shader::reload` method that reads the GLSL source from `path` again, compiles it
+//! with [shaderc] and returns a new `Shader` built from the result. Before doing so, the new SPIR-V
+//! is compared with the one embedded at compile time, and an error is returned if its descriptor
+//! sets, push constants or interface no longer match the generated types. This makes it possible
+//! to rebuild pipelines from the reloaded shader while the application is running.
+//!
+//! Can only be used in conjunction with the `path` field. The generated code calls shaderc at
+//! runtime, which means that the crate invoking the macro must depend on `shaderc` itself.
+//!
 //! ## `dump: true`
 //!
 //! The crate fails to compile but prints the generated rust code to stdout.
 //!
+//! [shaderc]: https://docs.rs/shaderc
 //! [reflect]: https://github.com/vulkano-rs/vulkano/blob/master/vulkano-shaders/src/lib.rs#L67
 //! [cargo-expand]: https://github.com/dtolnay/cargo-expand
 //! [ShaderModule::new]: https://docs.rs/vulkano/*/vulkano/pipeline/shader/struct.ShaderModule.html#method.new
@@ -192,11 +213,13 @@ struct MacroInput {
     include_directories: Vec<String>,
     macro_defines: Vec<(String, String)>,
     dump: bool,
+    hot_reload: bool,
 }
 
 impl Parse for MacroInput {
     fn parse(input: ParseStream) -> Result<Self> {
         let mut dump = None;
+        let mut hot_reload = None;
         let mut shader_kind = None;
         let mut source_kind = None;
         let mut include_directories = Vec::new();
@@ -279,6 +302,13 @@ impl Parse for MacroInput {
                     let dump_lit: LitBool = input.parse()?;
                     dump = Some(dump_lit.value);
                 }
+                "hot_reload" => {
+                    if hot_reload.is_some() {
+                        panic!("Only one `hot_reload` can be defined")
+                    }
+                    let hot_reload_lit: LitBool = input.parse()?;
+                    hot_reload = Some(hot_reload_lit.value);
+                }
                 name => panic!(format!("Unknown field name: {}", name))
             }
 
@@ -298,8 +328,15 @@ impl Parse for MacroInput {
         };
 
         let dump = dump.unwrap_or(false);
+        let hot_reload = hot_reload.unwrap_or(false);
 
-        Ok(MacroInput { shader_kind, source_kind, include_directories, dump, macro_defines })
+        if hot_reload {
+            if let SourceKind::Src(_) = source_kind {
+                panic!("`hot_reload` requires the source to be provided with `path`")
+            }
+        }
+
+        Ok(MacroInput { shader_kind, source_kind, include_directories, dump, macro_defines, hot_reload })
     }
 }
 
@@ -337,10 +374,21 @@ pub fn shader(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
         full_include_path
     }).collect::<Vec<_>>();
 
-    let content = match codegen::compile(path, &root_path, &source_code, input.shader_kind, &include_paths, &input.macro_defines) {
+    let content = match codegen::compile(path.clone(), &root_path, &source_code, input.shader_kind, &include_paths, &input.macro_defines) {
         Ok(ok) => ok,
         Err(e) => panic!(e.replace("(s): ", "(s):\n"))
     };
 
-    codegen::reflect("Shader", content.as_binary(), input.dump).unwrap().into()
+    let hot_reload = if input.hot_reload {
+        Some(codegen::HotReload {
+            source_path: root_path.join(path.unwrap()),
+            shader_kind: input.shader_kind,
+            include_directories: include_paths,
+            macro_defines: input.macro_defines,
+        })
+    } else {
+        None
+    };
+
+    codegen::reflect("Shader", content.as_binary(), hot_reload, input.dump).unwrap().into()
 }
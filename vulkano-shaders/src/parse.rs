@@ -131,6 +131,11 @@ pub enum Instruction {
         result_id: u32,
         data: Vec<u32>,
     },
+    ConstantComposite {
+        result_type_id: u32,
+        result_id: u32,
+        constituents: Vec<u32>,
+    },
     SpecConstantTrue { result_type_id: u32, result_id: u32 },
     SpecConstantFalse { result_type_id: u32, result_id: u32 },
     SpecConstant {
@@ -306,6 +311,11 @@ fn decode_instruction(opcode: u16, operands: &[u32]) -> Result<Instruction, Pars
                result_id: operands[1],
                data: operands[2 ..].to_owned(),
            },
+           44 => Instruction::ConstantComposite {
+               result_type_id: operands[0],
+               result_id: operands[1],
+               constituents: operands[2 ..].to_owned(),
+           },
            48 => Instruction::SpecConstantTrue {
                result_type_id: operands[0],
                result_id: operands[1],
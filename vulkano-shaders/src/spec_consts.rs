@@ -7,12 +7,13 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
+use std::collections::HashSet;
 use std::mem;
 
 use syn::Ident;
 use proc_macro2::{Span, TokenStream};
 
-use crate::enums::Decoration;
+use crate::enums::{Decoration, StorageClass};
 use crate::parse::{Instruction, Spirv};
 use crate::spirv_search;
 use crate::structs;
@@ -32,10 +33,112 @@ pub fn has_specialization_constants(doc: &Spirv) -> bool {
     false
 }
 
+/// Returns the tokens of a `WorkgroupSizeDimension` for the constant `id`, which must be a
+/// 32-bit integer constant or specialization constant.
+pub fn workgroup_size_dimension(doc: &Spirv, id: u32) -> TokenStream {
+    for instruction in doc.instructions.iter() {
+        match instruction {
+            &Instruction::Constant { result_id, ref data, .. } if result_id == id => {
+                let value = data[0];
+                return quote!{
+                    ::vulkano::pipeline::shader::WorkgroupSizeDimension::Constant(#value)
+                };
+            },
+            &Instruction::SpecConstant { result_id, ref data, .. } if result_id == id => {
+                let default_value = data[0];
+                let constant_id = doc.get_decoration_params(result_id, Decoration::DecorationSpecId)
+                    .expect("Found workgroup size specialization constant without a SpecId")[0];
+                return quote!{
+                    ::vulkano::pipeline::shader::WorkgroupSizeDimension::SpecializationConstant {
+                        constant_id: #constant_id,
+                        default_value: #default_value,
+                    }
+                };
+            },
+            _ => (),
+        }
+    }
+
+    panic!("Workgroup size constant #{} not found", id)
+}
+
+/// Returns the ids of the specialization constants that are used as the length of an array in
+/// the types generated for the shader, which are descriptors, push constants, interfaces and
+/// structs with an explicit layout.
+///
+/// The generated types use the default value of these constants, so they can't be specialized.
+pub fn layout_spec_constants(doc: &Spirv) -> HashSet<u32> {
+    // Arrays in structs with an explicit layout always have an `ArrayStride`.
+    let mut types = doc.instructions
+        .iter()
+        .filter_map(|i| match i {
+            &Instruction::TypeArray { result_id, .. }
+                if doc.get_decoration_params(result_id, Decoration::DecorationArrayStride)
+                    .is_some() => Some(result_id),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    for instruction in doc.instructions.iter() {
+        match instruction {
+            &Instruction::Variable { result_type_id, ref storage_class, .. } => {
+                match *storage_class {
+                    StorageClass::StorageClassUniformConstant |
+                    StorageClass::StorageClassInput |
+                    StorageClass::StorageClassUniform |
+                    StorageClass::StorageClassOutput |
+                    StorageClass::StorageClassPushConstant |
+                    StorageClass::StorageClassStorageBuffer => types.push(result_type_id),
+                    _ => (),
+                }
+            },
+            _ => (),
+        }
+    }
+
+    let mut visited = HashSet::new();
+    let mut lengths = HashSet::new();
+    while let Some(id) = types.pop() {
+        if !visited.insert(id) {
+            continue;
+        }
+
+        for instruction in doc.instructions.iter() {
+            match instruction {
+                &Instruction::TypePointer { result_id, type_id, .. } |
+                &Instruction::TypeRuntimeArray { result_id, type_id } if result_id == id => {
+                    types.push(type_id);
+                },
+                &Instruction::TypeArray { result_id, type_id, length_id } if result_id == id => {
+                    lengths.insert(length_id);
+                    types.push(type_id);
+                },
+                &Instruction::TypeStruct { result_id, ref member_types } if result_id == id => {
+                    types.extend(member_types.iter().cloned());
+                },
+                _ => (),
+            }
+        }
+    }
+
+    doc.instructions
+        .iter()
+        .filter_map(|i| match i {
+            &Instruction::SpecConstant { result_id, .. } if lengths.contains(&result_id) =>
+                Some(result_id),
+            _ => None,
+        })
+        .collect()
+}
+
 /// Writes the `SpecializationConstants` struct that contains the specialization constants and
 /// implements the `Default` and the `vulkano::pipeline::shader::SpecializationConstants` traits.
+///
+/// Composite specialization constants of vector type are exposed as methods that build their
+/// value from the members of the struct.
 pub fn write_specialization_constants(doc: &Spirv) -> TokenStream {
     struct SpecConst {
+        result_id: u32,
         name: String,
         constant_id: u32,
         rust_ty: TokenStream,
@@ -45,6 +148,7 @@ pub fn write_specialization_constants(doc: &Spirv) -> TokenStream {
     }
 
     let mut spec_consts = Vec::new();
+    let layout_spec_consts = layout_spec_constants(doc);
 
     for instruction in doc.instructions.iter() {
         let (type_id, result_id, default_value) = match instruction {
@@ -60,22 +164,28 @@ pub fn write_specialization_constants(doc: &Spirv) -> TokenStream {
                 };
                 (result_type_id, result_id, def_val)
             }
-            &Instruction::SpecConstantComposite { result_type_id, result_id, ref data } => {
-                let def_val = quote!{
-                    unsafe {{ ::std::mem::transmute([ #( #data ),* ]) }}
-                };
-                (result_type_id, result_id, def_val)
-            }
+            // Composites (such as the `gl_WorkGroupSize` built-in when `local_size_x_id` is
+            // used) are made of other specialization constants, which are handled individually.
             _ => continue,
         };
 
+        // Constants without a `SpecId` can't be set from the pipeline.
+        let constant_id = match doc.get_decoration_params(result_id, Decoration::DecorationSpecId) {
+            Some(params) => params[0],
+            None => continue,
+        };
+
+        // Constants that determine the layout of the generated types must keep their default
+        // value, otherwise the shader wouldn't match these types anymore.
+        if layout_spec_consts.contains(&result_id) {
+            continue;
+        }
+
         let (rust_ty, rust_size, rust_alignment) = spec_const_type_from_id(doc, type_id);
         let rust_size = rust_size.expect("Found runtime-sized specialization constant");
 
-        let constant_id = doc.get_decoration_params(result_id, Decoration::DecorationSpecId)
-            .unwrap()[0];
-
         spec_consts.push(SpecConst {
+            result_id,
             name: spirv_search::name_from_id(doc, result_id),
             constant_id,
             rust_ty,
@@ -108,6 +218,56 @@ pub fn write_specialization_constants(doc: &Spirv) -> TokenStream {
 
     let num_map_entries = map_entries.len();
 
+    let mut composite_methods = vec!();
+    for instruction in doc.instructions.iter() {
+        let (type_id, result_id, constituents) = match instruction {
+            &Instruction::SpecConstantComposite { result_type_id, result_id, ref data } =>
+                (result_type_id, result_id, data),
+            _ => continue,
+        };
+
+        let name = spirv_search::name_from_id(doc, result_id);
+        if name == "__unnamed" || !is_numeric_vector(doc, type_id) {
+            continue;
+        }
+
+        // Each constituent is either a member of the struct, or a constant whose value is
+        // known now.
+        let values = constituents
+            .iter()
+            .map(|&id| {
+                if let Some(spec_const) = spec_consts.iter().find(|c| c.result_id == id) {
+                    let member = Ident::new(&spec_const.name, Span::call_site());
+                    return Some(quote!{ self.#member });
+                }
+
+                doc.instructions
+                    .iter()
+                    .filter_map(|i| match i {
+                        &Instruction::Constant { result_id, ref data, .. } |
+                        &Instruction::SpecConstant { result_id, ref data, .. }
+                            if result_id == id => {
+                            Some(quote!{ unsafe { ::std::mem::transmute([ #( #data ),* ]) } })
+                        },
+                        _ => None,
+                    })
+                    .next()
+            })
+            .collect::<Option<Vec<_>>>();
+        let values = match values {
+            Some(v) => v,
+            None => continue,
+        };
+
+        let method = Ident::new(&name, Span::call_site());
+        let (rust_ty, _, _) = structs::type_from_id(doc, type_id);
+        composite_methods.push(quote!{
+            pub fn #method(&self) -> #rust_ty {
+                [ #( #values ),* ]
+            }
+        });
+    }
+
     let mut struct_members = vec!();
     let mut struct_member_defaults = vec!();
     for spec_const in spec_consts {
@@ -134,6 +294,11 @@ pub fn write_specialization_constants(doc: &Spirv) -> TokenStream {
             }
         }
 
+        #[allow(non_snake_case)]
+        impl SpecializationConstants {
+            #( #composite_methods )*
+        }
+
         unsafe impl SpecConstsTrait for SpecializationConstants {
             fn descriptors() -> &'static [SpecializationMapEntry] {
                 static DESCRIPTORS: [SpecializationMapEntry; #num_map_entries] = [
@@ -145,6 +310,24 @@ pub fn write_specialization_constants(doc: &Spirv) -> TokenStream {
     }
 }
 
+// Returns true if `id` is a vector of integers or floats.
+fn is_numeric_vector(doc: &Spirv, id: u32) -> bool {
+    let component_id = doc.instructions
+        .iter()
+        .filter_map(|i| match i {
+            &Instruction::TypeVector { result_id, component_id, .. } if result_id == id =>
+                Some(component_id),
+            _ => None,
+        })
+        .next();
+
+    doc.instructions.iter().any(|i| match i {
+        &Instruction::TypeInt { result_id, .. } |
+        &Instruction::TypeFloat { result_id, .. } => Some(result_id) == component_id,
+        _ => false,
+    })
+}
+
 // Wrapper around `type_from_id` that also handles booleans.
 fn spec_const_type_from_id(doc: &Spirv, searched: u32) -> (TokenStream, Option<usize>, usize) {
     for instruction in doc.instructions.iter() {
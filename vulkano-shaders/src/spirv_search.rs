@@ -83,16 +83,7 @@ pub fn format_from_id(doc: &Spirv, searched: u32, ignore_first_array: bool) -> (
                 }
 
                 let (format, sz) = format_from_id(doc, type_id, false);
-                let len = doc.instructions
-                    .iter()
-                    .filter_map(|e| match e {
-                        &Instruction::Constant { result_id, ref data, .. }
-                            if result_id == length_id => Some(data.clone()),
-                        _ => None,
-                    })
-                    .next()
-                    .expect("failed to find array length");
-                let len = len.iter().rev().fold(0u64, |a, &b| (a << 32) | b as u64);
+                let len = array_length(doc, length_id);
                 return (format, sz * len as usize);
             },
             &Instruction::TypePointer { result_id, type_id, .. }
@@ -106,6 +97,26 @@ pub fn format_from_id(doc: &Spirv, searched: u32, ignore_first_array: bool) -> (
     panic!("Type #{} not found or invalid", searched)
 }
 
+/// Returns the length of an array from the id of its length operand.
+///
+/// If the length is a specialization constant, its default value is returned. Such constants
+/// are excluded from the generated `SpecializationConstants` struct when the array is part of
+/// a generated type, see `spec_consts::layout_spec_constants`.
+pub fn array_length(doc: &Spirv, length_id: u32) -> u64 {
+    let len = doc.instructions
+        .iter()
+        .filter_map(|e| match e {
+            &Instruction::Constant { result_id, ref data, .. }
+                if result_id == length_id => Some(data.clone()),
+            &Instruction::SpecConstant { result_id, ref data, .. }
+                if result_id == length_id => Some(data.clone()),
+            _ => None,
+        })
+        .next()
+        .expect("failed to find array length");
+    len.iter().rev().fold(0u64, |a, &b| (a << 32) | b as u64)
+}
+
 pub fn name_from_id(doc: &Spirv, searched: u32) -> String {
     for instruction in &doc.instructions {
         if let &Instruction::Name { target_id, ref name } = instruction {
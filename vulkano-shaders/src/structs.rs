@@ -315,16 +315,7 @@ pub fn type_from_id(doc: &Spirv, searched: u32) -> (TokenStream, Option<usize>,
                 debug_assert_eq!(mem::align_of::<[u32; 3]>(), mem::align_of::<u32>());
                 let (ty, t_size, t_align) = type_from_id(doc, type_id);
                 let t_size = t_size.expect("array components must be sized");
-                let len = doc.instructions
-                    .iter()
-                    .filter_map(|e| match e {
-                        &Instruction::Constant { result_id, ref data, .. }
-                            if result_id == length_id => Some(data.clone()),
-                        _ => None,
-                    })
-                    .next()
-                    .expect("failed to find array length");
-                let len = len.iter().rev().fold(0u64, |a, &b| (a << 32) | b as u64);
+                let len = spirv_search::array_length(doc, length_id);
                 let stride = doc.get_decoration_params(searched, Decoration::DecorationArrayStride).unwrap()[0];
                 if stride as usize > t_size {
                     panic!("Not possible to generate a rust array with the correct alignment since the SPIR-V \
@@ -0,0 +1,159 @@
+// Copyright (c) 2026 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+// This file is generated from the Vulkan registry by the `autogen` crate. Don't edit it by hand.
+
+device_extensions! {
+    DeviceExtensions,
+    RawDeviceExtensions,
+    khr_swapchain => b"VK_KHR_swapchain" {
+        promoted_to: None,
+        requires: [],
+        requires_instance: [khr_surface],
+        conflicts: [],
+    },
+    khr_display_swapchain => b"VK_KHR_display_swapchain" {
+        promoted_to: None,
+        requires: [khr_swapchain],
+        requires_instance: [khr_display],
+        conflicts: [],
+    },
+    khr_sampler_mirror_clamp_to_edge => b"VK_KHR_sampler_mirror_clamp_to_edge" {
+        promoted_to: Some(Version::V1_2),
+        requires: [],
+        requires_instance: [],
+        conflicts: [],
+    },
+    khr_maintenance1 => b"VK_KHR_maintenance1" {
+        promoted_to: Some(Version::V1_1),
+        requires: [],
+        requires_instance: [],
+        conflicts: [amd_negative_viewport_height],
+    },
+    khr_get_memory_requirements2 => b"VK_KHR_get_memory_requirements2" {
+        promoted_to: Some(Version::V1_1),
+        requires: [],
+        requires_instance: [],
+        conflicts: [],
+    },
+    khr_dedicated_allocation => b"VK_KHR_dedicated_allocation" {
+        promoted_to: Some(Version::V1_1),
+        requires: [khr_get_memory_requirements2],
+        requires_instance: [],
+        conflicts: [],
+    },
+    khr_incremental_present => b"VK_KHR_incremental_present" {
+        promoted_to: None,
+        requires: [khr_swapchain],
+        requires_instance: [],
+        conflicts: [],
+    },
+    khr_16bit_storage => b"VK_KHR_16bit_storage" {
+        promoted_to: Some(Version::V1_1),
+        requires: [khr_storage_buffer_storage_class],
+        requires_instance: [khr_get_physical_device_properties2],
+        conflicts: [],
+    },
+    khr_storage_buffer_storage_class => b"VK_KHR_storage_buffer_storage_class" {
+        promoted_to: Some(Version::V1_1),
+        requires: [],
+        requires_instance: [],
+        conflicts: [],
+    },
+    khr_multiview => b"VK_KHR_multiview" {
+        promoted_to: Some(Version::V1_1),
+        requires: [],
+        requires_instance: [khr_get_physical_device_properties2],
+        conflicts: [],
+    },
+    khr_maintenance2 => b"VK_KHR_maintenance2" {
+        promoted_to: Some(Version::V1_1),
+        requires: [],
+        requires_instance: [],
+        conflicts: [],
+    },
+    khr_create_renderpass2 => b"VK_KHR_create_renderpass2" {
+        promoted_to: Some(Version::V1_2),
+        requires: [khr_multiview, khr_maintenance2],
+        requires_instance: [],
+        conflicts: [],
+    },
+    khr_depth_stencil_resolve => b"VK_KHR_depth_stencil_resolve" {
+        promoted_to: Some(Version::V1_2),
+        requires: [khr_create_renderpass2],
+        requires_instance: [],
+        conflicts: [],
+    },
+    ext_full_screen_exclusive => b"VK_EXT_full_screen_exclusive" {
+        promoted_to: None,
+        requires: [khr_swapchain],
+        requires_instance: [khr_get_physical_device_properties2, khr_surface, khr_get_surface_capabilities2],
+        conflicts: [],
+    },
+    ext_extended_dynamic_state => b"VK_EXT_extended_dynamic_state" {
+        promoted_to: None,
+        requires: [],
+        requires_instance: [khr_get_physical_device_properties2],
+        conflicts: [],
+    },
+    khr_maintenance3 => b"VK_KHR_maintenance3" {
+        promoted_to: Some(Version::V1_1),
+        requires: [],
+        requires_instance: [khr_get_physical_device_properties2],
+        conflicts: [],
+    },
+    ext_descriptor_indexing => b"VK_EXT_descriptor_indexing" {
+        promoted_to: Some(Version::V1_2),
+        requires: [khr_maintenance3],
+        requires_instance: [khr_get_physical_device_properties2],
+        conflicts: [],
+    },
+    khr_descriptor_update_template => b"VK_KHR_descriptor_update_template" {
+        promoted_to: Some(Version::V1_1),
+        requires: [],
+        requires_instance: [],
+        conflicts: [],
+    },
+    khr_push_descriptor => b"VK_KHR_push_descriptor" {
+        promoted_to: None,
+        requires: [],
+        requires_instance: [khr_get_physical_device_properties2],
+        conflicts: [],
+    },
+    khr_timeline_semaphore => b"VK_KHR_timeline_semaphore" {
+        promoted_to: Some(Version::V1_2),
+        requires: [],
+        requires_instance: [khr_get_physical_device_properties2],
+        conflicts: [],
+    },
+    khr_buffer_device_address => b"VK_KHR_buffer_device_address" {
+        promoted_to: Some(Version::V1_2),
+        requires: [],
+        requires_instance: [khr_get_physical_device_properties2],
+        conflicts: [],
+    },
+    ext_scalar_block_layout => b"VK_EXT_scalar_block_layout" {
+        promoted_to: Some(Version::V1_2),
+        requires: [],
+        requires_instance: [khr_get_physical_device_properties2],
+        conflicts: [],
+    },
+    khr_driver_properties => b"VK_KHR_driver_properties" {
+        promoted_to: Some(Version::V1_2),
+        requires: [],
+        requires_instance: [khr_get_physical_device_properties2],
+        conflicts: [],
+    },
+    amd_negative_viewport_height => b"VK_AMD_negative_viewport_height" {
+        promoted_to: None,
+        requires: [],
+        requires_instance: [],
+        conflicts: [khr_maintenance1],
+    },
+}
@@ -0,0 +1,119 @@
+// Copyright (c) 2026 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+// This file is generated from the Vulkan registry by the `autogen` crate. Don't edit it by hand.
+
+features! {
+    core {
+        robust_buffer_access => robustBufferAccess,
+        full_draw_index_uint32 => fullDrawIndexUint32,
+        image_cube_array => imageCubeArray,
+        independent_blend => independentBlend,
+        geometry_shader => geometryShader,
+        tessellation_shader => tessellationShader,
+        sample_rate_shading => sampleRateShading,
+        dual_src_blend => dualSrcBlend,
+        logic_op => logicOp,
+        multi_draw_indirect => multiDrawIndirect,
+        draw_indirect_first_instance => drawIndirectFirstInstance,
+        depth_clamp => depthClamp,
+        depth_bias_clamp => depthBiasClamp,
+        fill_mode_non_solid => fillModeNonSolid,
+        depth_bounds => depthBounds,
+        wide_lines => wideLines,
+        large_points => largePoints,
+        alpha_to_one => alphaToOne,
+        multi_viewport => multiViewport,
+        sampler_anisotropy => samplerAnisotropy,
+        texture_compression_etc2 => textureCompressionETC2,
+        texture_compression_astc_ldr => textureCompressionASTC_LDR,
+        texture_compression_bc => textureCompressionBC,
+        occlusion_query_precise => occlusionQueryPrecise,
+        pipeline_statistics_query => pipelineStatisticsQuery,
+        vertex_pipeline_stores_and_atomics => vertexPipelineStoresAndAtomics,
+        fragment_stores_and_atomics => fragmentStoresAndAtomics,
+        shader_tessellation_and_geometry_point_size => shaderTessellationAndGeometryPointSize,
+        shader_image_gather_extended => shaderImageGatherExtended,
+        shader_storage_image_extended_formats => shaderStorageImageExtendedFormats,
+        shader_storage_image_multisample => shaderStorageImageMultisample,
+        shader_storage_image_read_without_format => shaderStorageImageReadWithoutFormat,
+        shader_storage_image_write_without_format => shaderStorageImageWriteWithoutFormat,
+        shader_uniform_buffer_array_dynamic_indexing => shaderUniformBufferArrayDynamicIndexing,
+        shader_sampled_image_array_dynamic_indexing => shaderSampledImageArrayDynamicIndexing,
+        shader_storage_buffer_array_dynamic_indexing => shaderStorageBufferArrayDynamicIndexing,
+        shader_storage_image_array_dynamic_indexing => shaderStorageImageArrayDynamicIndexing,
+        shader_clip_distance => shaderClipDistance,
+        shader_cull_distance => shaderCullDistance,
+        shader_f3264 => shaderf3264,
+        shader_int64 => shaderInt64,
+        shader_int16 => shaderInt16,
+        shader_resource_residency => shaderResourceResidency,
+        shader_resource_min_lod => shaderResourceMinLod,
+        sparse_binding => sparseBinding,
+        sparse_residency_buffer => sparseResidencyBuffer,
+        sparse_residency_image2d => sparseResidencyImage2D,
+        sparse_residency_image3d => sparseResidencyImage3D,
+        sparse_residency2_samples => sparseResidency2Samples,
+        sparse_residency4_samples => sparseResidency4Samples,
+        sparse_residency8_samples => sparseResidency8Samples,
+        sparse_residency16_samples => sparseResidency16Samples,
+        sparse_residency_aliased => sparseResidencyAliased,
+        variable_multisample_rate => variableMultisampleRate,
+        inherited_queries => inheritedQueries,
+    },
+    chained {
+        storage_16bit => PhysicalDevice16BitStorageFeaturesKHR [STRUCTURE_TYPE_PHYSICAL_DEVICE_16BIT_STORAGE_FEATURES_KHR, b"VK_KHR_16bit_storage", Some(Version::V1_1)] {
+            storage_buffer_16bit_access => storageBuffer16BitAccess,
+            uniform_and_storage_buffer_16bit_access => uniformAndStorageBuffer16BitAccess,
+            storage_push_constant_16 => storagePushConstant16,
+            storage_input_output_16 => storageInputOutput16,
+        },
+        multiview => PhysicalDeviceMultiviewFeaturesKHR [STRUCTURE_TYPE_PHYSICAL_DEVICE_MULTIVIEW_FEATURES_KHR, b"VK_KHR_multiview", Some(Version::V1_1)] {
+            multiview => multiview,
+            multiview_geometry_shader => multiviewGeometryShader,
+            multiview_tessellation_shader => multiviewTessellationShader,
+        },
+        extended_dynamic_state => PhysicalDeviceExtendedDynamicStateFeaturesEXT [STRUCTURE_TYPE_PHYSICAL_DEVICE_EXTENDED_DYNAMIC_STATE_FEATURES_EXT, b"VK_EXT_extended_dynamic_state", None] {
+            extended_dynamic_state => extendedDynamicState,
+        },
+        descriptor_indexing => PhysicalDeviceDescriptorIndexingFeaturesEXT [STRUCTURE_TYPE_PHYSICAL_DEVICE_DESCRIPTOR_INDEXING_FEATURES_EXT, b"VK_EXT_descriptor_indexing", Some(Version::V1_2)] {
+            shader_input_attachment_array_dynamic_indexing => shaderInputAttachmentArrayDynamicIndexing,
+            shader_uniform_texel_buffer_array_dynamic_indexing => shaderUniformTexelBufferArrayDynamicIndexing,
+            shader_storage_texel_buffer_array_dynamic_indexing => shaderStorageTexelBufferArrayDynamicIndexing,
+            shader_uniform_buffer_array_non_uniform_indexing => shaderUniformBufferArrayNonUniformIndexing,
+            shader_sampled_image_array_non_uniform_indexing => shaderSampledImageArrayNonUniformIndexing,
+            shader_storage_buffer_array_non_uniform_indexing => shaderStorageBufferArrayNonUniformIndexing,
+            shader_storage_image_array_non_uniform_indexing => shaderStorageImageArrayNonUniformIndexing,
+            shader_input_attachment_array_non_uniform_indexing => shaderInputAttachmentArrayNonUniformIndexing,
+            shader_uniform_texel_buffer_array_non_uniform_indexing => shaderUniformTexelBufferArrayNonUniformIndexing,
+            shader_storage_texel_buffer_array_non_uniform_indexing => shaderStorageTexelBufferArrayNonUniformIndexing,
+            descriptor_binding_uniform_buffer_update_after_bind => descriptorBindingUniformBufferUpdateAfterBind,
+            descriptor_binding_sampled_image_update_after_bind => descriptorBindingSampledImageUpdateAfterBind,
+            descriptor_binding_storage_image_update_after_bind => descriptorBindingStorageImageUpdateAfterBind,
+            descriptor_binding_storage_buffer_update_after_bind => descriptorBindingStorageBufferUpdateAfterBind,
+            descriptor_binding_uniform_texel_buffer_update_after_bind => descriptorBindingUniformTexelBufferUpdateAfterBind,
+            descriptor_binding_storage_texel_buffer_update_after_bind => descriptorBindingStorageTexelBufferUpdateAfterBind,
+            descriptor_binding_update_unused_while_pending => descriptorBindingUpdateUnusedWhilePending,
+            descriptor_binding_partially_bound => descriptorBindingPartiallyBound,
+            descriptor_binding_variable_descriptor_count => descriptorBindingVariableDescriptorCount,
+            runtime_descriptor_array => runtimeDescriptorArray,
+        },
+        timeline_semaphore => PhysicalDeviceTimelineSemaphoreFeaturesKHR [STRUCTURE_TYPE_PHYSICAL_DEVICE_TIMELINE_SEMAPHORE_FEATURES_KHR, b"VK_KHR_timeline_semaphore", Some(Version::V1_2)] {
+            timeline_semaphore => timelineSemaphore,
+        },
+        buffer_device_address => PhysicalDeviceBufferDeviceAddressFeaturesKHR [STRUCTURE_TYPE_PHYSICAL_DEVICE_BUFFER_DEVICE_ADDRESS_FEATURES_KHR, b"VK_KHR_buffer_device_address", Some(Version::V1_2)] {
+            buffer_device_address => bufferDeviceAddress,
+            buffer_device_address_capture_replay => bufferDeviceAddressCaptureReplay,
+            buffer_device_address_multi_device => bufferDeviceAddressMultiDevice,
+        },
+        scalar_block_layout => PhysicalDeviceScalarBlockLayoutFeaturesEXT [STRUCTURE_TYPE_PHYSICAL_DEVICE_SCALAR_BLOCK_LAYOUT_FEATURES_EXT, b"VK_EXT_scalar_block_layout", Some(Version::V1_2)] {
+            scalar_block_layout => scalarBlockLayout,
+        },
+    },
+}
@@ -0,0 +1,83 @@
+// Copyright (c) 2026 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+// This file is generated from the Vulkan registry by the `autogen` crate. Don't edit it by hand.
+
+instance_extensions! {
+    InstanceExtensions,
+    RawInstanceExtensions,
+    khr_surface => b"VK_KHR_surface" {
+        promoted_to: None,
+        requires: [],
+    },
+    khr_display => b"VK_KHR_display" {
+        promoted_to: None,
+        requires: [khr_surface],
+    },
+    khr_xlib_surface => b"VK_KHR_xlib_surface" {
+        promoted_to: None,
+        requires: [khr_surface],
+    },
+    khr_xcb_surface => b"VK_KHR_xcb_surface" {
+        promoted_to: None,
+        requires: [khr_surface],
+    },
+    khr_wayland_surface => b"VK_KHR_wayland_surface" {
+        promoted_to: None,
+        requires: [khr_surface],
+    },
+    khr_android_surface => b"VK_KHR_android_surface" {
+        promoted_to: None,
+        requires: [khr_surface],
+    },
+    khr_win32_surface => b"VK_KHR_win32_surface" {
+        promoted_to: None,
+        requires: [khr_surface],
+    },
+    ext_debug_utils => b"VK_EXT_debug_utils" {
+        promoted_to: None,
+        requires: [],
+    },
+    mvk_ios_surface => b"VK_MVK_ios_surface" {
+        promoted_to: None,
+        requires: [khr_surface],
+    },
+    mvk_macos_surface => b"VK_MVK_macos_surface" {
+        promoted_to: None,
+        requires: [khr_surface],
+    },
+    mvk_moltenvk => b"VK_MVK_moltenvk" {
+        promoted_to: None,
+        requires: [],
+    },
+    nn_vi_surface => b"VK_NN_vi_surface" {
+        promoted_to: None,
+        requires: [khr_surface],
+    },
+    ext_swapchain_colorspace => b"VK_EXT_swapchain_colorspace" {
+        promoted_to: None,
+        requires: [khr_surface],
+    },
+    khr_get_physical_device_properties2 => b"VK_KHR_get_physical_device_properties2" {
+        promoted_to: Some(Version::V1_1),
+        requires: [],
+    },
+    khr_get_surface_capabilities2 => b"VK_KHR_get_surface_capabilities2" {
+        promoted_to: None,
+        requires: [khr_surface],
+    },
+    ext_headless_surface => b"VK_EXT_headless_surface" {
+        promoted_to: None,
+        requires: [khr_surface],
+    },
+    khr_external_memory_capabilities => b"VK_KHR_external_memory_capabilities" {
+        promoted_to: Some(Version::V1_1),
+        requires: [khr_get_physical_device_properties2],
+    },
+}
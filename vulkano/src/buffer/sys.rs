@@ -33,6 +33,8 @@ use std::mem;
 use std::mem::MaybeUninit;
 use std::ptr;
 use std::sync::Arc;
+use std::sync::atomic::AtomicU32;
+use std::sync::atomic::Ordering;
 
 use buffer::BufferUsage;
 use device::Device;
@@ -46,6 +48,7 @@ use Error;
 use OomError;
 use VulkanObject;
 use check_errors;
+use version::Version;
 use vk;
 
 /// Data storage in a GPU-accessible location.
@@ -54,6 +57,12 @@ pub struct UnsafeBuffer {
     device: Arc<Device>,
     size: usize,
     usage: vk::BufferUsageFlags,
+
+    // True if the buffer was created with `Sharing::Exclusive`.
+    exclusive_sharing: bool,
+    // Queue family that owns the buffer, or `QUEUE_FAMILY_IGNORED` if the ownership of the buffer
+    // has never been transferred.
+    queue_family_owner: AtomicU32,
 }
 
 impl UnsafeBuffer {
@@ -102,12 +111,12 @@ impl UnsafeBuffer {
             return Err(BufferCreationError::SparseResidencyAliasedFeatureNotEnabled);
         }
 
-        let buffer = {
-            let (sh_mode, sh_indices) = match sharing {
-                Sharing::Exclusive => (vk::SHARING_MODE_EXCLUSIVE, SmallVec::<[u32; 8]>::new()),
-                Sharing::Concurrent(ids) => (vk::SHARING_MODE_CONCURRENT, ids.collect()),
-            };
+        let (sh_mode, sh_indices) = match sharing {
+            Sharing::Exclusive => (vk::SHARING_MODE_EXCLUSIVE, SmallVec::<[u32; 8]>::new()),
+            Sharing::Concurrent(ids) => (vk::SHARING_MODE_CONCURRENT, ids.collect()),
+        };
 
+        let buffer = {
             let infos = vk::BufferCreateInfo {
                 sType: vk::STRUCTURE_TYPE_BUFFER_CREATE_INFO,
                 pNext: ptr::null(),
@@ -133,14 +142,18 @@ impl UnsafeBuffer {
                 al * (1 + (val - 1) / al)
             }
 
-            let mut output = if device.loaded_extensions().khr_get_memory_requirements2 {
+            let mut output = if device.api_version() >= Version::V1_1 ||
+                device.loaded_extensions().khr_get_memory_requirements2
+            {
                 let infos = vk::BufferMemoryRequirementsInfo2KHR {
                     sType: vk::STRUCTURE_TYPE_BUFFER_MEMORY_REQUIREMENTS_INFO_2_KHR,
                     pNext: ptr::null_mut(),
                     buffer: buffer,
                 };
 
-                let mut output2 = if device.loaded_extensions().khr_dedicated_allocation {
+                let mut output2 = if device.api_version() >= Version::V1_1 ||
+                    device.loaded_extensions().khr_dedicated_allocation
+                {
                     Some(vk::MemoryDedicatedRequirementsKHR {
                              sType: vk::STRUCTURE_TYPE_MEMORY_DEDICATED_REQUIREMENTS_KHR,
                              pNext: ptr::null(),
@@ -205,6 +218,8 @@ impl UnsafeBuffer {
             device: device.clone(),
             size: size as usize,
             usage: usage_bits,
+            exclusive_sharing: sh_mode == vk::SHARING_MODE_EXCLUSIVE,
+            queue_family_owner: AtomicU32::new(vk::QUEUE_FAMILY_IGNORED),
         };
 
         Ok((obj, mem_reqs))
@@ -253,6 +268,42 @@ impl UnsafeBuffer {
         self.size
     }
 
+    /// Returns true if the buffer was created with `Sharing::Exclusive`.
+    #[inline]
+    pub fn exclusive_sharing(&self) -> bool {
+        self.exclusive_sharing
+    }
+
+    /// Returns the queue family that currently owns the buffer.
+    ///
+    /// Returns `None` if the buffer was created with `Sharing::Concurrent`, or if its ownership
+    /// has never been transferred between queue families. In that situation the buffer can be
+    /// used from any queue family.
+    #[inline]
+    pub fn queue_family_owner(&self) -> Option<u32> {
+        if !self.exclusive_sharing {
+            return None;
+        }
+
+        match self.queue_family_owner.load(Ordering::SeqCst) {
+            vk::QUEUE_FAMILY_IGNORED => None,
+            family => Some(family),
+        }
+    }
+
+    /// Records that the ownership of the buffer has been transferred to the given queue family.
+    /// Has no effect if the buffer was created with `Sharing::Concurrent`.
+    ///
+    /// # Safety
+    ///
+    /// A command that releases the buffer to `queue_family` must have been submitted.
+    #[inline]
+    pub unsafe fn set_queue_family_owner(&self, queue_family: u32) {
+        if self.exclusive_sharing {
+            self.queue_family_owner.store(queue_family, Ordering::SeqCst);
+        }
+    }
+
     #[inline]
     pub fn usage_transfer_source(&self) -> bool {
         (self.usage & vk::BUFFER_USAGE_TRANSFER_SRC_BIT) != 0
@@ -601,4 +652,24 @@ mod tests {
                                       SparseLevel::none());
         };
     }
+
+    #[test]
+    fn queue_family_owner() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let (buf, _) = unsafe {
+            UnsafeBuffer::new(device.clone(),
+                              128,
+                              BufferUsage::all(),
+                              Sharing::Exclusive::<Empty<_>>,
+                              SparseLevel::none())
+        }.unwrap();
+
+        assert!(buf.exclusive_sharing());
+        assert_eq!(buf.queue_family_owner(), None);
+        unsafe {
+            buf.set_queue_family_owner(queue.family().id());
+        }
+        assert_eq!(buf.queue_family_owner(), Some(queue.family().id()));
+    }
 }
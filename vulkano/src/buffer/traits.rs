@@ -112,6 +112,21 @@ pub unsafe trait BufferAccess: DeviceOwned {
     /// verify whether they actually overlap.
     fn conflict_key(&self) -> (u64, usize);
 
+    /// Returns the queue family that currently owns the buffer, or `None` if the buffer can be
+    /// used from any queue family.
+    ///
+    /// The ownership of a buffer created with `Sharing::Exclusive` is tracked once it has been
+    /// released to another queue family with `release_buffer_ownership`. From then on, it can
+    /// only be used from the queue family it was released to.
+    ///
+    /// The owner changes once the command buffer that releases the buffer has been executed.
+    /// Until then, the buffer can only be used from the destination family by a submission that
+    /// comes after this command buffer in the same chain of futures.
+    #[inline]
+    fn queue_family_owner(&self) -> Option<u32> {
+        self.inner().buffer.queue_family_owner()
+    }
+
     /// Locks the resource for usage on the GPU. Returns an error if the lock can't be acquired.
     ///
     /// This function exists to prevent the user from causing a data race by reading and writing
@@ -43,7 +43,12 @@ use command_buffer::sys::UnsafeCommandBufferBuilderImageAspect;
 use command_buffer::sys::UnsafeCommandBufferBuilderImageBlit;
 use command_buffer::sys::UnsafeCommandBufferBuilderImageCopy;
 use command_buffer::validity::*;
+use descriptor::descriptor_set::DescriptorSet;
 use descriptor::descriptor_set::DescriptorSetsCollection;
+use descriptor::descriptor_set::effective_dynamic_offsets;
+use descriptor::descriptor_set::PersistentDescriptorSetResources;
+use descriptor::descriptor_set::PushDescriptorSet;
+use descriptor::descriptor_set::UnsafeDescriptorSetLayout;
 use descriptor::pipeline_layout::PipelineLayoutAbstract;
 use device::Device;
 use device::DeviceOwned;
@@ -90,6 +95,9 @@ pub struct AutoCommandBufferBuilder<P = StandardCommandPoolBuilder> {
     // True if the queue family supports compute operations.
     compute_allowed: bool,
 
+    // Id of the queue family the command buffer is allocated from.
+    queue_family: u32,
+
     // If we're inside a render pass, contains the render pass and the subpass index.
     render_pass: Option<(Box<dyn RenderPassAbstract>, u32)>,
 
@@ -102,6 +110,11 @@ pub struct AutoCommandBufferBuilder<P = StandardCommandPoolBuilder> {
 
     // Flags passed when creating the command buffer.
     flags: Flags,
+
+    // Layouts of the descriptors pushed with `push_descriptor_set` that haven't been replaced
+    // since, indexed by set number, for the graphics and compute bind points.
+    pushed_graphics_sets: Vec<Option<Arc<UnsafeDescriptorSetLayout>>>,
+    pushed_compute_sets: Vec<Option<Arc<UnsafeDescriptorSetLayout>>>,
 }
 
 impl AutoCommandBufferBuilder<StandardCommandPoolBuilder> {
@@ -389,16 +402,37 @@ impl AutoCommandBufferBuilder<StandardCommandPoolBuilder> {
                    state_cacher,
                    graphics_allowed,
                    compute_allowed,
+                   queue_family: queue_family.id(),
                    render_pass,
                    secondary_cb,
                    subpass_secondary: false,
                    flags,
+                   pushed_graphics_sets: Vec::new(),
+                   pushed_compute_sets: Vec::new(),
                })
         }
     }
 }
 
 impl<P> AutoCommandBufferBuilder<P> {
+    #[inline]
+    fn ensure_primary(&self) -> Result<(), AutoCommandBufferBuilderContextError> {
+        if self.secondary_cb {
+            Err(AutoCommandBufferBuilderContextError::ForbiddenInSecondary)
+        } else {
+            Ok(())
+        }
+    }
+
+    // Returns the queue family the command buffer is allocated from.
+    #[inline]
+    fn own_queue_family(&self) -> QueueFamily<'_> {
+        self.device()
+            .physical_device()
+            .queue_family_by_id(self.queue_family)
+            .unwrap()
+    }
+
     #[inline]
     fn ensure_outside_render_pass(&self) -> Result<(), AutoCommandBufferBuilderContextError> {
         if self.render_pass.is_none() {
@@ -957,7 +991,9 @@ impl<P> AutoCommandBufferBuilder<P> {
 
             self.ensure_outside_render_pass()?;
             check_push_constants_validity(&pipeline, &constants)?;
-            check_descriptor_sets_validity(&pipeline, &sets)?;
+            check_descriptor_sets_validity(&pipeline, &sets, &self.pushed_compute_sets)?;
+            let sets = sets.into_vec();
+            check_dynamic_offsets(&sets)?;
             check_dispatch(pipeline.device(), dimensions)?;
 
             if let StateCacherOutcome::NeedChange =
@@ -971,7 +1007,8 @@ impl<P> AutoCommandBufferBuilder<P> {
                             &mut self.state_cacher,
                             false,
                             pipeline.clone(),
-                            sets)?;
+                            sets,
+                            &mut self.pushed_compute_sets)?;
 
             self.inner.dispatch(dimensions);
             Ok(self)
@@ -994,7 +1031,9 @@ impl<P> AutoCommandBufferBuilder<P> {
             self.ensure_inside_render_pass_inline(&pipeline)?;
             check_dynamic_state_validity(&pipeline, dynamic)?;
             check_push_constants_validity(&pipeline, &constants)?;
-            check_descriptor_sets_validity(&pipeline, &sets)?;
+            check_descriptor_sets_validity(&pipeline, &sets, &self.pushed_graphics_sets)?;
+            let sets = sets.into_vec();
+            check_dynamic_offsets(&sets)?;
             let vb_infos = check_vertex_buffers(&pipeline, vertex_buffer)?;
 
             if let StateCacherOutcome::NeedChange =
@@ -1011,7 +1050,8 @@ impl<P> AutoCommandBufferBuilder<P> {
                             &mut self.state_cacher,
                             true,
                             pipeline.clone(),
-                            sets)?;
+                            sets,
+                            &mut self.pushed_graphics_sets)?;
             vertex_buffers(&mut self.inner,
                            &mut self.state_cacher,
                            vb_infos.vertex_buffers)?;
@@ -1045,7 +1085,9 @@ impl<P> AutoCommandBufferBuilder<P> {
             let ib_infos = check_index_buffer(self.device(), &index_buffer)?;
             check_dynamic_state_validity(&pipeline, dynamic)?;
             check_push_constants_validity(&pipeline, &constants)?;
-            check_descriptor_sets_validity(&pipeline, &sets)?;
+            check_descriptor_sets_validity(&pipeline, &sets, &self.pushed_graphics_sets)?;
+            let sets = sets.into_vec();
+            check_dynamic_offsets(&sets)?;
             let vb_infos = check_vertex_buffers(&pipeline, vertex_buffer)?;
 
             if let StateCacherOutcome::NeedChange =
@@ -1068,7 +1110,8 @@ impl<P> AutoCommandBufferBuilder<P> {
                             &mut self.state_cacher,
                             true,
                             pipeline.clone(),
-                            sets)?;
+                            sets,
+                            &mut self.pushed_graphics_sets)?;
             vertex_buffers(&mut self.inner,
                            &mut self.state_cacher,
                            vb_infos.vertex_buffers)?;
@@ -1108,7 +1151,9 @@ impl<P> AutoCommandBufferBuilder<P> {
             self.ensure_inside_render_pass_inline(&pipeline)?;
             check_dynamic_state_validity(&pipeline, dynamic)?;
             check_push_constants_validity(&pipeline, &constants)?;
-            check_descriptor_sets_validity(&pipeline, &sets)?;
+            check_descriptor_sets_validity(&pipeline, &sets, &self.pushed_graphics_sets)?;
+            let sets = sets.into_vec();
+            check_dynamic_offsets(&sets)?;
             let vb_infos = check_vertex_buffers(&pipeline, vertex_buffer)?;
 
             let draw_count = indirect_buffer.len() as u32;
@@ -1127,7 +1172,8 @@ impl<P> AutoCommandBufferBuilder<P> {
                             &mut self.state_cacher,
                             true,
                             pipeline.clone(),
-                            sets)?;
+                            sets,
+                            &mut self.pushed_graphics_sets)?;
             vertex_buffers(&mut self.inner,
                            &mut self.state_cacher,
                            vb_infos.vertex_buffers)?;
@@ -1167,7 +1213,9 @@ impl<P> AutoCommandBufferBuilder<P> {
             let ib_infos = check_index_buffer(self.device(), &index_buffer)?;
             check_dynamic_state_validity(&pipeline, dynamic)?;
             check_push_constants_validity(&pipeline, &constants)?;
-            check_descriptor_sets_validity(&pipeline, &sets)?;
+            check_descriptor_sets_validity(&pipeline, &sets, &self.pushed_graphics_sets)?;
+            let sets = sets.into_vec();
+            check_dynamic_offsets(&sets)?;
             let vb_infos = check_vertex_buffers(&pipeline, vertex_buffer)?;
 
             let draw_count = indirect_buffer.len() as u32;
@@ -1192,7 +1240,8 @@ impl<P> AutoCommandBufferBuilder<P> {
                             &mut self.state_cacher,
                             true,
                             pipeline.clone(),
-                            sets)?;
+                            sets,
+                            &mut self.pushed_graphics_sets)?;
             vertex_buffers(&mut self.inner,
                            &mut self.state_cacher,
                            vb_infos.vertex_buffers)?;
@@ -1255,6 +1304,8 @@ impl<P> AutoCommandBufferBuilder<P> {
         }
 
         self.state_cacher.invalidate();
+        self.pushed_graphics_sets.clear();
+        self.pushed_compute_sets.clear();
 
         Ok(self)
     }
@@ -1277,10 +1328,55 @@ impl<P> AutoCommandBufferBuilder<P> {
         }
 
         self.state_cacher.invalidate();
+        self.pushed_graphics_sets.clear();
+        self.pushed_compute_sets.clear();
 
         Ok(self)
     }
 
+    /// Pushes descriptors directly into the command buffer, replacing the descriptor set
+    /// `set_num` of the graphics or compute bind point.
+    ///
+    /// The layout of this set in `pipeline_layout` must have been created with
+    /// `UnsafeDescriptorSetLayout::push_descriptor`, and the `VK_KHR_push_descriptor` extension
+    /// must be enabled on the device.
+    ///
+    /// The `sets` passed to the draw and dispatch commands are bound starting from set 0, and
+    /// overwrite any pushed descriptors with the same set number. Therefore the pushed set should
+    /// usually be the last set of the pipeline layout, and the commands that follow should be
+    /// passed the sets that come before it.
+    #[inline]
+    pub fn push_descriptor_set<Pl, R>(mut self, graphics: bool, pipeline_layout: Pl,
+                                      set_num: u32, set: PushDescriptorSet<R>)
+                                      -> Result<Self, PushDescriptorSetError>
+        where Pl: PipelineLayoutAbstract + Send + Sync + 'static,
+              R: PersistentDescriptorSetResources + Send + Sync + 'static
+    {
+        unsafe {
+            if (graphics && !self.graphics_allowed) || (!graphics && !self.compute_allowed) {
+                return Err(AutoCommandBufferBuilderContextError::NotSupportedByQueueFamily.into());
+            }
+
+            check_push_descriptor_set(&pipeline_layout, set_num, &set)?;
+            let layout = set.layout().clone();
+            self.inner
+                .push_descriptor_set(graphics, pipeline_layout, set_num, set)?;
+            self.state_cacher.invalidate_descriptor_sets(graphics);
+
+            let pushed_sets = if graphics {
+                &mut self.pushed_graphics_sets
+            } else {
+                &mut self.pushed_compute_sets
+            };
+            if pushed_sets.len() <= set_num as usize {
+                pushed_sets.resize(set_num as usize + 1, None);
+            }
+            pushed_sets[set_num as usize] = Some(layout);
+
+            Ok(self)
+        }
+    }
+
     /// Adds a command that writes the content of a buffer.
     ///
     /// This function is similar to the `memset` function in C. The `data` parameter is a number
@@ -1342,6 +1438,97 @@ impl<P> AutoCommandBufferBuilder<P> {
         }
     }
 
+    /// Adds a command that releases the ownership of a buffer to another queue family.
+    ///
+    /// The content of a buffer created with `Sharing::Exclusive` is only preserved when it moves
+    /// to a queue of another family if its ownership is transferred. After this command buffer
+    /// has been submitted, the buffer must be acquired with `acquire_buffer_ownership` by a
+    /// command buffer of `destination_family`, and can no longer be used from queues of any other
+    /// family until it is released again.
+    ///
+    /// The buffer must not be used by this command buffer after it has been released.
+    #[inline]
+    pub fn release_buffer_ownership<B>(mut self, buffer: B, destination_family: QueueFamily)
+                                       -> Result<Self, QueueFamilyTransferError>
+        where B: BufferAccess + Send + Sync + 'static
+    {
+        unsafe {
+            self.ensure_outside_render_pass()?;
+            self.ensure_primary()?;
+            let source_family = self.own_queue_family();
+            check_buffer_ownership_transfer(self.device(), &buffer, source_family,
+                                            destination_family)?;
+            self.inner.release_buffer_ownership(buffer, source_family.id(),
+                                                destination_family.id());
+            Ok(self)
+        }
+    }
+
+    /// Adds a command that acquires the ownership of a buffer that has been released by a
+    /// command buffer of `source_family` with `release_buffer_ownership`.
+    ///
+    /// This must be the first use of the buffer in this command buffer.
+    #[inline]
+    pub fn acquire_buffer_ownership<B>(mut self, buffer: B, source_family: QueueFamily)
+                                       -> Result<Self, QueueFamilyTransferError>
+        where B: BufferAccess + Send + Sync + 'static
+    {
+        unsafe {
+            self.ensure_outside_render_pass()?;
+            self.ensure_primary()?;
+            let destination_family = self.own_queue_family();
+            check_buffer_ownership_transfer(self.device(), &buffer, source_family,
+                                            destination_family)?;
+            self.inner.acquire_buffer_ownership(buffer, source_family.id(),
+                                                destination_family.id());
+            Ok(self)
+        }
+    }
+
+    /// Adds a command that releases the ownership of an image to another queue family.
+    ///
+    /// This is the equivalent of `release_buffer_ownership` for images. The image is released in
+    /// the layout returned by its `final_layout_requirement()`.
+    #[inline]
+    pub fn release_image_ownership<I>(mut self, image: I, destination_family: QueueFamily)
+                                      -> Result<Self, QueueFamilyTransferError>
+        where I: ImageAccess + Send + Sync + 'static
+    {
+        unsafe {
+            self.ensure_outside_render_pass()?;
+            self.ensure_primary()?;
+            let source_family = self.own_queue_family();
+            check_image_ownership_transfer(self.device(), &image, source_family,
+                                           destination_family)?;
+            self.inner.release_image_ownership(image, source_family.id(),
+                                               destination_family.id());
+            Ok(self)
+        }
+    }
+
+    /// Adds a command that acquires the ownership of an image that has been released by a
+    /// command buffer of `source_family` with `release_image_ownership`.
+    ///
+    /// The image is acquired in the layout returned by its `initial_layout_requirement()`, which
+    /// must be the same as the layout it was released in. This must be the first use of the image
+    /// in this command buffer.
+    #[inline]
+    pub fn acquire_image_ownership<I>(mut self, image: I, source_family: QueueFamily)
+                                      -> Result<Self, QueueFamilyTransferError>
+        where I: ImageAccess + Send + Sync + 'static
+    {
+        unsafe {
+            self.ensure_outside_render_pass()?;
+            self.ensure_primary()?;
+            let destination_family = self.own_queue_family();
+            check_image_ownership_transfer(self.device(), &image, source_family,
+                                           destination_family)?;
+            self.inner.acquire_image_ownership(image, source_family.id(),
+                                               destination_family.id());
+            Ok(self)
+        }
+    }
+
     /// Adds a command that writes data to a buffer.
     ///
     /// If `data` is larger than the buffer, only the part of `data` that fits is written. If the
@@ -1427,6 +1614,52 @@ unsafe fn set_state<P>(destination: &mut SyncCommandBufferBuilder<P>, dynamic: &
     if let Some(reference) = dynamic.reference {
         destination.set_stencil_reference(reference);
     }
+
+    if let Some(depth_bias) = dynamic.depth_bias {
+        destination.set_depth_bias(depth_bias.constant_factor,
+                                   depth_bias.clamp,
+                                   depth_bias.slope_factor);
+    }
+
+    if let Some(ref depth_bounds) = dynamic.depth_bounds {
+        destination.set_depth_bounds(depth_bounds.start, depth_bounds.end);
+    }
+
+    if let Some(blend_constants) = dynamic.blend_constants {
+        destination.set_blend_constants(blend_constants);
+    }
+
+    if let Some(cull_mode) = dynamic.cull_mode {
+        destination.set_cull_mode(cull_mode);
+    }
+
+    if let Some(front_face) = dynamic.front_face {
+        destination.set_front_face(front_face);
+    }
+
+    if let Some(primitive_topology) = dynamic.primitive_topology {
+        destination.set_primitive_topology(primitive_topology);
+    }
+
+    if let Some(enable) = dynamic.depth_test_enable {
+        destination.set_depth_test_enable(enable);
+    }
+
+    if let Some(enable) = dynamic.depth_write_enable {
+        destination.set_depth_write_enable(enable);
+    }
+
+    if let Some(compare_op) = dynamic.depth_compare_op {
+        destination.set_depth_compare_op(compare_op);
+    }
+
+    if let Some(enable) = dynamic.stencil_test_enable {
+        destination.set_stencil_test_enable(enable);
+    }
+
+    if let Some(stencil_op) = dynamic.stencil_op {
+        destination.set_stencil_op(stencil_op);
+    }
 }
 
 // Shortcut function to bind vertex buffers.
@@ -1460,14 +1693,18 @@ unsafe fn vertex_buffers<P>(destination: &mut SyncCommandBufferBuilder<P>,
     Ok(())
 }
 
-unsafe fn descriptor_sets<P, Pl, S>(destination: &mut SyncCommandBufferBuilder<P>,
-                                    state_cacher: &mut StateCacher, gfx: bool, pipeline: Pl,
-                                    sets: S)
-                                    -> Result<(), SyncCommandBufferBuilderError>
-    where Pl: PipelineLayoutAbstract + Send + Sync + Clone + 'static,
-          S: DescriptorSetsCollection
+unsafe fn descriptor_sets<P, Pl>(destination: &mut SyncCommandBufferBuilder<P>,
+                                 state_cacher: &mut StateCacher, gfx: bool, pipeline: Pl,
+                                 sets: Vec<Box<dyn DescriptorSet + Send + Sync>>,
+                                 pushed_sets: &mut [Option<Arc<UnsafeDescriptorSetLayout>>])
+                                 -> Result<(), SyncCommandBufferBuilderError>
+    where Pl: PipelineLayoutAbstract + Send + Sync + Clone + 'static
 {
-    let sets = sets.into_vec();
+    // The sets are bound starting from set 0, and replace the descriptors that were pushed for
+    // the same set numbers.
+    for pushed in pushed_sets.iter_mut().take(sets.len()) {
+        *pushed = None;
+    }
 
     let first_binding = {
         let mut compare = state_cacher.bind_descriptor_sets(gfx);
@@ -1482,12 +1719,14 @@ unsafe fn descriptor_sets<P, Pl, S>(destination: &mut SyncCommandBufferBuilder<P
         Some(fb) => fb,
     };
 
+    let mut dynamic_offsets = Vec::new();
     let mut sets_binder = destination.bind_descriptor_sets();
     for set in sets.into_iter().skip(first_binding as usize) {
+        dynamic_offsets.extend(effective_dynamic_offsets(&set));
         sets_binder.add(set);
     }
     sets_binder
-        .submit(gfx, pipeline.clone(), first_binding, iter::empty())?;
+        .submit(gfx, pipeline.clone(), first_binding, dynamic_offsets.into_iter())?;
     Ok(())
 }
 
@@ -1702,6 +1941,7 @@ err_gen!(DispatchError {
              AutoCommandBufferBuilderContextError,
              CheckPushConstantsValidityError,
              CheckDescriptorSetsValidityError,
+             CheckDynamicOffsetsError,
              CheckDispatchError,
              SyncCommandBufferBuilderError,
          });
@@ -1711,6 +1951,7 @@ err_gen!(DrawError {
              CheckDynamicStateValidityError,
              CheckPushConstantsValidityError,
              CheckDescriptorSetsValidityError,
+             CheckDynamicOffsetsError,
              CheckVertexBufferError,
              SyncCommandBufferBuilderError,
          });
@@ -1720,6 +1961,7 @@ err_gen!(DrawIndexedError {
              CheckDynamicStateValidityError,
              CheckPushConstantsValidityError,
              CheckDescriptorSetsValidityError,
+             CheckDynamicOffsetsError,
              CheckVertexBufferError,
              CheckIndexBufferError,
              SyncCommandBufferBuilderError,
@@ -1730,6 +1972,7 @@ err_gen!(DrawIndirectError {
              CheckDynamicStateValidityError,
              CheckPushConstantsValidityError,
              CheckDescriptorSetsValidityError,
+             CheckDynamicOffsetsError,
              CheckVertexBufferError,
              SyncCommandBufferBuilderError,
          });
@@ -1739,6 +1982,7 @@ err_gen!(DrawIndexedIndirectError {
              CheckDynamicStateValidityError,
              CheckPushConstantsValidityError,
              CheckDescriptorSetsValidityError,
+             CheckDynamicOffsetsError,
              CheckVertexBufferError,
              CheckIndexBufferError,
              SyncCommandBufferBuilderError,
@@ -1749,6 +1993,17 @@ err_gen!(ExecuteCommandsError {
              SyncCommandBufferBuilderError,
          });
 
+err_gen!(PushDescriptorSetError {
+             AutoCommandBufferBuilderContextError,
+             CheckPushDescriptorSetError,
+             SyncCommandBufferBuilderError,
+         });
+
+err_gen!(QueueFamilyTransferError {
+             AutoCommandBufferBuilderContextError,
+             CheckQueueFamilyTransferError,
+         });
+
 err_gen!(UpdateBufferError {
              AutoCommandBufferBuilderContextError,
              CheckUpdateBufferError,
@@ -90,6 +90,8 @@ pub use self::auto::DrawIndexedIndirectError;
 pub use self::auto::DrawIndirectError;
 pub use self::auto::ExecuteCommandsError;
 pub use self::auto::FillBufferError;
+pub use self::auto::PushDescriptorSetError;
+pub use self::auto::QueueFamilyTransferError;
 pub use self::auto::UpdateBufferError;
 pub use self::state_cacher::StateCacher;
 pub use self::state_cacher::StateCacherOutcome;
@@ -97,14 +99,23 @@ pub use self::traits::CommandBuffer;
 pub use self::traits::CommandBufferExecError;
 pub use self::traits::CommandBufferExecFuture;
 
+use std::ops::Range;
+
 use pipeline::viewport::Scissor;
 use pipeline::viewport::Viewport;
+use pipeline::depth_stencil::Compare;
+use pipeline::depth_stencil::DynamicStencilOps;
 use pipeline::depth_stencil::DynamicStencilValue;
+use pipeline::input_assembly::PrimitiveTopology;
+use pipeline::raster::CullMode;
+use pipeline::raster::DepthBias;
+use pipeline::raster::FrontFace;
 
 pub mod pool;
 pub mod submit;
 pub mod synced;
 pub mod sys;
+pub mod upload;
 pub mod validity;
 
 mod auto;
@@ -148,7 +159,19 @@ pub struct DynamicState {
     pub compare_mask: Option<DynamicStencilValue>,
     pub write_mask: Option<DynamicStencilValue>,
     pub reference: Option<DynamicStencilValue>,
+    pub depth_bias: Option<DepthBias>,
+    pub depth_bounds: Option<Range<f32>>,
+    pub blend_constants: Option<[f32; 4]>,
 
+    // The states below require the `VK_EXT_extended_dynamic_state` extension.
+    pub cull_mode: Option<CullMode>,
+    pub front_face: Option<FrontFace>,
+    pub primitive_topology: Option<PrimitiveTopology>,
+    pub depth_test_enable: Option<bool>,
+    pub depth_write_enable: Option<bool>,
+    pub depth_compare_op: Option<Compare>,
+    pub stencil_test_enable: Option<bool>,
+    pub stencil_op: Option<DynamicStencilOps>,
 }
 
 impl DynamicState {
@@ -160,7 +183,18 @@ impl DynamicState {
             scissors: None,
             compare_mask: None,
             write_mask: None,
-            reference: None
+            reference: None,
+            depth_bias: None,
+            depth_bounds: None,
+            blend_constants: None,
+            cull_mode: None,
+            front_face: None,
+            primitive_topology: None,
+            depth_test_enable: None,
+            depth_write_enable: None,
+            depth_compare_op: None,
+            stencil_test_enable: None,
+            stencil_op: None,
         }
     }
 }
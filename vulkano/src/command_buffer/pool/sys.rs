@@ -24,6 +24,7 @@ use VulkanObject;
 use check_errors;
 use device::Device;
 use device::DeviceOwned;
+use version::Version;
 use vk;
 
 /// Low-level implementation of a command pool.
@@ -135,13 +136,16 @@ impl UnsafeCommandPool {
     ///
     /// Command buffers allocated from the pool are not affected by trimming.
     ///
-    /// This function is supported only if the `VK_KHR_maintenance1` extension was enabled at
-    /// device creation. Otherwise an error is returned.
+    /// This function is supported only if the device supports Vulkan 1.1 or if the
+    /// `VK_KHR_maintenance1` extension was enabled at device creation. Otherwise an error is
+    /// returned.
     /// Since this operation is purely an optimization it is legitimate to call this function and
     /// simply ignore any possible error.
     pub fn trim(&self) -> Result<(), CommandPoolTrimError> {
         unsafe {
-            if !self.device.loaded_extensions().khr_maintenance1 {
+            if !(self.device.api_version() >= Version::V1_1 ||
+                     self.device.loaded_extensions().khr_maintenance1)
+            {
                 return Err(CommandPoolTrimError::Maintenance1ExtensionNotEnabled);
             }
 
@@ -18,6 +18,9 @@ use smallvec::SmallVec;
 use std::ops::Range;
 use vk;
 
+// List of bound descriptor sets, with their dynamic offsets.
+type DescriptorSetsState = SmallVec<[(vk::DescriptorSet, SmallVec<[u32; 4]>); 12]>;
+
 /// Keep track of the state of a command buffer builder, so that you don't need to bind objects
 /// that were already bound.
 ///
@@ -30,10 +33,10 @@ pub struct StateCacher {
     compute_pipeline: vk::Pipeline,
     // The graphics pipeline currently bound. 0 if nothing bound.
     graphics_pipeline: vk::Pipeline,
-    // The descriptor sets for the compute pipeline.
-    compute_descriptor_sets: SmallVec<[vk::DescriptorSet; 12]>,
-    // The descriptor sets for the graphics pipeline.
-    graphics_descriptor_sets: SmallVec<[vk::DescriptorSet; 12]>,
+    // The descriptor sets for the compute pipeline, and their dynamic offsets.
+    compute_descriptor_sets: DescriptorSetsState,
+    // The descriptor sets for the graphics pipeline, and their dynamic offsets.
+    graphics_descriptor_sets: DescriptorSetsState,
     // If the user starts comparing descriptor sets, but drops the helper struct in the middle of
     // the processing then we will end up in a weird state. This bool is true when we start
     // comparing sets, and is set to false when we end up comparing. If it was true when we start
@@ -86,6 +89,17 @@ impl StateCacher {
         self.index_buffer = None;
     }
 
+    /// Forgets the descriptor sets bound to the graphics or compute bind point. You **must** call
+    /// this after pushing descriptors, as they replace a descriptor set.
+    #[inline]
+    pub fn invalidate_descriptor_sets(&mut self, graphics: bool) {
+        if graphics {
+            self.graphics_descriptor_sets = SmallVec::new();
+        } else {
+            self.compute_descriptor_sets = SmallVec::new();
+        }
+    }
+
     /// Compares the current state with `incoming`, and returns a new state that contains the
     /// states that differ and that need to be actually set in the command buffer builder.
     ///
@@ -111,6 +125,17 @@ impl StateCacher {
         cmp!(compare_mask);
         cmp!(reference);
         cmp!(write_mask);
+        cmp!(depth_bias);
+        cmp!(depth_bounds);
+        cmp!(blend_constants);
+        cmp!(cull_mode);
+        cmp!(front_face);
+        cmp!(primitive_topology);
+        cmp!(depth_test_enable);
+        cmp!(depth_write_enable);
+        cmp!(depth_compare_op);
+        cmp!(stencil_test_enable);
+        cmp!(stencil_op);
 
         changed
     }
@@ -238,7 +263,7 @@ pub struct StateCacherDescriptorSets<'s> {
     // Reference to the parent's `poisoned_descriptor_sets`.
     poisoned: &'s mut bool,
     // Reference to the descriptor sets list to compare to.
-    state: &'s mut SmallVec<[vk::DescriptorSet; 12]>,
+    state: &'s mut DescriptorSetsState,
     // Next offset within the list to compare to.
     offset: usize,
     // Contains the return value of `compare`.
@@ -252,17 +277,20 @@ impl<'s> StateCacherDescriptorSets<'s> {
         where S: ?Sized + DescriptorSet
     {
         let raw = set.inner().internal_object();
+        let dynamic_offsets = set.dynamic_offsets();
 
         if self.offset < self.state.len() {
-            if self.state[self.offset] == raw {
+            if self.state[self.offset].0 == raw &&
+                &self.state[self.offset].1[..] == dynamic_offsets
+            {
                 self.offset += 1;
                 return;
             }
 
-            self.state[self.offset] = raw;
+            self.state[self.offset] = (raw, dynamic_offsets.iter().cloned().collect());
 
         } else {
-            self.state.push(raw);
+            self.state.push((raw, dynamic_offsets.iter().cloned().collect()));
         }
 
         if self.found_diff.is_none() {
@@ -358,7 +386,67 @@ impl<'s> StateCacherVertexBuffers<'s> {
 mod tests {
     use buffer::BufferUsage;
     use buffer::CpuAccessibleBuffer;
+    use command_buffer::DynamicState;
     use command_buffer::state_cacher::StateCacher;
+    use pipeline::raster::CullMode;
+    use pipeline::raster::DepthBias;
+
+    #[test]
+    fn dynamic_state_caching() {
+        let mut cacher = StateCacher::new();
+
+        let state = DynamicState {
+            cull_mode: Some(CullMode::Back),
+            depth_write_enable: Some(false),
+            .. DynamicState::none()
+        };
+
+        let changed = cacher.dynamic_state(&state);
+        assert_eq!(changed.cull_mode, Some(CullMode::Back));
+        assert_eq!(changed.depth_write_enable, Some(false));
+
+        let changed = cacher.dynamic_state(&state);
+        assert_eq!(changed.cull_mode, None);
+        assert_eq!(changed.depth_write_enable, None);
+
+        let state = DynamicState {
+            cull_mode: Some(CullMode::Front),
+            .. state
+        };
+        let changed = cacher.dynamic_state(&state);
+        assert_eq!(changed.cull_mode, Some(CullMode::Front));
+        assert_eq!(changed.depth_write_enable, None);
+
+        cacher.invalidate();
+        let changed = cacher.dynamic_state(&state);
+        assert_eq!(changed.cull_mode, Some(CullMode::Front));
+    }
+
+    #[test]
+    fn dynamic_depth_and_blend_caching() {
+        let mut cacher = StateCacher::new();
+
+        let state = DynamicState {
+            depth_bias: Some(DepthBias { constant_factor: 1.0, clamp: 0.0, slope_factor: 2.0 }),
+            depth_bounds: Some(0.0 .. 1.0),
+            blend_constants: Some([0.5; 4]),
+            .. DynamicState::none()
+        };
+
+        let changed = cacher.dynamic_state(&state);
+        assert!(changed.depth_bias.is_some());
+        assert_eq!(changed.depth_bounds, Some(0.0 .. 1.0));
+        assert_eq!(changed.blend_constants, Some([0.5; 4]));
+
+        let state = DynamicState {
+            depth_bounds: Some(0.25 .. 0.75),
+            .. state
+        };
+        let changed = cacher.dynamic_state(&state);
+        assert!(changed.depth_bias.is_none());
+        assert_eq!(changed.depth_bounds, Some(0.25 .. 0.75));
+        assert_eq!(changed.blend_constants, None);
+    }
 
     #[test]
     fn vb_caching_single() {
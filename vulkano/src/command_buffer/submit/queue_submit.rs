@@ -18,6 +18,7 @@ use device::Queue;
 use sync::Fence;
 use sync::PipelineStages;
 use sync::Semaphore;
+use sync::TimelineSemaphore;
 
 use Error;
 use OomError;
@@ -31,8 +32,14 @@ use vk;
 #[derive(Debug)]
 pub struct SubmitCommandBufferBuilder<'a> {
     wait_semaphores: SmallVec<[vk::Semaphore; 16]>,
+    // Value to wait for for each semaphore of `wait_semaphores`. Ignored for binary semaphores.
+    wait_values: SmallVec<[u64; 16]>,
     destination_stages: SmallVec<[vk::PipelineStageFlags; 8]>,
     signal_semaphores: SmallVec<[vk::Semaphore; 16]>,
+    // Value to signal for each semaphore of `signal_semaphores`. Ignored for binary semaphores.
+    signal_values: SmallVec<[u64; 16]>,
+    // True if at least one timeline semaphore has been added.
+    has_timeline_semaphores: bool,
     command_buffers: SmallVec<[vk::CommandBuffer; 4]>,
     fence: vk::Fence,
     marker: PhantomData<&'a ()>,
@@ -44,8 +51,11 @@ impl<'a> SubmitCommandBufferBuilder<'a> {
     pub fn new() -> SubmitCommandBufferBuilder<'a> {
         SubmitCommandBufferBuilder {
             wait_semaphores: SmallVec::new(),
+            wait_values: SmallVec::new(),
             destination_stages: SmallVec::new(),
             signal_semaphores: SmallVec::new(),
+            signal_values: SmallVec::new(),
+            has_timeline_semaphores: false,
             command_buffers: SmallVec::new(),
             fence: 0,
             marker: PhantomData,
@@ -144,9 +154,37 @@ impl<'a> SubmitCommandBufferBuilder<'a> {
         debug_assert!(stages.into_vulkan_bits() != 0);
         // TODO: debug assert that the device supports the stages
         self.wait_semaphores.push(semaphore.internal_object());
+        self.wait_values.push(0);
         self.destination_stages.push(stages.into_vulkan_bits());
     }
 
+    /// Adds a timeline semaphore to be waited upon before the command buffers are executed.
+    ///
+    /// Only the given `stages` of the command buffers will wait until the counter of the
+    /// semaphore is greater than or equal to `value`.
+    ///
+    /// # Safety
+    ///
+    /// - The stages must be supported by the device.
+    ///
+    /// - If you submit this builder, the semaphore must be kept alive until you are guaranteed
+    ///   that the GPU has at least started executing the command buffers.
+    ///
+    /// - If you submit this builder, the counter of the semaphore must eventually reach `value`,
+    ///   either because of another submission or because of a signal from the CPU.
+    ///
+    /// - The fence, command buffers, and semaphores must all belong to the same device.
+    ///
+    #[inline]
+    pub unsafe fn add_wait_timeline_semaphore(&mut self, semaphore: &'a TimelineSemaphore,
+                                              value: u64, stages: PipelineStages) {
+        debug_assert!(stages.into_vulkan_bits() != 0);
+        self.wait_semaphores.push(semaphore.internal_object());
+        self.wait_values.push(value);
+        self.destination_stages.push(stages.into_vulkan_bits());
+        self.has_timeline_semaphores = true;
+    }
+
     /// Adds a command buffer that is executed as part of this command.
     ///
     /// The command buffers are submitted in the order in which they are added.
@@ -193,6 +231,28 @@ impl<'a> SubmitCommandBufferBuilder<'a> {
     #[inline]
     pub unsafe fn add_signal_semaphore(&mut self, semaphore: &'a Semaphore) {
         self.signal_semaphores.push(semaphore.internal_object());
+        self.signal_values.push(0);
+    }
+
+    /// Adds a timeline semaphore whose counter is set to `value` at the end of the submission.
+    ///
+    /// # Safety
+    ///
+    /// - If you submit this builder, the semaphore must be kept alive until you are guaranteed
+    ///   that the GPU has finished executing this submission.
+    ///
+    /// - `value` must be greater than the current value of the counter when queue execution
+    ///   reaches this submission, and than the values of all the other pending signal
+    ///   operations of the semaphore.
+    ///
+    /// - The fence, command buffers, and semaphores must all belong to the same device.
+    ///
+    #[inline]
+    pub unsafe fn add_signal_timeline_semaphore(&mut self, semaphore: &'a TimelineSemaphore,
+                                                value: u64) {
+        self.signal_semaphores.push(semaphore.internal_object());
+        self.signal_values.push(value);
+        self.has_timeline_semaphores = true;
     }
 
     /// Submits the command buffer to the given queue.
@@ -206,10 +266,25 @@ impl<'a> SubmitCommandBufferBuilder<'a> {
             let queue = queue.internal_object_guard();
 
             debug_assert_eq!(self.wait_semaphores.len(), self.destination_stages.len());
+            debug_assert_eq!(self.wait_semaphores.len(), self.wait_values.len());
+            debug_assert_eq!(self.signal_semaphores.len(), self.signal_values.len());
+
+            let timeline_infos = vk::TimelineSemaphoreSubmitInfoKHR {
+                sType: vk::STRUCTURE_TYPE_TIMELINE_SEMAPHORE_SUBMIT_INFO_KHR,
+                pNext: ptr::null(),
+                waitSemaphoreValueCount: self.wait_values.len() as u32,
+                pWaitSemaphoreValues: self.wait_values.as_ptr(),
+                signalSemaphoreValueCount: self.signal_values.len() as u32,
+                pSignalSemaphoreValues: self.signal_values.as_ptr(),
+            };
 
             let batch = vk::SubmitInfo {
                 sType: vk::STRUCTURE_TYPE_SUBMIT_INFO,
-                pNext: ptr::null(),
+                pNext: if self.has_timeline_semaphores {
+                    &timeline_infos as *const _ as *const _
+                } else {
+                    ptr::null()
+                },
                 waitSemaphoreCount: self.wait_semaphores.len() as u32,
                 pWaitSemaphores: self.wait_semaphores.as_ptr(),
                 pWaitDstStageMask: self.destination_stages.as_ptr(),
@@ -235,8 +310,11 @@ impl<'a> SubmitCommandBufferBuilder<'a> {
                 "Can't merge two queue submits that both have a fence");
 
         self.wait_semaphores.extend(other.wait_semaphores);
+        self.wait_values.extend(other.wait_values);
         self.destination_stages.extend(other.destination_stages); // TODO: meh? will be solved if we submit multiple batches
         self.signal_semaphores.extend(other.signal_semaphores);
+        self.signal_values.extend(other.signal_values);
+        self.has_timeline_semaphores |= other.has_timeline_semaphores;
         self.command_buffers.extend(other.command_buffers);
 
         if self.fence == 0 {
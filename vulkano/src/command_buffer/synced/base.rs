@@ -362,6 +362,9 @@ struct ResourceState {
 
     // Current layout at this stage of the building.
     current_layout: ImageLayout,
+
+    // Queue family the resource has been released to by the last command that used it, if any.
+    released_to: Option<u32>,
 }
 
 impl ResourceState {
@@ -375,6 +378,7 @@ impl ResourceState {
             exclusive: self.exclusive_any,
             initial_layout: self.initial_layout,
             final_layout: self.current_layout,
+            queue_family_owner: self.released_to,
         }
     }
 }
@@ -618,6 +622,7 @@ impl<P> SyncCommandBufferBuilder<P> {
                     entry.access = access;
                     entry.exclusive_any = true;
                     entry.exclusive = exclusive;
+                    entry.released_to = None;
                     if exclusive || end_layout != ImageLayout::Undefined {
                         // Only modify the layout in case of a write, because buffer operations
                         // pass `Undefined` for the layout. While a buffer write *must* set the
@@ -708,6 +713,7 @@ impl<P> SyncCommandBufferBuilder<P> {
                     exclusive: actually_exclusive,
                     initial_layout: actual_start_layout,
                     current_layout: end_layout,     // TODO: what if we reach the end with Undefined? that's not correct?
+                    released_to: None,
                 });
             },
         }
@@ -716,6 +722,28 @@ impl<P> SyncCommandBufferBuilder<P> {
         Ok(())
     }
 
+    // Call this after `prev_cmd_resource` if the previous command released the ownership of the
+    // resource to another queue family.
+    //
+    // The command buffer will then only be allowed to be followed by submissions that use the
+    // resource from `queue_family`.
+    pub(super) fn prev_cmd_released_resource(&mut self, resource_ty: KeyTy,
+                                             resource_index: usize, queue_family: u32) {
+        let latest_command_id = self.commands.lock().unwrap().commands.len() - 1;
+
+        let key = BuilderKey {
+            commands: self.commands.clone(),
+            command_ids: RefCell::new(vec![latest_command_id]),
+            resource_ty,
+            resource_index,
+        };
+
+        let state = self.resources
+            .get_mut(&key)
+            .expect("prev_cmd_resource must be called before prev_cmd_released_resource");
+        state.released_to = Some(queue_family);
+    }
+
     /// Builds the command buffer and turns it into a `SyncCommandBuffer`.
     #[inline]
     pub fn build(mut self) -> Result<SyncCommandBuffer<P::Alloc>, OomError>
@@ -840,6 +868,10 @@ struct ResourceFinalState {
 
     // Layout the image will be in at the end of the command buffer.
     final_layout: ImageLayout, // TODO: maybe wrap in an Option to mean that the layout doesn't change? because of buffers?
+
+    // Queue family that owns the resource at the end of the command buffer, if the command
+    // buffer releases it to another queue family.
+    queue_family_owner: Option<u32>,
 }
 
 /// Equivalent to `Command`, but with less methods. Typically contains less things than the
@@ -1123,7 +1155,9 @@ impl<P> SyncCommandBuffer<P> {
                         Err(err) => err,
                     };
 
-                    match (buf.try_gpu_lock(entry.exclusive, queue), prev_err) {
+                    let lock = check_queue_family_owner(buf.queue_family_owner(), queue)
+                        .and_then(|_| buf.try_gpu_lock(entry.exclusive, queue));
+                    match (lock, prev_err) {
                         (Ok(_), _) => (),
                         (Err(err), AccessCheckError::Unknown) |
                         (_, AccessCheckError::Denied(err)) => {
@@ -1155,7 +1189,9 @@ impl<P> SyncCommandBuffer<P> {
                         Err(err) => err
                     };
 
-                    match (img.try_gpu_lock(entry.exclusive, entry.initial_layout), prev_err) {
+                    let lock = check_queue_family_owner(img.queue_family_owner(), queue)
+                        .and_then(|_| img.try_gpu_lock(entry.exclusive, entry.initial_layout));
+                    match (lock, prev_err) {
                         (Ok(_), _) => (),
                         (Err(err), AccessCheckError::Unknown) |
                         (_, AccessCheckError::Denied(err)) => {
@@ -1238,15 +1274,24 @@ impl<P> SyncCommandBuffer<P> {
                 _ => unreachable!(),
             };
 
+            // Resources that have been released to another queue family now belong to it. This
+            // is only done here, once the submission has been executed, so that a failed or
+            // missing submission doesn't change the owner.
             match resource_ty {
                 KeyTy::Buffer => {
                     let cmd = &commands_lock[command_ids[0]];
                     let buf = cmd.buffer(resource_index);
+                    if let Some(queue_family) = val.queue_family_owner {
+                        buf.inner().buffer.set_queue_family_owner(queue_family);
+                    }
                     buf.unlock();
                 },
                 KeyTy::Image => {
                     let cmd = &commands_lock[command_ids[0]];
                     let img = cmd.image(resource_index);
+                    if let Some(queue_family) = val.queue_family_owner {
+                        img.inner().image.set_queue_family_owner(queue_family);
+                    }
                     let trans = if val.final_layout != val.initial_layout {
                         Some(val.final_layout)
                     } else {
@@ -1265,9 +1310,10 @@ impl<P> SyncCommandBuffer<P> {
     pub fn check_buffer_access(
         &self, buffer: &dyn BufferAccess, exclusive: bool, queue: &Queue)
         -> Result<Option<(PipelineStages, AccessFlagBits)>, AccessCheckError> {
-        // TODO: check the queue family
-
         if let Some(value) = self.resources.get(&CbKey::BufferRef(buffer)) {
+            let owner = value.queue_family_owner.or_else(|| buffer.queue_family_owner());
+            check_queue_family_owner(owner, queue).map_err(AccessCheckError::Denied)?;
+
             if !value.exclusive && exclusive {
                 return Err(AccessCheckError::Unknown);
             }
@@ -1285,9 +1331,10 @@ impl<P> SyncCommandBuffer<P> {
     pub fn check_image_access(
         &self, image: &dyn ImageAccess, layout: ImageLayout, exclusive: bool, queue: &Queue)
         -> Result<Option<(PipelineStages, AccessFlagBits)>, AccessCheckError> {
-        // TODO: check the queue family
-
         if let Some(value) = self.resources.get(&CbKey::ImageRef(image)) {
+            let owner = value.queue_family_owner.or_else(|| image.queue_family_owner());
+            check_queue_family_owner(owner, queue).map_err(AccessCheckError::Denied)?;
+
             if layout != ImageLayout::Undefined && value.final_layout != layout {
                 return Err(AccessCheckError::Denied(AccessError::UnexpectedImageLayout {
                                                         allowed: value.final_layout,
@@ -1306,6 +1353,20 @@ impl<P> SyncCommandBuffer<P> {
     }
 }
 
+// Checks whether a resource owned by `owner` can be used from `queue`.
+#[inline]
+fn check_queue_family_owner(owner: Option<u32>, queue: &Queue) -> Result<(), AccessError> {
+    match owner {
+        Some(owner) if owner != queue.family().id() => {
+            Err(AccessError::WrongQueueFamily {
+                    owner,
+                    requested: queue.family().id(),
+                })
+        },
+        _ => Ok(()),
+    }
+}
+
 unsafe impl<P> DeviceOwned for SyncCommandBuffer<P> {
     #[inline]
     fn device(&self) -> &Arc<Device> {
@@ -9,6 +9,7 @@
 
 use std::iter;
 
+use buffer::BufferAccess;
 use buffer::BufferUsage;
 use buffer::CpuAccessibleBuffer;
 use command_buffer::synced::base::SyncCommandBufferBuilder;
@@ -16,6 +17,9 @@ use command_buffer::synced::base::SyncCommandBufferBuilderError;
 use command_buffer::sys::Flags;
 use command_buffer::sys::Kind;
 use device::Device;
+use sync::AccessCheckError;
+use sync::AccessError;
+use sync::now;
 
 #[test]
 fn basic_creation() {
@@ -42,3 +46,54 @@ fn basic_conflict() {
         };
     }
 }
+
+#[test]
+fn released_buffer_denied_to_other_family() {
+    unsafe {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let pool = Device::standard_command_pool(&device, queue.family());
+        let mut sync = SyncCommandBufferBuilder::new(&pool, Kind::primary(), Flags::None).unwrap();
+
+        let buf = CpuAccessibleBuffer::from_data(device, BufferUsage::all(), false, 0u32).unwrap();
+
+        // The command buffer is never submitted, so the destination family doesn't need to exist.
+        let family = queue.family().id();
+        sync.fill_buffer(buf.clone(), 0);
+        sync.release_buffer_ownership(buf.clone(), family, family + 1);
+        let cb = sync.build().unwrap();
+
+        match cb.check_buffer_access(&buf, false, &queue) {
+            Err(AccessCheckError::Denied(AccessError::WrongQueueFamily { owner, requested })) => {
+                assert_eq!(owner, family + 1);
+                assert_eq!(requested, family);
+            },
+            _ => panic!(),
+        };
+    }
+}
+
+#[test]
+fn released_buffer_owner_changes_after_execution() {
+    unsafe {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let pool = Device::standard_command_pool(&device, queue.family());
+        let mut sync = SyncCommandBufferBuilder::new(&pool, Kind::primary(), Flags::None).unwrap();
+
+        let buf = CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), false, 0u32)
+            .unwrap();
+
+        let family = queue.family().id();
+        sync.release_buffer_ownership(buf.clone(), family, family + 1);
+        let cb = sync.build().unwrap();
+
+        // Locking happens before the submission, which may fail or never be made.
+        cb.lock_submit(&now(device), &queue).unwrap();
+        assert_eq!(buf.queue_family_owner(), None);
+
+        // Unlocking happens once the command buffer has been executed.
+        cb.unlock();
+        assert_eq!(buf.queue_family_owner(), Some(family + 1));
+    }
+}
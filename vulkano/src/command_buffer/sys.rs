@@ -24,7 +24,9 @@ use command_buffer::pool::CommandPool;
 use command_buffer::pool::CommandPoolAlloc;
 use command_buffer::pool::CommandPoolBuilderAlloc;
 use descriptor::descriptor::ShaderStages;
+use descriptor::descriptor_set::DescriptorWrite;
 use descriptor::descriptor_set::UnsafeDescriptorSet;
+use descriptor::descriptor_set::raw_descriptor_writes;
 use descriptor::pipeline_layout::PipelineLayoutAbstract;
 use device::Device;
 use device::DeviceOwned;
@@ -46,7 +48,12 @@ use pipeline::GraphicsPipelineAbstract;
 use pipeline::input_assembly::IndexType;
 use pipeline::viewport::Scissor;
 use pipeline::viewport::Viewport;
+use pipeline::depth_stencil::Compare;
 use pipeline::depth_stencil::StencilFaceFlags;
+use pipeline::depth_stencil::StencilOp;
+use pipeline::input_assembly::PrimitiveTopology;
+use pipeline::raster::CullMode;
+use pipeline::raster::FrontFace;
 use query::QueryPipelineStatisticFlags;
 use query::UnsafeQueriesRange;
 use query::UnsafeQuery;
@@ -490,6 +497,41 @@ impl<P> UnsafeCommandBufferBuilder<P> {
                                  dynamic_offsets.as_ptr());
     }
 
+    /// Calls `vkCmdPushDescriptorSetKHR` on the builder.
+    ///
+    /// Does nothing if the list of writes is empty, as it would be a no-op and isn't a valid usage
+    /// of the command anyway.
+    #[inline]
+    pub unsafe fn push_descriptor_set<'w, Pl, I>(&mut self, graphics: bool, pipeline_layout: &Pl,
+                                                 set_num: u32, writes: I)
+        where Pl: ?Sized + PipelineLayoutAbstract,
+              I: Iterator<Item = &'w DescriptorWrite>
+    {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+
+        debug_assert!(set_num < pipeline_layout.num_sets() as u32);
+
+        let bind_point = if graphics {
+            vk::PIPELINE_BIND_POINT_GRAPHICS
+        } else {
+            vk::PIPELINE_BIND_POINT_COMPUTE
+        };
+
+        raw_descriptor_writes(0, writes, |raw_writes| {
+            if raw_writes.is_empty() {
+                return;
+            }
+
+            vk.CmdPushDescriptorSetKHR(cmd,
+                                       bind_point,
+                                       pipeline_layout.sys().internal_object(),
+                                       set_num,
+                                       raw_writes.len() as u32,
+                                       raw_writes.as_ptr());
+        });
+    }
+
     /// Calls `vkCmdBindIndexBuffer` on the builder.
     #[inline]
     pub unsafe fn bind_index_buffer<B>(&mut self, buffer: &B, index_ty: IndexType)
@@ -1295,12 +1337,35 @@ impl<P> UnsafeCommandBufferBuilder<P> {
                              queries.count());
     }
 
+    // Panics if the commands of `VK_EXT_extended_dynamic_state` can't be recorded.
+    #[inline]
+    fn assert_extended_dynamic_state(&self) {
+        assert!(self.device().loaded_extensions().ext_extended_dynamic_state &&
+                    self.device().enabled_features().extended_dynamic_state,
+                "the `ext_extended_dynamic_state` extension and the `extended_dynamic_state` \
+                 feature must be enabled");
+    }
+
     /// Calls `vkCmdSetBlendConstants` on the builder.
     #[inline]
     pub unsafe fn set_blend_constants(&mut self, constants: [f32; 4]) {
         let vk = self.device().pointers();
         let cmd = self.internal_object();
-        vk.CmdSetBlendConstants(cmd, constants); // TODO: correct to pass array?
+        vk.CmdSetBlendConstants(cmd, constants.as_ptr());
+    }
+
+    /// Calls `vkCmdSetCullModeEXT` on the builder.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `ext_extended_dynamic_state` extension or the `extended_dynamic_state`
+    ///   feature isn't enabled on the device.
+    #[inline]
+    pub unsafe fn set_cull_mode(&mut self, cull_mode: CullMode) {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+        self.assert_extended_dynamic_state();
+        vk.CmdSetCullModeEXT(cmd, cull_mode as u32);
     }
 
     /// Calls `vkCmdSetDepthBias` on the builder.
@@ -1322,6 +1387,48 @@ impl<P> UnsafeCommandBufferBuilder<P> {
         vk.CmdSetDepthBounds(cmd, min, max);
     }
 
+    /// Calls `vkCmdSetDepthCompareOpEXT` on the builder.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `ext_extended_dynamic_state` extension or the `extended_dynamic_state`
+    ///   feature isn't enabled on the device.
+    #[inline]
+    pub unsafe fn set_depth_compare_op(&mut self, compare_op: Compare) {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+        self.assert_extended_dynamic_state();
+        vk.CmdSetDepthCompareOpEXT(cmd, compare_op as u32);
+    }
+
+    /// Calls `vkCmdSetDepthTestEnableEXT` on the builder.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `ext_extended_dynamic_state` extension or the `extended_dynamic_state`
+    ///   feature isn't enabled on the device.
+    #[inline]
+    pub unsafe fn set_depth_test_enable(&mut self, enable: bool) {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+        self.assert_extended_dynamic_state();
+        vk.CmdSetDepthTestEnableEXT(cmd, if enable { vk::TRUE } else { vk::FALSE });
+    }
+
+    /// Calls `vkCmdSetDepthWriteEnableEXT` on the builder.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `ext_extended_dynamic_state` extension or the `extended_dynamic_state`
+    ///   feature isn't enabled on the device.
+    #[inline]
+    pub unsafe fn set_depth_write_enable(&mut self, enable: bool) {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+        self.assert_extended_dynamic_state();
+        vk.CmdSetDepthWriteEnableEXT(cmd, if enable { vk::TRUE } else { vk::FALSE });
+    }
+
     /// Calls `vkCmdSetEvent` on the builder.
     #[inline]
     pub unsafe fn set_event(&mut self, event: &Event, stages: PipelineStages) {
@@ -1334,6 +1441,20 @@ impl<P> UnsafeCommandBufferBuilder<P> {
         vk.CmdSetEvent(cmd, event.internal_object(), stages.into_vulkan_bits());
     }
 
+    /// Calls `vkCmdSetFrontFaceEXT` on the builder.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `ext_extended_dynamic_state` extension or the `extended_dynamic_state`
+    ///   feature isn't enabled on the device.
+    #[inline]
+    pub unsafe fn set_front_face(&mut self, front_face: FrontFace) {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+        self.assert_extended_dynamic_state();
+        vk.CmdSetFrontFaceEXT(cmd, front_face as u32);
+    }
+
     /// Calls `vkCmdSetLineWidth` on the builder.
     #[inline]
     pub unsafe fn set_line_width(&mut self, line_width: f32) {
@@ -1343,6 +1464,20 @@ impl<P> UnsafeCommandBufferBuilder<P> {
         vk.CmdSetLineWidth(cmd, line_width);
     }
 
+    /// Calls `vkCmdSetPrimitiveTopologyEXT` on the builder.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `ext_extended_dynamic_state` extension or the `extended_dynamic_state`
+    ///   feature isn't enabled on the device.
+    #[inline]
+    pub unsafe fn set_primitive_topology(&mut self, topology: PrimitiveTopology) {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+        self.assert_extended_dynamic_state();
+        vk.CmdSetPrimitiveTopologyEXT(cmd, topology.into());
+    }
+
 
     /// Calls `vkCmdSetStencilCompareMask` on the builder.
     #[inline]
@@ -1352,6 +1487,41 @@ impl<P> UnsafeCommandBufferBuilder<P> {
         vk.CmdSetStencilCompareMask(cmd, face_mask as u32, compare_mask);
     }
 
+    /// Calls `vkCmdSetStencilOpEXT` on the builder.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `ext_extended_dynamic_state` extension or the `extended_dynamic_state`
+    ///   feature isn't enabled on the device.
+    #[inline]
+    pub unsafe fn set_stencil_op(&mut self, face_mask: StencilFaceFlags, fail_op: StencilOp,
+                                 pass_op: StencilOp, depth_fail_op: StencilOp,
+                                 compare_op: Compare) {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+        self.assert_extended_dynamic_state();
+        vk.CmdSetStencilOpEXT(cmd,
+                              face_mask as u32,
+                              fail_op as u32,
+                              pass_op as u32,
+                              depth_fail_op as u32,
+                              compare_op as u32);
+    }
+
+    /// Calls `vkCmdSetStencilTestEnableEXT` on the builder.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `ext_extended_dynamic_state` extension or the `extended_dynamic_state`
+    ///   feature isn't enabled on the device.
+    #[inline]
+    pub unsafe fn set_stencil_test_enable(&mut self, enable: bool) {
+        let vk = self.device().pointers();
+        let cmd = self.internal_object();
+        self.assert_extended_dynamic_state();
+        vk.CmdSetStencilTestEnableEXT(cmd, if enable { vk::TRUE } else { vk::FALSE });
+    }
+
 
     /// Calls `vkCmdSetStencilReference` on the builder.
     #[inline]
@@ -0,0 +1,1206 @@
+// Copyright (c) 2026 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Batched uploads of immutable resources through a dedicated transfer queue.
+//!
+//! `ImmutableBuffer::from_data` and `ImmutableImage::from_iter` allocate a staging buffer and
+//! submit a command buffer on the queue you pass for every single resource. When loading or
+//! streaming many resources this is wasteful, and it occupies the graphics queue with copies.
+//!
+//! The `UploadManager` instead copies the data of the resources into a shared staging ring
+//! buffer, and records the copies of many resources in a single command buffer that is submitted
+//! on a transfer queue. If the transfer queue and the queue that will use the resources
+//! (called the *destination queue*) belong to different families, the ownership of the resources
+//! is automatically transferred to the family of the destination queue.
+//!
+//! Each upload returns the newly-created resource and an `UploadFuture`. This future must be
+//! joined with the work that uses the resource, exactly like the future returned by
+//! `ImmutableBuffer::from_data`.
+//!
+//! Uploads are not submitted immediately. Call `submit_frame` once per frame to submit the pending
+//! uploads within the frame budget (see `set_frame_budget`), or `submit_all` to submit all of them.
+//! Flushing an `UploadFuture` whose upload is still pending submits it immediately, regardless of
+//! the budget.
+
+use std::cmp;
+use std::collections::VecDeque;
+use std::error;
+use std::fmt;
+use std::iter;
+use std::marker::PhantomData;
+use std::mem;
+use std::slice;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use buffer::BufferAccess;
+use buffer::BufferInner;
+use buffer::BufferUsage;
+use buffer::ImmutableBuffer;
+use buffer::TypedBufferAccess;
+use buffer::immutable::ImmutableBufferInitialization;
+use buffer::sys::BufferCreationError;
+use buffer::sys::SparseLevel;
+use buffer::sys::UnsafeBuffer;
+use command_buffer::AutoCommandBufferBuilder;
+use command_buffer::BuildError;
+use command_buffer::CommandBufferExecError;
+use command_buffer::submit::SubmitAnyBuilder;
+use command_buffer::validity::CheckCopyBufferImageError;
+use command_buffer::validity::CheckCopyBufferImageTy;
+use command_buffer::validity::check_copy_buffer_image;
+use device::Device;
+use device::DeviceOwned;
+use device::Queue;
+use format::AcceptsPixels;
+use format::Format;
+use format::FormatDesc;
+use image::Dimensions;
+use image::ImageAccess;
+use image::ImageInner;
+use image::ImageLayout;
+use image::ImageUsage;
+use image::ImmutableImage;
+use image::MipmapsCount;
+use image::immutable::ImmutableImageInitialization;
+use image::sys::ImageCreationError;
+use instance::QueueFamily;
+use memory::DedicatedAlloc;
+use memory::DeviceMemoryAllocError;
+use memory::pool::AllocFromRequirementsFilter;
+use memory::pool::AllocLayout;
+use memory::pool::MappingRequirement;
+use memory::pool::MemoryPool;
+use memory::pool::MemoryPoolAlloc;
+use memory::pool::PotentialDedicatedAllocation;
+use memory::pool::StdMemoryPoolAlloc;
+use sync::AccessCheckError;
+use sync::AccessError;
+use sync::AccessFlagBits;
+use sync::FenceSignalFuture;
+use sync::FlushError;
+use sync::GpuFuture;
+use sync::PipelineStages;
+use sync::Sharing;
+use sync::now;
+use VulkanObject;
+
+/// Collects uploads of immutable buffers and images, and submits them in batches on a transfer
+/// queue.
+///
+/// See the documentation of the `upload` module for more information.
+pub struct UploadManager {
+    inner: Arc<UploadManagerInner>,
+}
+
+// Content shared between the manager and the futures it returns.
+struct UploadManagerInner {
+    transfer_queue: Arc<Queue>,
+    destination_queue: Arc<Queue>,
+    staging: Arc<StagingBuffer>,
+    state: Mutex<UploadManagerState>,
+}
+
+struct UploadManagerState {
+    // Uploads that haven't been submitted yet, in the order in which they were requested.
+    pending: VecDeque<PendingUpload>,
+    // Batches that have been submitted and that may still be reading from the staging buffer,
+    // from the oldest to the newest.
+    in_flight: VecDeque<InFlightBatch>,
+    ring: StagingRing,
+    frame_budget: Option<usize>,
+}
+
+struct PendingUpload {
+    data: Vec<u8>,
+    alignment: usize,
+    target: Box<dyn UploadTarget + Send + Sync>,
+    slot: Arc<UploadSlot>,
+}
+
+struct InFlightBatch {
+    future: Arc<UploadBatchFuture>,
+    // Value of the head of the staging ring after the allocations of this batch.
+    staging_end: usize,
+}
+
+type UploadBatchFuture = FenceSignalFuture<Box<dyn GpuFuture + Send + Sync>>;
+
+// Filled with the future of the batch that contains an upload once this batch has been
+// submitted, or with the error that prevented its submission.
+type UploadSlot = Mutex<Option<Result<Arc<UploadBatchFuture>, FlushError>>>;
+
+impl UploadManager {
+    /// Builds a new `UploadManager` that submits its uploads on `transfer_queue`, and whose
+    /// resources are meant to be used on `destination_queue`.
+    ///
+    /// `staging_size` is the size in bytes of the staging ring buffer. A single upload can't be
+    /// larger than this size.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the two queues don't belong to the same device.
+    /// - Panics if `staging_size` is 0.
+    ///
+    pub fn new(transfer_queue: Arc<Queue>, destination_queue: Arc<Queue>, staging_size: usize)
+               -> Result<UploadManager, DeviceMemoryAllocError> {
+        assert_eq!(transfer_queue.device().internal_object(),
+                   destination_queue.device().internal_object());
+        assert!(staging_size != 0);
+
+        let staging = StagingBuffer::new(transfer_queue.device().clone(), staging_size)?;
+
+        Ok(UploadManager {
+               inner: Arc::new(UploadManagerInner {
+                                   transfer_queue,
+                                   destination_queue,
+                                   staging: Arc::new(staging),
+                                   state: Mutex::new(UploadManagerState {
+                                                         pending: VecDeque::new(),
+                                                         in_flight: VecDeque::new(),
+                                                         ring: StagingRing::new(staging_size),
+                                                         frame_budget: None,
+                                                     }),
+                               }),
+           })
+    }
+
+    /// Returns the queue on which the uploads are submitted.
+    #[inline]
+    pub fn transfer_queue(&self) -> &Arc<Queue> {
+        &self.inner.transfer_queue
+    }
+
+    /// Returns the queue that the uploaded resources are meant to be used on.
+    #[inline]
+    pub fn destination_queue(&self) -> &Arc<Queue> {
+        &self.inner.destination_queue
+    }
+
+    /// Returns the size in bytes of the staging ring buffer.
+    #[inline]
+    pub fn staging_size(&self) -> usize {
+        self.inner.staging.inner.size()
+    }
+
+    /// Returns the maximum number of bytes that `submit_frame` submits at once.
+    #[inline]
+    pub fn frame_budget(&self) -> Option<usize> {
+        self.inner.state.lock().unwrap().frame_budget
+    }
+
+    /// Sets the maximum number of bytes that `submit_frame` submits at once. `None`, which is the
+    /// default, means that there is no limit other than the size of the staging buffer.
+    ///
+    /// An upload that is larger than the budget is still submitted if it is the first one of its
+    /// frame, so that it doesn't stay pending forever.
+    #[inline]
+    pub fn set_frame_budget(&self, budget: Option<usize>) {
+        self.inner.state.lock().unwrap().frame_budget = budget;
+    }
+
+    /// Returns the number of uploads that haven't been submitted yet.
+    #[inline]
+    pub fn num_pending(&self) -> usize {
+        self.inner.state.lock().unwrap().pending.len()
+    }
+
+    /// Queues the upload of `data` to a new `ImmutableBuffer`.
+    ///
+    /// Like `CpuAccessibleBuffer`, the upload never runs the destructor of `data`.
+    pub fn upload_data<T>(&self, data: T, usage: BufferUsage)
+                          -> Result<(Arc<ImmutableBuffer<T>>, UploadFuture), UploadError>
+        where T: 'static + Send + Sync
+    {
+        let data = unsafe {
+            let bytes = slice::from_raw_parts(&data as *const T as *const u8, mem::size_of::<T>())
+                .to_vec();
+            mem::forget(data);
+            bytes
+        };
+
+        self.upload_buffer_bytes(data, usage)
+    }
+
+    /// Queues the upload of the content of an iterator to a new `ImmutableBuffer`.
+    ///
+    /// Like `CpuAccessibleBuffer`, the upload never runs the destructor of the elements.
+    pub fn upload_iter<T, D>(&self, data: D, usage: BufferUsage)
+                             -> Result<(Arc<ImmutableBuffer<[T]>>, UploadFuture), UploadError>
+        where D: ExactSizeIterator<Item = T>,
+              T: 'static + Send + Sync
+    {
+        self.upload_buffer_bytes(iter_to_bytes(data), usage)
+    }
+
+    /// Queues the upload of the content of an iterator to a new `ImmutableImage`.
+    ///
+    /// The image is created with the same usage and layout as `ImmutableImage::from_iter`.
+    pub fn upload_image<P, I, F>(&self, iter: I, dimensions: Dimensions, format: F)
+                                 -> Result<(Arc<ImmutableImage<F>>, UploadFuture), UploadError>
+        where P: Send + Sync + Clone + 'static,
+              F: FormatDesc + AcceptsPixels<P> + 'static + Send + Sync,
+              I: ExactSizeIterator<Item = P>,
+              Format: AcceptsPixels<P>
+    {
+        let data = iter_to_bytes(iter);
+        self.check_size(data.len())?;
+
+        let usage = ImageUsage {
+            transfer_destination: true,
+            sampled: true,
+            ..ImageUsage::none()
+        };
+
+        // The offset of a copy from a buffer to an image must be a multiple of both 4 and the size
+        // of a texel.
+        let texel_size = format.format().size().unwrap_or(mem::size_of::<P>());
+        let alignment = least_common_multiple(4, cmp::max(texel_size, 1));
+
+        let (image, init) = ImmutableImage::uninitialized(self.device().clone(),
+                                                          dimensions,
+                                                          format,
+                                                          MipmapsCount::One,
+                                                          usage,
+                                                          ImageLayout::ShaderReadOnlyOptimal,
+                                                          iter::once(self.inner
+                                                                         .transfer_queue
+                                                                         .family()))?;
+        let init = Arc::new(init);
+
+        // Checking the copy now, as the command buffer is only recorded when the upload is
+        // submitted.
+        {
+            let staging = StagingRegion::<[P]>::new(self.inner.staging.clone(), 0, data.len());
+            check_copy_buffer_image(self.device(),
+                                    &staging,
+                                    &init,
+                                    CheckCopyBufferImageTy::BufferToImage,
+                                    [0, 0, 0],
+                                    dimensions.width_height_depth(),
+                                    0,
+                                    dimensions.array_layers_with_cube(),
+                                    0)?;
+        }
+
+        let target = ImageTarget {
+            image: image.clone(),
+            init,
+            dimensions,
+            marker: PhantomData,
+        };
+
+        let future = self.push(data, alignment, Box::new(target));
+        Ok((image, future))
+    }
+
+    /// Submits the pending uploads, in the order in which they were requested, until the frame
+    /// budget is reached or the staging buffer is full. Meant to be called once per frame.
+    ///
+    /// The uploads that don't fit stay pending until the next call.
+    pub fn submit_frame(&self) -> Result<(), FlushError> {
+        let mut state = self.inner.state.lock().unwrap();
+        let budget = state.frame_budget;
+        self.inner.submit_batch(&mut state, budget, false)?;
+        Ok(())
+    }
+
+    /// Submits all the pending uploads, regardless of the frame budget.
+    ///
+    /// Blocks the current thread if the staging buffer is full, until the uploads that use it are
+    /// finished.
+    pub fn submit_all(&self) -> Result<(), FlushError> {
+        let mut state = self.inner.state.lock().unwrap();
+        while self.inner.submit_batch(&mut state, None, true)? {}
+        Ok(())
+    }
+
+    /// Releases the parts of the staging buffer that are used by finished uploads.
+    ///
+    /// This is done automatically when submitting uploads.
+    #[inline]
+    pub fn cleanup_finished(&self) {
+        self.inner.state.lock().unwrap().cleanup_finished();
+    }
+
+    fn upload_buffer_bytes<T>(&self, data: Vec<u8>, usage: BufferUsage)
+                              -> Result<(Arc<ImmutableBuffer<T>>, UploadFuture), UploadError>
+        where T: ?Sized + 'static + Send + Sync
+    {
+        self.check_size(data.len())?;
+
+        let usage = BufferUsage {
+            transfer_destination: true,
+            ..usage
+        };
+
+        let (buffer, init) = unsafe {
+            ImmutableBuffer::raw(self.device().clone(),
+                                 data.len(),
+                                 usage,
+                                 iter::once(self.inner.transfer_queue.family()))?
+        };
+
+        let future = self.push(data, 4, Box::new(BufferTarget { init }));
+        Ok((buffer, future))
+    }
+
+    fn check_size(&self, size: usize) -> Result<(), UploadError> {
+        if size == 0 {
+            return Err(UploadError::EmptyUpload);
+        }
+
+        let capacity = self.staging_size();
+        if size > capacity {
+            return Err(UploadError::UploadTooLarge {
+                           size,
+                           capacity,
+                       });
+        }
+
+        Ok(())
+    }
+
+    fn push(&self, data: Vec<u8>, alignment: usize, target: Box<dyn UploadTarget + Send + Sync>)
+            -> UploadFuture {
+        let slot = Arc::new(Mutex::new(None));
+
+        self.inner.state.lock().unwrap().pending.push_back(PendingUpload {
+                                                               data,
+                                                               alignment,
+                                                               target,
+                                                               slot: slot.clone(),
+                                                           });
+
+        UploadFuture {
+            manager: self.inner.clone(),
+            slot,
+        }
+    }
+}
+
+unsafe impl DeviceOwned for UploadManager {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.inner.transfer_queue.device()
+    }
+}
+
+impl UploadManagerInner {
+    // Records and submits a batch of pending uploads. Returns `false` if there was nothing to
+    // submit.
+    //
+    // If `block` is true and the staging buffer is too full for the first pending upload, waits
+    // for the oldest batches to finish. Otherwise the upload stays pending.
+    fn submit_batch(&self, state: &mut UploadManagerState, budget: Option<usize>, block: bool)
+                    -> Result<bool, FlushError> {
+        state.cleanup_finished();
+
+        if state.pending.is_empty() {
+            return Ok(false);
+        }
+
+        let device = self.transfer_queue.device();
+        let transfer_family = self.transfer_queue.family();
+        let destination_family = self.destination_queue.family();
+
+        let mut transfer =
+            AutoCommandBufferBuilder::primary_one_time_submit(device.clone(), transfer_family)
+                .map_err(FlushError::OomError)?;
+        let mut acquire =
+            AutoCommandBufferBuilder::primary_one_time_submit(device.clone(), destination_family)
+                .map_err(FlushError::OomError)?;
+
+        let ring_before = state.ring.clone();
+        let mut slots = Vec::new();
+        let mut batch_size = 0;
+
+        while let Some((size, alignment)) = state
+            .pending
+            .front()
+            .map(|upload| (upload.data.len(), upload.alignment))
+        {
+
+            if let Some(budget) = budget {
+                if !slots.is_empty() && batch_size + size > budget {
+                    break;
+                }
+            }
+
+            let offset = match state.ring.allocate(size, alignment) {
+                Some(offset) => offset,
+                None if block && slots.is_empty() => {
+                    let oldest = state
+                        .in_flight
+                        .pop_front()
+                        .expect("upload larger than the staging buffer");
+                    oldest.future.wait(None)?;
+                    let in_use = !state.in_flight.is_empty();
+                    state.ring.free_until(oldest.staging_end, in_use);
+                    continue;
+                },
+                None => break,
+            };
+
+            let upload = state.pending.pop_front().unwrap();
+
+            unsafe {
+                let memory = &self.staging.memory;
+                let start = memory.offset() + offset;
+                let mut access = memory
+                    .mapped_memory()
+                    .unwrap()
+                    .read_write::<[u8]>(start .. start + size);
+                access.copy_from_slice(&upload.data);
+            }
+
+            let staging = StagingAlloc {
+                buffer: self.staging.clone(),
+                offset,
+                size,
+            };
+            let (t, a) = upload.target.record(staging,
+                                              transfer,
+                                              acquire,
+                                              transfer_family,
+                                              destination_family);
+            transfer = t;
+            acquire = a;
+
+            slots.push(upload.slot);
+            batch_size += size;
+        }
+
+        if slots.is_empty() {
+            return Ok(false);
+        }
+
+        let result = self.execute_batch(transfer, acquire);
+
+        match result {
+            Ok(ref future) => {
+                state.in_flight.push_back(InFlightBatch {
+                                              future: future.clone(),
+                                              staging_end: state.ring.head,
+                                          });
+            },
+            Err(_) => {
+                state.ring = ring_before;
+            },
+        }
+
+        for slot in slots {
+            *slot.lock().unwrap() = Some(result.clone());
+        }
+
+        result.map(|_| true)
+    }
+
+    fn execute_batch(&self, transfer: AutoCommandBufferBuilder,
+                     acquire: AutoCommandBufferBuilder)
+                     -> Result<Arc<UploadBatchFuture>, FlushError> {
+        let transfer = transfer.build().map_err(build_to_flush_error)?;
+        let acquire = acquire.build().map_err(build_to_flush_error)?;
+
+        let future = now(self.transfer_queue.device().clone())
+            .then_execute(self.transfer_queue.clone(), transfer)
+            .map_err(exec_to_flush_error)?
+            .then_signal_semaphore_and_flush()?
+            .then_execute(self.destination_queue.clone(), acquire)
+            .map_err(exec_to_flush_error)?;
+        let future: Box<dyn GpuFuture + Send + Sync> = Box::new(future);
+
+        Ok(Arc::new(future.then_signal_fence_and_flush()?))
+    }
+
+    // Returns the future of the batch that contains the upload of `slot`, submitting the pending
+    // uploads up to this one if necessary.
+    fn submitted_batch(&self, slot: &UploadSlot) -> Result<Arc<UploadBatchFuture>, FlushError> {
+        if let Some(ref result) = *slot.lock().unwrap() {
+            return result.clone();
+        }
+
+        let mut state = self.state.lock().unwrap();
+        loop {
+            if let Some(ref result) = *slot.lock().unwrap() {
+                return result.clone();
+            }
+
+            let submitted = self.submit_batch(&mut state, None, true)?;
+            assert!(submitted);
+        }
+    }
+}
+
+impl UploadManagerState {
+    // Releases the staging memory of the batches that are finished.
+    fn cleanup_finished(&mut self) {
+        while let Some(batch) = self.in_flight.front() {
+            if batch.future.wait(Some(Duration::from_secs(0))).is_err() {
+                break;
+            }
+
+            let staging_end = batch.staging_end;
+            self.in_flight.pop_front();
+            let in_use = !self.in_flight.is_empty();
+            self.ring.free_until(staging_end, in_use);
+        }
+    }
+}
+
+#[inline]
+fn build_to_flush_error(err: BuildError) -> FlushError {
+    match err {
+        BuildError::OomError(err) => FlushError::OomError(err),
+        // We never start a render pass.
+        BuildError::AutoCommandBufferBuilderContextError(_) => unreachable!(),
+    }
+}
+
+#[inline]
+fn exec_to_flush_error(err: CommandBufferExecError) -> FlushError {
+    match err {
+        CommandBufferExecError::AccessError { error, .. } => FlushError::AccessError(error),
+        // We only submit one-time-submit command buffers that we have just built.
+        _ => unreachable!(),
+    }
+}
+
+// Returns the bytes of the elements of an iterator, without running their destructors.
+fn iter_to_bytes<T, I>(iter: I) -> Vec<u8>
+    where I: Iterator<Item = T>
+{
+    let mut elements = iter.collect::<Vec<T>>();
+
+    unsafe {
+        let bytes = slice::from_raw_parts(elements.as_ptr() as *const u8,
+                                          elements.len() * mem::size_of::<T>())
+            .to_vec();
+        elements.set_len(0);
+        bytes
+    }
+}
+
+fn least_common_multiple(a: usize, b: usize) -> usize {
+    let (mut x, mut y) = (a, b);
+    while y != 0 {
+        let r = x % y;
+        x = y;
+        y = r;
+    }
+
+    a / x * b
+}
+
+/// Represents the upload of a resource by an `UploadManager`.
+///
+/// Must be joined with the operations that use the resource. If the upload hasn't been submitted
+/// yet when the future is flushed or used, the pending uploads of the manager up to this one are
+/// submitted immediately.
+pub struct UploadFuture {
+    manager: Arc<UploadManagerInner>,
+    slot: Arc<UploadSlot>,
+}
+
+impl UploadFuture {
+    /// Returns true if the upload has been submitted.
+    #[inline]
+    pub fn is_submitted(&self) -> bool {
+        self.slot.lock().unwrap().is_some()
+    }
+
+    #[inline]
+    fn batch(&self) -> Result<Arc<UploadBatchFuture>, FlushError> {
+        self.manager.submitted_batch(&self.slot)
+    }
+
+    // Returns the future of the batch if it has been submitted successfully.
+    #[inline]
+    fn submitted(&self) -> Option<Arc<UploadBatchFuture>> {
+        match *self.slot.lock().unwrap() {
+            Some(Ok(ref future)) => Some(future.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl Clone for UploadFuture {
+    #[inline]
+    fn clone(&self) -> UploadFuture {
+        UploadFuture {
+            manager: self.manager.clone(),
+            slot: self.slot.clone(),
+        }
+    }
+}
+
+unsafe impl GpuFuture for UploadFuture {
+    #[inline]
+    fn cleanup_finished(&mut self) {
+        if let Some(mut future) = self.submitted() {
+            future.cleanup_finished();
+        }
+    }
+
+    #[inline]
+    unsafe fn build_submission(&self) -> Result<SubmitAnyBuilder<'_>, FlushError> {
+        // A `FenceSignalFuture` always returns `SubmitAnyBuilder::Empty`, which doesn't borrow
+        // the batch.
+        match self.batch()?.build_submission()? {
+            SubmitAnyBuilder::Empty => Ok(SubmitAnyBuilder::Empty),
+            _ => unreachable!(),
+        }
+    }
+
+    #[inline]
+    fn flush(&self) -> Result<(), FlushError> {
+        self.batch()?.flush()
+    }
+
+    #[inline]
+    unsafe fn signal_finished(&self) {
+        if let Some(future) = self.submitted() {
+            future.signal_finished();
+        }
+    }
+
+    #[inline]
+    fn queue_change_allowed(&self) -> bool {
+        match self.submitted() {
+            Some(future) => future.queue_change_allowed(),
+            None => false,
+        }
+    }
+
+    #[inline]
+    fn queue(&self) -> Option<Arc<Queue>> {
+        match self.submitted() {
+            Some(future) => future.queue(),
+            None => Some(self.manager.destination_queue.clone()),
+        }
+    }
+
+    #[inline]
+    fn check_buffer_access(
+        &self, buffer: &dyn BufferAccess, exclusive: bool, queue: &Queue)
+        -> Result<Option<(PipelineStages, AccessFlagBits)>, AccessCheckError> {
+        match self.batch() {
+            Ok(future) => future.check_buffer_access(buffer, exclusive, queue),
+            Err(_) => Err(AccessCheckError::Unknown),
+        }
+    }
+
+    #[inline]
+    fn check_image_access(&self, image: &dyn ImageAccess, layout: ImageLayout, exclusive: bool,
+                          queue: &Queue)
+                          -> Result<Option<(PipelineStages, AccessFlagBits)>, AccessCheckError> {
+        match self.batch() {
+            Ok(future) => future.check_image_access(image, layout, exclusive, queue),
+            Err(_) => Err(AccessCheckError::Unknown),
+        }
+    }
+}
+
+unsafe impl DeviceOwned for UploadFuture {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.manager.transfer_queue.device()
+    }
+}
+
+// Destination of a pending upload.
+trait UploadTarget {
+    // Records the copy from the staging buffer to the resource in `transfer`, and the transfer of
+    // ownership between the two queue families if they differ.
+    fn record(self: Box<Self>, staging: StagingAlloc, transfer: AutoCommandBufferBuilder,
+              acquire: AutoCommandBufferBuilder, transfer_family: QueueFamily,
+              destination_family: QueueFamily)
+              -> (AutoCommandBufferBuilder, AutoCommandBufferBuilder);
+}
+
+struct BufferTarget<T: ?Sized> {
+    init: ImmutableBufferInitialization<T>,
+}
+
+impl<T: ?Sized> UploadTarget for BufferTarget<T>
+    where T: 'static + Send + Sync
+{
+    fn record(self: Box<Self>, staging: StagingAlloc, transfer: AutoCommandBufferBuilder,
+              acquire: AutoCommandBufferBuilder, transfer_family: QueueFamily,
+              destination_family: QueueFamily)
+              -> (AutoCommandBufferBuilder, AutoCommandBufferBuilder) {
+        let source = StagingRegion::<T>::new(staging.buffer, staging.offset, staging.size);
+        // The copy can't fail, as the staging region has the same size as the buffer.
+        let mut transfer = transfer.copy_buffer(source, self.init.clone()).unwrap();
+        let mut acquire = acquire;
+
+        if transfer_family.id() != destination_family.id() {
+            transfer = transfer
+                .release_buffer_ownership(self.init.clone(), destination_family)
+                .unwrap();
+            acquire = acquire
+                .acquire_buffer_ownership(self.init, transfer_family)
+                .unwrap();
+        }
+
+        (transfer, acquire)
+    }
+}
+
+struct ImageTarget<F, P> {
+    image: Arc<ImmutableImage<F>>,
+    init: Arc<ImmutableImageInitialization<F>>,
+    dimensions: Dimensions,
+    marker: PhantomData<fn() -> P>,
+}
+
+impl<F, P> UploadTarget for ImageTarget<F, P>
+    where F: 'static + Send + Sync,
+          P: 'static,
+          Format: AcceptsPixels<P>
+{
+    fn record(self: Box<Self>, staging: StagingAlloc, transfer: AutoCommandBufferBuilder,
+              acquire: AutoCommandBufferBuilder, transfer_family: QueueFamily,
+              destination_family: QueueFamily)
+              -> (AutoCommandBufferBuilder, AutoCommandBufferBuilder) {
+        let source = StagingRegion::<[P]>::new(staging.buffer, staging.offset, staging.size);
+        // The copy has already been checked in `upload_image`.
+        let mut transfer = transfer
+            .copy_buffer_to_image_dimensions(source,
+                                             self.init.clone(),
+                                             [0, 0, 0],
+                                             self.dimensions.width_height_depth(),
+                                             0,
+                                             self.dimensions.array_layers_with_cube(),
+                                             0)
+            .unwrap();
+        let mut acquire = acquire;
+
+        if transfer_family.id() != destination_family.id() {
+            transfer = transfer
+                .release_image_ownership(self.init, destination_family)
+                .unwrap();
+            acquire = acquire
+                .acquire_image_ownership(AcquiredImage(self.image), transfer_family)
+                .unwrap();
+        }
+
+        (transfer, acquire)
+    }
+}
+
+// Access to an `ImmutableImage` whose content has been written by the transfer queue, used to
+// acquire its ownership on the destination queue without discarding its content.
+struct AcquiredImage<F>(Arc<ImmutableImage<F>>);
+
+unsafe impl<F> ImageAccess for AcquiredImage<F>
+    where F: 'static + Send + Sync
+{
+    #[inline]
+    fn inner(&self) -> ImageInner<'_> {
+        self.0.inner()
+    }
+
+    #[inline]
+    fn is_layout_initialized(&self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn initial_layout_requirement(&self) -> ImageLayout {
+        self.0.initial_layout_requirement()
+    }
+
+    #[inline]
+    fn final_layout_requirement(&self) -> ImageLayout {
+        self.0.final_layout_requirement()
+    }
+
+    #[inline]
+    fn conflicts_buffer(&self, other: &dyn BufferAccess) -> bool {
+        self.0.conflicts_buffer(other)
+    }
+
+    #[inline]
+    fn conflicts_image(&self, other: &dyn ImageAccess) -> bool {
+        self.0.conflicts_image(other)
+    }
+
+    #[inline]
+    fn conflict_key(&self) -> u64 {
+        self.0.conflict_key()
+    }
+
+    #[inline]
+    fn try_gpu_lock(&self, exclusive_access: bool, expected_layout: ImageLayout)
+                    -> Result<(), AccessError> {
+        self.0.try_gpu_lock(exclusive_access, expected_layout)
+    }
+
+    #[inline]
+    unsafe fn increase_gpu_lock(&self) {
+        self.0.increase_gpu_lock()
+    }
+
+    #[inline]
+    unsafe fn unlock(&self, _: Option<ImageLayout>) {
+    }
+}
+
+// Host-visible buffer that holds the data of the uploads until they are copied.
+struct StagingBuffer {
+    inner: UnsafeBuffer,
+    memory: PotentialDedicatedAllocation<StdMemoryPoolAlloc>,
+}
+
+impl StagingBuffer {
+    fn new(device: Arc<Device>, size: usize) -> Result<StagingBuffer, DeviceMemoryAllocError> {
+        let (buffer, mem_reqs) = unsafe {
+            match UnsafeBuffer::new(device.clone(),
+                                    size,
+                                    BufferUsage::transfer_source(),
+                                    Sharing::Exclusive::<iter::Empty<_>>,
+                                    SparseLevel::none()) {
+                Ok(b) => b,
+                Err(BufferCreationError::AllocError(err)) => return Err(err),
+                Err(_) => unreachable!(),        // We don't use sparse binding, therefore the other
+                // errors can't happen
+            }
+        };
+
+        let mem = MemoryPool::alloc_from_requirements(&Device::standard_pool(&device),
+                                                      &mem_reqs,
+                                                      AllocLayout::Linear,
+                                                      MappingRequirement::Map,
+                                                      DedicatedAlloc::Buffer(&buffer),
+                                                      |m| if m.is_host_cached() {
+                                                          AllocFromRequirementsFilter::Allowed
+                                                      } else {
+                                                          AllocFromRequirementsFilter::Preferred
+                                                      })?;
+        debug_assert!((mem.offset() % mem_reqs.alignment) == 0);
+        debug_assert!(mem.mapped_memory().is_some());
+        unsafe {
+            buffer.bind_memory(mem.memory(), mem.offset())?;
+        }
+
+        Ok(StagingBuffer {
+               inner: buffer,
+               memory: mem,
+           })
+    }
+}
+
+// Region of the staging buffer allocated to an upload.
+struct StagingAlloc {
+    buffer: Arc<StagingBuffer>,
+    offset: usize,
+    size: usize,
+}
+
+// Typed access to a region of the staging buffer.
+//
+// The manager never reuses a region before the batch that reads from it is finished, therefore
+// locking always succeeds.
+struct StagingRegion<T: ?Sized> {
+    buffer: Arc<StagingBuffer>,
+    offset: usize,
+    size: usize,
+    marker: PhantomData<fn() -> Box<T>>,
+}
+
+impl<T: ?Sized> StagingRegion<T> {
+    #[inline]
+    fn new(buffer: Arc<StagingBuffer>, offset: usize, size: usize) -> StagingRegion<T> {
+        StagingRegion {
+            buffer,
+            offset,
+            size,
+            marker: PhantomData,
+        }
+    }
+}
+
+unsafe impl<T: ?Sized> BufferAccess for StagingRegion<T> {
+    #[inline]
+    fn inner(&self) -> BufferInner<'_> {
+        BufferInner {
+            buffer: &self.buffer.inner,
+            offset: self.offset,
+        }
+    }
+
+    #[inline]
+    fn size(&self) -> usize {
+        self.size
+    }
+
+    #[inline]
+    fn conflicts_buffer(&self, other: &dyn BufferAccess) -> bool {
+        self.conflict_key() == other.conflict_key() // TODO:
+    }
+
+    #[inline]
+    fn conflicts_image(&self, other: &dyn ImageAccess) -> bool {
+        false
+    }
+
+    #[inline]
+    fn conflict_key(&self) -> (u64, usize) {
+        (self.buffer.inner.key(), self.offset)
+    }
+
+    #[inline]
+    fn try_gpu_lock(&self, _: bool, _: &Queue) -> Result<(), AccessError> {
+        Ok(())
+    }
+
+    #[inline]
+    unsafe fn increase_gpu_lock(&self) {
+    }
+
+    #[inline]
+    unsafe fn unlock(&self) {
+    }
+}
+
+unsafe impl<T: ?Sized> TypedBufferAccess for StagingRegion<T> {
+    type Content = T;
+}
+
+unsafe impl<T: ?Sized> DeviceOwned for StagingRegion<T> {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.buffer.inner.device()
+    }
+}
+
+// Allocator of the staging buffer. Regions are allocated after each other and freed in the same
+// order, wrapping around at the end of the buffer.
+#[derive(Debug, Clone)]
+struct StagingRing {
+    capacity: usize,
+    // End of the most recent allocation.
+    head: usize,
+    // Start of the oldest allocation that is still in use.
+    tail: usize,
+    // False if no allocation is in use, in which case `head` and `tail` are meaningless.
+    in_use: bool,
+}
+
+impl StagingRing {
+    #[inline]
+    fn new(capacity: usize) -> StagingRing {
+        StagingRing {
+            capacity,
+            head: 0,
+            tail: 0,
+            in_use: false,
+        }
+    }
+
+    // Returns the offset of a new region of `size` bytes, or `None` if there is no room for it.
+    fn allocate(&mut self, size: usize, alignment: usize) -> Option<usize> {
+        debug_assert!(size != 0);
+        let aligned_head = match self.head % alignment {
+            0 => self.head,
+            rest => self.head + alignment - rest,
+        };
+
+        let offset = if !self.in_use {
+            self.tail = 0;
+            0
+        } else if self.head > self.tail {
+            // The free space is between the head and the end of the buffer, and between the start
+            // of the buffer and the tail.
+            if aligned_head + size <= self.capacity {
+                aligned_head
+            } else if size <= self.tail {
+                0
+            } else {
+                return None;
+            }
+        } else {
+            // The free space is between the head and the tail.
+            if aligned_head + size <= self.tail {
+                aligned_head
+            } else {
+                return None;
+            }
+        };
+
+        if offset + size > self.capacity {
+            return None;
+        }
+
+        self.head = offset + size;
+        self.in_use = true;
+        Some(offset)
+    }
+
+    // Frees all the regions allocated before `end` was the head. `in_use` must be true if regions
+    // allocated afterwards are still in use.
+    #[inline]
+    fn free_until(&mut self, end: usize, in_use: bool) {
+        self.tail = end;
+        self.in_use = in_use;
+    }
+}
+
+/// Error that can happen when queuing an upload.
+#[derive(Debug, Clone)]
+pub enum UploadError {
+    /// Not enough memory to create the resource.
+    DeviceMemoryAllocError(DeviceMemoryAllocError),
+
+    /// Error while creating the image.
+    ImageCreationError(ImageCreationError),
+
+    /// The data can't be copied to the image.
+    CopyError(CheckCopyBufferImageError),
+
+    /// The data to upload is empty.
+    EmptyUpload,
+
+    /// The data to upload is larger than the staging buffer.
+    UploadTooLarge {
+        /// Size of the data in bytes.
+        size: usize,
+        /// Size of the staging buffer in bytes.
+        capacity: usize,
+    },
+}
+
+impl error::Error for UploadError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            UploadError::DeviceMemoryAllocError(_) => "not enough memory to create the resource",
+            UploadError::ImageCreationError(_) => "error while creating the image",
+            UploadError::CopyError(_) => "the data can't be copied to the image",
+            UploadError::EmptyUpload => "the data to upload is empty",
+            UploadError::UploadTooLarge { .. } => {
+                "the data to upload is larger than the staging buffer"
+            },
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&dyn error::Error> {
+        match *self {
+            UploadError::DeviceMemoryAllocError(ref err) => Some(err),
+            UploadError::ImageCreationError(ref err) => Some(err),
+            UploadError::CopyError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for UploadError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
+
+impl From<DeviceMemoryAllocError> for UploadError {
+    #[inline]
+    fn from(err: DeviceMemoryAllocError) -> UploadError {
+        UploadError::DeviceMemoryAllocError(err)
+    }
+}
+
+impl From<ImageCreationError> for UploadError {
+    #[inline]
+    fn from(err: ImageCreationError) -> UploadError {
+        UploadError::ImageCreationError(err)
+    }
+}
+
+impl From<CheckCopyBufferImageError> for UploadError {
+    #[inline]
+    fn from(err: CheckCopyBufferImageError) -> UploadError {
+        UploadError::CopyError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::StagingRing;
+    use super::UploadError;
+    use super::UploadManager;
+    use buffer::BufferUsage;
+    use sync::GpuFuture;
+
+    #[test]
+    fn ring_allocates_in_order() {
+        let mut ring = StagingRing::new(256);
+        assert_eq!(ring.allocate(10, 4), Some(0));
+        assert_eq!(ring.allocate(10, 4), Some(12));
+        assert_eq!(ring.allocate(240, 4), None);
+    }
+
+    #[test]
+    fn ring_wraps_around() {
+        let mut ring = StagingRing::new(256);
+        assert_eq!(ring.allocate(100, 4), Some(0));
+        let end = ring.head;
+        assert_eq!(ring.allocate(100, 4), Some(100));
+        assert_eq!(ring.allocate(100, 4), None);
+
+        ring.free_until(end, true);
+        assert_eq!(ring.allocate(100, 4), Some(0));
+        assert_eq!(ring.allocate(1, 4), None);
+    }
+
+    #[test]
+    fn ring_reset_when_unused() {
+        let mut ring = StagingRing::new(256);
+        assert_eq!(ring.allocate(200, 4), Some(0));
+        let end = ring.head;
+        ring.free_until(end, false);
+        assert_eq!(ring.allocate(256, 4), Some(0));
+    }
+
+    #[test]
+    fn upload_too_large() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let manager = UploadManager::new(queue.clone(), queue, 16).unwrap();
+
+        match manager.upload_iter(0 .. 32u32, BufferUsage::all()) {
+            Err(UploadError::UploadTooLarge { size: 128, capacity: 16 }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn upload_budget() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let manager = UploadManager::new(queue.clone(), queue, 1024).unwrap();
+        manager.set_frame_budget(Some(8));
+
+        let (_, first) = manager.upload_data(12u32, BufferUsage::all()).unwrap();
+        let (_, second) = manager.upload_data(5u32, BufferUsage::all()).unwrap();
+        let (_, third) = manager.upload_data(7u32, BufferUsage::all()).unwrap();
+        assert_eq!(manager.num_pending(), 3);
+
+        manager.submit_frame().unwrap();
+        assert!(first.is_submitted());
+        assert!(second.is_submitted());
+        assert!(!third.is_submitted());
+
+        third
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+        assert_eq!(manager.num_pending(), 0);
+    }
+}
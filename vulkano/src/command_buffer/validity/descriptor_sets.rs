@@ -9,13 +9,22 @@
 
 use std::error;
 use std::fmt;
+use std::sync::Arc;
 
+use descriptor::descriptor::DescriptorDesc;
 use descriptor::descriptor::DescriptorDescSupersetError;
+use descriptor::descriptor_set::DescriptorSetDesc;
 use descriptor::descriptor_set::DescriptorSetsCollection;
+use descriptor::descriptor_set::UnsafeDescriptorSetLayout;
 use descriptor::pipeline_layout::PipelineLayoutDesc;
 
 /// Checks whether descriptor sets are compatible with the pipeline.
-pub fn check_descriptor_sets_validity<Pl, D>(pipeline: &Pl, descriptor_sets: &D)
+///
+/// `pushed_sets` contains, for each set number, the layout of the descriptors that are currently
+/// pushed to the command buffer with `push_descriptor_set`, if any. The sets of the pipeline
+/// whose layout is a push descriptor layout are checked against them.
+pub fn check_descriptor_sets_validity<Pl, D>(pipeline: &Pl, descriptor_sets: &D,
+                                             pushed_sets: &[Option<Arc<UnsafeDescriptorSetLayout>>])
                                              -> Result<(), CheckDescriptorSetsValidityError>
     where Pl: ?Sized + PipelineLayoutDesc,
           D: ?Sized + DescriptorSetsCollection
@@ -25,38 +34,82 @@ pub fn check_descriptor_sets_validity<Pl, D>(pipeline: &Pl, descriptor_sets: &D)
     // a problem if the descriptor sets provide more elements than expected.
 
     for set_num in 0 .. pipeline.num_sets() {
-        for binding_num in 0 .. pipeline.num_bindings_in_set(set_num).unwrap_or(0) {
-            let set_desc = descriptor_sets.descriptor(set_num, binding_num);
-            let pipeline_desc = pipeline.descriptor(set_num, binding_num);
-
-            let (set_desc, pipeline_desc) = match (set_desc, pipeline_desc) {
-                (Some(s), Some(p)) => (s, p),
-                (None, Some(_)) =>
-                    return Err(CheckDescriptorSetsValidityError::MissingDescriptor {
-                                   set_num: set_num,
-                                   binding_num: binding_num,
-                               }),
-                (Some(_), None) => continue,
-                (None, None) => continue,
-            };
+        let is_push_descriptor = pipeline
+            .provided_set_layout(set_num)
+            .map(|layout| layout.is_push_descriptor())
+            .unwrap_or(false);
 
-            if let Err(err) = set_desc.is_superset_of(&pipeline_desc) {
-                return Err(CheckDescriptorSetsValidityError::IncompatibleDescriptor {
-                               error: err,
-                               set_num: set_num,
-                               binding_num: binding_num,
+        if is_push_descriptor {
+            // The sets passed to the command are bound starting from set 0, and would replace the
+            // pushed descriptors.
+            if descriptor_sets.num_bindings_in_set(set_num).is_some() {
+                return Err(CheckDescriptorSetsValidityError::PushDescriptorSetOverwritten {
+                               set_num,
                            });
             }
+
+            let pushed = match pushed_sets.get(set_num) {
+                Some(Some(layout)) => layout,
+                _ => {
+                    return Err(CheckDescriptorSetsValidityError::MissingPushDescriptorSet {
+                                   set_num,
+                               });
+                },
+            };
+
+            for binding_num in 0 .. pipeline.num_bindings_in_set(set_num).unwrap_or(0) {
+                check_descriptor(set_num,
+                                 binding_num,
+                                 pushed.descriptor(binding_num),
+                                 pipeline.descriptor(set_num, binding_num))?;
+            }
+
+            continue;
+        }
+
+        for binding_num in 0 .. pipeline.num_bindings_in_set(set_num).unwrap_or(0) {
+            check_descriptor(set_num,
+                             binding_num,
+                             descriptor_sets.descriptor(set_num, binding_num),
+                             pipeline.descriptor(set_num, binding_num))?;
         }
     }
 
     Ok(())
 }
 
+// Checks whether the descriptor provided for a binding is a superset of what the pipeline
+// expects.
+fn check_descriptor(set_num: usize, binding_num: usize, set_desc: Option<DescriptorDesc>,
+                    pipeline_desc: Option<DescriptorDesc>)
+                    -> Result<(), CheckDescriptorSetsValidityError> {
+    let (set_desc, pipeline_desc) = match (set_desc, pipeline_desc) {
+        (Some(s), Some(p)) => (s, p),
+        (None, Some(_)) =>
+            return Err(CheckDescriptorSetsValidityError::MissingDescriptor {
+                           set_num,
+                           binding_num,
+                       }),
+        (Some(_), None) => return Ok(()),
+        (None, None) => return Ok(()),
+    };
+
+    if let Err(err) = set_desc.is_superset_of(&pipeline_desc) {
+        return Err(CheckDescriptorSetsValidityError::IncompatibleDescriptor {
+                       error: err,
+                       set_num,
+                       binding_num,
+                   });
+    }
+
+    Ok(())
+}
+
 /// Error that can happen when checking descriptor sets validity.
 #[derive(Debug, Clone)]
 pub enum CheckDescriptorSetsValidityError {
-    /// A descriptor is missing in the descriptor sets that were provided.
+    /// A descriptor is missing in the descriptor sets or the pushed descriptors that were
+    /// provided.
     MissingDescriptor {
         /// The index of the set of the descriptor.
         set_num: usize,
@@ -64,7 +117,8 @@ pub enum CheckDescriptorSetsValidityError {
         binding_num: usize,
     },
 
-    /// A descriptor in the provided sets is not compatible with what is expected.
+    /// A descriptor in the provided sets or the pushed descriptors is not compatible with what is
+    /// expected.
     IncompatibleDescriptor {
         /// The reason why the two descriptors aren't compatible.
         error: DescriptorDescSupersetError,
@@ -73,6 +127,20 @@ pub enum CheckDescriptorSetsValidityError {
         /// The binding number of the descriptor.
         binding_num: usize,
     },
+
+    /// The pipeline layout expects descriptors to be pushed for a set, but none were pushed with
+    /// `push_descriptor_set` or they were replaced by a descriptor set bound afterwards.
+    MissingPushDescriptorSet {
+        /// The index of the set.
+        set_num: usize,
+    },
+
+    /// One of the provided sets would replace descriptors that the pipeline layout expects to be
+    /// pushed.
+    PushDescriptorSetOverwritten {
+        /// The index of the set.
+        set_num: usize,
+    },
 }
 
 impl error::Error for CheckDescriptorSetsValidityError {
@@ -85,6 +153,13 @@ impl error::Error for CheckDescriptorSetsValidityError {
             CheckDescriptorSetsValidityError::IncompatibleDescriptor { .. } => {
                 "a descriptor in the provided sets is not compatible with what is expected"
             },
+            CheckDescriptorSetsValidityError::MissingPushDescriptorSet { .. } => {
+                "the pipeline layout expects descriptors to be pushed for a set, but none were \
+                 pushed"
+            },
+            CheckDescriptorSetsValidityError::PushDescriptorSetOverwritten { .. } => {
+                "one of the provided sets would replace descriptors that must be pushed"
+            },
         }
     }
 
@@ -106,4 +181,63 @@ impl fmt::Display for CheckDescriptorSetsValidityError {
     }
 }
 
-// TODO: tests
+#[cfg(test)]
+mod tests {
+    use command_buffer::validity::CheckDescriptorSetsValidityError;
+    use command_buffer::validity::check_descriptor_sets_validity;
+    use descriptor::descriptor::DescriptorBufferDesc;
+    use descriptor::descriptor::DescriptorDesc;
+    use descriptor::descriptor::DescriptorDescTy;
+    use descriptor::descriptor::ShaderStages;
+    use descriptor::descriptor_set::UnsafeDescriptorSetLayout;
+    use descriptor::pipeline_layout::PipelineLayoutDescTweaks;
+    use descriptor::pipeline_layout::RuntimePipelineDesc;
+    use std::iter;
+    use std::sync::Arc;
+
+    fn buffer_desc(storage: bool) -> DescriptorDesc {
+        DescriptorDesc {
+            ty: DescriptorDescTy::Buffer(DescriptorBufferDesc {
+                                             dynamic: Some(false),
+                                             storage,
+                                         }),
+            array_count: 1,
+            stages: ShaderStages::all_graphics(),
+            readonly: true,
+        }
+    }
+
+    #[test]
+    fn push_descriptors() {
+        let (device, _) = gfx_dev_and_queue!(extensions: [khr_push_descriptor]);
+
+        let layout = UnsafeDescriptorSetLayout::push_descriptor(device.clone(),
+                                                                iter::once(Some(buffer_desc(false))))
+            .unwrap();
+        let layout = Arc::new(layout);
+        let pipeline_desc = RuntimePipelineDesc::new(vec![vec![Some(buffer_desc(false))]],
+                                                     iter::empty())
+            .unwrap();
+        let pipeline_desc = PipelineLayoutDescTweaks::new(pipeline_desc, iter::empty())
+            .with_set_layout(0, layout.clone());
+
+        match check_descriptor_sets_validity(&pipeline_desc, &(), &[]) {
+            Err(CheckDescriptorSetsValidityError::MissingPushDescriptorSet { set_num: 0 }) => (),
+            _ => panic!(),
+        }
+
+        check_descriptor_sets_validity(&pipeline_desc, &(), &[Some(layout)]).unwrap();
+
+        let other = UnsafeDescriptorSetLayout::push_descriptor(device,
+                                                               iter::once(Some(buffer_desc(true))))
+            .unwrap();
+        match check_descriptor_sets_validity(&pipeline_desc, &(), &[Some(Arc::new(other))]) {
+            Err(CheckDescriptorSetsValidityError::IncompatibleDescriptor {
+                    set_num: 0,
+                    binding_num: 0,
+                    ..
+                }) => (),
+            _ => panic!(),
+        }
+    }
+}
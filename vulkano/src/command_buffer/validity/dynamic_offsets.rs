@@ -0,0 +1,224 @@
+// Copyright (c) 2026 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::error;
+use std::fmt;
+
+use descriptor::descriptor::DescriptorDescTy;
+use descriptor::descriptor_set::DescriptorSet;
+use descriptor::descriptor_set::buffers_dynamic_offsets;
+use descriptor::descriptor_set::num_dynamic_offsets;
+
+/// Checks whether the dynamic offsets of descriptor sets are valid.
+///
+/// Each offset must be a multiple of the `min_uniform_buffer_offset_alignment` or
+/// `min_storage_buffer_offset_alignment` limit, and the range of the buffer designated by the
+/// offset must be within the buffer.
+pub fn check_dynamic_offsets<S>(sets: &[S]) -> Result<(), CheckDynamicOffsetsError>
+    where S: DescriptorSet
+{
+    for (set_num, set) in sets.iter().enumerate() {
+        let provided = set.dynamic_offsets();
+        if provided.is_empty() {
+            // Offsets of zero are used, which are always valid.
+            continue;
+        }
+
+        let expected = num_dynamic_offsets(set);
+        if provided.len() != expected {
+            return Err(CheckDynamicOffsetsError::WrongNumberOfOffsets {
+                           set_num,
+                           expected,
+                           obtained: provided.len(),
+                       });
+        }
+
+        let limits = set.device().physical_device().limits();
+        let buffers_offsets = buffers_dynamic_offsets(set, provided);
+
+        for (buf_num, offset) in buffers_offsets.into_iter().enumerate() {
+            let offset = match offset {
+                Some(o) => o,
+                None => continue,
+            };
+
+            let (buffer, binding_num) = set.buffer(buf_num).unwrap();
+            let storage = match set.descriptor(binding_num as usize).map(|d| d.ty) {
+                Some(DescriptorDescTy::Buffer(ref desc)) => desc.storage,
+                _ => unreachable!(),
+            };
+
+            let required_alignment = if storage {
+                limits.min_storage_buffer_offset_alignment()
+            } else {
+                limits.min_uniform_buffer_offset_alignment()
+            };
+
+            // The alignment limits are guaranteed to be powers of two.
+            if offset as u64 & (required_alignment - 1) != 0 {
+                return Err(CheckDynamicOffsetsError::UnalignedOffset {
+                               set_num,
+                               binding_num,
+                               offset,
+                               required_alignment,
+                           });
+            }
+
+            let inner = buffer.inner();
+            if inner.offset + offset as usize + buffer.size() > inner.buffer.size() {
+                return Err(CheckDynamicOffsetsError::OffsetOutOfRange {
+                               set_num,
+                               binding_num,
+                               offset,
+                           });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Error that can happen when checking the dynamic offsets of descriptor sets.
+#[derive(Debug, Copy, Clone)]
+pub enum CheckDynamicOffsetsError {
+    /// The number of dynamic offsets of a set doesn't match the number of dynamic descriptors.
+    WrongNumberOfOffsets {
+        /// The index of the set.
+        set_num: usize,
+        /// Number of dynamic descriptors of the set.
+        expected: usize,
+        /// Number of offsets that were provided.
+        obtained: usize,
+    },
+
+    /// A dynamic offset isn't a multiple of the required alignment.
+    UnalignedOffset {
+        /// The index of the set.
+        set_num: usize,
+        /// The binding number of the descriptor.
+        binding_num: u32,
+        /// The offset that was provided.
+        offset: u32,
+        /// The alignment required by the device limits.
+        required_alignment: u64,
+    },
+
+    /// A dynamic offset moves the range of a descriptor past the end of its buffer.
+    OffsetOutOfRange {
+        /// The index of the set.
+        set_num: usize,
+        /// The binding number of the descriptor.
+        binding_num: u32,
+        /// The offset that was provided.
+        offset: u32,
+    },
+}
+
+impl error::Error for CheckDynamicOffsetsError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            CheckDynamicOffsetsError::WrongNumberOfOffsets { .. } => {
+                "the number of dynamic offsets of a set doesn't match its number of dynamic \
+                 descriptors"
+            },
+            CheckDynamicOffsetsError::UnalignedOffset { .. } => {
+                "a dynamic offset isn't a multiple of the required alignment"
+            },
+            CheckDynamicOffsetsError::OffsetOutOfRange { .. } => {
+                "a dynamic offset moves the range of a descriptor past the end of its buffer"
+            },
+        }
+    }
+}
+
+impl fmt::Display for CheckDynamicOffsetsError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use buffer::BufferAccess;
+    use buffer::BufferUsage;
+    use buffer::CpuAccessibleBuffer;
+    use command_buffer::validity::CheckDynamicOffsetsError;
+    use command_buffer::validity::check_dynamic_offsets;
+    use descriptor::descriptor::DescriptorBufferDesc;
+    use descriptor::descriptor::DescriptorDesc;
+    use descriptor::descriptor::DescriptorDescTy;
+    use descriptor::descriptor::ShaderStages;
+    use descriptor::descriptor_set::DescriptorSetWithOffsets;
+    use descriptor::descriptor_set::PersistentDescriptorSet;
+    use descriptor::descriptor_set::UnsafeDescriptorSetLayout;
+    use std::iter;
+    use std::sync::Arc;
+
+    #[test]
+    fn dynamic_offsets() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let desc = DescriptorDesc {
+            ty: DescriptorDescTy::Buffer(DescriptorBufferDesc {
+                                             dynamic: Some(true),
+                                             storage: false,
+                                         }),
+            array_count: 1,
+            stages: ShaderStages::all_graphics(),
+            readonly: true,
+        };
+        let layout = Arc::new(UnsafeDescriptorSetLayout::new(device.clone(),
+                                                             iter::once(Some(desc)))
+                                  .unwrap());
+
+        let alignment = device
+            .physical_device()
+            .limits()
+            .min_uniform_buffer_offset_alignment() as usize;
+        let buffer = CpuAccessibleBuffer::from_iter(device.clone(),
+                                                    BufferUsage::all(),
+                                                    false,
+                                                    (0 .. alignment * 4).map(|_| 0u8))
+            .unwrap();
+        let set = Arc::new(PersistentDescriptorSet::start(layout)
+                               .add_buffer(buffer.slice::<u8>(0 .. alignment).unwrap())
+                               .unwrap()
+                               .build()
+                               .unwrap());
+
+        let valid = DescriptorSetWithOffsets::new(set.clone(), Some(alignment as u32 * 3));
+        assert!(check_dynamic_offsets(&[valid]).is_ok());
+
+        if alignment > 1 {
+            let unaligned = DescriptorSetWithOffsets::new(set.clone(), Some(1));
+            match check_dynamic_offsets(&[unaligned]) {
+                Err(CheckDynamicOffsetsError::UnalignedOffset { .. }) => (),
+                _ => panic!(),
+            }
+        }
+
+        let out_of_range = DescriptorSetWithOffsets::new(set.clone(), Some(alignment as u32 * 4));
+        match check_dynamic_offsets(&[out_of_range]) {
+            Err(CheckDynamicOffsetsError::OffsetOutOfRange { .. }) => (),
+            _ => panic!(),
+        }
+
+        let wrong_number = DescriptorSetWithOffsets::new(set, vec![0, 0]);
+        match check_dynamic_offsets(&[wrong_number]) {
+            Err(CheckDynamicOffsetsError::WrongNumberOfOffsets {
+                    expected: 1,
+                    obtained: 2,
+                    ..
+                }) => (),
+            _ => panic!(),
+        }
+    }
+}
@@ -12,6 +12,7 @@ use std::fmt;
 
 use command_buffer::DynamicState;
 use pipeline::GraphicsPipelineAbstract;
+use pipeline::input_assembly::PrimitiveTopologyClass;
 
 /// Checks whether states that are about to be set are correct.
 pub fn check_dynamic_state_validity<Pl>(pipeline: &Pl, state: &DynamicState)
@@ -104,6 +105,144 @@ pub fn check_dynamic_state_validity<Pl>(pipeline: &Pl, state: &DynamicState)
         }
     }
 
+    if pipeline.has_dynamic_depth_bias() {
+        if let Some(depth_bias) = state.depth_bias {
+            if depth_bias.clamp != 0.0 && !device.enabled_features().depth_bias_clamp {
+                return Err(CheckDynamicStateValidityError::DepthBiasClampFeatureNotEnabled);
+            }
+        } else {
+            return Err(CheckDynamicStateValidityError::DepthBiasMissing);
+        }
+
+    } else {
+        if state.depth_bias.is_some() {
+            return Err(CheckDynamicStateValidityError::DepthBiasNotDynamic);
+        }
+    }
+
+    if pipeline.has_dynamic_depth_bounds() {
+        if let Some(ref depth_bounds) = state.depth_bounds {
+            if depth_bounds.start < 0.0 || depth_bounds.start > 1.0 || depth_bounds.end < 0.0 ||
+                depth_bounds.end > 1.0
+            {
+                return Err(CheckDynamicStateValidityError::DepthBoundsOutOfRange);
+            }
+        } else {
+            return Err(CheckDynamicStateValidityError::DepthBoundsMissing);
+        }
+
+    } else {
+        if state.depth_bounds.is_some() {
+            return Err(CheckDynamicStateValidityError::DepthBoundsNotDynamic);
+        }
+    }
+
+    if pipeline.has_dynamic_blend_constants() {
+        if state.blend_constants.is_none() {
+            return Err(CheckDynamicStateValidityError::BlendConstantsMissing);
+        }
+
+    } else {
+        if state.blend_constants.is_some() {
+            return Err(CheckDynamicStateValidityError::BlendConstantsNotDynamic);
+        }
+    }
+
+    if pipeline.has_dynamic_cull_mode() {
+        if state.cull_mode.is_none() {
+            return Err(CheckDynamicStateValidityError::CullModeMissing);
+        }
+
+    } else {
+        if state.cull_mode.is_some() {
+            return Err(CheckDynamicStateValidityError::CullModeNotDynamic);
+        }
+    }
+
+    if pipeline.has_dynamic_front_face() {
+        if state.front_face.is_none() {
+            return Err(CheckDynamicStateValidityError::FrontFaceMissing);
+        }
+
+    } else {
+        if state.front_face.is_some() {
+            return Err(CheckDynamicStateValidityError::FrontFaceNotDynamic);
+        }
+    }
+
+    if pipeline.has_dynamic_primitive_topology() {
+        if let Some(topology) = state.primitive_topology {
+            if topology.class() != pipeline.primitive_topology().class() {
+                return Err(CheckDynamicStateValidityError::PrimitiveTopologyClassMismatch {
+                               expected: pipeline.primitive_topology().class(),
+                               obtained: topology.class(),
+                           });
+            }
+        } else {
+            return Err(CheckDynamicStateValidityError::PrimitiveTopologyMissing);
+        }
+
+    } else {
+        if state.primitive_topology.is_some() {
+            return Err(CheckDynamicStateValidityError::PrimitiveTopologyNotDynamic);
+        }
+    }
+
+    if pipeline.has_dynamic_depth_test_enable() {
+        if state.depth_test_enable.is_none() {
+            return Err(CheckDynamicStateValidityError::DepthTestEnableMissing);
+        }
+
+    } else {
+        if state.depth_test_enable.is_some() {
+            return Err(CheckDynamicStateValidityError::DepthTestEnableNotDynamic);
+        }
+    }
+
+    if pipeline.has_dynamic_depth_write_enable() {
+        if state.depth_write_enable.is_none() {
+            return Err(CheckDynamicStateValidityError::DepthWriteEnableMissing);
+        }
+
+    } else {
+        if state.depth_write_enable.is_some() {
+            return Err(CheckDynamicStateValidityError::DepthWriteEnableNotDynamic);
+        }
+    }
+
+    if pipeline.has_dynamic_depth_compare_op() {
+        if state.depth_compare_op.is_none() {
+            return Err(CheckDynamicStateValidityError::DepthCompareOpMissing);
+        }
+
+    } else {
+        if state.depth_compare_op.is_some() {
+            return Err(CheckDynamicStateValidityError::DepthCompareOpNotDynamic);
+        }
+    }
+
+    if pipeline.has_dynamic_stencil_test_enable() {
+        if state.stencil_test_enable.is_none() {
+            return Err(CheckDynamicStateValidityError::StencilTestEnableMissing);
+        }
+
+    } else {
+        if state.stencil_test_enable.is_some() {
+            return Err(CheckDynamicStateValidityError::StencilTestEnableNotDynamic);
+        }
+    }
+
+    if pipeline.has_dynamic_stencil_op() {
+        if state.stencil_op.is_none() {
+            return Err(CheckDynamicStateValidityError::StencilOpMissing);
+        }
+
+    } else {
+        if state.stencil_op.is_some() {
+            return Err(CheckDynamicStateValidityError::StencilOpNotDynamic);
+        }
+    }
+
     Ok(())
 }
 
@@ -151,6 +290,65 @@ pub enum CheckDynamicStateValidityError {
     ReferenceNotDynamic,
     /// The pipeline has dynamic reference, but no reference was passed.
     ReferenceMissing,
+    /// Passed a dynamic depth bias, while the pipeline doesn't have the depth bias set as dynamic.
+    DepthBiasNotDynamic,
+    /// The pipeline has a dynamic depth bias, but no depth bias was passed.
+    DepthBiasMissing,
+    /// The `depth_bias_clamp` feature must be enabled in order to use a depth bias clamp
+    /// different from 0.0.
+    DepthBiasClampFeatureNotEnabled,
+    /// Passed dynamic depth bounds, while the pipeline doesn't have the depth bounds set as
+    /// dynamic.
+    DepthBoundsNotDynamic,
+    /// The pipeline has dynamic depth bounds, but no depth bounds were passed.
+    DepthBoundsMissing,
+    /// The depth bounds must be between 0.0 and 1.0.
+    DepthBoundsOutOfRange,
+    /// Passed dynamic blend constants, while the pipeline doesn't have the blend constants set
+    /// as dynamic.
+    BlendConstantsNotDynamic,
+    /// The pipeline has dynamic blend constants, but no blend constants were passed.
+    BlendConstantsMissing,
+    /// Passed a dynamic cull mode, while the pipeline doesn't have the cull mode set as dynamic.
+    CullModeNotDynamic,
+    /// The pipeline has a dynamic cull mode, but no cull mode was passed.
+    CullModeMissing,
+    /// Passed a dynamic front face, while the pipeline doesn't have the front face set as dynamic.
+    FrontFaceNotDynamic,
+    /// The pipeline has a dynamic front face, but no front face was passed.
+    FrontFaceMissing,
+    /// Passed a dynamic primitive topology, while the pipeline doesn't have the primitive topology set as dynamic.
+    PrimitiveTopologyNotDynamic,
+    /// The pipeline has a dynamic primitive topology, but no primitive topology was passed.
+    PrimitiveTopologyMissing,
+    /// The dynamic primitive topology isn't of the same class as the topology the pipeline was
+    /// created with.
+    PrimitiveTopologyClassMismatch {
+        /// Class of the topology of the pipeline.
+        expected: PrimitiveTopologyClass,
+        /// Class of the topology that was passed.
+        obtained: PrimitiveTopologyClass,
+    },
+    /// Passed a dynamic depth test enable state, while the pipeline doesn't have the depth test enable state set as dynamic.
+    DepthTestEnableNotDynamic,
+    /// The pipeline has a dynamic depth test enable state, but no depth test enable state was passed.
+    DepthTestEnableMissing,
+    /// Passed a dynamic depth write enable state, while the pipeline doesn't have the depth write enable state set as dynamic.
+    DepthWriteEnableNotDynamic,
+    /// The pipeline has a dynamic depth write enable state, but no depth write enable state was passed.
+    DepthWriteEnableMissing,
+    /// Passed a dynamic depth compare operator, while the pipeline doesn't have the depth compare operator set as dynamic.
+    DepthCompareOpNotDynamic,
+    /// The pipeline has a dynamic depth compare operator, but no depth compare operator was passed.
+    DepthCompareOpMissing,
+    /// Passed a dynamic stencil test enable state, while the pipeline doesn't have the stencil test enable state set as dynamic.
+    StencilTestEnableNotDynamic,
+    /// The pipeline has a dynamic stencil test enable state, but no stencil test enable state was passed.
+    StencilTestEnableMissing,
+    /// Passed dynamic stencil operations, while the pipeline doesn't have the stencil operations set as dynamic.
+    StencilOpNotDynamic,
+    /// The pipeline has dynamic stencil operations, but no stencil operations were passed.
+    StencilOpMissing,
 }
 
 impl error::Error for CheckDynamicStateValidityError {
@@ -205,6 +403,94 @@ impl error::Error for CheckDynamicStateValidityError {
             CheckDynamicStateValidityError::ReferenceMissing => {
                 "the pipeline has dynamic reference, but no reference was passed"
             },
+            CheckDynamicStateValidityError::DepthBiasNotDynamic => {
+                "passed a dynamic depth bias, while the pipeline doesn't have the depth bias set as \
+                 dynamic"
+            },
+            CheckDynamicStateValidityError::DepthBiasMissing => {
+                "the pipeline has a dynamic depth bias, but no depth bias was passed"
+            },
+            CheckDynamicStateValidityError::DepthBiasClampFeatureNotEnabled => {
+                "the `depth_bias_clamp` feature must be enabled in order to use a depth bias clamp \
+                 different from 0.0"
+            },
+            CheckDynamicStateValidityError::DepthBoundsNotDynamic => {
+                "passed dynamic depth bounds, while the pipeline doesn't have the depth bounds set \
+                 as dynamic"
+            },
+            CheckDynamicStateValidityError::DepthBoundsMissing => {
+                "the pipeline has dynamic depth bounds, but no depth bounds were passed"
+            },
+            CheckDynamicStateValidityError::DepthBoundsOutOfRange => {
+                "the depth bounds must be between 0.0 and 1.0"
+            },
+            CheckDynamicStateValidityError::BlendConstantsNotDynamic => {
+                "passed dynamic blend constants, while the pipeline doesn't have the blend \
+                 constants set as dynamic"
+            },
+            CheckDynamicStateValidityError::BlendConstantsMissing => {
+                "the pipeline has dynamic blend constants, but no blend constants were passed"
+            },
+            CheckDynamicStateValidityError::CullModeNotDynamic => {
+                "passed a dynamic cull mode, while the pipeline doesn't have the cull mode set as \
+                 dynamic"
+            },
+            CheckDynamicStateValidityError::CullModeMissing => {
+                "the pipeline has a dynamic cull mode, but no cull mode was passed"
+            },
+            CheckDynamicStateValidityError::FrontFaceNotDynamic => {
+                "passed a dynamic front face, while the pipeline doesn't have the front face set as \
+                 dynamic"
+            },
+            CheckDynamicStateValidityError::FrontFaceMissing => {
+                "the pipeline has a dynamic front face, but no front face was passed"
+            },
+            CheckDynamicStateValidityError::PrimitiveTopologyNotDynamic => {
+                "passed a dynamic primitive topology, while the pipeline doesn't have the primitive topology set as \
+                 dynamic"
+            },
+            CheckDynamicStateValidityError::PrimitiveTopologyMissing => {
+                "the pipeline has a dynamic primitive topology, but no primitive topology was passed"
+            },
+            CheckDynamicStateValidityError::PrimitiveTopologyClassMismatch { .. } => {
+                "the dynamic primitive topology isn't of the same class as the topology of the \
+                 pipeline"
+            },
+            CheckDynamicStateValidityError::DepthTestEnableNotDynamic => {
+                "passed a dynamic depth test enable state, while the pipeline doesn't have the depth test enable state set as \
+                 dynamic"
+            },
+            CheckDynamicStateValidityError::DepthTestEnableMissing => {
+                "the pipeline has a dynamic depth test enable state, but no depth test enable state was passed"
+            },
+            CheckDynamicStateValidityError::DepthWriteEnableNotDynamic => {
+                "passed a dynamic depth write enable state, while the pipeline doesn't have the depth write enable state set as \
+                 dynamic"
+            },
+            CheckDynamicStateValidityError::DepthWriteEnableMissing => {
+                "the pipeline has a dynamic depth write enable state, but no depth write enable state was passed"
+            },
+            CheckDynamicStateValidityError::DepthCompareOpNotDynamic => {
+                "passed a dynamic depth compare operator, while the pipeline doesn't have the depth compare operator set as \
+                 dynamic"
+            },
+            CheckDynamicStateValidityError::DepthCompareOpMissing => {
+                "the pipeline has a dynamic depth compare operator, but no depth compare operator was passed"
+            },
+            CheckDynamicStateValidityError::StencilTestEnableNotDynamic => {
+                "passed a dynamic stencil test enable state, while the pipeline doesn't have the stencil test enable state set as \
+                 dynamic"
+            },
+            CheckDynamicStateValidityError::StencilTestEnableMissing => {
+                "the pipeline has a dynamic stencil test enable state, but no stencil test enable state was passed"
+            },
+            CheckDynamicStateValidityError::StencilOpNotDynamic => {
+                "passed dynamic stencil operations, while the pipeline doesn't have the stencil operations set as \
+                 dynamic"
+            },
+            CheckDynamicStateValidityError::StencilOpMissing => {
+                "the pipeline has dynamic stencil operations, but no stencil operations were passed"
+            },
         }
     }
 }
@@ -16,11 +16,16 @@ pub use self::copy_image::{CheckCopyImageError, check_copy_image};
 pub use self::copy_image_buffer::{CheckCopyBufferImageError, CheckCopyBufferImageTy,
                                   check_copy_buffer_image};
 pub use self::descriptor_sets::{CheckDescriptorSetsValidityError, check_descriptor_sets_validity};
+pub use self::dynamic_offsets::{CheckDynamicOffsetsError, check_dynamic_offsets};
 pub use self::dispatch::{CheckDispatchError, check_dispatch};
 pub use self::dynamic_state::{CheckDynamicStateValidityError, check_dynamic_state_validity};
 pub use self::fill_buffer::{CheckFillBufferError, check_fill_buffer};
 pub use self::index_buffer::{CheckIndexBuffer, CheckIndexBufferError, check_index_buffer};
+pub use self::push_descriptor::{CheckPushDescriptorSetError, check_push_descriptor_set};
 pub use self::push_constants::{CheckPushConstantsValidityError, check_push_constants_validity};
+pub use self::queue_family_transfer::{CheckQueueFamilyTransferError,
+                                      check_buffer_ownership_transfer,
+                                      check_image_ownership_transfer};
 pub use self::update_buffer::{CheckUpdateBufferError, check_update_buffer};
 pub use self::vertex_buffers::{CheckVertexBuffer, CheckVertexBufferError, check_vertex_buffers};
 
@@ -31,9 +36,12 @@ mod copy_image;
 mod copy_image_buffer;
 mod descriptor_sets;
 mod dispatch;
+mod dynamic_offsets;
 mod dynamic_state;
 mod fill_buffer;
 mod index_buffer;
 mod push_constants;
+mod push_descriptor;
+mod queue_family_transfer;
 mod update_buffer;
 mod vertex_buffers;
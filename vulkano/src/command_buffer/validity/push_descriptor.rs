@@ -0,0 +1,191 @@
+// Copyright (c) 2026 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::error;
+use std::fmt;
+
+use descriptor::descriptor::DescriptorDescSupersetError;
+use descriptor::descriptor_set::DescriptorSetDesc;
+use descriptor::descriptor_set::PushDescriptorSet;
+use descriptor::pipeline_layout::PipelineLayoutDesc;
+use device::DeviceOwned;
+
+/// Checks whether descriptors can be pushed to the given set of a pipeline layout.
+pub fn check_push_descriptor_set<Pl, R>(pipeline_layout: &Pl, set_num: u32,
+                                        set: &PushDescriptorSet<R>)
+                                        -> Result<(), CheckPushDescriptorSetError>
+    where Pl: ?Sized + PipelineLayoutDesc
+{
+    if !set.device().loaded_extensions().khr_push_descriptor {
+        return Err(CheckPushDescriptorSetError::ExtensionNotEnabled);
+    }
+
+    let set_num = set_num as usize;
+
+    if set_num >= pipeline_layout.num_sets() {
+        return Err(CheckPushDescriptorSetError::SetOutOfRange);
+    }
+
+    match pipeline_layout.provided_set_layout(set_num) {
+        Some(ref layout) if layout.is_push_descriptor() => (),
+        _ => return Err(CheckPushDescriptorSetError::NotPushDescriptorLayout),
+    }
+
+    // Same as for regular descriptor sets, the pushed descriptors must be a superset of what the
+    // pipeline layout expects.
+    for binding_num in 0 .. pipeline_layout.num_bindings_in_set(set_num).unwrap_or(0) {
+        let set_desc = set.descriptor(binding_num);
+        let pipeline_desc = pipeline_layout.descriptor(set_num, binding_num);
+
+        let (set_desc, pipeline_desc) = match (set_desc, pipeline_desc) {
+            (Some(s), Some(p)) => (s, p),
+            (None, Some(_)) =>
+                return Err(CheckPushDescriptorSetError::MissingDescriptor { binding_num }),
+            (Some(_), None) => continue,
+            (None, None) => continue,
+        };
+
+        if let Err(error) = set_desc.is_superset_of(&pipeline_desc) {
+            return Err(CheckPushDescriptorSetError::IncompatibleDescriptor {
+                           error,
+                           binding_num,
+                       });
+        }
+    }
+
+    Ok(())
+}
+
+/// Error that can happen when checking whether descriptors can be pushed.
+#[derive(Debug, Clone)]
+pub enum CheckPushDescriptorSetError {
+    /// The `VK_KHR_push_descriptor` extension isn't enabled on the device.
+    ExtensionNotEnabled,
+
+    /// The set number is out of range of the pipeline layout.
+    SetOutOfRange,
+
+    /// The layout of the set in the pipeline layout wasn't created with
+    /// `UnsafeDescriptorSetLayout::push_descriptor`.
+    NotPushDescriptorLayout,
+
+    /// A descriptor expected by the pipeline layout is missing in the pushed descriptors.
+    MissingDescriptor {
+        /// The binding number of the descriptor.
+        binding_num: usize,
+    },
+
+    /// A pushed descriptor is not compatible with what is expected.
+    IncompatibleDescriptor {
+        /// The reason why the two descriptors aren't compatible.
+        error: DescriptorDescSupersetError,
+        /// The binding number of the descriptor.
+        binding_num: usize,
+    },
+}
+
+impl error::Error for CheckPushDescriptorSetError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            CheckPushDescriptorSetError::ExtensionNotEnabled => {
+                "the `VK_KHR_push_descriptor` extension isn't enabled"
+            },
+            CheckPushDescriptorSetError::SetOutOfRange => {
+                "the set number is out of range of the pipeline layout"
+            },
+            CheckPushDescriptorSetError::NotPushDescriptorLayout => {
+                "the layout of the set in the pipeline layout isn't a push descriptor layout"
+            },
+            CheckPushDescriptorSetError::MissingDescriptor { .. } => {
+                "a descriptor is missing in the pushed descriptors"
+            },
+            CheckPushDescriptorSetError::IncompatibleDescriptor { .. } => {
+                "a pushed descriptor is not compatible with what is expected"
+            },
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&dyn error::Error> {
+        match *self {
+            CheckPushDescriptorSetError::IncompatibleDescriptor { ref error, .. } => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for CheckPushDescriptorSetError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use buffer::BufferUsage;
+    use buffer::CpuAccessibleBuffer;
+    use command_buffer::validity::CheckPushDescriptorSetError;
+    use command_buffer::validity::check_push_descriptor_set;
+    use descriptor::descriptor::DescriptorBufferDesc;
+    use descriptor::descriptor::DescriptorDesc;
+    use descriptor::descriptor::DescriptorDescTy;
+    use descriptor::descriptor::ShaderStages;
+    use descriptor::descriptor_set::PersistentDescriptorSet;
+    use descriptor::descriptor_set::UnsafeDescriptorSetLayout;
+    use descriptor::pipeline_layout::EmptyPipelineDesc;
+    use descriptor::pipeline_layout::PipelineLayoutDescTweaks;
+    use descriptor::pipeline_layout::RuntimePipelineDesc;
+    use std::iter;
+    use std::sync::Arc;
+
+    #[test]
+    fn push_to_layout() {
+        let (device, _) = gfx_dev_and_queue!(extensions: [khr_push_descriptor]);
+
+        let desc = DescriptorDesc {
+            ty: DescriptorDescTy::Buffer(DescriptorBufferDesc {
+                                             dynamic: Some(false),
+                                             storage: false,
+                                         }),
+            array_count: 1,
+            stages: ShaderStages::all_graphics(),
+            readonly: true,
+        };
+
+        let layout = UnsafeDescriptorSetLayout::push_descriptor(device.clone(),
+                                                                iter::once(Some(desc.clone())))
+            .unwrap();
+        let layout = Arc::new(layout);
+
+        let buffer = CpuAccessibleBuffer::from_data(device, BufferUsage::all(), false, 0u32)
+            .unwrap();
+        let set = PersistentDescriptorSet::start(layout.clone())
+            .add_buffer(buffer)
+            .unwrap()
+            .build_push()
+            .unwrap();
+
+        match check_push_descriptor_set(&EmptyPipelineDesc, 0, &set) {
+            Err(CheckPushDescriptorSetError::SetOutOfRange) => (),
+            _ => panic!(),
+        }
+
+        // Same descriptors, but the pipeline layout creates a regular set layout for set 0.
+        let regular = RuntimePipelineDesc::new(vec![vec![Some(desc)]], iter::empty()).unwrap();
+        match check_push_descriptor_set(&regular, 0, &set) {
+            Err(CheckPushDescriptorSetError::NotPushDescriptorLayout) => (),
+            _ => panic!(),
+        }
+
+        let push = PipelineLayoutDescTweaks::new(regular, iter::empty()).with_set_layout(0, layout);
+        check_push_descriptor_set(&push, 0, &set).unwrap();
+    }
+}
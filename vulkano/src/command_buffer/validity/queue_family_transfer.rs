@@ -0,0 +1,124 @@
+// Copyright (c) 2026 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::error;
+use std::fmt;
+
+use VulkanObject;
+use buffer::BufferAccess;
+use device::Device;
+use device::DeviceOwned;
+use image::ImageAccess;
+use instance::QueueFamily;
+
+/// Checks whether a queue family ownership transfer of a buffer is valid.
+///
+/// # Panic
+///
+/// - Panics if the buffer was not created with `device`.
+/// - Panics if one of the queue families doesn't belong to the physical device of `device`.
+///
+pub fn check_buffer_ownership_transfer<B>(device: &Device, buffer: &B, source: QueueFamily,
+                                          destination: QueueFamily)
+                                          -> Result<(), CheckQueueFamilyTransferError>
+    where B: ?Sized + BufferAccess
+{
+    assert_eq!(buffer.inner().buffer.device().internal_object(),
+               device.internal_object());
+
+    check_transfer(device, buffer.inner().buffer.exclusive_sharing(), source, destination)
+}
+
+/// Checks whether a queue family ownership transfer of an image is valid.
+///
+/// # Panic
+///
+/// - Panics if the image was not created with `device`.
+/// - Panics if one of the queue families doesn't belong to the physical device of `device`.
+///
+pub fn check_image_ownership_transfer<I>(device: &Device, image: &I, source: QueueFamily,
+                                         destination: QueueFamily)
+                                         -> Result<(), CheckQueueFamilyTransferError>
+    where I: ?Sized + ImageAccess
+{
+    assert_eq!(image.inner().image.device().internal_object(),
+               device.internal_object());
+
+    check_transfer(device, image.inner().image.exclusive_sharing(), source, destination)
+}
+
+fn check_transfer(device: &Device, exclusive_sharing: bool, source: QueueFamily,
+                  destination: QueueFamily)
+                  -> Result<(), CheckQueueFamilyTransferError> {
+    let physical_device = device.physical_device().internal_object();
+    assert_eq!(source.physical_device().internal_object(), physical_device);
+    assert_eq!(destination.physical_device().internal_object(), physical_device);
+
+    if !exclusive_sharing {
+        return Err(CheckQueueFamilyTransferError::ConcurrentSharing);
+    }
+
+    if source.id() == destination.id() {
+        return Err(CheckQueueFamilyTransferError::SameQueueFamily);
+    }
+
+    Ok(())
+}
+
+/// Error that can happen when attempting to transfer the ownership of a resource between queue
+/// families.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CheckQueueFamilyTransferError {
+    /// The resource was created with `Sharing::Concurrent`, which means that it can already be
+    /// used from all of its queue families.
+    ConcurrentSharing,
+    /// The source and destination queue families are the same.
+    SameQueueFamily,
+}
+
+impl error::Error for CheckQueueFamilyTransferError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            CheckQueueFamilyTransferError::ConcurrentSharing => {
+                "the ownership of a resource created with concurrent sharing can't be transferred"
+            },
+            CheckQueueFamilyTransferError::SameQueueFamily => {
+                "the source and destination queue families are the same"
+            },
+        }
+    }
+}
+
+impl fmt::Display for CheckQueueFamilyTransferError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use buffer::BufferUsage;
+    use buffer::CpuAccessibleBuffer;
+
+    #[test]
+    fn same_queue_family() {
+        let (device, queue) = gfx_dev_and_queue!();
+        let buffer =
+            CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), false, 0u32)
+                .unwrap();
+
+        match check_buffer_ownership_transfer(&device, &buffer, queue.family(), queue.family()) {
+            Err(CheckQueueFamilyTransferError::SameQueueFamily) => (),
+            _ => panic!(),
+        }
+    }
+}
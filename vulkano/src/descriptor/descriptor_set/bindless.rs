@@ -0,0 +1,724 @@
+// Copyright (c) 2026 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Descriptor set containing a large array of images that can be updated after being bound.
+//!
+//! This requires the `VK_EXT_descriptor_indexing` extension. The typical usage is to create a
+//! single set containing all the textures of a scene, and to index it dynamically from the
+//! shaders.
+
+use std::error;
+use std::fmt;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::iter;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::Weak;
+
+use buffer::BufferAccess;
+use descriptor::descriptor::DescriptorDesc;
+use descriptor::descriptor::DescriptorDescTy;
+use descriptor::descriptor_set::DescriptorPoolAllocError;
+use descriptor::descriptor_set::DescriptorSet;
+use descriptor::descriptor_set::DescriptorSetDesc;
+use descriptor::descriptor_set::DescriptorWrite;
+use descriptor::descriptor_set::PersistentDescriptorSetError;
+use descriptor::descriptor_set::UnsafeDescriptorPool;
+use descriptor::descriptor_set::UnsafeDescriptorSet;
+use descriptor::descriptor_set::UnsafeDescriptorSetLayout;
+use descriptor::descriptor_set::persistent::image_match_desc;
+use device::Device;
+use device::DeviceOwned;
+use image::ImageViewAccess;
+use sampler::Sampler;
+
+use OomError;
+use VulkanObject;
+
+/// A descriptor set made of a single array of images, whose elements can be modified at any
+/// time.
+///
+/// The layout must have exactly one binding, of type combined image sampler or sampled image,
+/// created with at least the `update_after_bind`, `update_unused_while_pending` and
+/// `partially_bound` flags. If the binding also has the `variable_descriptor_count` flag, the set
+/// is allocated with the full array size of the binding.
+///
+/// Slots are filled with `set_sampled_image` or `set_image` and emptied with `clear`. Empty
+/// slots must not be accessed by the shaders.
+///
+/// The set itself can't be bound to a command buffer. Instead, call `snapshot` and bind the
+/// returned object. A snapshot keeps alive the resources that were in the set when it was created
+/// and reports them for synchronization.
+///
+/// # Updates
+///
+/// The set may be in use by the GPU when it is modified. In order to never overwrite a descriptor
+/// that a command buffer may read, the slots that were filled when a snapshot was created can't
+/// be written as long as this snapshot is alive, and writing to them returns
+/// `BindlessSlotError::SlotInUse`. Since command buffers hold the snapshots that are bound to
+/// them, a slot becomes writable again once the command buffers that use it are destroyed. Slots
+/// that were empty in all the live snapshots can be written freely. Clearing a slot is always
+/// possible, as the snapshots keep the previous resources alive.
+pub struct BindlessDescriptorSet {
+    // The pool the set is allocated from. Destroying it frees the set.
+    _pool: UnsafeDescriptorPool,
+    set: UnsafeDescriptorSet,
+    layout: Arc<UnsafeDescriptorSetLayout>,
+    // Description of the single binding of the layout.
+    desc: DescriptorDesc,
+    state: Mutex<BindlessState>,
+}
+
+struct BindlessState {
+    slots: Vec<Option<BindlessSlot>>,
+    // For each slot, the number of live snapshots in which the slot is filled.
+    holders: Vec<u32>,
+    // Cached snapshot of `slots`. Reset whenever a slot is modified. This is a weak reference, as
+    // the set must not keep its slots in use.
+    snapshot: Weak<BindlessDescriptorSetSnapshot>,
+}
+
+#[derive(Clone)]
+struct BindlessSlot {
+    image: Arc<dyn ImageViewAccess + Send + Sync>,
+    sampler: Option<Arc<Sampler>>,
+}
+
+impl BindlessDescriptorSet {
+    /// Builds a new `BindlessDescriptorSet` from a layout.
+    ///
+    /// All the slots start empty.
+    pub fn new(layout: Arc<UnsafeDescriptorSetLayout>)
+               -> Result<Arc<BindlessDescriptorSet>, BindlessDescriptorSetError> {
+        let desc = match (layout.num_bindings(), layout.descriptor(0)) {
+            (1, Some(desc)) => desc,
+            _ => return Err(BindlessDescriptorSetError::UnsupportedLayout),
+        };
+
+        match desc.ty {
+            DescriptorDescTy::CombinedImageSampler(_) => (),
+            DescriptorDescTy::Image(ref d) if d.sampled => (),
+            _ => return Err(BindlessDescriptorSetError::UnsupportedLayout),
+        }
+
+        let flags = layout.binding_flags(0);
+        if !flags.update_after_bind || !flags.update_unused_while_pending ||
+            !flags.partially_bound
+        {
+            return Err(BindlessDescriptorSetError::MissingBindingFlags);
+        }
+
+        let mut pool = UnsafeDescriptorPool::with_update_after_bind(layout.device().clone(),
+                                                                    layout.descriptors_count(),
+                                                                    1,
+                                                                    false)?;
+
+        let set = unsafe {
+            let mut sets = if flags.variable_descriptor_count {
+                pool.alloc_with_variable_counts(iter::once((&*layout, desc.array_count)))?
+            } else {
+                pool.alloc(iter::once(&*layout))?
+            };
+            sets.next().unwrap()
+        };
+
+        Ok(Arc::new(BindlessDescriptorSet {
+                        _pool: pool,
+                        set,
+                        layout,
+                        state: Mutex::new(BindlessState {
+                                              slots: vec![None; desc.array_count as usize],
+                                              holders: vec![0; desc.array_count as usize],
+                                              snapshot: Weak::new(),
+                                          }),
+                        desc,
+                    }))
+    }
+
+    /// Returns the number of slots of the set.
+    #[inline]
+    pub fn capacity(&self) -> u32 {
+        self.desc.array_count
+    }
+
+    /// Returns the layout of the set.
+    #[inline]
+    pub fn layout(&self) -> &Arc<UnsafeDescriptorSetLayout> {
+        &self.layout
+    }
+
+    /// Puts an image view with a sampler in a slot. The binding must be of type combined image
+    /// sampler.
+    ///
+    /// Returns `BindlessSlotError::SlotInUse` if the slot is filled in a snapshot that is still
+    /// alive. See the documentation of `BindlessDescriptorSet`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the image view or the sampler doesn't have the same device as the set.
+    ///
+    pub fn set_sampled_image<I>(&self, slot: u32, image_view: I, sampler: Arc<Sampler>)
+                                -> Result<(), BindlessSlotError>
+        where I: ImageViewAccess + Send + Sync + 'static
+    {
+        assert_eq!(self.device().internal_object(),
+                   image_view.parent().inner().image.device().internal_object());
+        assert_eq!(self.device().internal_object(),
+                   sampler.device().internal_object());
+
+        match self.desc.ty {
+            DescriptorDescTy::CombinedImageSampler(ref desc) => {
+                image_match_desc(&image_view, desc)?;
+            },
+            ref ty => {
+                return Err(PersistentDescriptorSetError::WrongDescriptorTy {
+                                   expected: ty.ty().unwrap(),
+                               }
+                               .into());
+            },
+        }
+
+        if !image_view.can_be_sampled(&sampler) {
+            return Err(PersistentDescriptorSetError::IncompatibleImageViewSampler.into());
+        }
+
+        let write = DescriptorWrite::combined_image_sampler(0, slot, &sampler, &image_view);
+        self.set_slot(slot, write, BindlessSlot {
+            image: Arc::new(image_view),
+            sampler: Some(sampler),
+        })
+    }
+
+    /// Puts an image view in a slot. The binding must be of type sampled image.
+    ///
+    /// Returns `BindlessSlotError::SlotInUse` if the slot is filled in a snapshot that is still
+    /// alive. See the documentation of `BindlessDescriptorSet`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the image view doesn't have the same device as the set.
+    ///
+    pub fn set_image<I>(&self, slot: u32, image_view: I) -> Result<(), BindlessSlotError>
+        where I: ImageViewAccess + Send + Sync + 'static
+    {
+        assert_eq!(self.device().internal_object(),
+                   image_view.parent().inner().image.device().internal_object());
+
+        match self.desc.ty {
+            DescriptorDescTy::Image(ref desc) => {
+                image_match_desc(&image_view, desc)?;
+            },
+            ref ty => {
+                return Err(PersistentDescriptorSetError::WrongDescriptorTy {
+                                   expected: ty.ty().unwrap(),
+                               }
+                               .into());
+            },
+        }
+
+        let write = DescriptorWrite::sampled_image(0, slot, &image_view);
+        self.set_slot(slot, write, BindlessSlot {
+            image: Arc::new(image_view),
+            sampler: None,
+        })
+    }
+
+    /// Empties a slot. Does nothing if the slot is already empty.
+    ///
+    /// The descriptor itself isn't modified, but the set no longer holds the resources alive.
+    /// Thanks to the `partially_bound` flag, this is valid as long as the shaders don't access
+    /// the slot.
+    pub fn clear(&self, slot: u32) -> Result<(), PersistentDescriptorSetError> {
+        let mut state = self.state.lock().unwrap();
+        match state.slots.get_mut(slot as usize) {
+            Some(s) => {
+                if s.take().is_some() {
+                    state.snapshot = Weak::new();
+                }
+                Ok(())
+            },
+            None => Err(PersistentDescriptorSetError::ArrayOutOfBounds),
+        }
+    }
+
+    /// Returns a snapshot of the current content of the set, which can be bound to a command
+    /// buffer.
+    ///
+    /// Successive calls return the same snapshot as long as the set isn't modified.
+    pub fn snapshot(this: &Arc<BindlessDescriptorSet>) -> Arc<BindlessDescriptorSetSnapshot> {
+        let mut state = this.state.lock().unwrap();
+
+        if let Some(snapshot) = state.snapshot.upgrade() {
+            return snapshot;
+        }
+
+        let state = &mut *state;
+        let mut slot_nums = Vec::new();
+        let mut slots = Vec::new();
+        for (num, slot) in state.slots.iter().enumerate() {
+            if let Some(ref slot) = *slot {
+                state.holders[num] += 1;
+                slot_nums.push(num);
+                slots.push(slot.clone());
+            }
+        }
+
+        let snapshot = Arc::new(BindlessDescriptorSetSnapshot {
+                                    set: this.clone(),
+                                    slot_nums,
+                                    slots,
+                                });
+        state.snapshot = Arc::downgrade(&snapshot);
+        snapshot
+    }
+
+    // Writes a descriptor and stores the resources of the slot, if no live snapshot holds the
+    // slot.
+    fn set_slot(&self, slot: u32, write: DescriptorWrite, resources: BindlessSlot)
+                -> Result<(), BindlessSlotError> {
+        let mut state = self.state.lock().unwrap();
+
+        match state.holders.get(slot as usize) {
+            Some(&0) => (),
+            Some(_) => return Err(BindlessSlotError::SlotInUse),
+            None => return Err(PersistentDescriptorSetError::ArrayOutOfBounds.into()),
+        }
+
+        // The lock guarantees that no other thread writes to the set at the same time. Since no
+        // snapshot contains the slot, the command buffers don't access it, and the layout flags
+        // allow writing to a set that is bound or in use.
+        unsafe {
+            self.set.write_unsynchronized(self.device(), iter::once(write));
+        }
+
+        state.slots[slot as usize] = Some(resources);
+        state.snapshot = Weak::new();
+        Ok(())
+    }
+}
+
+unsafe impl DeviceOwned for BindlessDescriptorSet {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.layout.device()
+    }
+}
+
+impl fmt::Debug for BindlessDescriptorSet {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        fmt.debug_struct("BindlessDescriptorSet")
+            .field("set", &self.set)
+            .field("capacity", &self.desc.array_count)
+            .finish()
+    }
+}
+
+/// Content of a `BindlessDescriptorSet` at a given point in time.
+///
+/// This is the object that must be bound to command buffers. It holds alive the resources that
+/// were in the set when it was created.
+pub struct BindlessDescriptorSetSnapshot {
+    set: Arc<BindlessDescriptorSet>,
+    // Indices of the slots that were filled, and their content.
+    slot_nums: Vec<usize>,
+    slots: Vec<BindlessSlot>,
+}
+
+impl BindlessDescriptorSetSnapshot {
+    /// Returns the set this snapshot was created from.
+    #[inline]
+    pub fn set(&self) -> &Arc<BindlessDescriptorSet> {
+        &self.set
+    }
+}
+
+impl Drop for BindlessDescriptorSetSnapshot {
+    fn drop(&mut self) {
+        let mut state = self.set.state.lock().unwrap();
+        for &num in self.slot_nums.iter() {
+            state.holders[num] -= 1;
+        }
+    }
+}
+
+unsafe impl DescriptorSet for BindlessDescriptorSetSnapshot {
+    #[inline]
+    fn inner(&self) -> &UnsafeDescriptorSet {
+        &self.set.set
+    }
+
+    #[inline]
+    fn num_buffers(&self) -> usize {
+        0
+    }
+
+    #[inline]
+    fn buffer(&self, _: usize) -> Option<(&dyn BufferAccess, u32)> {
+        None
+    }
+
+    #[inline]
+    fn num_images(&self) -> usize {
+        self.slots.len()
+    }
+
+    #[inline]
+    fn image(&self, index: usize) -> Option<(&dyn ImageViewAccess, u32)> {
+        self.slots
+            .get(index)
+            .map(|s| (&*s.image as &dyn ImageViewAccess, 0))
+    }
+}
+
+unsafe impl DescriptorSetDesc for BindlessDescriptorSetSnapshot {
+    #[inline]
+    fn num_bindings(&self) -> usize {
+        self.set.layout.num_bindings()
+    }
+
+    #[inline]
+    fn descriptor(&self, binding: usize) -> Option<DescriptorDesc> {
+        self.set.layout.descriptor(binding)
+    }
+}
+
+unsafe impl DeviceOwned for BindlessDescriptorSetSnapshot {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.set.device()
+    }
+}
+
+impl PartialEq for BindlessDescriptorSetSnapshot {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        self.inner().internal_object() == other.inner().internal_object() &&
+        self.device() == other.device()
+    }
+}
+
+impl Eq for BindlessDescriptorSetSnapshot {}
+
+impl Hash for BindlessDescriptorSetSnapshot {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.inner().internal_object().hash(state);
+        self.device().hash(state);
+    }
+}
+
+/// Error when creating a `BindlessDescriptorSet`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BindlessDescriptorSetError {
+    /// Not enough memory.
+    OomError(OomError),
+    /// Failed to allocate the descriptor set.
+    AllocError(DescriptorPoolAllocError),
+    /// The layout must have exactly one binding, of type combined image sampler or sampled
+    /// image.
+    UnsupportedLayout,
+    /// The binding must have the `update_after_bind`, `update_unused_while_pending` and
+    /// `partially_bound` flags.
+    MissingBindingFlags,
+}
+
+impl error::Error for BindlessDescriptorSetError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            BindlessDescriptorSetError::OomError(_) => {
+                "not enough memory available"
+            },
+            BindlessDescriptorSetError::AllocError(_) => {
+                "failed to allocate the descriptor set"
+            },
+            BindlessDescriptorSetError::UnsupportedLayout => {
+                "the layout must have exactly one binding, of type combined image sampler or \
+                 sampled image"
+            },
+            BindlessDescriptorSetError::MissingBindingFlags => {
+                "the binding must have the `update_after_bind`, `update_unused_while_pending` and \
+                 `partially_bound` flags"
+            },
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&dyn error::Error> {
+        match *self {
+            BindlessDescriptorSetError::OomError(ref err) => Some(err),
+            BindlessDescriptorSetError::AllocError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for BindlessDescriptorSetError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
+
+impl From<OomError> for BindlessDescriptorSetError {
+    #[inline]
+    fn from(err: OomError) -> BindlessDescriptorSetError {
+        BindlessDescriptorSetError::OomError(err)
+    }
+}
+
+impl From<DescriptorPoolAllocError> for BindlessDescriptorSetError {
+    #[inline]
+    fn from(err: DescriptorPoolAllocError) -> BindlessDescriptorSetError {
+        BindlessDescriptorSetError::AllocError(err)
+    }
+}
+
+/// Error when modifying a slot of a `BindlessDescriptorSet`.
+#[derive(Debug, Clone)]
+pub enum BindlessSlotError {
+    /// The resource can't be put in the slot.
+    DescriptorSetError(PersistentDescriptorSetError),
+    /// The slot is filled in a snapshot that is still alive, and may be read by a command
+    /// buffer.
+    SlotInUse,
+}
+
+impl error::Error for BindlessSlotError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            BindlessSlotError::DescriptorSetError(_) => {
+                "the resource can't be put in the slot"
+            },
+            BindlessSlotError::SlotInUse => {
+                "the slot is filled in a snapshot that is still alive"
+            },
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&dyn error::Error> {
+        match *self {
+            BindlessSlotError::DescriptorSetError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for BindlessSlotError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
+
+impl From<PersistentDescriptorSetError> for BindlessSlotError {
+    #[inline]
+    fn from(err: PersistentDescriptorSetError) -> BindlessSlotError {
+        BindlessSlotError::DescriptorSetError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use descriptor::descriptor::DescriptorDesc;
+    use descriptor::descriptor::DescriptorDescTy;
+    use descriptor::descriptor::DescriptorImageDesc;
+    use descriptor::descriptor::DescriptorImageDescArray;
+    use descriptor::descriptor::DescriptorImageDescDimensions;
+    use descriptor::descriptor::DescriptorType;
+    use descriptor::descriptor::ShaderStages;
+    use descriptor::descriptor_set::BindlessDescriptorSet;
+    use descriptor::descriptor_set::BindlessDescriptorSetError;
+    use descriptor::descriptor_set::BindlessSlotError;
+    use descriptor::descriptor_set::DescriptorBindingFlags;
+    use descriptor::descriptor_set::DescriptorSet;
+    use descriptor::descriptor_set::DescriptorsCount;
+    use descriptor::descriptor_set::PersistentDescriptorSetError;
+    use descriptor::descriptor_set::UnsafeDescriptorPool;
+    use descriptor::descriptor_set::UnsafeDescriptorSetLayout;
+    use format::Format;
+    use image::AttachmentImage;
+    use sampler::Sampler;
+    use std::iter;
+    use std::sync::Arc;
+
+    fn sampler_desc() -> DescriptorDesc {
+        DescriptorDesc {
+            ty: DescriptorDescTy::CombinedImageSampler(DescriptorImageDesc {
+                sampled: true,
+                dimensions: DescriptorImageDescDimensions::TwoDimensional,
+                format: None,
+                multisampled: false,
+                array_layers: DescriptorImageDescArray::NonArrayed,
+            }),
+            array_count: 16,
+            stages: ShaderStages::all_graphics(),
+            readonly: true,
+        }
+    }
+
+    #[test]
+    fn update_after_bind() {
+        let (device, _) = gfx_dev_and_queue!(extensions: [ext_descriptor_indexing],
+                                             descriptor_binding_sampled_image_update_after_bind,
+                                             descriptor_binding_update_unused_while_pending,
+                                             descriptor_binding_partially_bound);
+
+        let flags = DescriptorBindingFlags {
+            update_after_bind: true,
+            update_unused_while_pending: true,
+            partially_bound: true,
+            ..DescriptorBindingFlags::none()
+        };
+        let layout = UnsafeDescriptorSetLayout::with_binding_flags(device.clone(),
+                                                                   iter::once(Some(sampler_desc())),
+                                                                   iter::once(flags))
+            .unwrap();
+
+        let set = BindlessDescriptorSet::new(Arc::new(layout)).unwrap();
+        assert_eq!(set.capacity(), 16);
+
+        let sampler = Sampler::simple_repeat_linear(device.clone());
+        let image = AttachmentImage::sampled(device, [4, 4], Format::R8G8B8A8Unorm).unwrap();
+
+        set.set_sampled_image(3, image.clone(), sampler.clone()).unwrap();
+        match set.set_sampled_image(16, image, sampler) {
+            Err(BindlessSlotError::DescriptorSetError(
+                    PersistentDescriptorSetError::ArrayOutOfBounds)) => (),
+            _ => panic!(),
+        }
+
+        let snapshot = BindlessDescriptorSet::snapshot(&set);
+        assert_eq!(snapshot.num_images(), 1);
+        assert!(Arc::ptr_eq(&snapshot, &BindlessDescriptorSet::snapshot(&set)));
+
+        set.clear(3).unwrap();
+        let snapshot = BindlessDescriptorSet::snapshot(&set);
+        assert_eq!(snapshot.num_images(), 0);
+    }
+
+    #[test]
+    fn slots_held_by_snapshots() {
+        let (device, _) = gfx_dev_and_queue!(extensions: [ext_descriptor_indexing],
+                                             descriptor_binding_sampled_image_update_after_bind,
+                                             descriptor_binding_update_unused_while_pending,
+                                             descriptor_binding_partially_bound);
+
+        let flags = DescriptorBindingFlags {
+            update_after_bind: true,
+            update_unused_while_pending: true,
+            partially_bound: true,
+            ..DescriptorBindingFlags::none()
+        };
+        let layout = UnsafeDescriptorSetLayout::with_binding_flags(device.clone(),
+                                                                   iter::once(Some(sampler_desc())),
+                                                                   iter::once(flags))
+            .unwrap();
+
+        let set = BindlessDescriptorSet::new(Arc::new(layout)).unwrap();
+        let sampler = Sampler::simple_repeat_linear(device.clone());
+        let image = AttachmentImage::sampled(device, [4, 4], Format::R8G8B8A8Unorm).unwrap();
+
+        set.set_sampled_image(3, image.clone(), sampler.clone()).unwrap();
+        let snapshot = BindlessDescriptorSet::snapshot(&set);
+
+        // The slot may be read by a command buffer that uses the snapshot.
+        match set.set_sampled_image(3, image.clone(), sampler.clone()) {
+            Err(BindlessSlotError::SlotInUse) => (),
+            _ => panic!(),
+        }
+
+        // Clearing doesn't modify the descriptor, but the slot stays in use.
+        set.clear(3).unwrap();
+        match set.set_sampled_image(3, image.clone(), sampler.clone()) {
+            Err(BindlessSlotError::SlotInUse) => (),
+            _ => panic!(),
+        }
+
+        // Slots that are empty in the snapshot can be written.
+        set.set_sampled_image(4, image.clone(), sampler.clone()).unwrap();
+        let snapshot2 = BindlessDescriptorSet::snapshot(&set);
+        assert!(!Arc::ptr_eq(&snapshot, &snapshot2));
+
+        drop(snapshot);
+        set.set_sampled_image(3, image.clone(), sampler.clone()).unwrap();
+        match set.set_sampled_image(4, image.clone(), sampler.clone()) {
+            Err(BindlessSlotError::SlotInUse) => (),
+            _ => panic!(),
+        }
+
+        drop(snapshot2);
+        set.set_sampled_image(4, image, sampler).unwrap();
+    }
+
+    #[test]
+    fn variable_descriptor_count() {
+        let (device, _) = gfx_dev_and_queue!(extensions: [ext_descriptor_indexing],
+                                             descriptor_binding_sampled_image_update_after_bind,
+                                             descriptor_binding_update_unused_while_pending,
+                                             descriptor_binding_partially_bound,
+                                             descriptor_binding_variable_descriptor_count);
+
+        let flags = DescriptorBindingFlags {
+            update_after_bind: true,
+            update_unused_while_pending: true,
+            partially_bound: true,
+            variable_descriptor_count: true,
+        };
+        let layout = UnsafeDescriptorSetLayout::with_binding_flags(device.clone(),
+                                                                   iter::once(Some(sampler_desc())),
+                                                                   iter::once(flags))
+            .unwrap();
+        let layout = Arc::new(layout);
+
+        // Allocating fewer descriptors than the upper bound of the binding, so that both sets
+        // fit in a pool of 20 descriptors.
+        let mut count = DescriptorsCount::zero();
+        count.add_num(DescriptorType::CombinedImageSampler, 20);
+        let mut pool = UnsafeDescriptorPool::with_update_after_bind(device.clone(), &count, 2,
+                                                                    false)
+            .unwrap();
+        unsafe {
+            let sets = pool.alloc_with_variable_counts(vec![(&*layout, 4), (&*layout, 16)])
+                .unwrap();
+            assert_eq!(sets.count(), 2);
+        }
+
+        let set = BindlessDescriptorSet::new(layout).unwrap();
+        assert_eq!(set.capacity(), 16);
+    }
+
+    #[test]
+    fn layout_without_flags() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let desc = DescriptorDesc {
+            ty: DescriptorDescTy::CombinedImageSampler(DescriptorImageDesc {
+                sampled: true,
+                dimensions: DescriptorImageDescDimensions::TwoDimensional,
+                format: None,
+                multisampled: false,
+                array_layers: DescriptorImageDescArray::NonArrayed,
+            }),
+            array_count: 16,
+            stages: ShaderStages::all_graphics(),
+            readonly: true,
+        };
+
+        let layout = UnsafeDescriptorSetLayout::new(device, iter::once(Some(desc))).unwrap();
+
+        match BindlessDescriptorSet::new(Arc::new(layout)) {
+            Err(BindlessDescriptorSetError::MissingBindingFlags) => (),
+            _ => panic!(),
+        }
+    }
+}
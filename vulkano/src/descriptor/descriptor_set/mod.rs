@@ -45,6 +45,10 @@ use device::DeviceOwned;
 use descriptor::descriptor::DescriptorDesc;
 use image::ImageViewAccess;
 
+pub use self::bindless::BindlessDescriptorSet;
+pub use self::bindless::BindlessDescriptorSetError;
+pub use self::bindless::BindlessDescriptorSetSnapshot;
+pub use self::bindless::BindlessSlotError;
 pub use self::collection::DescriptorSetsCollection;
 pub use self::fixed_size_pool::FixedSizeDescriptorSet;
 pub use self::fixed_size_pool::FixedSizeDescriptorSetBuilder;
@@ -59,8 +63,11 @@ pub use self::persistent::PersistentDescriptorSetBuilderArray;
 pub use self::persistent::PersistentDescriptorSetError;
 pub use self::persistent::PersistentDescriptorSetImg;
 pub use self::persistent::PersistentDescriptorSetSampler;
+pub use self::push::PushDescriptorSet;
 pub use self::std_pool::StdDescriptorPool;
 pub use self::std_pool::StdDescriptorPoolAlloc;
+pub use self::std_pool::StdDescriptorPoolBuilder;
+pub use self::std_pool::StdDescriptorPoolStats;
 pub use self::sys::DescriptorPool;
 pub use self::sys::DescriptorPoolAlloc;
 pub use self::sys::DescriptorPoolAllocError;
@@ -69,15 +76,30 @@ pub use self::sys::DescriptorsCount;
 pub use self::sys::UnsafeDescriptorPool;
 pub use self::sys::UnsafeDescriptorPoolAllocIter;
 pub use self::sys::UnsafeDescriptorSet;
+pub use self::unsafe_layout::DescriptorBindingFlags;
+pub use self::unsafe_layout::DescriptorSetLayoutCreationError;
 pub use self::unsafe_layout::UnsafeDescriptorSetLayout;
+pub use self::update_template::DescriptorUpdateTemplate;
+pub use self::update_template::DescriptorUpdateTemplateCreationError;
+pub use self::with_offsets::DescriptorSetWithOffsets;
+
+pub(crate) use self::persistent::PersistentDescriptorSetResources;
+pub(crate) use self::sys::raw_descriptor_writes;
+pub(crate) use self::with_offsets::buffers_dynamic_offsets;
+pub(crate) use self::with_offsets::effective_dynamic_offsets;
+pub(crate) use self::with_offsets::num_dynamic_offsets;
 
 pub mod collection;
 
+mod bindless;
 mod fixed_size_pool;
 mod persistent;
+mod push;
 mod std_pool;
 mod sys;
 mod unsafe_layout;
+mod update_template;
+mod with_offsets;
 
 /// Trait for objects that contain a collection of resources that will be accessible by shaders.
 ///
@@ -103,6 +125,17 @@ pub unsafe trait DescriptorSet: DescriptorSetDesc + DeviceOwned {
     ///
     /// The valid range is between 0 and `num_images()`.
     fn image(&self, index: usize) -> Option<(&dyn ImageViewAccess, u32)>;
+
+    /// Returns the dynamic offsets to apply to the dynamic buffer descriptors of this set when it
+    /// is bound. There is one offset for each element of each dynamic buffer descriptor, ordered
+    /// by binding number then by array element.
+    ///
+    /// An empty list means that all the offsets are 0. The default implementation returns an
+    /// empty list. See `DescriptorSetWithOffsets`.
+    #[inline]
+    fn dynamic_offsets(&self) -> &[u32] {
+        &[]
+    }
 }
 
 unsafe impl<T> DescriptorSet for T
@@ -133,6 +166,11 @@ unsafe impl<T> DescriptorSet for T
     fn image(&self, index: usize) -> Option<(&dyn ImageViewAccess, u32)> {
         (**self).image(index)
     }
+
+    #[inline]
+    fn dynamic_offsets(&self) -> &[u32] {
+        (**self).dynamic_offsets()
+    }
 }
 
 impl PartialEq for dyn DescriptorSet + Send + Sync {
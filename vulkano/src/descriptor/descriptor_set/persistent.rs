@@ -28,6 +28,7 @@ use descriptor::descriptor_set::DescriptorPoolAlloc;
 use descriptor::descriptor_set::DescriptorSet;
 use descriptor::descriptor_set::DescriptorSetDesc;
 use descriptor::descriptor_set::DescriptorWrite;
+use descriptor::descriptor_set::PushDescriptorSet;
 use descriptor::descriptor_set::StdDescriptorPoolAlloc;
 use descriptor::descriptor_set::UnsafeDescriptorSet;
 use descriptor::descriptor_set::UnsafeDescriptorSetLayout;
@@ -216,8 +217,21 @@ impl<R> PersistentDescriptorSetBuilder<R>
 
         let set = unsafe {
             let mut set = pool.alloc(&self.layout)?;
-            set.inner_mut()
-                .write(pool.device(), self.writes.into_iter());
+
+            // Using a template is faster, but only possible if all the descriptors are written.
+            let written = match self.layout.update_template() {
+                Some(template) => {
+                    set.inner_mut()
+                        .write_with_template_impl(pool.device(), &template, &self.writes)
+                },
+                None => false,
+            };
+
+            if !written {
+                set.inner_mut()
+                    .write(pool.device(), self.writes.into_iter());
+            }
+
             set
         };
 
@@ -228,6 +242,29 @@ impl<R> PersistentDescriptorSetBuilder<R>
            })
     }
 
+    /// Builds a `PushDescriptorSet` from the builder, which can be pushed into a command buffer
+    /// with `AutoCommandBufferBuilder::push_descriptor_set`.
+    ///
+    /// # Panic
+    ///
+    /// Panics if the layout wasn't created with `UnsafeDescriptorSetLayout::push_descriptor`.
+    ///
+    pub fn build_push(self) -> Result<PushDescriptorSet<R>, PersistentDescriptorSetBuildError> {
+        assert!(self.layout.is_push_descriptor(),
+                "The layout of a push descriptor set must be a push descriptor layout");
+
+        let expected_desc = self.layout.num_bindings();
+
+        if expected_desc > self.binding_id {
+            return Err(PersistentDescriptorSetBuildError::MissingDescriptors {
+                           expected: expected_desc as u32,
+                           obtained: self.binding_id as u32,
+                       });
+        }
+
+        Ok(PushDescriptorSet::new(self.layout, self.writes, self.resources))
+    }
+
     /// Call this function if the next element of the set is an array in order to set the value of
     /// each element.
     ///
@@ -426,9 +463,16 @@ impl<R> PersistentDescriptorSetBuilderArray<R>
                     }
 
                     unsafe {
-                        DescriptorWrite::storage_buffer(self.builder.binding_id as u32,
-                                                        self.array_element as u32,
-                                                        &buffer)
+                        if buffer_desc.dynamic == Some(true) {
+                            DescriptorWrite::dynamic_storage_buffer(self.builder.binding_id as
+                                                                        u32,
+                                                                    self.array_element as u32,
+                                                                    &buffer)
+                        } else {
+                            DescriptorWrite::storage_buffer(self.builder.binding_id as u32,
+                                                            self.array_element as u32,
+                                                            &buffer)
+                        }
                     }
                 } else {
                     if !buffer.inner().buffer.usage_uniform_buffer() {
@@ -437,9 +481,16 @@ impl<R> PersistentDescriptorSetBuilderArray<R>
                     }
 
                     unsafe {
-                        DescriptorWrite::uniform_buffer(self.builder.binding_id as u32,
-                                                        self.array_element as u32,
-                                                        &buffer)
+                        if buffer_desc.dynamic == Some(true) {
+                            DescriptorWrite::dynamic_uniform_buffer(self.builder.binding_id as
+                                                                        u32,
+                                                                    self.array_element as u32,
+                                                                    &buffer)
+                        } else {
+                            DescriptorWrite::uniform_buffer(self.builder.binding_id as u32,
+                                                            self.array_element as u32,
+                                                            &buffer)
+                        }
                     }
                 }
             },
@@ -754,8 +805,8 @@ impl<R> PersistentDescriptorSetBuilderArray<R>
 }
 
 // Checks whether an image view matches the descriptor.
-fn image_match_desc<I>(image_view: &I, desc: &DescriptorImageDesc)
-                       -> Result<(), PersistentDescriptorSetError>
+pub(super) fn image_match_desc<I>(image_view: &I, desc: &DescriptorImageDesc)
+                                  -> Result<(), PersistentDescriptorSetError>
     where I: ?Sized + ImageViewAccess
 {
     if desc.sampled && !image_view.parent().inner().image.usage_sampled() {
@@ -0,0 +1,108 @@
+// Copyright (c) 2026 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::sync::Arc;
+
+use buffer::BufferAccess;
+use descriptor::descriptor::DescriptorDesc;
+use descriptor::descriptor_set::DescriptorSetDesc;
+use descriptor::descriptor_set::DescriptorWrite;
+use descriptor::descriptor_set::UnsafeDescriptorSetLayout;
+use descriptor::descriptor_set::persistent::PersistentDescriptorSetResources;
+use device::Device;
+use device::DeviceOwned;
+use image::ImageViewAccess;
+
+/// List of descriptors to push directly into a command buffer, without allocating a descriptor
+/// set.
+///
+/// Built with `PersistentDescriptorSetBuilder::build_push`, from a layout created with
+/// `UnsafeDescriptorSetLayout::push_descriptor`. Pass it to
+/// `AutoCommandBufferBuilder::push_descriptor_set`.
+///
+/// The template parameter `R` is an unspecified type that represents the list of resources.
+pub struct PushDescriptorSet<R> {
+    layout: Arc<UnsafeDescriptorSetLayout>,
+    writes: Vec<DescriptorWrite>,
+    resources: R,
+}
+
+impl<R> PushDescriptorSet<R> {
+    #[inline]
+    pub(super) fn new(layout: Arc<UnsafeDescriptorSetLayout>, writes: Vec<DescriptorWrite>,
+                      resources: R)
+                      -> PushDescriptorSet<R> {
+        PushDescriptorSet {
+            layout,
+            writes,
+            resources,
+        }
+    }
+
+    /// Returns the layout of the descriptors.
+    #[inline]
+    pub fn layout(&self) -> &Arc<UnsafeDescriptorSetLayout> {
+        &self.layout
+    }
+
+    // Returns the writes to perform when pushing the descriptors.
+    #[inline]
+    pub(crate) fn writes(&self) -> &[DescriptorWrite] {
+        &self.writes
+    }
+}
+
+impl<R> PushDescriptorSet<R>
+    where R: PersistentDescriptorSetResources
+{
+    /// Returns the number of buffers within the descriptors.
+    #[inline]
+    pub fn num_buffers(&self) -> usize {
+        self.resources.num_buffers()
+    }
+
+    /// Returns the `index`th buffer, or `None` if out of range. Also returns the index of the
+    /// descriptor that uses this buffer.
+    #[inline]
+    pub fn buffer(&self, index: usize) -> Option<(&dyn BufferAccess, u32)> {
+        self.resources.buffer(index)
+    }
+
+    /// Returns the number of images within the descriptors.
+    #[inline]
+    pub fn num_images(&self) -> usize {
+        self.resources.num_images()
+    }
+
+    /// Returns the `index`th image, or `None` if out of range. Also returns the index of the
+    /// descriptor that uses this image.
+    #[inline]
+    pub fn image(&self, index: usize) -> Option<(&dyn ImageViewAccess, u32)> {
+        self.resources.image(index)
+    }
+}
+
+unsafe impl<R> DescriptorSetDesc for PushDescriptorSet<R> {
+    #[inline]
+    fn num_bindings(&self) -> usize {
+        self.layout.num_bindings()
+    }
+
+    #[inline]
+    fn descriptor(&self, binding: usize) -> Option<DescriptorDesc> {
+        self.layout.descriptor(binding)
+    }
+}
+
+unsafe impl<R> DeviceOwned for PushDescriptorSet<R> {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.layout.device()
+    }
+}
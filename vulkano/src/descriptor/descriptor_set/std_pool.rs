@@ -7,8 +7,11 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
+use std::cmp;
 use std::sync::Arc;
 use std::sync::Mutex;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
 
 use OomError;
 use descriptor::descriptor_set::DescriptorPool;
@@ -27,29 +30,208 @@ use device::DeviceOwned;
 /// desirable so that we can store a `Weak<StdDescriptorPool>`.
 ///
 /// Whenever a set is allocated, this implementation will try to find a pool that has some space
-/// for it. If there is one, allocate from it. If there is none, create a new pool whose capacity
-/// is 40 sets and 40 times the requested descriptors. This number is arbitrary.
+/// for it. If there is one, allocate from it. If there is none, create a new pool. The size of the
+/// new pools is determined by the parameters of the `StdDescriptorPoolBuilder`. By default, a new
+/// pool's capacity is 40 sets and 40 times the requested descriptors.
+///
+/// When all the sets of a pool have been freed, the pool is automatically reset. This reclaims
+/// the space lost to fragmentation.
 pub struct StdDescriptorPool {
     device: Arc<Device>,
-    pools: Mutex<Vec<Arc<Mutex<Pool>>>>,
+    pools: Mutex<Pools>,
+    descriptors_per_set: DescriptorsCount,
+    max_sets_per_pool: u32,
+    growth_factor: f32,
+    free_descriptor_set: bool,
+    num_resets: AtomicUsize,
+    num_failed_allocs: AtomicUsize,
+}
+
+struct Pools {
+    list: Vec<Arc<Mutex<Pool>>>,
+    // Number of sets of the next pool to create.
+    next_sets_count: u32,
 }
 
 struct Pool {
     pool: UnsafeDescriptorPool,
+    capacity: DescriptorsCount,
+    sets_count: u32,
     remaining_capacity: DescriptorsCount,
     remaining_sets_count: u32,
+    // Number of sets currently allocated from this pool.
+    allocated_sets_count: u32,
+    // True if an allocation failed despite the remaining capacity, which means that the pool is
+    // fragmented. No more sets are allocated from this pool until it is reset.
+    exhausted: bool,
 }
 
 impl StdDescriptorPool {
-    /// Builds a new `StdDescriptorPool`.
+    /// Builds a new `StdDescriptorPool` with the default parameters.
+    ///
+    /// > **Note**: This is just a shortcut for `StdDescriptorPool::start(device).build()`.
+    #[inline]
     pub fn new(device: Arc<Device>) -> StdDescriptorPool {
+        StdDescriptorPool::start(device).build()
+    }
+
+    /// Starts building a new `StdDescriptorPool`.
+    #[inline]
+    pub fn start(device: Arc<Device>) -> StdDescriptorPoolBuilder {
+        StdDescriptorPoolBuilder {
+            device,
+            descriptors_per_set: DescriptorsCount::zero(),
+            sets_per_pool: 40,
+            max_sets_per_pool: 40,
+            growth_factor: 1.0,
+            free_descriptor_set: true,
+        }
+    }
+
+    /// Returns statistics about the usage of the pool.
+    pub fn stats(&self) -> StdDescriptorPoolStats {
+        let pools = self.pools.lock().unwrap();
+
+        let mut stats = StdDescriptorPoolStats {
+            num_pools: pools.list.len(),
+            allocated_sets: 0,
+            capacity_sets: 0,
+            capacity_descriptors: DescriptorsCount::zero(),
+            num_resets: self.num_resets.load(Ordering::Relaxed),
+            num_failed_allocs: self.num_failed_allocs.load(Ordering::Relaxed),
+        };
+
+        for pool in pools.list.iter() {
+            let pool = pool.lock().unwrap();
+            stats.allocated_sets += pool.allocated_sets_count;
+            stats.capacity_sets += pool.sets_count;
+            stats.capacity_descriptors += pool.capacity;
+        }
+
+        stats
+    }
+}
+
+/// Prototype of a `StdDescriptorPool`.
+///
+/// Created with `StdDescriptorPool::start`.
+pub struct StdDescriptorPoolBuilder {
+    device: Arc<Device>,
+    descriptors_per_set: DescriptorsCount,
+    sets_per_pool: u32,
+    max_sets_per_pool: u32,
+    growth_factor: f32,
+    free_descriptor_set: bool,
+}
+
+impl StdDescriptorPoolBuilder {
+    /// Sets the number of descriptors of each type to reserve per set when creating a pool.
+    ///
+    /// A new pool can hold `descriptors_per_set` times its number of sets descriptors of each
+    /// type. If the layout that triggered the creation of the pool requires more descriptors of a
+    /// type, the layout's count is used instead. The default value is zero for each type, which
+    /// means that pools are sized only according to the layout that triggered their creation.
+    #[inline]
+    pub fn descriptors_per_set(mut self, count: DescriptorsCount) -> StdDescriptorPoolBuilder {
+        self.descriptors_per_set = count;
+        self
+    }
+
+    /// Sets the number of sets of the first pool that is created. The default value is 40.
+    ///
+    /// If this is greater than the maximum set with `max_sets_per_pool`, the maximum is raised.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `sets` is 0.
+    ///
+    #[inline]
+    pub fn sets_per_pool(mut self, sets: u32) -> StdDescriptorPoolBuilder {
+        assert_ne!(sets, 0);
+        self.sets_per_pool = sets;
+        self.max_sets_per_pool = cmp::max(self.max_sets_per_pool, sets);
+        self
+    }
+
+    /// Sets the maximum number of sets of a pool. The default value is 40.
+    ///
+    /// If this is lower than the number set with `sets_per_pool`, that number is lowered.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `sets` is 0.
+    ///
+    #[inline]
+    pub fn max_sets_per_pool(mut self, sets: u32) -> StdDescriptorPoolBuilder {
+        assert_ne!(sets, 0);
+        self.max_sets_per_pool = sets;
+        self.sets_per_pool = cmp::min(self.sets_per_pool, sets);
+        self
+    }
+
+    /// Sets the factor by which the number of sets is multiplied every time a new pool is
+    /// created, up to the value set with `max_sets_per_pool`. The default value is 1.0, meaning
+    /// that all pools have the same size.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `factor` is lower than 1.0.
+    ///
+    #[inline]
+    pub fn growth_factor(mut self, factor: f32) -> StdDescriptorPoolBuilder {
+        assert!(factor >= 1.0, "The growth factor must be at least 1.0");
+        self.growth_factor = factor;
+        self
+    }
+
+    /// Sets whether the pools are created with the ability to free individual sets. The default
+    /// value is `true`.
+    ///
+    /// If `false`, the space occupied by a set isn't reclaimed when the set is destroyed, and is
+    /// only reclaimed when all the sets of its pool have been destroyed. This is suitable for
+    /// sets that are destroyed all at once, and may be faster on some implementations.
+    #[inline]
+    pub fn free_descriptor_set(mut self, free: bool) -> StdDescriptorPoolBuilder {
+        self.free_descriptor_set = free;
+        self
+    }
+
+    /// Builds the `StdDescriptorPool`.
+    #[inline]
+    pub fn build(self) -> StdDescriptorPool {
         StdDescriptorPool {
-            device: device,
-            pools: Mutex::new(Vec::new()),
+            device: self.device,
+            pools: Mutex::new(Pools {
+                                  list: Vec::new(),
+                                  next_sets_count: self.sets_per_pool,
+                              }),
+            descriptors_per_set: self.descriptors_per_set,
+            max_sets_per_pool: self.max_sets_per_pool,
+            growth_factor: self.growth_factor,
+            free_descriptor_set: self.free_descriptor_set,
+            num_resets: AtomicUsize::new(0),
+            num_failed_allocs: AtomicUsize::new(0),
         }
     }
 }
 
+/// Statistics about the usage of a `StdDescriptorPool`.
+#[derive(Debug, Copy, Clone)]
+pub struct StdDescriptorPoolStats {
+    /// Number of Vulkan pools that currently exist.
+    pub num_pools: usize,
+    /// Number of sets that are currently allocated.
+    pub allocated_sets: u32,
+    /// Total number of sets that the existing pools can hold.
+    pub capacity_sets: u32,
+    /// Total number of descriptors that the existing pools can hold.
+    pub capacity_descriptors: DescriptorsCount,
+    /// Number of times a pool has been reset after all of its sets have been freed.
+    pub num_resets: usize,
+    /// Number of times allocating from an existing pool failed because of fragmentation.
+    pub num_failed_allocs: usize,
+}
+
 /// A descriptor set allocated from a `StdDescriptorPool`.
 pub struct StdDescriptorPoolAlloc {
     pool: Arc<Mutex<Pool>>,
@@ -70,10 +252,10 @@ unsafe impl DescriptorPool for Arc<StdDescriptorPool> {
         let mut pools = self.pools.lock().unwrap();
 
         // Try find an existing pool with some free space.
-        for pool_arc in pools.iter_mut() {
+        for pool_arc in pools.list.iter_mut() {
             let mut pool = pool_arc.lock().unwrap();
 
-            if pool.remaining_sets_count == 0 {
+            if pool.exhausted || pool.remaining_sets_count == 0 {
                 continue;
             }
 
@@ -81,22 +263,24 @@ unsafe impl DescriptorPool for Arc<StdDescriptorPool> {
                 continue;
             }
 
-            // Note that we decrease these values *before* trying to allocate from the pool.
-            // If allocating from the pool results in an error, we just ignore it. In order to
-            // avoid trying the same failing pool every time, we "pollute" it by reducing the
-            // available space.
-            pool.remaining_sets_count -= 1;
-            pool.remaining_capacity -= *layout.descriptors_count();
-
             let alloc = unsafe {
                 match pool.pool.alloc(Some(layout)) {
                     Ok(mut sets) => sets.next().unwrap(),
                     // An error can happen if we're out of memory, or if the pool is fragmented.
-                    // We handle these errors by just ignoring this pool and trying the next ones.
-                    Err(_) => continue,
+                    // We handle these errors by ignoring this pool until it is reset, and trying
+                    // the next ones.
+                    Err(_) => {
+                        pool.exhausted = true;
+                        self.num_failed_allocs.fetch_add(1, Ordering::Relaxed);
+                        continue;
+                    },
                 }
             };
 
+            pool.remaining_sets_count -= 1;
+            pool.remaining_capacity -= *layout.descriptors_count();
+            pool.allocated_sets_count += 1;
+
             return Ok(StdDescriptorPoolAlloc {
                           pool: pool_arc.clone(),
                           set: Some(alloc),
@@ -106,11 +290,14 @@ unsafe impl DescriptorPool for Arc<StdDescriptorPool> {
         }
 
         // No existing pool can be used. Create a new one.
-        // We use an arbitrary number of 40 sets and 40 times the requested descriptors.
-        let count = layout.descriptors_count().clone() * 40;
+        let sets_count = pools.next_sets_count;
+        let count = self.descriptors_per_set.max(layout.descriptors_count()) * sets_count;
         // Failure to allocate a new pool results in an error for the whole function because
         // there's no way we can recover from that.
-        let mut new_pool = UnsafeDescriptorPool::new(self.device.clone(), &count, 40, true)?;
+        let mut new_pool = UnsafeDescriptorPool::new(self.device.clone(),
+                                                     &count,
+                                                     sets_count,
+                                                     self.free_descriptor_set)?;
 
         let alloc = unsafe {
             match new_pool.alloc(Some(layout)) {
@@ -128,14 +315,23 @@ unsafe impl DescriptorPool for Arc<StdDescriptorPool> {
             }
         };
 
+        // Empty pools that were too small for this layout are replaced by the new pool.
+        pools.list.retain(|pool| pool.lock().unwrap().allocated_sets_count != 0);
+
         let pool_obj = Arc::new(Mutex::new(Pool {
                                                pool: new_pool,
+                                               capacity: count,
+                                               sets_count,
                                                remaining_capacity: count -
                                                    *layout.descriptors_count(),
-                                               remaining_sets_count: 40 - 1,
+                                               remaining_sets_count: sets_count - 1,
+                                               allocated_sets_count: 1,
+                                               exhausted: false,
                                            }));
 
-        pools.push(pool_obj.clone());
+        pools.list.push(pool_obj.clone());
+        pools.next_sets_count = cmp::min((sets_count as f32 * self.growth_factor).ceil() as u32,
+                                         self.max_sets_per_pool);
 
         Ok(StdDescriptorPoolAlloc {
                pool: pool_obj,
@@ -170,10 +366,23 @@ impl Drop for StdDescriptorPoolAlloc {
     fn drop(&mut self) {
         unsafe {
             let mut pool = self.pool.lock().unwrap();
-            pool.pool.free(self.set.take()).unwrap();
-            // Add back the capacity only after freeing, in case of a panic during the free.
-            pool.remaining_sets_count += 1;
-            pool.remaining_capacity += self.descriptors;
+            pool.allocated_sets_count -= 1;
+
+            if pool.allocated_sets_count == 0 {
+                // This was the last set of the pool. Resetting the pool frees it along with the
+                // other sets, and gets rid of the fragmentation.
+                self.set.take();
+                pool.pool.reset().unwrap();
+                pool.remaining_sets_count = pool.sets_count;
+                pool.remaining_capacity = pool.capacity;
+                pool.exhausted = false;
+                self.pool_parent.num_resets.fetch_add(1, Ordering::Relaxed);
+            } else if self.pool_parent.free_descriptor_set {
+                pool.pool.free(self.set.take()).unwrap();
+                // Add back the capacity only after freeing, in case of a panic during the free.
+                pool.remaining_sets_count += 1;
+                pool.remaining_capacity += self.descriptors;
+            }
         }
     }
 }
@@ -184,6 +393,7 @@ mod tests {
     use descriptor::descriptor::DescriptorDescTy;
     use descriptor::descriptor::ShaderStages;
     use descriptor::descriptor_set::DescriptorPool;
+    use descriptor::descriptor_set::DescriptorsCount;
     use descriptor::descriptor_set::StdDescriptorPool;
     use descriptor::descriptor_set::UnsafeDescriptorSetLayout;
     use std::iter;
@@ -209,4 +419,79 @@ mod tests {
         drop(pool);
         assert!(pool_weak.upgrade().is_some());
     }
+
+    #[test]
+    fn reset_when_empty() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let desc = DescriptorDesc {
+            ty: DescriptorDescTy::Sampler,
+            array_count: 1,
+            stages: ShaderStages::all(),
+            readonly: false,
+        };
+        let layout = UnsafeDescriptorSetLayout::new(device.clone(), iter::once(Some(desc)))
+            .unwrap();
+
+        let mut pool = Arc::new(StdDescriptorPool::start(device)
+                                    .sets_per_pool(2)
+                                    .free_descriptor_set(false)
+                                    .build());
+
+        let alloc1 = pool.alloc(&layout).unwrap();
+        let alloc2 = pool.alloc(&layout).unwrap();
+        let alloc3 = pool.alloc(&layout).unwrap();
+
+        let stats = pool.stats();
+        assert_eq!(stats.num_pools, 2);
+        assert_eq!(stats.allocated_sets, 3);
+        assert_eq!(stats.capacity_sets, 4);
+
+        drop(alloc1);
+        drop(alloc2);
+        assert_eq!(pool.stats().num_resets, 1);
+
+        // The first pool has been reset and can be used again.
+        let _alloc4 = pool.alloc(&layout).unwrap();
+        let stats = pool.stats();
+        assert_eq!(stats.num_pools, 2);
+        assert_eq!(stats.allocated_sets, 2);
+        drop(alloc3);
+    }
+
+    #[test]
+    fn growth_factor() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let desc = DescriptorDesc {
+            ty: DescriptorDescTy::Sampler,
+            array_count: 1,
+            stages: ShaderStages::all(),
+            readonly: false,
+        };
+        let layout = UnsafeDescriptorSetLayout::new(device.clone(), iter::once(Some(desc)))
+            .unwrap();
+
+        let mut pool = Arc::new(StdDescriptorPool::start(device)
+                                    .sets_per_pool(1)
+                                    .max_sets_per_pool(3)
+                                    .growth_factor(2.0)
+                                    .descriptors_per_set(DescriptorsCount {
+                                                             uniform_buffer: 2,
+                                                             ..DescriptorsCount::zero()
+                                                         })
+                                    .build());
+
+        let _allocs = (0 .. 6).map(|_| pool.alloc(&layout).unwrap()).collect::<Vec<_>>();
+
+        let stats = pool.stats();
+        assert_eq!(stats.num_pools, 3);
+        assert_eq!(stats.capacity_sets, 1 + 2 + 3);
+        assert_eq!(stats.capacity_descriptors,
+                   DescriptorsCount {
+                       sampler: 6,
+                       uniform_buffer: 12,
+                       ..DescriptorsCount::zero()
+                   });
+    }
 }
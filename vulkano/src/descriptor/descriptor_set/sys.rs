@@ -8,9 +8,11 @@
 // according to those terms.
 
 use smallvec::SmallVec;
+use std::borrow::Borrow;
 use std::cmp;
 use std::error;
 use std::fmt;
+use std::mem;
 use std::mem::MaybeUninit;
 use std::ops;
 use std::ptr;
@@ -21,6 +23,7 @@ use buffer::BufferAccess;
 use buffer::BufferInner;
 use buffer::BufferView;
 use descriptor::descriptor::DescriptorType;
+use descriptor::descriptor_set::DescriptorUpdateTemplate;
 use descriptor::descriptor_set::UnsafeDescriptorSetLayout;
 use device::Device;
 use device::DeviceOwned;
@@ -30,6 +33,7 @@ use sampler::Sampler;
 use OomError;
 use VulkanObject;
 use check_errors;
+use version::Version;
 use vk;
 
 /// A pool from which descriptor sets can be allocated.
@@ -89,21 +93,37 @@ macro_rules! descriptors_count {
                 }
             }
 
+            /// Returns, for each descriptor type, the highest of the two counts.
+            #[inline]
+            pub fn max(&self, other: &DescriptorsCount) -> DescriptorsCount {
+                DescriptorsCount {
+                    $(
+                        $name: cmp::max(self.$name, other.$name),
+                    )+
+                }
+            }
+
             /// Adds one descriptor of the given type to the count.
             #[inline]
             pub fn add_one(&mut self, ty: DescriptorType) {
+                self.add_num(ty, 1);
+            }
+
+            /// Adds `num` descriptors of the given type to the count.
+            #[inline]
+            pub fn add_num(&mut self, ty: DescriptorType, num: u32) {
                 match ty {
-                    DescriptorType::Sampler => self.sampler += 1,
-                    DescriptorType::CombinedImageSampler => self.combined_image_sampler += 1,
-                    DescriptorType::SampledImage => self.sampled_image += 1,
-                    DescriptorType::StorageImage => self.storage_image += 1,
-                    DescriptorType::UniformTexelBuffer => self.uniform_texel_buffer += 1,
-                    DescriptorType::StorageTexelBuffer => self.storage_texel_buffer += 1,
-                    DescriptorType::UniformBuffer => self.uniform_buffer += 1,
-                    DescriptorType::StorageBuffer => self.storage_buffer += 1,
-                    DescriptorType::UniformBufferDynamic => self.uniform_buffer_dynamic += 1,
-                    DescriptorType::StorageBufferDynamic => self.storage_buffer_dynamic += 1,
-                    DescriptorType::InputAttachment => self.input_attachment += 1,
+                    DescriptorType::Sampler => self.sampler += num,
+                    DescriptorType::CombinedImageSampler => self.combined_image_sampler += num,
+                    DescriptorType::SampledImage => self.sampled_image += num,
+                    DescriptorType::StorageImage => self.storage_image += num,
+                    DescriptorType::UniformTexelBuffer => self.uniform_texel_buffer += num,
+                    DescriptorType::StorageTexelBuffer => self.storage_texel_buffer += num,
+                    DescriptorType::UniformBuffer => self.uniform_buffer += num,
+                    DescriptorType::StorageBuffer => self.storage_buffer += num,
+                    DescriptorType::UniformBufferDynamic => self.uniform_buffer_dynamic += num,
+                    DescriptorType::StorageBufferDynamic => self.storage_buffer_dynamic += num,
+                    DescriptorType::InputAttachment => self.input_attachment += num,
                 };
             }
         }
@@ -245,9 +265,38 @@ impl UnsafeDescriptorPool {
     /// - Panics if all the descriptors count are 0.
     /// - Panics if `max_sets` is 0.
     ///
+    #[inline]
     pub fn new(device: Arc<Device>, count: &DescriptorsCount, max_sets: u32,
                free_descriptor_set_bit: bool)
                -> Result<UnsafeDescriptorPool, OomError> {
+        UnsafeDescriptorPool::new_impl(device, count, max_sets, free_descriptor_set_bit, false)
+    }
+
+    /// Same as `new`, but creates a pool with the `UPDATE_AFTER_BIND` flag.
+    ///
+    /// Such a pool is required in order to allocate descriptor sets whose layout contains
+    /// bindings with the `update_after_bind` flag. See `DescriptorBindingFlags`.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the `VK_EXT_descriptor_indexing` extension wasn't enabled on the device.
+    /// - Panics if all the descriptors count are 0.
+    /// - Panics if `max_sets` is 0.
+    ///
+    #[inline]
+    pub fn with_update_after_bind(device: Arc<Device>, count: &DescriptorsCount, max_sets: u32,
+                                  free_descriptor_set_bit: bool)
+                                  -> Result<UnsafeDescriptorPool, OomError> {
+        assert!(device.api_version() >= Version::V1_2 ||
+                    device.loaded_extensions().ext_descriptor_indexing,
+                "The VK_EXT_descriptor_indexing extension must be enabled");
+        UnsafeDescriptorPool::new_impl(device, count, max_sets, free_descriptor_set_bit, true)
+    }
+
+    // Actual implementation of `new` and `with_update_after_bind`.
+    fn new_impl(device: Arc<Device>, count: &DescriptorsCount, max_sets: u32,
+                free_descriptor_set_bit: bool, update_after_bind: bool)
+                -> Result<UnsafeDescriptorPool, OomError> {
         let vk = device.pointers();
 
         assert_ne!(max_sets, 0, "The maximum number of sets can't be 0");
@@ -289,10 +338,15 @@ impl UnsafeDescriptorPool {
             let infos = vk::DescriptorPoolCreateInfo {
                 sType: vk::STRUCTURE_TYPE_DESCRIPTOR_POOL_CREATE_INFO,
                 pNext: ptr::null(),
-                flags: if free_descriptor_set_bit {
-                    vk::DESCRIPTOR_POOL_CREATE_FREE_DESCRIPTOR_SET_BIT
-                } else {
-                    0
+                flags: {
+                    let mut flags = 0;
+                    if free_descriptor_set_bit {
+                        flags |= vk::DESCRIPTOR_POOL_CREATE_FREE_DESCRIPTOR_SET_BIT;
+                    }
+                    if update_after_bind {
+                        flags |= vk::DESCRIPTOR_POOL_CREATE_UPDATE_AFTER_BIND_BIT_EXT;
+                    }
+                    flags
                 },
                 maxSets: max_sets,
                 poolSizeCount: pool_sizes.len() as u32,
@@ -322,6 +376,7 @@ impl UnsafeDescriptorPool {
     /// # Panic
     ///
     /// - Panics if one of the layouts wasn't created with the same device as the pool.
+    /// - Panics if one of the layouts is a push descriptor layout.
     ///
     /// # Safety
     ///
@@ -344,15 +399,58 @@ impl UnsafeDescriptorPool {
                                 l.device().internal_object(),
                                 "Tried to allocate from a pool with a set layout of a different \
                                  device");
+                     assert!(!l.is_push_descriptor(),
+                             "Tried to allocate a descriptor set with a push descriptor layout");
+                     l.internal_object()
+                 })
+            .collect();
+
+        self.alloc_impl(&layouts, None)
+    }
+
+    /// Same as `alloc`, but also passes the number of descriptors to allocate for the binding
+    /// with the `variable_descriptor_count` flag of each layout.
+    ///
+    /// The count is ignored for layouts that don't have such a binding.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if one of the layouts wasn't created with the same device as the pool.
+    /// - Panics if one of the layouts is a push descriptor layout.
+    ///
+    /// # Safety
+    ///
+    /// Same as `alloc`. In addition:
+    ///
+    /// - The `VK_EXT_descriptor_indexing` extension must be enabled on the device.
+    /// - Each count must not exceed the descriptor count of the variable binding of its layout.
+    ///
+    pub unsafe fn alloc_with_variable_counts<'l, I>(
+        &mut self, layouts: I)
+        -> Result<UnsafeDescriptorPoolAllocIter, DescriptorPoolAllocError>
+        where I: IntoIterator<Item = (&'l UnsafeDescriptorSetLayout, u32)>
+    {
+        let mut counts: SmallVec<[u32; 8]> = SmallVec::new();
+        let layouts: SmallVec<[_; 8]> = layouts
+            .into_iter()
+            .map(|(l, count)| {
+                     assert_eq!(self.device.internal_object(),
+                                l.device().internal_object(),
+                                "Tried to allocate from a pool with a set layout of a different \
+                                 device");
+                     assert!(!l.is_push_descriptor(),
+                             "Tried to allocate a descriptor set with a push descriptor layout");
+                     counts.push(count);
                      l.internal_object()
                  })
             .collect();
 
-        self.alloc_impl(&layouts)
+        self.alloc_impl(&layouts, Some(&counts))
     }
 
     // Actual implementation of `alloc`. Separated so that it is not inlined.
-    unsafe fn alloc_impl(&mut self, layouts: &SmallVec<[vk::DescriptorSetLayout; 8]>)
+    unsafe fn alloc_impl(&mut self, layouts: &SmallVec<[vk::DescriptorSetLayout; 8]>,
+                         variable_counts: Option<&SmallVec<[u32; 8]>>)
                          -> Result<UnsafeDescriptorPoolAllocIter, DescriptorPoolAllocError> {
         let num = layouts.len();
 
@@ -360,9 +458,22 @@ impl UnsafeDescriptorPool {
             return Ok(UnsafeDescriptorPoolAllocIter { sets: vec![].into_iter() });
         }
 
+        let variable_infos = variable_counts.map(|counts| {
+            debug_assert_eq!(counts.len(), num);
+            vk::DescriptorSetVariableDescriptorCountAllocateInfoEXT {
+                sType: vk::STRUCTURE_TYPE_DESCRIPTOR_SET_VARIABLE_DESCRIPTOR_COUNT_ALLOCATE_INFO_EXT,
+                pNext: ptr::null(),
+                descriptorSetCount: counts.len() as u32,
+                pDescriptorCounts: counts.as_ptr(),
+            }
+        });
+
         let infos = vk::DescriptorSetAllocateInfo {
             sType: vk::STRUCTURE_TYPE_DESCRIPTOR_SET_ALLOCATE_INFO,
-            pNext: ptr::null(),
+            pNext: variable_infos
+                .as_ref()
+                .map(|i| i as *const _ as *const _)
+                .unwrap_or(ptr::null()),
             descriptorPool: self.pool,
             descriptorSetCount: layouts.len() as u32,
             pSetLayouts: layouts.as_ptr(),
@@ -564,169 +675,318 @@ impl UnsafeDescriptorSet {
     ///   command buffer contains a pointer/reference to a descriptor set, it is illegal to write
     ///   to it.
     ///
+    #[inline]
     pub unsafe fn write<I>(&mut self, device: &Device, writes: I)
         where I: Iterator<Item = DescriptorWrite>
+    {
+        self.write_unsynchronized(device, writes)
+    }
+
+    // Same as `write`, but doesn't require exclusive access to the set. Used by descriptor sets
+    // whose bindings have the `update_after_bind` flag, which can be written while the set is
+    // bound. The caller is responsible for not writing the same descriptor from multiple threads.
+    pub(super) unsafe fn write_unsynchronized<I>(&self, device: &Device, writes: I)
+        where I: Iterator<Item = DescriptorWrite>
     {
         let vk = device.pointers();
 
-        // In this function, we build 4 arrays: one array of image descriptors (image_descriptors),
-        // one for buffer descriptors (buffer_descriptors), one for buffer view descriptors
-        // (buffer_views_descriptors), and one for the final list of writes (raw_writes).
-        // Only the final list is passed to Vulkan, but it will contain pointers to the first three
-        // lists in `pImageInfo`, `pBufferInfo` and `pTexelBufferView`.
-        //
-        // In order to handle that, we start by writing null pointers as placeholders in the final
-        // writes, and we store in `raw_writes_img_infos`, `raw_writes_buf_infos` and
-        // `raw_writes_buf_view_infos` the offsets of the pointers compared to the start of the
-        // list.
-        // Once we have finished iterating all the writes requested by the user, we modify
-        // `raw_writes` to point to the correct locations.
-
-        let mut buffer_descriptors: SmallVec<[_; 64]> = SmallVec::new();
-        let mut image_descriptors: SmallVec<[_; 64]> = SmallVec::new();
-        let mut buffer_views_descriptors: SmallVec<[_; 64]> = SmallVec::new();
-
-        let mut raw_writes: SmallVec<[_; 64]> = SmallVec::new();
-        let mut raw_writes_img_infos: SmallVec<[_; 64]> = SmallVec::new();
-        let mut raw_writes_buf_infos: SmallVec<[_; 64]> = SmallVec::new();
-        let mut raw_writes_buf_view_infos: SmallVec<[_; 64]> = SmallVec::new();
-
-        for indiv_write in writes {
-            // Since the `DescriptorWrite` objects are built only through functions, we know for
-            // sure that it's impossible to have an empty descriptor write.
-            debug_assert!(!indiv_write.inner.is_empty());
-
-            // The whole struct thats written here is valid, except for pImageInfo, pBufferInfo
-            // and pTexelBufferView which are placeholder values.
-            raw_writes.push(vk::WriteDescriptorSet {
-                                sType: vk::STRUCTURE_TYPE_WRITE_DESCRIPTOR_SET,
-                                pNext: ptr::null(),
-                                dstSet: self.set,
-                                dstBinding: indiv_write.binding,
-                                dstArrayElement: indiv_write.first_array_element,
-                                descriptorCount: indiv_write.inner.len() as u32,
-                                descriptorType: indiv_write.ty() as u32,
-                                pImageInfo: ptr::null(),
-                                pBufferInfo: ptr::null(),
-                                pTexelBufferView: ptr::null(),
-                            });
-
-            match indiv_write.inner[0] {
-                DescriptorWriteInner::Sampler(_) |
-                DescriptorWriteInner::CombinedImageSampler(_, _, _) |
-                DescriptorWriteInner::SampledImage(_, _) |
-                DescriptorWriteInner::StorageImage(_, _) |
-                DescriptorWriteInner::InputAttachment(_, _) => {
-                    raw_writes_img_infos.push(Some(image_descriptors.len()));
-                    raw_writes_buf_infos.push(None);
-                    raw_writes_buf_view_infos.push(None);
-                },
-                DescriptorWriteInner::UniformBuffer(_, _, _) |
-                DescriptorWriteInner::StorageBuffer(_, _, _) |
-                DescriptorWriteInner::DynamicUniformBuffer(_, _, _) |
-                DescriptorWriteInner::DynamicStorageBuffer(_, _, _) => {
-                    raw_writes_img_infos.push(None);
-                    raw_writes_buf_infos.push(Some(buffer_descriptors.len()));
-                    raw_writes_buf_view_infos.push(None);
-                },
-                DescriptorWriteInner::UniformTexelBuffer(_) |
-                DescriptorWriteInner::StorageTexelBuffer(_) => {
-                    raw_writes_img_infos.push(None);
-                    raw_writes_buf_infos.push(None);
-                    raw_writes_buf_view_infos.push(Some(buffer_views_descriptors.len()));
-                },
+        raw_descriptor_writes(self.set, writes, |raw_writes| {
+            // It is forbidden to call `vkUpdateDescriptorSets` with 0 writes, so we need to
+            // perform this emptiness check.
+            if !raw_writes.is_empty() {
+                vk.UpdateDescriptorSets(device.internal_object(),
+                                        raw_writes.len() as u32,
+                                        raw_writes.as_ptr(),
+                                        0,
+                                        ptr::null());
+            }
+        })
+    }
+
+    /// Modifies a descriptor set by using a `DescriptorUpdateTemplate`.
+    ///
+    /// Contrary to `write`, the writes must cover every descriptor of the template. If the same
+    /// descriptor is written multiple times, the last write wins.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the writes don't cover every descriptor of the template, or if a write is out
+    ///   of the range of the template.
+    ///
+    /// # Safety
+    ///
+    /// Same as `write`. In addition:
+    ///
+    /// - The template must have been created from the layout this set was allocated with.
+    ///
+    pub unsafe fn write_with_template<I>(&mut self, device: &Device,
+                                         template: &DescriptorUpdateTemplate, writes: I)
+        where I: IntoIterator<Item = DescriptorWrite>
+    {
+        let writes = writes.into_iter().collect::<SmallVec<[_; 32]>>();
+        let written = self.write_with_template_impl(device, template, &writes);
+        assert!(written,
+                "The descriptor writes don't cover all the descriptors of the template");
+    }
+
+    // Actual implementation of `write_with_template`. Returns `false` and doesn't modify the set
+    // if the writes don't cover every descriptor of the template.
+    pub(super) unsafe fn write_with_template_impl(&mut self, device: &Device,
+                                                  template: &DescriptorUpdateTemplate,
+                                                  writes: &[DescriptorWrite])
+                                                  -> bool {
+        assert_eq!(device.internal_object(),
+                   template.device().internal_object());
+
+        // We use a buffer of `u64`s in order to have a correct alignment for the Vulkan structs.
+        // All these structs have a size that is a multiple of 8.
+        debug_assert_eq!(template.data_size() % mem::size_of::<u64>(), 0);
+        let mut data: SmallVec<[u64; 64]> =
+            SmallVec::from_elem(0, template.data_size() / mem::size_of::<u64>());
+        let mut written: SmallVec<[bool; 64]> =
+            SmallVec::from_elem(false, template.num_descriptors());
+
+        for write in writes {
+            let entry = match template.entry(write.binding) {
+                Some(e) => e,
+                None => return false,
+            };
+
+            // The size of the structure written for each element depends on the type, so a
+            // write whose type doesn't match is left to the regular path.
+            if !write.inner.is_empty() && write.ty() != entry.ty {
+                return false;
             }
 
-            for elem in indiv_write.inner.iter() {
+            for (num, elem) in write.inner.iter().enumerate() {
+                let element = write.first_array_element as usize + num;
+                if element >= entry.count as usize {
+                    return false;
+                }
+
+                written[entry.first_descriptor + element] = true;
+                debug_assert!(entry.offset + (element + 1) * entry.stride <= template.data_size());
+                let dest = (data.as_mut_ptr() as *mut u8)
+                    .add(entry.offset + element * entry.stride);
+
                 match *elem {
                     DescriptorWriteInner::UniformBuffer(buffer, offset, size) |
-                    DescriptorWriteInner::DynamicUniformBuffer(buffer, offset, size) => {
-                        buffer_descriptors.push(vk::DescriptorBufferInfo {
-                                                    buffer: buffer,
-                                                    offset: offset as u64,
-                                                    range: size as u64,
-                                                });
-                    },
+                    DescriptorWriteInner::DynamicUniformBuffer(buffer, offset, size) |
                     DescriptorWriteInner::StorageBuffer(buffer, offset, size) |
                     DescriptorWriteInner::DynamicStorageBuffer(buffer, offset, size) => {
-                        buffer_descriptors.push(vk::DescriptorBufferInfo {
-                                                    buffer: buffer,
-                                                    offset: offset as u64,
-                                                    range: size as u64,
-                                                });
+                        ptr::write(dest as *mut vk::DescriptorBufferInfo,
+                                   vk::DescriptorBufferInfo {
+                                       buffer,
+                                       offset: offset as u64,
+                                       range: size as u64,
+                                   });
                     },
                     DescriptorWriteInner::Sampler(sampler) => {
-                        image_descriptors.push(vk::DescriptorImageInfo {
-                                                   sampler: sampler,
-                                                   imageView: 0,
-                                                   imageLayout: 0,
-                                               });
+                        ptr::write(dest as *mut vk::DescriptorImageInfo,
+                                   vk::DescriptorImageInfo {
+                                       sampler,
+                                       imageView: 0,
+                                       imageLayout: 0,
+                                   });
                     },
                     DescriptorWriteInner::CombinedImageSampler(sampler, view, layout) => {
-                        image_descriptors.push(vk::DescriptorImageInfo {
-                                                   sampler: sampler,
-                                                   imageView: view,
-                                                   imageLayout: layout,
-                                               });
-                    },
-                    DescriptorWriteInner::StorageImage(view, layout) => {
-                        image_descriptors.push(vk::DescriptorImageInfo {
-                                                   sampler: 0,
-                                                   imageView: view,
-                                                   imageLayout: layout,
-                                               });
-                    },
-                    DescriptorWriteInner::SampledImage(view, layout) => {
-                        image_descriptors.push(vk::DescriptorImageInfo {
-                                                   sampler: 0,
-                                                   imageView: view,
-                                                   imageLayout: layout,
-                                               });
+                        ptr::write(dest as *mut vk::DescriptorImageInfo,
+                                   vk::DescriptorImageInfo {
+                                       sampler,
+                                       imageView: view,
+                                       imageLayout: layout,
+                                   });
                     },
+                    DescriptorWriteInner::StorageImage(view, layout) |
+                    DescriptorWriteInner::SampledImage(view, layout) |
                     DescriptorWriteInner::InputAttachment(view, layout) => {
-                        image_descriptors.push(vk::DescriptorImageInfo {
-                                                   sampler: 0,
-                                                   imageView: view,
-                                                   imageLayout: layout,
-                                               });
+                        ptr::write(dest as *mut vk::DescriptorImageInfo,
+                                   vk::DescriptorImageInfo {
+                                       sampler: 0,
+                                       imageView: view,
+                                       imageLayout: layout,
+                                   });
                     },
                     DescriptorWriteInner::UniformTexelBuffer(view) |
                     DescriptorWriteInner::StorageTexelBuffer(view) => {
-                        buffer_views_descriptors.push(view);
+                        ptr::write(dest as *mut vk::BufferView, view);
                     },
                 }
             }
         }
 
-        // Now that `image_descriptors`, `buffer_descriptors` and `buffer_views_descriptors` are
-        // entirely filled and will never move again, we can fill the pointers in `raw_writes`.
-        for (i, write) in raw_writes.iter_mut().enumerate() {
-            write.pImageInfo = match raw_writes_img_infos[i] {
-                Some(off) => image_descriptors.as_ptr().offset(off as isize),
-                None => ptr::null(),
-            };
+        if written.iter().any(|w| !w) {
+            return false;
+        }
 
-            write.pBufferInfo = match raw_writes_buf_infos[i] {
-                Some(off) => buffer_descriptors.as_ptr().offset(off as isize),
-                None => ptr::null(),
-            };
+        let vk = device.pointers();
+        vk.UpdateDescriptorSetWithTemplateKHR(device.internal_object(),
+                                              self.set,
+                                              template.internal_object(),
+                                              data.as_ptr() as *const _);
+        true
+    }
+}
 
-            write.pTexelBufferView = match raw_writes_buf_view_infos[i] {
-                Some(off) => buffer_views_descriptors.as_ptr().offset(off as isize),
-                None => ptr::null(),
-            };
+// Turns a list of `DescriptorWrite`s into a list of `VkWriteDescriptorSet`s that write to
+// `dst_set`, and passes it to `f`. `dst_set` is ignored by Vulkan for push descriptors.
+pub(crate) unsafe fn raw_descriptor_writes<I, F, R>(dst_set: vk::DescriptorSet, writes: I, f: F)
+                                                    -> R
+    where I: Iterator,
+          I::Item: Borrow<DescriptorWrite>,
+          F: FnOnce(&[vk::WriteDescriptorSet]) -> R
+{
+    // In this function, we build 4 arrays: one array of image descriptors (image_descriptors),
+    // one for buffer descriptors (buffer_descriptors), one for buffer view descriptors
+    // (buffer_views_descriptors), and one for the final list of writes (raw_writes).
+    // Only the final list is passed to Vulkan, but it will contain pointers to the first three
+    // lists in `pImageInfo`, `pBufferInfo` and `pTexelBufferView`.
+    //
+    // In order to handle that, we start by writing null pointers as placeholders in the final
+    // writes, and we store in `raw_writes_img_infos`, `raw_writes_buf_infos` and
+    // `raw_writes_buf_view_infos` the offsets of the pointers compared to the start of the
+    // list.
+    // Once we have finished iterating all the writes requested by the user, we modify
+    // `raw_writes` to point to the correct locations.
+
+    let mut buffer_descriptors: SmallVec<[_; 64]> = SmallVec::new();
+    let mut image_descriptors: SmallVec<[_; 64]> = SmallVec::new();
+    let mut buffer_views_descriptors: SmallVec<[_; 64]> = SmallVec::new();
+
+    let mut raw_writes: SmallVec<[_; 64]> = SmallVec::new();
+    let mut raw_writes_img_infos: SmallVec<[_; 64]> = SmallVec::new();
+    let mut raw_writes_buf_infos: SmallVec<[_; 64]> = SmallVec::new();
+    let mut raw_writes_buf_view_infos: SmallVec<[_; 64]> = SmallVec::new();
+
+    for indiv_write in writes {
+        let indiv_write = indiv_write.borrow();
+
+        // Since the `DescriptorWrite` objects are built only through functions, we know for
+        // sure that it's impossible to have an empty descriptor write.
+        debug_assert!(!indiv_write.inner.is_empty());
+
+        // The whole struct thats written here is valid, except for pImageInfo, pBufferInfo
+        // and pTexelBufferView which are placeholder values.
+        raw_writes.push(vk::WriteDescriptorSet {
+                            sType: vk::STRUCTURE_TYPE_WRITE_DESCRIPTOR_SET,
+                            pNext: ptr::null(),
+                            dstSet: dst_set,
+                            dstBinding: indiv_write.binding,
+                            dstArrayElement: indiv_write.first_array_element,
+                            descriptorCount: indiv_write.inner.len() as u32,
+                            descriptorType: indiv_write.ty() as u32,
+                            pImageInfo: ptr::null(),
+                            pBufferInfo: ptr::null(),
+                            pTexelBufferView: ptr::null(),
+                        });
+
+        match indiv_write.inner[0] {
+            DescriptorWriteInner::Sampler(_) |
+            DescriptorWriteInner::CombinedImageSampler(_, _, _) |
+            DescriptorWriteInner::SampledImage(_, _) |
+            DescriptorWriteInner::StorageImage(_, _) |
+            DescriptorWriteInner::InputAttachment(_, _) => {
+                raw_writes_img_infos.push(Some(image_descriptors.len()));
+                raw_writes_buf_infos.push(None);
+                raw_writes_buf_view_infos.push(None);
+            },
+            DescriptorWriteInner::UniformBuffer(_, _, _) |
+            DescriptorWriteInner::StorageBuffer(_, _, _) |
+            DescriptorWriteInner::DynamicUniformBuffer(_, _, _) |
+            DescriptorWriteInner::DynamicStorageBuffer(_, _, _) => {
+                raw_writes_img_infos.push(None);
+                raw_writes_buf_infos.push(Some(buffer_descriptors.len()));
+                raw_writes_buf_view_infos.push(None);
+            },
+            DescriptorWriteInner::UniformTexelBuffer(_) |
+            DescriptorWriteInner::StorageTexelBuffer(_) => {
+                raw_writes_img_infos.push(None);
+                raw_writes_buf_infos.push(None);
+                raw_writes_buf_view_infos.push(Some(buffer_views_descriptors.len()));
+            },
         }
 
-        // It is forbidden to call `vkUpdateDescriptorSets` with 0 writes, so we need to perform
-        // this emptiness check.
-        if !raw_writes.is_empty() {
-            vk.UpdateDescriptorSets(device.internal_object(),
-                                    raw_writes.len() as u32,
-                                    raw_writes.as_ptr(),
-                                    0,
-                                    ptr::null());
+        for elem in indiv_write.inner.iter() {
+            match *elem {
+                DescriptorWriteInner::UniformBuffer(buffer, offset, size) |
+                DescriptorWriteInner::DynamicUniformBuffer(buffer, offset, size) => {
+                    buffer_descriptors.push(vk::DescriptorBufferInfo {
+                                                buffer: buffer,
+                                                offset: offset as u64,
+                                                range: size as u64,
+                                            });
+                },
+                DescriptorWriteInner::StorageBuffer(buffer, offset, size) |
+                DescriptorWriteInner::DynamicStorageBuffer(buffer, offset, size) => {
+                    buffer_descriptors.push(vk::DescriptorBufferInfo {
+                                                buffer: buffer,
+                                                offset: offset as u64,
+                                                range: size as u64,
+                                            });
+                },
+                DescriptorWriteInner::Sampler(sampler) => {
+                    image_descriptors.push(vk::DescriptorImageInfo {
+                                               sampler: sampler,
+                                               imageView: 0,
+                                               imageLayout: 0,
+                                           });
+                },
+                DescriptorWriteInner::CombinedImageSampler(sampler, view, layout) => {
+                    image_descriptors.push(vk::DescriptorImageInfo {
+                                               sampler: sampler,
+                                               imageView: view,
+                                               imageLayout: layout,
+                                           });
+                },
+                DescriptorWriteInner::StorageImage(view, layout) => {
+                    image_descriptors.push(vk::DescriptorImageInfo {
+                                               sampler: 0,
+                                               imageView: view,
+                                               imageLayout: layout,
+                                           });
+                },
+                DescriptorWriteInner::SampledImage(view, layout) => {
+                    image_descriptors.push(vk::DescriptorImageInfo {
+                                               sampler: 0,
+                                               imageView: view,
+                                               imageLayout: layout,
+                                           });
+                },
+                DescriptorWriteInner::InputAttachment(view, layout) => {
+                    image_descriptors.push(vk::DescriptorImageInfo {
+                                               sampler: 0,
+                                               imageView: view,
+                                               imageLayout: layout,
+                                           });
+                },
+                DescriptorWriteInner::UniformTexelBuffer(view) |
+                DescriptorWriteInner::StorageTexelBuffer(view) => {
+                    buffer_views_descriptors.push(view);
+                },
+            }
         }
     }
+
+    // Now that `image_descriptors`, `buffer_descriptors` and `buffer_views_descriptors` are
+    // entirely filled and will never move again, we can fill the pointers in `raw_writes`.
+    for (i, write) in raw_writes.iter_mut().enumerate() {
+        write.pImageInfo = match raw_writes_img_infos[i] {
+            Some(off) => image_descriptors.as_ptr().offset(off as isize),
+            None => ptr::null(),
+        };
+
+        write.pBufferInfo = match raw_writes_buf_infos[i] {
+            Some(off) => buffer_descriptors.as_ptr().offset(off as isize),
+            None => ptr::null(),
+        };
+
+        write.pTexelBufferView = match raw_writes_buf_view_infos[i] {
+            Some(off) => buffer_views_descriptors.as_ptr().offset(off as isize),
+            None => ptr::null(),
+        };
+    }
+
+    f(&raw_writes)
 }
 
 unsafe impl VulkanObject for UnsafeDescriptorSet {
@@ -1040,7 +1300,11 @@ mod tests {
     use descriptor::descriptor::DescriptorBufferDesc;
     use descriptor::descriptor::DescriptorDesc;
     use descriptor::descriptor::DescriptorDescTy;
+    use buffer::BufferUsage;
+    use buffer::CpuAccessibleBuffer;
     use descriptor::descriptor::ShaderStages;
+    use descriptor::descriptor_set::DescriptorUpdateTemplate;
+    use descriptor::descriptor_set::DescriptorWrite;
     use descriptor::descriptor_set::DescriptorsCount;
     use descriptor::descriptor_set::UnsafeDescriptorPool;
     use descriptor::descriptor_set::UnsafeDescriptorSetLayout;
@@ -1157,4 +1421,41 @@ mod tests {
             assert_eq!(sets.count(), 0);
         }
     }
+
+    #[test]
+    fn template_type_mismatch() {
+        let (device, _) = gfx_dev_and_queue!(extensions: [khr_descriptor_update_template]);
+
+        let layout = DescriptorDesc {
+            ty: DescriptorDescTy::TexelBuffer {
+                storage: false,
+                format: None,
+            },
+            array_count: 1,
+            stages: ShaderStages::all_graphics(),
+            readonly: true,
+        };
+
+        let set_layout = UnsafeDescriptorSetLayout::new(device.clone(), iter::once(Some(layout)))
+            .unwrap();
+        let template = DescriptorUpdateTemplate::new(&set_layout).unwrap();
+
+        let desc = DescriptorsCount {
+            uniform_texel_buffer: 1,
+            ..DescriptorsCount::zero()
+        };
+
+        let mut pool = UnsafeDescriptorPool::new(device.clone(), &desc, 1, false).unwrap();
+        let buffer = CpuAccessibleBuffer::from_data(device.clone(), BufferUsage::all(), false,
+                                                    0u32)
+            .unwrap();
+
+        unsafe {
+            let mut set = pool.alloc(iter::once(&set_layout)).unwrap().next().unwrap();
+
+            // A uniform buffer descriptor is bigger than the texel buffer view of the binding.
+            let write = DescriptorWrite::uniform_buffer(0, 0, &buffer);
+            assert!(!set.write_with_template_impl(&device, &template, &[write]));
+        }
+    }
 }
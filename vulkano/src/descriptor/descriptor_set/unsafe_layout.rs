@@ -8,19 +8,24 @@
 // according to those terms.
 
 use smallvec::SmallVec;
+use std::error;
 use std::fmt;
 use std::mem::MaybeUninit;
 use std::ptr;
 use std::sync::Arc;
+use std::sync::Mutex;
 
 use OomError;
 use VulkanObject;
 use check_errors;
+use version::Version;
 use vk;
 
 use descriptor::descriptor::DescriptorDesc;
+use descriptor::descriptor::DescriptorType;
 use descriptor::descriptor_set::DescriptorsCount;
 use descriptor::descriptor_set::DescriptorSetDesc;
+use descriptor::descriptor_set::DescriptorUpdateTemplate;
 use device::Device;
 use device::DeviceOwned;
 
@@ -38,6 +43,12 @@ pub struct UnsafeDescriptorSetLayout {
     descriptors: SmallVec<[Option<DescriptorDesc>; 32]>,
     // Number of descriptors.
     descriptors_count: DescriptorsCount,
+    // Flags of each binding. Empty if the layout was created without flags.
+    binding_flags: SmallVec<[DescriptorBindingFlags; 32]>,
+    // True if the layout was created for push descriptors.
+    push_descriptor: bool,
+    // Template used to write sets with this layout, created on first use.
+    update_template: Mutex<Option<Arc<DescriptorUpdateTemplate>>>,
 }
 
 impl UnsafeDescriptorSetLayout {
@@ -51,7 +62,84 @@ impl UnsafeDescriptorSetLayout {
         where I: IntoIterator<Item = Option<DescriptorDesc>>
     {
         let descriptors = descriptors.into_iter().collect::<SmallVec<[_; 32]>>();
+
+        match UnsafeDescriptorSetLayout::new_impl(device, descriptors, SmallVec::new(), false) {
+            Ok(layout) => Ok(layout),
+            Err(DescriptorSetLayoutCreationError::OomError(err)) => Err(err),
+            Err(_) => unreachable!(),
+        }
+    }
+
+    /// Builds a new `UnsafeDescriptorSetLayout` with the given descriptors and flags for each
+    /// binding.
+    ///
+    /// Same as `new`, except that `flags` yields the flags of each binding in the same order as
+    /// `descriptors`. Bindings for which `flags` doesn't yield any value use
+    /// `DescriptorBindingFlags::none()`.
+    ///
+    /// Using any flag requires the `VK_EXT_descriptor_indexing` extension and the corresponding
+    /// features to be enabled on the device.
+    pub fn with_binding_flags<I, F>(device: Arc<Device>, descriptors: I, flags: F)
+                                    -> Result<UnsafeDescriptorSetLayout,
+                                              DescriptorSetLayoutCreationError>
+        where I: IntoIterator<Item = Option<DescriptorDesc>>,
+              F: IntoIterator<Item = DescriptorBindingFlags>
+    {
+        let descriptors = descriptors.into_iter().collect::<SmallVec<[_; 32]>>();
+        let mut flags = flags.into_iter().collect::<SmallVec<[_; 32]>>();
+        flags.truncate(descriptors.len());
+        while flags.len() < descriptors.len() {
+            flags.push(DescriptorBindingFlags::none());
+        }
+
+        if flags.iter().all(|f| *f == DescriptorBindingFlags::none()) {
+            flags.clear();
+        } else {
+            check_binding_flags(&device, &descriptors, &flags)?;
+        }
+
+        UnsafeDescriptorSetLayout::new_impl(device, descriptors, flags, false)
+    }
+
+    /// Builds a new `UnsafeDescriptorSetLayout` for push descriptors.
+    ///
+    /// Descriptor sets can't be allocated with such a layout. Instead, the descriptors are pushed
+    /// directly into a command buffer with `AutoCommandBufferBuilder::push_descriptor_set`.
+    ///
+    /// Requires the `VK_KHR_push_descriptor` extension to be enabled on the device. Dynamic
+    /// buffers are not allowed in such a layout, and the total number of descriptors must not
+    /// exceed the `maxPushDescriptors` limit of the implementation, which is at least 32.
+    pub fn push_descriptor<I>(device: Arc<Device>, descriptors: I)
+                              -> Result<UnsafeDescriptorSetLayout,
+                                        DescriptorSetLayoutCreationError>
+        where I: IntoIterator<Item = Option<DescriptorDesc>>
+    {
+        if !device.loaded_extensions().khr_push_descriptor {
+            return Err(DescriptorSetLayoutCreationError::PushDescriptorExtensionNotEnabled);
+        }
+
+        let descriptors = descriptors.into_iter().collect::<SmallVec<[_; 32]>>();
+
+        for desc in descriptors.iter().filter_map(|d| d.as_ref()) {
+            match desc.ty.ty() {
+                Some(DescriptorType::UniformBufferDynamic) |
+                Some(DescriptorType::StorageBufferDynamic) => {
+                    return Err(DescriptorSetLayoutCreationError::PushDescriptorDynamicBuffer);
+                },
+                _ => (),
+            }
+        }
+
+        UnsafeDescriptorSetLayout::new_impl(device, descriptors, SmallVec::new(), true)
+    }
+
+    // Actual implementation of `new` and `with_binding_flags`. `flags` is either empty or has
+    // the same length as `descriptors`.
+    fn new_impl(device: Arc<Device>, descriptors: SmallVec<[Option<DescriptorDesc>; 32]>,
+                flags: SmallVec<[DescriptorBindingFlags; 32]>, push_descriptor: bool)
+                -> Result<UnsafeDescriptorSetLayout, DescriptorSetLayoutCreationError> {
         let mut descriptors_count = DescriptorsCount::zero();
+        let mut raw_flags: SmallVec<[vk::DescriptorBindingFlagsEXT; 32]> = SmallVec::new();
 
         let bindings = descriptors
             .iter()
@@ -66,7 +154,11 @@ impl UnsafeDescriptorSetLayout {
                 //        doesn't have tess shaders enabled
 
                 let ty = desc.ty.ty().unwrap(); // TODO: shouldn't panic
-                descriptors_count.add_one(ty);
+                descriptors_count.add_num(ty, desc.array_count);
+
+                if let Some(f) = flags.get(binding) {
+                    raw_flags.push(f.into_vulkan_bits());
+                }
 
                 Some(vk::DescriptorSetLayoutBinding {
                          binding: binding as u32,
@@ -81,10 +173,33 @@ impl UnsafeDescriptorSetLayout {
         // Note that it seems legal to have no descriptor at all in the set.
 
         let layout = unsafe {
+            let flags_infos = if !flags.is_empty() {
+                Some(vk::DescriptorSetLayoutBindingFlagsCreateInfoEXT {
+                         sType: vk::STRUCTURE_TYPE_DESCRIPTOR_SET_LAYOUT_BINDING_FLAGS_CREATE_INFO_EXT,
+                         pNext: ptr::null(),
+                         bindingCount: raw_flags.len() as u32,
+                         pBindingFlags: raw_flags.as_ptr(),
+                     })
+            } else {
+                None
+            };
+
             let infos = vk::DescriptorSetLayoutCreateInfo {
                 sType: vk::STRUCTURE_TYPE_DESCRIPTOR_SET_LAYOUT_CREATE_INFO,
-                pNext: ptr::null(),
-                flags: 0, // reserved
+                pNext: flags_infos
+                    .as_ref()
+                    .map(|i| i as *const _ as *const _)
+                    .unwrap_or(ptr::null()),
+                flags: {
+                    let mut raw = 0;
+                    if flags.iter().any(|f| f.update_after_bind) {
+                        raw |= vk::DESCRIPTOR_SET_LAYOUT_CREATE_UPDATE_AFTER_BIND_POOL_BIT_EXT;
+                    }
+                    if push_descriptor {
+                        raw |= vk::DESCRIPTOR_SET_LAYOUT_CREATE_PUSH_DESCRIPTOR_BIT_KHR;
+                    }
+                    raw
+                },
                 bindingCount: bindings.len() as u32,
                 pBindings: bindings.as_ptr(),
             };
@@ -94,18 +209,65 @@ impl UnsafeDescriptorSetLayout {
             check_errors(vk.CreateDescriptorSetLayout(device.internal_object(),
                                                       &infos,
                                                       ptr::null(),
-                                                      output.as_mut_ptr()))?;
+                                                      output.as_mut_ptr()))
+                .map_err(OomError::from)?;
             output.assume_init()
         };
 
         Ok(UnsafeDescriptorSetLayout {
-               layout: layout,
-               device: device,
-               descriptors: descriptors,
-               descriptors_count: descriptors_count,
+               layout,
+               device,
+               descriptors,
+               descriptors_count,
+               binding_flags: flags,
+               push_descriptor,
+               update_template: Mutex::new(None),
            })
     }
 
+    /// Returns the flags of the given binding.
+    ///
+    /// Returns `DescriptorBindingFlags::none()` if the binding doesn't exist or if the layout was
+    /// created without flags.
+    #[inline]
+    pub fn binding_flags(&self, binding: usize) -> DescriptorBindingFlags {
+        self.binding_flags
+            .get(binding)
+            .cloned()
+            .unwrap_or(DescriptorBindingFlags::none())
+    }
+
+    /// Returns true if the layout was created with `push_descriptor`.
+    #[inline]
+    pub fn is_push_descriptor(&self) -> bool {
+        self.push_descriptor
+    }
+
+    // Returns a template that writes all the descriptors of a set with this layout, or `None` if
+    // neither Vulkan 1.1 nor `VK_KHR_descriptor_update_template` is available or if the layout
+    // has binding flags or is a push descriptor layout.
+    pub(crate) fn update_template(&self) -> Option<Arc<DescriptorUpdateTemplate>> {
+        if !(self.device.api_version() >= Version::V1_1 ||
+                 self.device.loaded_extensions().khr_descriptor_update_template) ||
+            !self.binding_flags.is_empty() || self.push_descriptor
+        {
+            return None;
+        }
+
+        let mut template = self.update_template.lock().unwrap();
+        if template.is_none() {
+            *template = DescriptorUpdateTemplate::new(self).ok().map(Arc::new);
+        }
+        template.clone()
+    }
+
+    /// Returns true if descriptor sets with this layout must be allocated from a pool created
+    /// with `UnsafeDescriptorPool::with_update_after_bind`.
+    #[inline]
+    pub fn requires_update_after_bind_pool(&self) -> bool {
+        self.binding_flags.iter().any(|f| f.update_after_bind)
+    }
+
     /// Returns the number of descriptors of each type.
     #[inline]
     pub fn descriptors_count(&self) -> &DescriptorsCount {
@@ -113,6 +275,242 @@ impl UnsafeDescriptorSetLayout {
     }
 }
 
+// Checks whether `flags` are valid for `descriptors` on `device`.
+fn check_binding_flags(device: &Device, descriptors: &[Option<DescriptorDesc>],
+                       flags: &[DescriptorBindingFlags])
+                       -> Result<(), DescriptorSetLayoutCreationError> {
+    if !(device.api_version() >= Version::V1_2 || device.loaded_extensions().ext_descriptor_indexing) {
+        return Err(DescriptorSetLayoutCreationError::DescriptorIndexingExtensionNotEnabled);
+    }
+
+    let features = device.enabled_features();
+    let last_binding = descriptors.iter().rposition(|d| d.is_some());
+
+    for (binding, (desc, flags)) in descriptors.iter().zip(flags.iter()).enumerate() {
+        let desc = match desc {
+            Some(d) => d,
+            None => continue,
+        };
+
+        if flags.partially_bound && !features.descriptor_binding_partially_bound {
+            return Err(DescriptorSetLayoutCreationError::PartiallyBoundFeatureNotEnabled);
+        }
+
+        if flags.update_unused_while_pending &&
+            !features.descriptor_binding_update_unused_while_pending
+        {
+            return Err(DescriptorSetLayoutCreationError::UpdateUnusedWhilePendingFeatureNotEnabled);
+        }
+
+        if flags.variable_descriptor_count {
+            if !features.descriptor_binding_variable_descriptor_count {
+                return Err(DescriptorSetLayoutCreationError::VariableDescriptorCountFeatureNotEnabled);
+            }
+
+            if Some(binding) != last_binding {
+                return Err(DescriptorSetLayoutCreationError::VariableDescriptorCountNotLastBinding);
+            }
+        }
+
+        if flags.update_after_bind {
+            let supported = match desc.ty.ty() {
+                Some(DescriptorType::Sampler) |
+                Some(DescriptorType::CombinedImageSampler) |
+                Some(DescriptorType::SampledImage) => {
+                    features.descriptor_binding_sampled_image_update_after_bind
+                },
+                Some(DescriptorType::StorageImage) => {
+                    features.descriptor_binding_storage_image_update_after_bind
+                },
+                Some(DescriptorType::UniformBuffer) => {
+                    features.descriptor_binding_uniform_buffer_update_after_bind
+                },
+                Some(DescriptorType::StorageBuffer) => {
+                    features.descriptor_binding_storage_buffer_update_after_bind
+                },
+                Some(DescriptorType::UniformTexelBuffer) => {
+                    features.descriptor_binding_uniform_texel_buffer_update_after_bind
+                },
+                Some(DescriptorType::StorageTexelBuffer) => {
+                    features.descriptor_binding_storage_texel_buffer_update_after_bind
+                },
+                Some(DescriptorType::UniformBufferDynamic) |
+                Some(DescriptorType::StorageBufferDynamic) => {
+                    return Err(DescriptorSetLayoutCreationError::UpdateAfterBindDynamicBuffer);
+                },
+                Some(DescriptorType::InputAttachment) | None => false,
+            };
+
+            if !supported {
+                return Err(DescriptorSetLayoutCreationError::UpdateAfterBindFeatureNotEnabled);
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Flags that can be attached to a binding of a descriptor set layout.
+///
+/// These flags are provided by the `VK_EXT_descriptor_indexing` extension and each of them
+/// requires the corresponding feature to be enabled.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct DescriptorBindingFlags {
+    /// The descriptors of the binding can be updated after the set has been bound to a command
+    /// buffer, as long as the command buffer hasn't been submitted yet. Requires the
+    /// `descriptor_binding_*_update_after_bind` feature corresponding to the descriptor type.
+    pub update_after_bind: bool,
+    /// Descriptors that aren't used by a pending command buffer can be updated while it is
+    /// executing. Requires the `descriptor_binding_update_unused_while_pending` feature.
+    pub update_unused_while_pending: bool,
+    /// Descriptors that aren't dynamically used by the shaders don't need to be valid. Requires
+    /// the `descriptor_binding_partially_bound` feature.
+    pub partially_bound: bool,
+    /// The number of descriptors of the binding is an upper bound, and the actual number is
+    /// chosen when allocating the set. Only allowed on the last binding. Requires the
+    /// `descriptor_binding_variable_descriptor_count` feature.
+    pub variable_descriptor_count: bool,
+}
+
+impl DescriptorBindingFlags {
+    /// Returns a `DescriptorBindingFlags` with none of the flags set.
+    #[inline]
+    pub fn none() -> DescriptorBindingFlags {
+        DescriptorBindingFlags {
+            update_after_bind: false,
+            update_unused_while_pending: false,
+            partially_bound: false,
+            variable_descriptor_count: false,
+        }
+    }
+
+    /// Returns the flags usually wanted for a large "bindless" array of descriptors: every flag
+    /// is set.
+    #[inline]
+    pub fn bindless() -> DescriptorBindingFlags {
+        DescriptorBindingFlags {
+            update_after_bind: true,
+            update_unused_while_pending: true,
+            partially_bound: true,
+            variable_descriptor_count: true,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn into_vulkan_bits(self) -> vk::DescriptorBindingFlagsEXT {
+        let mut result = 0;
+        if self.update_after_bind {
+            result |= vk::DESCRIPTOR_BINDING_UPDATE_AFTER_BIND_BIT_EXT;
+        }
+        if self.update_unused_while_pending {
+            result |= vk::DESCRIPTOR_BINDING_UPDATE_UNUSED_WHILE_PENDING_BIT_EXT;
+        }
+        if self.partially_bound {
+            result |= vk::DESCRIPTOR_BINDING_PARTIALLY_BOUND_BIT_EXT;
+        }
+        if self.variable_descriptor_count {
+            result |= vk::DESCRIPTOR_BINDING_VARIABLE_DESCRIPTOR_COUNT_BIT_EXT;
+        }
+        result
+    }
+}
+
+/// Error that can happen when creating a descriptor set layout with binding flags or for push
+/// descriptors.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DescriptorSetLayoutCreationError {
+    /// Not enough memory.
+    OomError(OomError),
+    /// Binding flags were used but the `VK_EXT_descriptor_indexing` extension isn't enabled.
+    DescriptorIndexingExtensionNotEnabled,
+    /// The `partially_bound` flag was used but the corresponding feature isn't enabled.
+    PartiallyBoundFeatureNotEnabled,
+    /// The `variable_descriptor_count` flag was used but the corresponding feature isn't
+    /// enabled.
+    VariableDescriptorCountFeatureNotEnabled,
+    /// The `update_unused_while_pending` flag was used but the corresponding feature isn't
+    /// enabled.
+    UpdateUnusedWhilePendingFeatureNotEnabled,
+    /// The `update_after_bind` flag was used on a binding whose descriptor type doesn't have
+    /// the corresponding feature enabled.
+    UpdateAfterBindFeatureNotEnabled,
+    /// The `variable_descriptor_count` flag was used on a binding that isn't the last one.
+    VariableDescriptorCountNotLastBinding,
+    /// The `update_after_bind` flag was used on a dynamic buffer binding.
+    UpdateAfterBindDynamicBuffer,
+    /// A push descriptor layout was requested but the `VK_KHR_push_descriptor` extension isn't
+    /// enabled.
+    PushDescriptorExtensionNotEnabled,
+    /// A push descriptor layout can't contain dynamic buffers.
+    PushDescriptorDynamicBuffer,
+}
+
+impl error::Error for DescriptorSetLayoutCreationError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            DescriptorSetLayoutCreationError::OomError(_) => {
+                "not enough memory available"
+            },
+            DescriptorSetLayoutCreationError::DescriptorIndexingExtensionNotEnabled => {
+                "binding flags were used but the `VK_EXT_descriptor_indexing` extension isn't \
+                 enabled"
+            },
+            DescriptorSetLayoutCreationError::PartiallyBoundFeatureNotEnabled => {
+                "the `partially_bound` flag was used but the corresponding feature isn't enabled"
+            },
+            DescriptorSetLayoutCreationError::VariableDescriptorCountFeatureNotEnabled => {
+                "the `variable_descriptor_count` flag was used but the corresponding feature \
+                 isn't enabled"
+            },
+            DescriptorSetLayoutCreationError::UpdateUnusedWhilePendingFeatureNotEnabled => {
+                "the `update_unused_while_pending` flag was used but the corresponding feature \
+                 isn't enabled"
+            },
+            DescriptorSetLayoutCreationError::UpdateAfterBindFeatureNotEnabled => {
+                "the `update_after_bind` flag was used on a binding whose descriptor type \
+                 doesn't have the corresponding feature enabled"
+            },
+            DescriptorSetLayoutCreationError::VariableDescriptorCountNotLastBinding => {
+                "the `variable_descriptor_count` flag was used on a binding that isn't the last \
+                 one"
+            },
+            DescriptorSetLayoutCreationError::UpdateAfterBindDynamicBuffer => {
+                "the `update_after_bind` flag was used on a dynamic buffer binding"
+            },
+            DescriptorSetLayoutCreationError::PushDescriptorExtensionNotEnabled => {
+                "a push descriptor layout was requested but the `VK_KHR_push_descriptor` \
+                 extension isn't enabled"
+            },
+            DescriptorSetLayoutCreationError::PushDescriptorDynamicBuffer => {
+                "a push descriptor layout can't contain dynamic buffers"
+            },
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&dyn error::Error> {
+        match *self {
+            DescriptorSetLayoutCreationError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for DescriptorSetLayoutCreationError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
+
+impl From<OomError> for DescriptorSetLayoutCreationError {
+    #[inline]
+    fn from(err: OomError) -> DescriptorSetLayoutCreationError {
+        DescriptorSetLayoutCreationError::OomError(err)
+    }
+}
+
 unsafe impl DescriptorSetDesc for UnsafeDescriptorSetLayout {
     #[inline]
     fn num_bindings(&self) -> usize {
@@ -154,6 +552,9 @@ unsafe impl VulkanObject for UnsafeDescriptorSetLayout {
 impl Drop for UnsafeDescriptorSetLayout {
     #[inline]
     fn drop(&mut self) {
+        // Destroy the template before the layout it was created from.
+        self.update_template.get_mut().unwrap().take();
+
         unsafe {
             let vk = self.device.pointers();
             vk.DestroyDescriptorSetLayout(self.device.internal_object(), self.layout, ptr::null());
@@ -167,6 +568,8 @@ mod tests {
     use descriptor::descriptor::DescriptorDesc;
     use descriptor::descriptor::DescriptorDescTy;
     use descriptor::descriptor::ShaderStages;
+    use descriptor::descriptor_set::DescriptorBindingFlags;
+    use descriptor::descriptor_set::DescriptorSetLayoutCreationError;
     use descriptor::descriptor_set::DescriptorsCount;
     use descriptor::descriptor_set::UnsafeDescriptorSetLayout;
     use std::iter;
@@ -199,4 +602,74 @@ mod tests {
                        ..DescriptorsCount::zero()
                    });
     }
+
+    #[test]
+    fn array_count() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let layout = DescriptorDesc {
+            ty: DescriptorDescTy::Buffer(DescriptorBufferDesc {
+                                             dynamic: Some(false),
+                                             storage: true,
+                                         }),
+            array_count: 4,
+            stages: ShaderStages::all_graphics(),
+            readonly: true,
+        };
+
+        let sl = UnsafeDescriptorSetLayout::new(device.clone(), iter::once(Some(layout))).unwrap();
+
+        assert_eq!(sl.descriptors_count(),
+                   &DescriptorsCount {
+                       storage_buffer: 4,
+                       ..DescriptorsCount::zero()
+                   });
+        assert_eq!(sl.binding_flags(0), DescriptorBindingFlags::none());
+    }
+
+    #[test]
+    fn binding_flags_extension_missing() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let layout = DescriptorDesc {
+            ty: DescriptorDescTy::Buffer(DescriptorBufferDesc {
+                                             dynamic: Some(false),
+                                             storage: false,
+                                         }),
+            array_count: 1,
+            stages: ShaderStages::all_graphics(),
+            readonly: true,
+        };
+
+        let flags = DescriptorBindingFlags {
+            partially_bound: true,
+            ..DescriptorBindingFlags::none()
+        };
+
+        match UnsafeDescriptorSetLayout::with_binding_flags(device, iter::once(Some(layout)),
+                                                            iter::once(flags)) {
+            Err(DescriptorSetLayoutCreationError::DescriptorIndexingExtensionNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn push_descriptor_extension_missing() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let layout = DescriptorDesc {
+            ty: DescriptorDescTy::Buffer(DescriptorBufferDesc {
+                                             dynamic: Some(false),
+                                             storage: false,
+                                         }),
+            array_count: 1,
+            stages: ShaderStages::all_graphics(),
+            readonly: true,
+        };
+
+        match UnsafeDescriptorSetLayout::push_descriptor(device, iter::once(Some(layout))) {
+            Err(DescriptorSetLayoutCreationError::PushDescriptorExtensionNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
 }
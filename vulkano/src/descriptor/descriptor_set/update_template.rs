@@ -0,0 +1,387 @@
+// Copyright (c) 2026 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use smallvec::SmallVec;
+use std::error;
+use std::fmt;
+use std::mem;
+use std::mem::MaybeUninit;
+use std::ptr;
+use std::sync::Arc;
+
+use descriptor::descriptor::DescriptorType;
+use descriptor::descriptor_set::DescriptorSetDesc;
+use descriptor::descriptor_set::UnsafeDescriptorSetLayout;
+use device::Device;
+use device::DeviceOwned;
+
+use OomError;
+use VulkanObject;
+use check_errors;
+use version::Version;
+use vk;
+
+/// Describes to the Vulkan implementation how to update all the descriptors of a set at once.
+///
+/// A template is built from the description of a descriptor set layout. It contains one entry
+/// per non-empty binding, covering every element of the binding. Updating a descriptor set with
+/// a template is usually cheaper than using `UnsafeDescriptorSet::write`, but every descriptor
+/// of the set must be written at once. See `UnsafeDescriptorSet::write_with_template`.
+///
+/// Requires the `VK_KHR_descriptor_update_template` extension. The `PersistentDescriptorSet` and
+/// `FixedSizeDescriptorSetsPool` types automatically use a template if this extension is enabled.
+pub struct DescriptorUpdateTemplate {
+    template: vk::DescriptorUpdateTemplateKHR,
+    device: Arc<Device>,
+    // Entry of each binding, or `None` if the binding is empty.
+    entries: SmallVec<[Option<TemplateEntry>; 32]>,
+    // Size in bytes of the data passed to the template.
+    data_size: usize,
+    // Total number of descriptors written by the template.
+    num_descriptors: usize,
+}
+
+// Location of the data of a binding within the data passed to the template.
+#[derive(Debug, Copy, Clone)]
+pub(super) struct TemplateEntry {
+    // Offset in bytes of the first element.
+    pub offset: usize,
+    // Number of bytes between two elements.
+    pub stride: usize,
+    // Number of elements of the binding.
+    pub count: u32,
+    // Index of the first element among all the descriptors of the template.
+    pub first_descriptor: usize,
+    // Type of the descriptors of the binding, which determines the structure of the elements.
+    pub ty: DescriptorType,
+}
+
+impl DescriptorUpdateTemplate {
+    /// Builds a new `DescriptorUpdateTemplate` for the descriptor sets of the given layout.
+    pub fn new(layout: &UnsafeDescriptorSetLayout)
+               -> Result<DescriptorUpdateTemplate, DescriptorUpdateTemplateCreationError> {
+        let device = layout.device();
+
+        if !(device.api_version() >= Version::V1_1 ||
+                 device.loaded_extensions().khr_descriptor_update_template)
+        {
+            return Err(DescriptorUpdateTemplateCreationError::ExtensionNotEnabled);
+        }
+
+        let mut entries = SmallVec::new();
+        let mut raw_entries: SmallVec<[_; 32]> = SmallVec::new();
+        let mut data_size = 0;
+        let mut num_descriptors = 0;
+
+        for binding in 0 .. layout.num_bindings() {
+            let desc = match layout.descriptor(binding) {
+                Some(d) => d,
+                None => {
+                    entries.push(None);
+                    continue;
+                },
+            };
+
+            let ty = match desc.ty.ty() {
+                Some(ty) => ty,
+                None => {
+                    return Err(DescriptorUpdateTemplateCreationError::UnknownDescriptorType {
+                                   binding: binding as u32,
+                               });
+                },
+            };
+            let stride = descriptor_stride(ty);
+
+            entries.push(Some(TemplateEntry {
+                                  offset: data_size,
+                                  stride,
+                                  count: desc.array_count,
+                                  first_descriptor: num_descriptors,
+                                  ty,
+                              }));
+
+            raw_entries.push(vk::DescriptorUpdateTemplateEntryKHR {
+                                 dstBinding: binding as u32,
+                                 dstArrayElement: 0,
+                                 descriptorCount: desc.array_count,
+                                 descriptorType: ty as u32,
+                                 offset: data_size,
+                                 stride,
+                             });
+
+            data_size += stride * desc.array_count as usize;
+            num_descriptors += desc.array_count as usize;
+        }
+
+        let template = unsafe {
+            let infos = vk::DescriptorUpdateTemplateCreateInfoKHR {
+                sType: vk::STRUCTURE_TYPE_DESCRIPTOR_UPDATE_TEMPLATE_CREATE_INFO_KHR,
+                pNext: ptr::null(),
+                flags: 0, // reserved
+                descriptorUpdateEntryCount: raw_entries.len() as u32,
+                pDescriptorUpdateEntries: raw_entries.as_ptr(),
+                templateType: vk::DESCRIPTOR_UPDATE_TEMPLATE_TYPE_DESCRIPTOR_SET_KHR,
+                descriptorSetLayout: layout.internal_object(),
+                // The following members are ignored for this template type.
+                pipelineBindPoint: 0,
+                pipelineLayout: 0,
+                set: 0,
+            };
+
+            let mut output = MaybeUninit::uninit();
+            let vk = device.pointers();
+            check_errors(vk.CreateDescriptorUpdateTemplateKHR(device.internal_object(),
+                                                              &infos,
+                                                              ptr::null(),
+                                                              output.as_mut_ptr()))
+                .map_err(OomError::from)?;
+            output.assume_init()
+        };
+
+        Ok(DescriptorUpdateTemplate {
+               template,
+               device: device.clone(),
+               entries,
+               data_size,
+               num_descriptors,
+           })
+    }
+
+    // Returns the entry of the given binding, or `None` if the binding is empty or out of range.
+    #[inline]
+    pub(super) fn entry(&self, binding: u32) -> Option<TemplateEntry> {
+        self.entries.get(binding as usize).cloned().unwrap_or(None)
+    }
+
+    // Returns the size in bytes of the data to pass when updating a set.
+    #[inline]
+    pub(super) fn data_size(&self) -> usize {
+        self.data_size
+    }
+
+    // Returns the total number of descriptors written by the template.
+    #[inline]
+    pub(super) fn num_descriptors(&self) -> usize {
+        self.num_descriptors
+    }
+}
+
+// Returns the size of the Vulkan structure that describes a descriptor of the given type.
+fn descriptor_stride(ty: DescriptorType) -> usize {
+    match ty {
+        DescriptorType::Sampler |
+        DescriptorType::CombinedImageSampler |
+        DescriptorType::SampledImage |
+        DescriptorType::StorageImage |
+        DescriptorType::InputAttachment => mem::size_of::<vk::DescriptorImageInfo>(),
+        DescriptorType::UniformTexelBuffer |
+        DescriptorType::StorageTexelBuffer => mem::size_of::<vk::BufferView>(),
+        DescriptorType::UniformBuffer |
+        DescriptorType::StorageBuffer |
+        DescriptorType::UniformBufferDynamic |
+        DescriptorType::StorageBufferDynamic => mem::size_of::<vk::DescriptorBufferInfo>(),
+    }
+}
+
+unsafe impl DeviceOwned for DescriptorUpdateTemplate {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
+impl fmt::Debug for DescriptorUpdateTemplate {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        fmt.debug_struct("DescriptorUpdateTemplate")
+            .field("raw", &self.template)
+            .field("device", &self.device)
+            .finish()
+    }
+}
+
+unsafe impl VulkanObject for DescriptorUpdateTemplate {
+    type Object = vk::DescriptorUpdateTemplateKHR;
+
+    const TYPE: vk::ObjectType = vk::OBJECT_TYPE_DESCRIPTOR_UPDATE_TEMPLATE_KHR;
+
+    #[inline]
+    fn internal_object(&self) -> vk::DescriptorUpdateTemplateKHR {
+        self.template
+    }
+}
+
+impl Drop for DescriptorUpdateTemplate {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            let vk = self.device.pointers();
+            vk.DestroyDescriptorUpdateTemplateKHR(self.device.internal_object(),
+                                                  self.template,
+                                                  ptr::null());
+        }
+    }
+}
+
+/// Error that can happen when creating a `DescriptorUpdateTemplate`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DescriptorUpdateTemplateCreationError {
+    /// Not enough memory.
+    OomError(OomError),
+    /// The `VK_KHR_descriptor_update_template` extension isn't enabled on the device.
+    ExtensionNotEnabled,
+    /// The description of a binding of the layout doesn't contain enough information to
+    /// determine its descriptor type.
+    UnknownDescriptorType {
+        /// The binding whose type is unknown.
+        binding: u32,
+    },
+}
+
+impl error::Error for DescriptorUpdateTemplateCreationError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            DescriptorUpdateTemplateCreationError::OomError(_) => {
+                "not enough memory available"
+            },
+            DescriptorUpdateTemplateCreationError::ExtensionNotEnabled => {
+                "the `VK_KHR_descriptor_update_template` extension isn't enabled"
+            },
+            DescriptorUpdateTemplateCreationError::UnknownDescriptorType { .. } => {
+                "the descriptor type of a binding of the layout is unknown"
+            },
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&dyn error::Error> {
+        match *self {
+            DescriptorUpdateTemplateCreationError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for DescriptorUpdateTemplateCreationError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
+
+impl From<OomError> for DescriptorUpdateTemplateCreationError {
+    #[inline]
+    fn from(err: OomError) -> DescriptorUpdateTemplateCreationError {
+        DescriptorUpdateTemplateCreationError::OomError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use descriptor::descriptor::DescriptorBufferDesc;
+    use descriptor::descriptor::DescriptorDesc;
+    use descriptor::descriptor::DescriptorDescTy;
+    use descriptor::descriptor::DescriptorImageDesc;
+    use descriptor::descriptor::DescriptorImageDescArray;
+    use descriptor::descriptor::DescriptorImageDescDimensions;
+    use descriptor::descriptor::DescriptorType;
+    use descriptor::descriptor::ShaderStages;
+    use descriptor::descriptor_set::DescriptorUpdateTemplate;
+    use descriptor::descriptor_set::DescriptorUpdateTemplateCreationError;
+    use descriptor::descriptor_set::UnsafeDescriptorSetLayout;
+    use std::iter;
+    use std::mem;
+    use vk;
+
+    #[test]
+    fn data_layout() {
+        let (device, _) = gfx_dev_and_queue!(extensions: [khr_descriptor_update_template]);
+
+        let buffer = DescriptorDesc {
+            ty: DescriptorDescTy::Buffer(DescriptorBufferDesc {
+                                             dynamic: Some(false),
+                                             storage: false,
+                                         }),
+            array_count: 1,
+            stages: ShaderStages::all_graphics(),
+            readonly: true,
+        };
+        let images = DescriptorDesc {
+            ty: DescriptorDescTy::Image(DescriptorImageDesc {
+                                            sampled: true,
+                                            dimensions: DescriptorImageDescDimensions::TwoDimensional,
+                                            format: None,
+                                            multisampled: false,
+                                            array_layers: DescriptorImageDescArray::NonArrayed,
+                                        }),
+            array_count: 3,
+            stages: ShaderStages::all_graphics(),
+            readonly: true,
+        };
+        let texel_buffers = DescriptorDesc {
+            ty: DescriptorDescTy::TexelBuffer {
+                storage: false,
+                format: None,
+            },
+            array_count: 2,
+            stages: ShaderStages::all_graphics(),
+            readonly: true,
+        };
+
+        let layout = UnsafeDescriptorSetLayout::new(device,
+                                                    vec![Some(buffer),
+                                                         None,
+                                                         Some(images),
+                                                         Some(texel_buffers)])
+            .unwrap();
+        let template = DescriptorUpdateTemplate::new(&layout).unwrap();
+
+        let buffer_size = mem::size_of::<vk::DescriptorBufferInfo>();
+        let image_size = mem::size_of::<vk::DescriptorImageInfo>();
+        let view_size = mem::size_of::<vk::BufferView>();
+
+        let entry = template.entry(0).unwrap();
+        assert_eq!((entry.offset, entry.stride, entry.count, entry.first_descriptor),
+                   (0, buffer_size, 1, 0));
+        assert!(template.entry(1).is_none());
+        let entry = template.entry(2).unwrap();
+        assert_eq!((entry.offset, entry.stride, entry.count, entry.first_descriptor),
+                   (buffer_size, image_size, 3, 1));
+        let entry = template.entry(3).unwrap();
+        assert_eq!((entry.offset, entry.stride, entry.count, entry.first_descriptor),
+                   (buffer_size + 3 * image_size, view_size, 2, 4));
+        assert_eq!(entry.ty, DescriptorType::UniformTexelBuffer);
+        assert!(template.entry(4).is_none());
+
+        assert_eq!(template.data_size(), buffer_size + 3 * image_size + 2 * view_size);
+        assert_eq!(template.num_descriptors(), 6);
+    }
+
+    #[test]
+    fn extension_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let desc = DescriptorDesc {
+            ty: DescriptorDescTy::Buffer(DescriptorBufferDesc {
+                                             dynamic: Some(false),
+                                             storage: false,
+                                         }),
+            array_count: 1,
+            stages: ShaderStages::all_graphics(),
+            readonly: true,
+        };
+
+        let layout = UnsafeDescriptorSetLayout::new(device, iter::once(Some(desc))).unwrap();
+
+        match DescriptorUpdateTemplate::new(&layout) {
+            Err(DescriptorUpdateTemplateCreationError::ExtensionNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+}
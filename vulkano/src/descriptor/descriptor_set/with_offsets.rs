@@ -0,0 +1,187 @@
+// Copyright (c) 2026 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use smallvec::SmallVec;
+use std::sync::Arc;
+
+use buffer::BufferAccess;
+use descriptor::descriptor::DescriptorDesc;
+use descriptor::descriptor::DescriptorDescTy;
+use descriptor::descriptor_set::DescriptorSet;
+use descriptor::descriptor_set::DescriptorSetDesc;
+use descriptor::descriptor_set::UnsafeDescriptorSet;
+use device::Device;
+use device::DeviceOwned;
+use image::ImageViewAccess;
+
+/// Wraps around a descriptor set and associates dynamic offsets to it.
+///
+/// When the set is bound, each dynamic uniform or storage buffer descriptor is offset by the
+/// corresponding value. This makes it possible to use a single descriptor set to access different
+/// parts of a buffer between draw or dispatch commands.
+///
+/// There must be one offset for each element of each dynamic buffer descriptor of the set,
+/// ordered by binding number then by array element. The offsets are checked when the set is
+/// passed to a draw or dispatch command of `AutoCommandBufferBuilder`.
+///
+/// # Example
+///
+/// ```ignore
+/// // `set` contains a single dynamic uniform buffer descriptor.
+/// let set = DescriptorSetWithOffsets::new(set.clone(), Some(256 * object_index));
+/// ```
+pub struct DescriptorSetWithOffsets<S> {
+    set: S,
+    dynamic_offsets: SmallVec<[u32; 4]>,
+}
+
+impl<S> DescriptorSetWithOffsets<S> {
+    /// Builds a new `DescriptorSetWithOffsets` from a set and a list of offsets in bytes.
+    #[inline]
+    pub fn new<I>(set: S, dynamic_offsets: I) -> DescriptorSetWithOffsets<S>
+        where I: IntoIterator<Item = u32>
+    {
+        DescriptorSetWithOffsets {
+            set,
+            dynamic_offsets: dynamic_offsets.into_iter().collect(),
+        }
+    }
+
+    /// Returns the wrapped descriptor set.
+    #[inline]
+    pub fn set(&self) -> &S {
+        &self.set
+    }
+}
+
+unsafe impl<S> DescriptorSet for DescriptorSetWithOffsets<S>
+    where S: DescriptorSet
+{
+    #[inline]
+    fn inner(&self) -> &UnsafeDescriptorSet {
+        self.set.inner()
+    }
+
+    #[inline]
+    fn num_buffers(&self) -> usize {
+        self.set.num_buffers()
+    }
+
+    #[inline]
+    fn buffer(&self, index: usize) -> Option<(&dyn BufferAccess, u32)> {
+        self.set.buffer(index)
+    }
+
+    #[inline]
+    fn num_images(&self) -> usize {
+        self.set.num_images()
+    }
+
+    #[inline]
+    fn image(&self, index: usize) -> Option<(&dyn ImageViewAccess, u32)> {
+        self.set.image(index)
+    }
+
+    #[inline]
+    fn dynamic_offsets(&self) -> &[u32] {
+        &self.dynamic_offsets
+    }
+}
+
+unsafe impl<S> DescriptorSetDesc for DescriptorSetWithOffsets<S>
+    where S: DescriptorSetDesc
+{
+    #[inline]
+    fn num_bindings(&self) -> usize {
+        self.set.num_bindings()
+    }
+
+    #[inline]
+    fn descriptor(&self, binding: usize) -> Option<DescriptorDesc> {
+        self.set.descriptor(binding)
+    }
+}
+
+unsafe impl<S> DeviceOwned for DescriptorSetWithOffsets<S>
+    where S: DeviceOwned
+{
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.set.device()
+    }
+}
+
+// Returns true if the descriptor is a dynamic uniform or storage buffer.
+#[inline]
+fn is_dynamic_buffer(desc: &DescriptorDesc) -> bool {
+    match desc.ty {
+        DescriptorDescTy::Buffer(ref buffer) => buffer.dynamic == Some(true),
+        _ => false,
+    }
+}
+
+// Returns the number of dynamic offsets that must be passed when binding the set.
+pub(crate) fn num_dynamic_offsets<S>(set: &S) -> usize
+    where S: ?Sized + DescriptorSetDesc
+{
+    (0 .. set.num_bindings())
+        .filter_map(|binding| set.descriptor(binding))
+        .filter(is_dynamic_buffer)
+        .map(|desc| desc.array_count as usize)
+        .sum()
+}
+
+// Returns the dynamic offsets to pass when binding the set, replacing an empty list with zeroes.
+pub(crate) fn effective_dynamic_offsets<S>(set: &S) -> SmallVec<[u32; 4]>
+    where S: ?Sized + DescriptorSet
+{
+    if set.dynamic_offsets().is_empty() {
+        SmallVec::from_elem(0, num_dynamic_offsets(set))
+    } else {
+        set.dynamic_offsets().iter().cloned().collect()
+    }
+}
+
+// Returns, for each buffer of the set, which of `offsets` applies to it or `None` if the buffer
+// isn't bound to a dynamic descriptor.
+//
+// The buffers of a given binding are assumed to be returned by `DescriptorSet::buffer` in the
+// order of their array element, which is the case for the sets built by vulkano. Missing offsets
+// are treated as 0.
+pub(crate) fn buffers_dynamic_offsets<S>(set: &S, offsets: &[u32])
+                                         -> SmallVec<[Option<u32>; 16]>
+    where S: ?Sized + DescriptorSet
+{
+    // For each binding, the index of its first offset within `offsets`.
+    let mut first_offset: SmallVec<[Option<usize>; 16]> = SmallVec::new();
+    let mut num_offsets = 0;
+    for binding in 0 .. set.num_bindings() {
+        match set.descriptor(binding) {
+            Some(ref desc) if is_dynamic_buffer(desc) => {
+                first_offset.push(Some(num_offsets));
+                num_offsets += desc.array_count as usize;
+            },
+            _ => first_offset.push(None),
+        }
+    }
+
+    let mut next_element: SmallVec<[usize; 16]> = SmallVec::from_elem(0, first_offset.len());
+    (0 .. set.num_buffers())
+        .map(|index| {
+            let binding = set.buffer(index).unwrap().1 as usize;
+            let first = match first_offset.get(binding) {
+                Some(&Some(first)) => first,
+                _ => return None,
+            };
+            let element = next_element[binding];
+            next_element[binding] += 1;
+            Some(offsets.get(first + element).cloned().unwrap_or(0))
+        })
+        .collect()
+}
@@ -203,6 +203,11 @@ unsafe impl<D> PipelineLayoutDesc for PipelineLayout<D>
         self.desc.descriptor(set, binding)
     }
 
+    #[inline]
+    fn provided_set_layout(&self, set: usize) -> Option<Arc<UnsafeDescriptorSetLayout>> {
+        self.layouts.get(set).cloned()
+    }
+
     #[inline]
     fn num_push_constants_ranges(&self) -> usize {
         self.desc.num_push_constants_ranges()
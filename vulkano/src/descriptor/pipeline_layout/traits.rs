@@ -151,6 +151,11 @@ unsafe impl<T> PipelineLayoutDesc for T
         (**self).descriptor(set, binding)
     }
 
+    #[inline]
+    fn provided_set_layout(&self, set: usize) -> Option<Arc<UnsafeDescriptorSetLayout>> {
+        (**self).provided_set_layout(set)
+    }
+
     #[inline]
     fn num_push_constants_ranges(&self) -> usize {
         (**self).num_push_constants_ranges()
@@ -10,9 +10,12 @@
 use descriptor::descriptor::DescriptorDesc;
 use descriptor::descriptor::DescriptorDescTy;
 use descriptor::descriptor::DescriptorBufferDesc;
+use descriptor::descriptor_set::UnsafeDescriptorSetLayout;
 use descriptor::pipeline_layout::PipelineLayoutDesc;
 use descriptor::pipeline_layout::PipelineLayoutDescPcRange;
+use fnv::FnvHashMap;
 use fnv::FnvHashSet;
+use std::sync::Arc;
 
 /// Transforms a `PipelineLayoutDesc`.
 ///
@@ -20,6 +23,7 @@ use fnv::FnvHashSet;
 pub struct PipelineLayoutDescTweaks<T> {
     inner: T,
     dynamic_buffers: FnvHashSet<(usize, usize)>,
+    set_layouts: FnvHashMap<usize, Arc<UnsafeDescriptorSetLayout>>,
 }
 
 impl<T> PipelineLayoutDescTweaks<T>
@@ -38,7 +42,16 @@ impl<T> PipelineLayoutDescTweaks<T>
                           }),
                           "tried to make the non-buffer descriptor at set {} binding {} a dynamic buffer", set, binding);
         }
-        Self { inner, dynamic_buffers }
+        Self { inner, dynamic_buffers, set_layouts: FnvHashMap::default() }
+    }
+
+    /// Uses an existing `UnsafeDescriptorSetLayout` for the given set instead of creating one.
+    ///
+    /// This is how layouts with binding flags (for example for descriptor indexing) can be used
+    /// in a pipeline whose layout was inferred from the shaders.
+    pub fn with_set_layout(mut self, set: usize, layout: Arc<UnsafeDescriptorSetLayout>) -> Self {
+        self.set_layouts.insert(set, layout);
+        self
     }
 }
 
@@ -69,6 +82,14 @@ unsafe impl<T> PipelineLayoutDesc for PipelineLayoutDescTweaks<T>
         })
     }
 
+    #[inline]
+    fn provided_set_layout(&self, set: usize) -> Option<Arc<UnsafeDescriptorSetLayout>> {
+        match self.set_layouts.get(&set) {
+            Some(layout) => Some(layout.clone()),
+            None => self.inner.provided_set_layout(set),
+        }
+    }
+
     #[inline]
     fn num_push_constants_ranges(&self) -> usize { self.inner.num_push_constants_ranges() }
 
@@ -16,15 +16,101 @@ use std::str;
 
 use VulkanObject;
 use check_errors;
+use instance::InstanceExtensions;
 use instance::PhysicalDevice;
+use extensions::ExtensionRestriction::*;
+use extensions::ExtensionRestrictionError;
 use extensions::SupportedExtensionsError;
+use version::Version;
 use vk;
 
 macro_rules! device_extensions {
-    ($sname:ident, $rawname:ident, $($ext:ident => $s:expr,)*) => (
+    (
+        $sname:ident, $rawname:ident,
+        $(
+            $ext:ident => $s:tt {
+                promoted_to: $core:expr,
+                requires: [$($req:ident),*],
+                requires_instance: [$($ireq:ident),*],
+                conflicts: [$($conflict:ident),*],
+            },
+        )*
+    ) => (
         extensions! {
             $sname, $rawname,
-            $( $ext => $s,)*
+            $(
+                $ext => $s {
+                    promoted_to: $core,
+                    requires: [$($req),*],
+                },
+            )*
+        }
+
+        impl $sname {
+            /// Returns the instance extensions that the extensions of this list directly
+            /// require, according to the Vulkan registry.
+            pub fn required_instance_extensions(&self) -> InstanceExtensions {
+                #[allow(unused_mut)]
+                let mut required = InstanceExtensions::none();
+                $(
+                    if self.$ext {
+                        $(
+                            required.$ireq = true;
+                        )*
+                    }
+                )*
+                required
+            }
+
+            /// Checks that the extensions of this list can be enabled on a device that uses
+            /// `api_version` of Vulkan.
+            ///
+            /// `instance_extensions` must contain the extensions enabled on the instance, along
+            /// with those that have been promoted to the version of Vulkan of the instance.
+            ///
+            /// Each device extension that the extensions of the list require must be in the list
+            /// as well, unless it has been promoted to `api_version`, and each instance extension
+            /// that they require must be in `instance_extensions`. In addition, some extensions
+            /// can't be enabled together.
+            pub fn check_requirements(&self, api_version: Version,
+                                      instance_extensions: &InstanceExtensions)
+                                      -> Result<(), ExtensionRestrictionError> {
+                let available = self.union(&$sname::promoted_to_core(api_version));
+                $(
+                    if self.$ext {
+                        let extension = str::from_utf8($s).unwrap();
+                        let single = $sname { $ext: true, .. $sname::none() };
+
+                        let required = single.required_extensions();
+                        if let Some(&missing) = required.difference(&available).names().first() {
+                            return Err(ExtensionRestrictionError {
+                                extension,
+                                restriction: RequiresDeviceExtension(missing),
+                            });
+                        }
+
+                        let required = single.required_instance_extensions();
+                        let missing = required.difference(instance_extensions).names();
+                        if let Some(&missing) = missing.first() {
+                            return Err(ExtensionRestrictionError {
+                                extension,
+                                restriction: RequiresInstanceExtension(missing),
+                            });
+                        }
+
+                        $(
+                            if available.$conflict {
+                                let other = $sname { $conflict: true, .. $sname::none() }.names();
+                                return Err(ExtensionRestrictionError {
+                                    extension,
+                                    restriction: ConflictsDeviceExtension(other[0]),
+                                });
+                            }
+                        )*
+                    }
+                )*
+                Ok(())
+            }
         }
 
         impl $rawname {
@@ -103,22 +189,7 @@ macro_rules! device_extensions {
     );
 }
 
-device_extensions! {
-    DeviceExtensions,
-    RawDeviceExtensions,
-    khr_swapchain => b"VK_KHR_swapchain",
-    khr_display_swapchain => b"VK_KHR_display_swapchain",
-    khr_sampler_mirror_clamp_to_edge => b"VK_KHR_sampler_mirror_clamp_to_edge",
-    khr_maintenance1 => b"VK_KHR_maintenance1",
-    khr_get_memory_requirements2 => b"VK_KHR_get_memory_requirements2",
-    khr_dedicated_allocation => b"VK_KHR_dedicated_allocation",
-    khr_incremental_present => b"VK_KHR_incremental_present",
-    khr_16bit_storage => b"VK_KHR_16bit_storage",
-    khr_storage_buffer_storage_class => b"VK_KHR_storage_buffer_storage_class",
-    ext_debug_utils => b"VK_EXT_debug_utils",
-    khr_multiview => b"VK_KHR_multiview",
-    ext_full_screen_exclusive => b"VK_EXT_full_screen_exclusive",
-}
+include!("../autogen/device_extensions.rs");
 
 /// This helper type can only be instantiated inside this module.
 /// See `*Extensions::_unbuildable`.
@@ -129,10 +200,126 @@ pub struct Unbuildable(());
 #[cfg(test)]
 mod tests {
     use device::{DeviceExtensions, RawDeviceExtensions};
+    use extensions::ExtensionRestriction;
+    use extensions::ExtensionRestrictionError;
+    use instance::InstanceExtensions;
+    use version::Version;
 
     #[test]
     fn empty_extensions() {
         let d: RawDeviceExtensions = (&DeviceExtensions::none()).into();
         assert!(d.iter().next().is_none());
     }
+
+    #[test]
+    fn requirements() {
+        let extensions = DeviceExtensions {
+            khr_display_swapchain: true,
+            ext_descriptor_indexing: true,
+            ..DeviceExtensions::none()
+        };
+
+        assert_eq!(extensions.required_extensions(),
+                   DeviceExtensions {
+                       khr_swapchain: true,
+                       khr_maintenance3: true,
+                       ..DeviceExtensions::none()
+                   });
+        assert_eq!(extensions.required_instance_extensions(),
+                   InstanceExtensions {
+                       khr_display: true,
+                       khr_get_physical_device_properties2: true,
+                       ..InstanceExtensions::none()
+                   });
+    }
+
+    #[test]
+    fn promoted_to_core() {
+        assert_eq!(DeviceExtensions::promoted_to_core(Version::V1_0), DeviceExtensions::none());
+
+        let v1_1 = DeviceExtensions::promoted_to_core(Version::V1_1);
+        assert!(v1_1.khr_maintenance1 && v1_1.khr_multiview);
+        assert!(!v1_1.khr_timeline_semaphore && !v1_1.khr_swapchain);
+
+        let v1_2 = DeviceExtensions::promoted_to_core(Version::V1_2);
+        assert!(v1_2.khr_maintenance1 && v1_2.khr_timeline_semaphore);
+    }
+
+    #[test]
+    fn check_requirements() {
+        let instance_extensions = InstanceExtensions::none();
+
+        let extensions = DeviceExtensions {
+            khr_dedicated_allocation: true,
+            ..DeviceExtensions::none()
+        };
+        assert_eq!(extensions.check_requirements(Version::V1_0, &instance_extensions),
+                   Err(ExtensionRestrictionError {
+                       extension: "VK_KHR_dedicated_allocation",
+                       restriction: ExtensionRestriction::RequiresDeviceExtension(
+                           "VK_KHR_get_memory_requirements2"),
+                   }));
+        assert_eq!(extensions.check_requirements(Version::V1_1, &instance_extensions), Ok(()));
+
+        let extensions = DeviceExtensions {
+            khr_swapchain: true,
+            ..DeviceExtensions::none()
+        };
+        assert_eq!(extensions.check_requirements(Version::V1_0, &instance_extensions),
+                   Err(ExtensionRestrictionError {
+                       extension: "VK_KHR_swapchain",
+                       restriction: ExtensionRestriction::RequiresInstanceExtension(
+                           "VK_KHR_surface"),
+                   }));
+        let instance_extensions = InstanceExtensions {
+            khr_surface: true,
+            ..InstanceExtensions::none()
+        };
+        assert_eq!(extensions.check_requirements(Version::V1_0, &instance_extensions), Ok(()));
+    }
+
+    #[test]
+    fn check_conflicts() {
+        let instance_extensions = InstanceExtensions::none();
+        let extensions = DeviceExtensions {
+            amd_negative_viewport_height: true,
+            ..DeviceExtensions::none()
+        };
+        assert_eq!(extensions.check_requirements(Version::V1_0, &instance_extensions), Ok(()));
+
+        // `VK_KHR_maintenance1` is part of Vulkan 1.1.
+        let error = ExtensionRestrictionError {
+            extension: "VK_AMD_negative_viewport_height",
+            restriction: ExtensionRestriction::ConflictsDeviceExtension("VK_KHR_maintenance1"),
+        };
+        assert_eq!(extensions.check_requirements(Version::V1_1, &instance_extensions),
+                   Err(error));
+
+        let extensions = DeviceExtensions {
+            khr_maintenance1: true,
+            ..extensions
+        };
+        assert!(extensions.check_requirements(Version::V1_0, &instance_extensions).is_err());
+    }
+
+    #[test]
+    fn with_dependencies() {
+        let extensions = DeviceExtensions {
+            khr_display_swapchain: true,
+            ext_descriptor_indexing: true,
+            ..DeviceExtensions::none()
+        };
+
+        assert_eq!(extensions.with_dependencies(Version::V1_0),
+                   DeviceExtensions {
+                       khr_swapchain: true,
+                       khr_maintenance3: true,
+                       ..extensions
+                   });
+        assert_eq!(extensions.with_dependencies(Version::V1_1),
+                   DeviceExtensions {
+                       khr_swapchain: true,
+                       ..extensions
+                   });
+    }
 }
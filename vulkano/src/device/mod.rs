@@ -91,6 +91,7 @@
 
 use fnv::FnvHasher;
 use smallvec::SmallVec;
+use std::cmp;
 use std::collections::HashMap;
 use std::collections::hash_map::Entry;
 use std::error;
@@ -101,6 +102,7 @@ use std::hash::Hasher;
 use std::mem::MaybeUninit;
 use std::ops::Deref;
 use std::ptr;
+use std::str;
 use std::sync::Arc;
 use std::sync::Mutex;
 use std::sync::MutexGuard;
@@ -109,10 +111,16 @@ use std::ffi::CStr;
 
 use command_buffer::pool::StandardCommandPool;
 use descriptor::descriptor_set::StdDescriptorPool;
+use extensions::is_promoted;
+use features::FeaturesFfi;
 use instance::Instance;
+use instance::InstanceExtensions;
 use instance::PhysicalDevice;
 use instance::QueueFamily;
 use memory::pool::StdMemoryPool;
+#[cfg(feature = "async")]
+use sync::fence_future::FenceWaiter;
+use version::Version;
 
 use Error;
 use OomError;
@@ -122,6 +130,8 @@ use VulkanHandle;
 use check_errors;
 use vk;
 
+pub use extensions::ExtensionRestriction;
+pub use extensions::ExtensionRestrictionError;
 pub use self::extensions::DeviceExtensions;
 pub use self::extensions::RawDeviceExtensions;
 pub use ::features::Features;
@@ -139,11 +149,14 @@ pub struct Device {
         Mutex<HashMap<u32, Weak<StandardCommandPool>, BuildHasherDefault<FnvHasher>>>,
     features: Features,
     extensions: DeviceExtensions,
+    api_version: Version,
     active_queue_families: SmallVec<[u32; 8]>,
     allocation_count: Mutex<u32>,
     fence_pool: Mutex<Vec<vk::Fence>>,
     semaphore_pool: Mutex<Vec<vk::Semaphore>>,
     event_pool: Mutex<Vec<vk::Event>>,
+    #[cfg(feature = "async")]
+    fence_waiter: Mutex<Weak<FenceWaiter>>,
 }
 
 // The `StandardCommandPool` type doesn't implement Send/Sync, so we have to manually reimplement
@@ -168,6 +181,10 @@ impl Device {
     ///   queue with a lower value. Note however that no guarantee can be made on the way the
     ///   priority value is handled by the implementation.
     ///
+    /// The dependencies and conflicts between the extensions that are known to vulkano are
+    /// checked with `DeviceExtensions::check_requirements`. Use
+    /// `DeviceExtensions::with_dependencies` to add the extensions that are missing.
+    ///
     /// # Panic
     ///
     /// - Panics if one of the queue families doesn't belong to the given device.
@@ -205,7 +222,15 @@ impl Device {
             .map(|layer| layer.as_ptr())
             .collect::<SmallVec<[_; 16]>>();
 
+        let instance = phys.instance();
+        let api_version = cmp::min(instance.api_version(), phys.api_version());
         let extensions = extensions.into();
+        let instance_extensions = instance
+            .loaded_extensions()
+            .union(&InstanceExtensions::promoted_to_core(instance.api_version()));
+        DeviceExtensions::from(&extensions)
+            .check_requirements(api_version, &instance_extensions)?;
+
         let extensions_list = extensions
             .iter()
             .map(|extension| extension.as_ptr())
@@ -275,9 +300,24 @@ impl Device {
                 features
             };
 
+            // Features that aren't part of `VkPhysicalDeviceFeatures` are passed through the
+            // `pNext` chain. Their extension must be enabled, unless it has been promoted to
+            // the version of the device.
+            let is_enabled = |name: &[u8], core_version| {
+                is_promoted(core_version, api_version) ||
+                    extensions.iter().any(|ext| ext.as_bytes() == name)
+            };
+            let mut features_chain = FeaturesFfi::for_device_creation(requested_features,
+                                                                      is_enabled)
+                .map_err(|extension| {
+                    DeviceCreationError::FeatureRequiresExtension {
+                        extension: str::from_utf8(extension).unwrap(),
+                    }
+                })?;
+
             let infos = vk::DeviceCreateInfo {
                 sType: vk::STRUCTURE_TYPE_DEVICE_CREATE_INFO,
-                pNext: ptr::null(),
+                pNext: features_chain.head_as_mut_ptr() as *const _,
                 flags: 0, // reserved
                 queueCreateInfoCount: queues.len() as u32,
                 pQueueCreateInfos: queues.as_ptr(),
@@ -324,11 +364,14 @@ impl Device {
                              ..requested_features.clone()
                          },
                          extensions: (&extensions).into(),
+                         api_version,
                          active_queue_families,
                          allocation_count: Mutex::new(0),
                          fence_pool: Mutex::new(Vec::new()),
                          semaphore_pool: Mutex::new(Vec::new()),
                          event_pool: Mutex::new(Vec::new()),
+                         #[cfg(feature = "async")]
+                         fence_waiter: Mutex::new(Weak::new()),
                      });
 
         // Iterator for the produced queues.
@@ -400,6 +443,16 @@ impl Device {
         &self.extensions
     }
 
+    /// Returns the version of Vulkan that is used by the device.
+    ///
+    /// This is the lowest of the version used by the instance and of the version supported by
+    /// the physical device. The functionality of an extension that was promoted to this version
+    /// or an older one is available even if the extension isn't loaded.
+    #[inline]
+    pub fn api_version(&self) -> Version {
+        self.api_version
+    }
+
     /// Returns the standard memory pool used by default if you don't provide any other pool.
     pub fn standard_pool(me: &Arc<Self>) -> Arc<StdMemoryPool> {
         let mut pool = me.standard_pool.lock().unwrap();
@@ -467,6 +520,21 @@ impl Device {
         &self.allocation_count
     }
 
+    /// Returns the object that wakes the tasks awaiting the fences of this device.
+    #[cfg(feature = "async")]
+    pub(crate) fn fence_waiter(me: &Arc<Self>) -> Arc<FenceWaiter> {
+        let mut waiter = me.fence_waiter.lock().unwrap();
+
+        if let Some(w) = waiter.upgrade() {
+            return w;
+        }
+
+        // The weak pointer is empty, so we create the waiter.
+        let new_waiter = Arc::new(FenceWaiter::new());
+        *waiter = Arc::downgrade(&new_waiter);
+        new_waiter
+    }
+
     pub(crate) fn fence_pool(&self) -> &Mutex<Vec<vk::Fence>> {
         &self.fence_pool
     }
@@ -641,8 +709,17 @@ pub enum DeviceCreationError {
     DeviceLost,
     /// Some of the requested features are unsupported by the physical device.
     FeatureNotPresent,
+    /// One of the requested features belongs to a device extension that isn't enabled and that
+    /// hasn't been promoted to the version of Vulkan of the device.
+    FeatureRequiresExtension {
+        /// Name of the extension.
+        extension: &'static str,
+    },
     /// Some of the requested device extensions are not supported by the physical device.
     ExtensionNotPresent,
+    /// One of the restrictions of the requested device extensions isn't met. For example an
+    /// extension requires another extension that wasn't requested.
+    ExtensionRestrictionNotMet(ExtensionRestrictionError),
     /// Tried to create too many queues for a given family.
     TooManyQueuesForFamily,
     /// The priority of one of the queues is out of the [0.0; 1.0] range.
@@ -673,18 +750,32 @@ impl error::Error for DeviceCreationError {
             DeviceCreationError::FeatureNotPresent => {
                 "some of the requested features are unsupported by the physical device"
             },
+            DeviceCreationError::FeatureRequiresExtension { .. } => {
+                "one of the requested features belongs to a device extension that isn't enabled"
+            },
             DeviceCreationError::PriorityOutOfRange => {
                 "the priority of one of the queues is out of the [0.0; 1.0] range"
             },
             DeviceCreationError::ExtensionNotPresent => {
                 "some of the requested device extensions are not supported by the physical device"
             },
+            DeviceCreationError::ExtensionRestrictionNotMet(_) => {
+                "a restriction of one of the requested device extensions isn't met"
+            },
             DeviceCreationError::TooManyObjects => {
                 "you have reached the limit to the number of devices that can be created from the
                  same physical device"
             },
         }
     }
+
+    #[inline]
+    fn cause(&self) -> Option<&dyn error::Error> {
+        match *self {
+            DeviceCreationError::ExtensionRestrictionNotMet(ref err) => Some(err),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for DeviceCreationError {
@@ -694,6 +785,13 @@ impl fmt::Display for DeviceCreationError {
     }
 }
 
+impl From<ExtensionRestrictionError> for DeviceCreationError {
+    #[inline]
+    fn from(err: ExtensionRestrictionError) -> DeviceCreationError {
+        DeviceCreationError::ExtensionRestrictionNotMet(err)
+    }
+}
+
 impl From<Error> for DeviceCreationError {
     #[inline]
     fn from(err: Error) -> DeviceCreationError {
@@ -846,6 +944,42 @@ mod tests {
         };
     }
 
+    #[test]
+    fn feature_requires_extension() {
+        let instance = instance!(instance::InstanceExtensions {
+                                     khr_get_physical_device_properties2: true,
+                                     ..instance::InstanceExtensions::none()
+                                 });
+        let physical = match instance::PhysicalDevice::enumerate(&instance).next() {
+            Some(p) => p,
+            None => return,
+        };
+
+        let family = physical.queue_families().next().unwrap();
+
+        let features = Features {
+            multiview: true,
+            ..Features::none()
+        };
+        if !physical.supported_features().superset_of(&features) {
+            return;
+        }
+
+        // The instance uses Vulkan 1.0, so `VK_KHR_multiview` must be enabled.
+        match Device::new(physical, &features, &DeviceExtensions::none(), Some((family, 1.0))) {
+            Err(DeviceCreationError::FeatureRequiresExtension { extension }) => {
+                assert_eq!(extension, "VK_KHR_multiview");
+            },
+            _ => panic!(),
+        };
+
+        let extensions = DeviceExtensions {
+            khr_multiview: true,
+            ..DeviceExtensions::none()
+        };
+        Device::new(physical, &features, &extensions, Some((family, 1.0))).unwrap();
+    }
+
     #[test]
     fn priority_out_of_range() {
         let instance = instance!();
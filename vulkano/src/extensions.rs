@@ -14,9 +14,18 @@ use std::str;
 use Error;
 use OomError;
 use instance::loader::LoadingError;
+use version::Version;
 
 macro_rules! extensions {
-    ($sname:ident, $rawname:ident, $($ext:ident => $s:expr,)*) => (
+    (
+        $sname:ident, $rawname:ident,
+        $(
+            $ext:ident => $s:tt {
+                promoted_to: $core:expr,
+                requires: [$($req:ident),*],
+            },
+        )*
+    ) => (
         /// List of extensions that are enabled or available.
         #[derive(Copy, Clone, PartialEq, Eq)]
         #[allow(missing_docs)]
@@ -74,6 +83,66 @@ macro_rules! extensions {
                     _unbuildable: Unbuildable(())
                 }
             }
+
+            /// Returns the extensions of the same kind that the extensions of this list directly
+            /// require, according to the Vulkan registry.
+            ///
+            /// The requirements of the returned extensions aren't included.
+            pub fn required_extensions(&self) -> $sname {
+                #[allow(unused_mut)]
+                let mut required = $sname::none();
+                $(
+                    if self.$ext {
+                        $(
+                            required.$req = true;
+                        )*
+                    }
+                )*
+                required
+            }
+
+            /// Returns this list with all the extensions that its extensions require, directly or
+            /// indirectly, added to it.
+            ///
+            /// The extensions that have been promoted to `api_version` of Vulkan or to an earlier
+            /// version aren't added, since their functionality is available without them.
+            pub fn with_dependencies(&self, api_version: Version) -> $sname {
+                let core = $sname::promoted_to_core(api_version);
+                let mut extensions = *self;
+                loop {
+                    let required = extensions.required_extensions().difference(&core);
+                    let added = extensions.union(&required);
+                    if added == extensions {
+                        return extensions;
+                    }
+                    extensions = added;
+                }
+            }
+
+            // Returns the names of the extensions of this list.
+            pub(crate) fn names(&self) -> Vec<&'static str> {
+                let mut names = Vec::new();
+                $(
+                    if self.$ext {
+                        names.push(str::from_utf8($s).unwrap());
+                    }
+                )*
+                names
+            }
+
+            /// Returns the extensions that have been promoted to `version` of Vulkan or to an
+            /// earlier version.
+            ///
+            /// The functionality of these extensions is available without enabling them if
+            /// `version` is used.
+            pub fn promoted_to_core(version: Version) -> $sname {
+                $sname {
+                    $(
+                        $ext: ::extensions::is_promoted($core, version),
+                    )*
+                    _unbuildable: Unbuildable(())
+                }
+            }
         }
 
         impl fmt::Debug for $sname {
@@ -174,6 +243,76 @@ macro_rules! extensions {
     );
 }
 
+// Returns true if an extension that was promoted to `core` is part of `version`.
+#[inline]
+pub(crate) fn is_promoted(core: Option<Version>, version: Version) -> bool {
+    match core {
+        Some(core) => version >= core,
+        None => false,
+    }
+}
+
+/// Error that can happen when the extensions enabled on an instance or a device don't meet the
+/// restrictions of the Vulkan specification.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct ExtensionRestrictionError {
+    /// The name of the extension whose restriction isn't met.
+    pub extension: &'static str,
+    /// The restriction that isn't met.
+    pub restriction: ExtensionRestriction,
+}
+
+impl error::Error for ExtensionRestrictionError {
+    #[inline]
+    fn description(&self) -> &str {
+        match self.restriction {
+            ExtensionRestriction::RequiresInstanceExtension(_) => {
+                "an extension requires an instance extension that isn't enabled"
+            },
+            ExtensionRestriction::RequiresDeviceExtension(_) => {
+                "an extension requires a device extension that isn't enabled"
+            },
+            ExtensionRestriction::ConflictsDeviceExtension(_) => {
+                "an extension can't be enabled together with another device extension"
+            },
+        }
+    }
+}
+
+impl fmt::Display for ExtensionRestrictionError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match self.restriction {
+            ExtensionRestriction::RequiresInstanceExtension(other) => {
+                write!(fmt, "{} requires the instance extension {}, which isn't enabled",
+                       self.extension, other)
+            },
+            ExtensionRestriction::RequiresDeviceExtension(other) => {
+                write!(fmt, "{} requires the device extension {}, which isn't enabled",
+                       self.extension, other)
+            },
+            ExtensionRestriction::ConflictsDeviceExtension(other) => {
+                write!(fmt, "{} can't be enabled together with {} or with the version of \
+                             Vulkan it was promoted to", self.extension, other)
+            },
+        }
+    }
+}
+
+/// A restriction of an extension, as described by `ExtensionRestrictionError`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum ExtensionRestriction {
+    /// The extension requires an instance extension, which isn't enabled and hasn't been
+    /// promoted to the version of Vulkan of the instance.
+    RequiresInstanceExtension(&'static str),
+    /// The extension requires a device extension, which isn't enabled and hasn't been promoted
+    /// to the version of Vulkan of the device.
+    RequiresDeviceExtension(&'static str),
+    /// The extension can't be enabled together with a device extension. This device extension
+    /// is either enabled, or has been promoted to the version of Vulkan of the device.
+    ConflictsDeviceExtension(&'static str),
+}
+
 /// Error that can happen when loading the list of layers.
 #[derive(Clone, Debug)]
 pub enum SupportedExtensionsError {
@@ -7,16 +7,37 @@
 // notice may not be copied, modified, or distributed except
 // according to those terms.
 
+use std::os::raw::c_void;
+use std::ptr;
+
+use version::Version;
 use vk;
 
 macro_rules! features {
-    ($($name:ident => $vk:ident,)+) => (
+    (
+        core { $($name:ident => $vk:ident,)+ },
+        chained {
+            $(
+                $group:ident => $ty:ident [$stype:ident, $extension:expr, $core:expr] {
+                    $($ext_name:ident => $ext_vk:ident,)+
+                },
+            )+
+        },
+    ) => (
         /// Represents all the features that are available on a physical device or enabled on
         /// a logical device.
         ///
         /// Note that the `robust_buffer_access` is guaranteed to be supported by all Vulkan
         /// implementations.
         ///
+        /// The features that aren't part of Vulkan 1.0 belong to a device extension, such as
+        /// `khr_16bit_storage`, `khr_multiview`, `ext_descriptor_indexing`,
+        /// `khr_timeline_semaphore`, `khr_buffer_device_address` or `ext_scalar_block_layout`.
+        /// They can only be queried if the instance supports Vulkan 1.1 or was created with
+        /// `VK_KHR_get_physical_device_properties2`, and if the physical device supports the
+        /// extension or the version of Vulkan the extension was promoted to. Similarly, they can
+        /// only be enabled if the device is created with the extension or supports that version.
+        ///
         /// # Example
         ///
         /// ```
@@ -47,6 +68,9 @@ macro_rules! features {
             $(
                 pub $name: bool,
             )+
+            $($(
+                pub $ext_name: bool,
+            )+)+
         }
 
         impl Features {
@@ -56,6 +80,9 @@ macro_rules! features {
                     $(
                         $name: false,
                     )+
+                    $($(
+                        $ext_name: false,
+                    )+)+
                 }
             }
 
@@ -68,6 +95,9 @@ macro_rules! features {
                     $(
                         $name: true,
                     )+
+                    $($(
+                        $ext_name: true,
+                    )+)+
                 }
             }
 
@@ -76,7 +106,22 @@ macro_rules! features {
             /// That is, for each feature of the parameter that is true, the corresponding value
             /// in self is true as well.
             pub fn superset_of(&self, other: &Features) -> bool {
-                $((self.$name == true || other.$name == false))&&+
+                $((self.$name == true || other.$name == false))&&+ &&
+                    $($((self.$ext_name == true || other.$ext_name == false))&&+)&&+
+            }
+
+            /// Builds a `Features` that is the union of `self` and another `Features` object.
+            ///
+            /// The result's field will be true if it is true in either `self` or `other`.
+            pub fn union(&self, other: &Features) -> Features {
+                Features {
+                    $(
+                        $name: self.$name || other.$name,
+                    )+
+                    $($(
+                        $ext_name: self.$ext_name || other.$ext_name,
+                    )+)+
+                }
             }
 
             /// Builds a `Features` that is the intersection of `self` and another `Features`
@@ -88,6 +133,9 @@ macro_rules! features {
                     $(
                         $name: self.$name && other.$name,
                     )+
+                    $($(
+                        $ext_name: self.$ext_name && other.$ext_name,
+                    )+)+
                 }
             }
 
@@ -99,14 +147,23 @@ macro_rules! features {
                     $(
                         $name: self.$name && !other.$name,
                     )+
+                    $($(
+                        $ext_name: self.$ext_name && !other.$ext_name,
+                    )+)+
                 }
             }
 
-            pub(crate) fn from_vulkan_features(features: vk::PhysicalDeviceFeatures) -> Features {
+            pub(crate) fn from_vulkan_features(features: vk::PhysicalDeviceFeatures,
+                                               chain: &FeaturesFfi)
+                                               -> Features
+            {
                 Features {
                     $(
                         $name: features.$vk != 0,
                     )+
+                    $($(
+                        $ext_name: chain.$group.as_ref().map(|f| f.$ext_vk != 0).unwrap_or(false),
+                    )+)+
                 }
             }
 
@@ -118,63 +175,118 @@ macro_rules! features {
                 }
             }
         }
+
+        /// The structs that are chained to `VkPhysicalDeviceFeatures2` or to `VkDeviceCreateInfo`
+        /// for the features that aren't part of `VkPhysicalDeviceFeatures`.
+        ///
+        /// Each member is `None` if the corresponding struct isn't part of the chain.
+        pub(crate) struct FeaturesFfi {
+            $(
+                $group: Option<vk::$ty>,
+            )+
+        }
+
+        impl FeaturesFfi {
+            /// Builds the structs to query the features of a physical device.
+            ///
+            /// `is_supported` is called with the name of the extension that each struct belongs
+            /// to and with the version of Vulkan it was promoted to core in, if any, and the
+            /// struct is left out if it returns false.
+            pub(crate) fn for_query<F>(is_supported: F) -> FeaturesFfi
+                where F: Fn(&[u8], Option<Version>) -> bool
+            {
+                FeaturesFfi {
+                    $(
+                        $group: if is_supported($extension, $core) {
+                            Some(vk::$ty {
+                                sType: vk::$stype,
+                                pNext: ptr::null_mut(),
+                                $(
+                                    $ext_vk: vk::FALSE,
+                                )+
+                            })
+                        } else {
+                            None
+                        },
+                    )+
+                }
+            }
+
+            /// Builds the structs to enable `features` when creating a device.
+            ///
+            /// Structs whose features are all false are left out. `is_enabled` is called with
+            /// the name of the extension that each remaining struct belongs to and with the
+            /// version of Vulkan it was promoted to core in, if any. If it returns false, the name
+            /// of the extension is returned as an error.
+            pub(crate) fn for_device_creation<F>(features: &Features, is_enabled: F)
+                                                 -> Result<FeaturesFfi, &'static [u8]>
+                where F: Fn(&[u8], Option<Version>) -> bool
+            {
+                Ok(FeaturesFfi {
+                    $(
+                        $group: if $(features.$ext_name)||+ {
+                            if !is_enabled($extension, $core) {
+                                return Err($extension);
+                            }
+
+                            Some(vk::$ty {
+                                sType: vk::$stype,
+                                pNext: ptr::null_mut(),
+                                $(
+                                    $ext_vk: if features.$ext_name { vk::TRUE } else { vk::FALSE },
+                                )+
+                            })
+                        } else {
+                            None
+                        },
+                    )+
+                })
+            }
+
+            /// Links the structs together and returns a pointer to the first one, or a null
+            /// pointer if there is none.
+            ///
+            /// The pointer is only valid as long as `self` isn't moved or dropped.
+            pub(crate) fn head_as_mut_ptr(&mut self) -> *mut c_void {
+                let mut p_next: *mut c_void = ptr::null_mut();
+                $(
+                    if let Some(ref mut f) = self.$group {
+                        f.pNext = p_next;
+                        p_next = f as *mut _ as *mut c_void;
+                    }
+                )+
+                p_next
+            }
+        }
     )
 }
 
-features!{
-    robust_buffer_access => robustBufferAccess,
-    full_draw_index_uint32 => fullDrawIndexUint32,
-    image_cube_array => imageCubeArray,
-    independent_blend => independentBlend,
-    geometry_shader => geometryShader,
-    tessellation_shader => tessellationShader,
-    sample_rate_shading => sampleRateShading,
-    dual_src_blend => dualSrcBlend,
-    logic_op => logicOp,
-    multi_draw_indirect => multiDrawIndirect,
-    draw_indirect_first_instance => drawIndirectFirstInstance,
-    depth_clamp => depthClamp,
-    depth_bias_clamp => depthBiasClamp,
-    fill_mode_non_solid => fillModeNonSolid,
-    depth_bounds => depthBounds,
-    wide_lines => wideLines,
-    large_points => largePoints,
-    alpha_to_one => alphaToOne,
-    multi_viewport => multiViewport,
-    sampler_anisotropy => samplerAnisotropy,
-    texture_compression_etc2 => textureCompressionETC2,
-    texture_compression_astc_ldr => textureCompressionASTC_LDR,
-    texture_compression_bc => textureCompressionBC,
-    occlusion_query_precise => occlusionQueryPrecise,
-    pipeline_statistics_query => pipelineStatisticsQuery,
-    vertex_pipeline_stores_and_atomics => vertexPipelineStoresAndAtomics,
-    fragment_stores_and_atomics => fragmentStoresAndAtomics,
-    shader_tessellation_and_geometry_point_size => shaderTessellationAndGeometryPointSize,
-    shader_image_gather_extended => shaderImageGatherExtended,
-    shader_storage_image_extended_formats => shaderStorageImageExtendedFormats,
-    shader_storage_image_multisample => shaderStorageImageMultisample,
-    shader_storage_image_read_without_format => shaderStorageImageReadWithoutFormat,
-    shader_storage_image_write_without_format => shaderStorageImageWriteWithoutFormat,
-    shader_uniform_buffer_array_dynamic_indexing => shaderUniformBufferArrayDynamicIndexing,
-    shader_sampled_image_array_dynamic_indexing => shaderSampledImageArrayDynamicIndexing,
-    shader_storage_buffer_array_dynamic_indexing => shaderStorageBufferArrayDynamicIndexing,
-    shader_storage_image_array_dynamic_indexing => shaderStorageImageArrayDynamicIndexing,
-    shader_clip_distance => shaderClipDistance,
-    shader_cull_distance => shaderCullDistance,
-    shader_f3264 => shaderf3264,
-    shader_int64 => shaderInt64,
-    shader_int16 => shaderInt16,
-    shader_resource_residency => shaderResourceResidency,
-    shader_resource_min_lod => shaderResourceMinLod,
-    sparse_binding => sparseBinding,
-    sparse_residency_buffer => sparseResidencyBuffer,
-    sparse_residency_image2d => sparseResidencyImage2D,
-    sparse_residency_image3d => sparseResidencyImage3D,
-    sparse_residency2_samples => sparseResidency2Samples,
-    sparse_residency4_samples => sparseResidency4Samples,
-    sparse_residency8_samples => sparseResidency8Samples,
-    sparse_residency16_samples => sparseResidency16Samples,
-    sparse_residency_aliased => sparseResidencyAliased,
-    variable_multisample_rate => variableMultisampleRate,
-    inherited_queries => inheritedQueries,
+include!("autogen/features.rs");
+
+#[cfg(test)]
+mod tests {
+    use features::Features;
+
+    #[test]
+    fn superset_of_chained_features() {
+        let core = Features {
+            geometry_shader: true,
+            ..Features::none()
+        };
+        let chained = Features {
+            geometry_shader: true,
+            multiview: true,
+            scalar_block_layout: true,
+            ..Features::none()
+        };
+
+        assert!(chained.superset_of(&core));
+        assert!(!core.superset_of(&chained));
+        assert_eq!(chained.difference(&core),
+                   Features {
+                       multiview: true,
+                       scalar_block_layout: true,
+                       ..Features::none()
+                   });
+    }
 }
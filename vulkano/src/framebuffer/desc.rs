@@ -100,6 +100,19 @@ pub unsafe trait RenderPassDesc: RenderPassDescClearValues<Vec<ClearValue>> {
         }
     }
 
+    /// Returns the correlation masks of the render pass when it uses multiview.
+    ///
+    /// Each mask is a set of views that are likely to be spatially correlated (for example the
+    /// two eyes of a stereo rendering), which the implementation may use as an optimization hint.
+    /// A view must not be included in more than one mask. Must be empty if none of the subpasses
+    /// has a non-zero `view_mask`.
+    ///
+    /// The default implementation returns an empty list.
+    #[inline]
+    fn correlation_masks(&self) -> Vec<u32> {
+        Vec::new()
+    }
+
     /// Returns true if this render pass is compatible with another render pass.
     ///
     /// Two render passes that contain one subpass are compatible if they are identical. Two render
@@ -343,6 +356,11 @@ unsafe impl<T> RenderPassDesc for T
     fn dependency_desc(&self, num: usize) -> Option<PassDependencyDescription> {
         (**self).dependency_desc(num)
     }
+
+    #[inline]
+    fn correlation_masks(&self) -> Vec<u32> {
+        (**self).correlation_masks()
+    }
 }
 
 /// Iterator to the attachments of a `RenderPassDesc`.
@@ -501,6 +519,31 @@ pub struct PassDescription {
 
     /// Indices of attachments that will be preserved during this pass.
     pub preserve_attachments: Vec<usize>, // TODO: Vec is slow
+
+    /// Set of views that this pass renders to when using multiview, where bit `n` corresponds
+    /// to layer `n` of the attachments. The pass is executed once per view, and shaders can read
+    /// the index of the current view through the `ViewIndex` built-in.
+    ///
+    /// A value of `0` disables multiview. Either all the passes of a render pass must have a
+    /// non-zero view mask, or none of them. Using multiview requires the `multiview` feature
+    /// to be enabled on the device.
+    pub view_mask: u32,
+
+    /// Index and layout of the attachment that the depth-stencil attachment is resolved into at
+    /// the end of this pass.
+    ///
+    /// If this value is not `None`, then `depth_stencil` must be a multisampled attachment and
+    /// the resolve attachment must have the same format and one sample. Resolving depth-stencil
+    /// attachments requires Vulkan 1.2 or the `khr_depth_stencil_resolve` extension.
+    pub depth_stencil_resolve: Option<(usize, ImageLayout)>,
+
+    /// How the depth aspect is resolved into `depth_stencil_resolve`. `None` means that the depth
+    /// aspect isn't resolved.
+    pub depth_resolve_mode: Option<ResolveMode>,
+
+    /// How the stencil aspect is resolved into `depth_stencil_resolve`. `None` means that the
+    /// stencil aspect isn't resolved.
+    pub stencil_resolve_mode: Option<ResolveMode>,
 }
 
 /// Describes a dependency between two passes of a render pass.
@@ -565,6 +608,26 @@ pub enum StoreOp {
     DontCare = vk::ATTACHMENT_STORE_OP_DONT_CARE,
 }
 
+/// Describes how the samples of a multisampled attachment are combined when it is resolved.
+///
+/// Only applies to depth-stencil attachments. The modes supported by a device are returned by
+/// `PhysicalDevice::depth_stencil_resolve_properties`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[repr(u32)]
+pub enum ResolveMode {
+    /// The value of sample zero is written. This mode is always supported.
+    SampleZero = vk::RESOLVE_MODE_SAMPLE_ZERO_BIT_KHR,
+
+    /// The average of all the samples is written.
+    Average = vk::RESOLVE_MODE_AVERAGE_BIT_KHR,
+
+    /// The minimum of all the samples is written.
+    Min = vk::RESOLVE_MODE_MIN_BIT_KHR,
+
+    /// The maximum of all the samples is written.
+    Max = vk::RESOLVE_MODE_MAX_BIT_KHR,
+}
+
 /// Describes what the implementation should do with an attachment at the start of the subpass.
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
 #[repr(u32)]
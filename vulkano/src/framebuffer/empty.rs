@@ -57,6 +57,10 @@ unsafe impl RenderPassDesc for EmptySinglePassRenderPassDesc {
                      input_attachments: vec![],
                      resolve_attachments: vec![],
                      preserve_attachments: vec![],
+                     view_mask: 0,
+                     depth_stencil_resolve: None,
+                     depth_resolve_mode: None,
+                     stencil_resolve_mode: None,
                  })
         } else {
             None
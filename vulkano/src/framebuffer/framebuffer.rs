@@ -79,6 +79,10 @@ use vk;
 /// If the dimensions of the framebuffer don't match the dimensions of one of its attachment, then
 /// only the top-left hand corner of the image will be drawn to.
 ///
+/// If the render pass uses multiview, then each attachment must have at least as many array
+/// layers as the highest view index used by the subpasses plus one, and the framebuffer itself
+/// always has exactly one layer.
+///
 #[derive(Debug)]
 pub struct Framebuffer<Rp, A> {
     device: Arc<Device>,
@@ -176,6 +180,14 @@ impl<Rp, A> FramebufferBuilder<Rp, A>
         let img_dims = attachment.dimensions();
         debug_assert_eq!(img_dims.depth(), 1);
 
+        let required_layers = multiview_layers(&self.render_pass);
+        if img_dims.array_layers() < required_layers {
+            return Err(FramebufferCreationError::AttachmentNotEnoughLayers {
+                           required: required_layers,
+                           obtained: img_dims.array_layers(),
+                       });
+        }
+
         let dimensions = match self.dimensions {
             FramebufferBuilderDimensions::AutoIdentical(None) => {
                 let dims = [img_dims.width(), img_dims.height(), img_dims.array_layers()];
@@ -265,11 +277,22 @@ impl<Rp, A> FramebufferBuilder<Rp, A>
         }
 
         // Compute the dimensions.
+        let multiview = multiview_layers(&self.render_pass) != 0;
         let dimensions = match self.dimensions {
-            FramebufferBuilderDimensions::Specific(dims) |
+            FramebufferBuilderDimensions::Specific(dims) => {
+                if multiview && dims[2] != 1 {
+                    return Err(FramebufferCreationError::MultiviewMultipleLayers);
+                }
+                dims
+            },
             FramebufferBuilderDimensions::AutoIdentical(Some(dims)) |
             FramebufferBuilderDimensions::AutoSmaller(Some(dims)) => {
-                dims
+                if multiview {
+                    // The views are selected by the view masks of the subpasses instead.
+                    [dims[0], dims[1], 1]
+                } else {
+                    dims
+                }
             },
             FramebufferBuilderDimensions::AutoIdentical(None) |
             FramebufferBuilderDimensions::AutoSmaller(None) => {
@@ -323,6 +346,17 @@ impl<Rp, A> FramebufferBuilder<Rp, A>
     }
 }
 
+// Returns the number of array layers that the attachments must have so that all the views of the
+// render pass can be rendered to, or 0 if the render pass doesn't use multiview.
+fn multiview_layers<Rp>(render_pass: &Rp) -> u32
+    where Rp: ?Sized + RenderPassDesc
+{
+    let views = (0 .. render_pass.num_subpasses())
+        .filter_map(|num| render_pass.subpass_desc(num))
+        .fold(0u32, |views, pass| views | pass.view_mask);
+    32 - views.leading_zeros()
+}
+
 impl<Rp, A> Framebuffer<Rp, A> {
     /// Returns the width, height and layers of this framebuffer.
     #[inline]
@@ -413,6 +447,11 @@ unsafe impl<Rp, A> RenderPassDesc for Framebuffer<Rp, A>
     fn dependency_desc(&self, num: usize) -> Option<PassDependencyDescription> {
         self.render_pass.dependency_desc(num)
     }
+
+    #[inline]
+    fn correlation_masks(&self) -> Vec<u32> {
+        self.render_pass.correlation_masks()
+    }
 }
 
 unsafe impl<C, Rp, A> RenderPassDescClearValues<C> for Framebuffer<Rp, A>
@@ -490,6 +529,16 @@ pub enum FramebufferCreationError {
     IncompatibleAttachment(IncompatibleRenderPassAttachmentError),
     /// The framebuffer has no attachment and no dimension was specified.
     CantDetermineDimensions,
+    /// The attachment doesn't have enough array layers for the views used by the render pass.
+    AttachmentNotEnoughLayers {
+        /// Minimum number of array layers required by the view masks of the render pass.
+        required: u32,
+        /// Number of array layers of the attachment.
+        obtained: u32,
+    },
+    /// The render pass uses multiview but the requested framebuffer dimensions have more than
+    /// one layer.
+    MultiviewMultipleLayers,
 }
 
 impl From<OomError> for FramebufferCreationError {
@@ -518,6 +567,12 @@ impl error::Error for FramebufferCreationError {
             FramebufferCreationError::CantDetermineDimensions => {
                 "the framebuffer has no attachment and no dimension was specified"
             },
+            FramebufferCreationError::AttachmentNotEnoughLayers { .. } => {
+                "the attachment doesn't have enough array layers for the views of the render pass"
+            },
+            FramebufferCreationError::MultiviewMultipleLayers => {
+                "the render pass uses multiview but the framebuffer has more than one layer"
+            },
         }
     }
 
@@ -901,4 +956,58 @@ mod tests {
             _ => panic!(),
         }
     }
+
+
+    #[test]
+    fn multiview_attachment_not_enough_layers() {
+        let (device, _) = gfx_dev_and_queue!(extensions: [khr_multiview], multiview);
+
+        let render_pass = Arc::new(
+            single_pass_renderpass!(device.clone(),
+            attachments: {
+                color: {
+                    load: Clear,
+                    store: DontCare,
+                    format: Format::R8G8B8A8Unorm,
+                    samples: 1,
+                }
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {},
+                view_mask: 0b11,
+            }
+        ).unwrap(),
+        );
+
+        let image = AttachmentImage::new(device.clone(), [1024, 768], Format::R8G8B8A8Unorm)
+            .unwrap();
+
+        match Framebuffer::start(render_pass).add(image) {
+            Err(FramebufferCreationError::AttachmentNotEnoughLayers {
+                    required: 2,
+                    obtained: 1,
+                }) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn multiview_multiple_layers() {
+        let (device, _) = gfx_dev_and_queue!(extensions: [khr_multiview], multiview);
+
+        let render_pass = Arc::new(
+            ordered_passes_renderpass!(device.clone(),
+            attachments: {},
+            passes: [
+                { color: [], depth_stencil: {}, input: [], view_mask: 0b1 }
+            ]
+        ).unwrap(),
+        );
+
+        match Framebuffer::with_dimensions(render_pass, [512, 512, 2]).build() {
+            Err(FramebufferCreationError::MultiviewMultipleLayers) => (),
+            _ => panic!(),
+        }
+    }
 }
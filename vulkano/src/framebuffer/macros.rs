@@ -8,6 +8,9 @@
 // according to those terms.
 
 /// Builds a `RenderPass` object whose template parameter is of indeterminate type.
+///
+/// The pass accepts the same optional entries as the passes of `ordered_passes_renderpass!`, and
+/// the render pass the same optional `correlation_masks`.
 #[macro_export]
 macro_rules! single_pass_renderpass {
     (
@@ -15,9 +18,14 @@ macro_rules! single_pass_renderpass {
         attachments: { $($a:tt)* },
         pass: {
             color: [$($color_atch:ident),*],
-            depth_stencil: {$($depth_atch:ident)*}$(,)*
-            $(resolve: [$($resolve_atch:ident),*])*$(,)*
+            depth_stencil: {$($depth_atch:ident)*}
+            $(, resolve: [$($resolve_atch:ident),*])*
+            $(, depth_stencil_resolve: {$($depth_resolve_atch:ident)*})?
+            $(, depth_resolve_mode: $depth_resolve_mode:ident)?
+            $(, stencil_resolve_mode: $stencil_resolve_mode:ident)?
+            $(, view_mask: $view_mask:expr)?$(,)?
         }
+        $(, correlation_masks: [$($correlation_mask:expr),*])?$(,)?
     ) => (
         $crate::ordered_passes_renderpass!(
             $device,
@@ -27,14 +35,53 @@ macro_rules! single_pass_renderpass {
                     color: [$($color_atch),*],
                     depth_stencil: {$($depth_atch)*},
                     input: [],
-                    resolve: [$($($resolve_atch),*)*]
+                    resolve: [$($($resolve_atch),*)*],
+                    $(depth_stencil_resolve: {$($depth_resolve_atch)*},)?
+                    $(depth_resolve_mode: $depth_resolve_mode,)?
+                    $(stencil_resolve_mode: $stencil_resolve_mode,)?
+                    $(view_mask: $view_mask)?
                 }
-            ]
+            ],
+            correlation_masks: [$($($correlation_mask),*)?]
         )
     )
 }
 
 /// Builds a `RenderPass` object whose template parameter is of indeterminate type.
+///
+/// Each pass can have the following optional entries, in this order after `input`:
+///
+/// - `resolve: [...]`, the attachments that the color attachments are resolved into.
+/// - `depth_stencil_resolve: {...}`, the attachment that the depth-stencil attachment is resolved
+///   into, along with `depth_resolve_mode` and `stencil_resolve_mode` which are variants of
+///   `ResolveMode` and default to `SampleZero`.
+/// - `view_mask`, to render with multiview (see `PassDescription::view_mask`).
+///
+/// The render pass can have optional `correlation_masks` (see
+/// `RenderPassDesc::correlation_masks`). The view and correlation masks must be constant
+/// expressions, as they are evaluated inside a module generated by the macro.
+///
+/// ```ignore
+/// let render_pass = ordered_passes_renderpass!(device.clone(),
+///     attachments: {
+///         color: {
+///             load: Clear,
+///             store: Store,
+///             format: Format::R8G8B8A8Unorm,
+///             samples: 1,
+///         }
+///     },
+///     passes: [
+///         {
+///             color: [color],
+///             depth_stencil: {},
+///             input: [],
+///             view_mask: 0b11,
+///         }
+///     ],
+///     correlation_masks: [0b11]
+/// ).unwrap();
+/// ```
 #[macro_export]
 macro_rules! ordered_passes_renderpass {
     (
@@ -56,11 +103,16 @@ macro_rules! ordered_passes_renderpass {
                 {
                     color: [$($color_atch:ident),*],
                     depth_stencil: {$($depth_atch:ident)*},
-                    input: [$($input_atch:ident),*]$(,)*
-                    $(resolve: [$($resolve_atch:ident),*])*$(,)*
+                    input: [$($input_atch:ident),*]
+                    $(, resolve: [$($resolve_atch:ident),*])*
+                    $(, depth_stencil_resolve: {$($depth_resolve_atch:ident)*})?
+                    $(, depth_resolve_mode: $depth_resolve_mode:ident)?
+                    $(, stencil_resolve_mode: $stencil_resolve_mode:ident)?
+                    $(, view_mask: $view_mask:expr)?$(,)?
                 }
             ),*
         ]
+        $(, correlation_masks: [$($correlation_mask:expr),*])?$(,)?
     ) => ({
         use $crate::framebuffer::RenderPassDesc;
 
@@ -75,6 +127,7 @@ macro_rules! ordered_passes_renderpass {
             use $crate::framebuffer::AttachmentDescription;
             use $crate::framebuffer::PassDescription;
             use $crate::framebuffer::PassDependencyDescription;
+            use $crate::framebuffer::ResolveMode;
             use $crate::image::ImageLayout;
             use $crate::sync::AccessFlagBits;
             use $crate::sync::PipelineStages;
@@ -116,6 +169,11 @@ macro_rules! ordered_passes_renderpass {
                 fn dependency_desc(&self, id: usize) -> Option<PassDependencyDescription> {
                     dependency(id)
                 }
+
+                #[inline]
+                fn correlation_masks(&self) -> Vec<u32> {
+                    vec![$($($correlation_mask),*)?]
+                }
             }
 
             unsafe impl RenderPassDescClearValues<Vec<ClearValue>> for CustomRenderPassDesc {
@@ -195,6 +253,21 @@ macro_rules! ordered_passes_renderpass {
                             depth = Some(($depth_atch, ImageLayout::DepthStencilAttachmentOptimal));
                         )*
 
+                        let mut depth_resolve = None;
+                        $($(
+                            depth_resolve = Some(($depth_resolve_atch,
+                                                  ImageLayout::DepthStencilAttachmentOptimal));
+                        )*)?
+
+                        let (mut depth_resolve_mode, mut stencil_resolve_mode) =
+                            if depth_resolve.is_some() {
+                                (Some(ResolveMode::SampleZero), Some(ResolveMode::SampleZero))
+                            } else {
+                                (None, None)
+                            };
+                        $(depth_resolve_mode = Some(ResolveMode::$depth_resolve_mode);)?
+                        $(stencil_resolve_mode = Some(ResolveMode::$stencil_resolve_mode);)?
+
                         let mut desc = PassDescription {
                             color_attachments: vec![
                                 $(
@@ -217,8 +290,17 @@ macro_rules! ordered_passes_renderpass {
                                 $(if a == $depth_atch { return false; })*
                                 $(if a == $input_atch { return false; })*
                                 $($(if a == $resolve_atch { return false; })*)*
+                                $($(if a == $depth_resolve_atch { return false; })*)?
                                 true
-                            }).collect()
+                            }).collect(),
+                            view_mask: {
+                                let mut view_mask = 0;
+                                $(view_mask = $view_mask;)?
+                                view_mask
+                            },
+                            depth_stencil_resolve: depth_resolve,
+                            depth_resolve_mode,
+                            stencil_resolve_mode,
                         };
 
                         assert!(desc.resolve_attachments.is_empty() ||
@@ -301,6 +383,15 @@ macro_rules! ordered_passes_renderpass {
                         }
                     )*)*
 
+                    $($(
+                        if $depth_resolve_atch == num {
+                            if initial_layout.is_none() {
+                                initial_layout = Some(ImageLayout::DepthStencilAttachmentOptimal);
+                            }
+                            final_layout = Some(ImageLayout::DepthStencilAttachmentOptimal);
+                        }
+                    )*)?
+
                     $(
                         if $input_atch == num {
                             if initial_layout.is_none() {
@@ -337,6 +428,9 @@ macro_rules! ordered_passes_renderpass {
 #[cfg(test)]
 mod tests {
     use format::Format;
+    use framebuffer::RenderPassCreationError;
+    use framebuffer::RenderPassDesc;
+    use framebuffer::ResolveMode;
 
     #[test]
     fn single_pass_resolve() {
@@ -363,4 +457,62 @@ mod tests {
             }
         ).unwrap();
     }
+    #[test]
+    fn single_pass_multiview() {
+        let (device, _) = gfx_dev_and_queue!(extensions: [khr_multiview], multiview);
+        let _ = single_pass_renderpass!(device.clone(),
+            attachments: {
+                a: {
+                    load: Clear,
+                    store: Store,
+                    format: Format::R8G8B8A8Unorm,
+                    samples: 1,
+                }
+            },
+            pass: {
+                color: [a],
+                depth_stencil: {},
+                view_mask: 0b11,
+            },
+            correlation_masks: [0b11]
+        ).unwrap();
+    }
+
+    #[test]
+    fn single_pass_depth_stencil_resolve() {
+        let (device, _) = gfx_dev_and_queue!();
+        let rp = single_pass_renderpass!(device.clone(),
+            attachments: {
+                depth: {
+                    load: Clear,
+                    store: DontCare,
+                    format: Format::D16Unorm,
+                    samples: 4,
+                },
+                resolved: {
+                    load: DontCare,
+                    store: Store,
+                    format: Format::D16Unorm,
+                    samples: 1,
+                }
+            },
+            pass: {
+                color: [],
+                depth_stencil: {depth},
+                depth_stencil_resolve: {resolved},
+                depth_resolve_mode: SampleZero,
+            }
+        );
+
+        let rp = match rp {
+            Ok(rp) => rp,
+            Err(RenderPassCreationError::DepthStencilResolveExtensionNotEnabled) => return,
+            Err(err) => panic!("{:?}", err),
+        };
+
+        let pass = rp.desc().subpass_desc(0).unwrap();
+        assert_eq!(pass.depth_stencil_resolve.map(|(a, _)| a), Some(1));
+        assert_eq!(pass.depth_resolve_mode, Some(ResolveMode::SampleZero));
+        assert!(pass.preserve_attachments.is_empty());
+    }
 }
@@ -101,6 +101,7 @@ pub use self::desc::RenderPassDesc;
 pub use self::desc::RenderPassDescAttachments;
 pub use self::desc::RenderPassDescDependencies;
 pub use self::desc::RenderPassDescSubpasses;
+pub use self::desc::ResolveMode;
 pub use self::desc::StoreOp;
 pub use self::empty::EmptySinglePassRenderPassDesc;
 pub use self::framebuffer::Framebuffer;
@@ -19,6 +19,7 @@ use std::sync::Mutex;
 use device::Device;
 use device::DeviceOwned;
 use format::ClearValue;
+use format::FormatTy;
 use framebuffer::EmptySinglePassRenderPassDesc;
 use framebuffer::AttachmentDescription;
 use framebuffer::PassDependencyDescription;
@@ -27,6 +28,11 @@ use framebuffer::LoadOp;
 use framebuffer::RenderPassAbstract;
 use framebuffer::RenderPassDesc;
 use framebuffer::RenderPassDescClearValues;
+use framebuffer::ResolveMode;
+use image::ImageLayout;
+use instance::DepthStencilResolveProperties;
+use instance::ResolveModes;
+use version::Version;
 
 use Error;
 use OomError;
@@ -65,8 +71,6 @@ impl<D> RenderPass<D>
     ///
     pub fn new(device: Arc<Device>, description: D)
                -> Result<RenderPass<D>, RenderPassCreationError> {
-        let vk = device.pointers();
-
         // If the first use of an attachment in this render pass is as an input attachment, and
         // the attachment is not also used as a color or depth/stencil attachment in the same
         // subpass, then loadOp must not be VK_ATTACHMENT_LOAD_OP_CLEAR
@@ -101,263 +105,697 @@ impl<D> RenderPass<D>
             true
         }));
 
-        let attachments = description
-            .attachment_descs()
-            .map(|attachment| {
-                debug_assert!(attachment.samples.is_power_of_two());
-
-                vk::AttachmentDescription {
-                    flags: 0, // FIXME: may alias flag
-                    format: attachment.format as u32,
-                    samples: attachment.samples,
-                    loadOp: attachment.load as u32,
-                    storeOp: attachment.store as u32,
-                    stencilLoadOp: attachment.stencil_load as u32,
-                    stencilStoreOp: attachment.stencil_store as u32,
-                    initialLayout: attachment.initial_layout as u32,
-                    finalLayout: attachment.final_layout as u32,
-                }
-            })
+        // Multiview is either enabled for all the subpasses or for none of them.
+        let view_masks = description
+            .subpass_descs()
+            .map(|pass| pass.view_mask)
             .collect::<SmallVec<[_; 16]>>();
+        let multiview = view_masks.iter().any(|&m| m != 0);
+        let correlation_masks = description.correlation_masks();
 
-        // We need to pass pointers to vkAttachmentReference structs when creating the render pass.
-        // Therefore we need to allocate them in advance.
-        //
-        // This block allocates, for each pass, in order, all color attachment references, then all
-        // input attachment references, then all resolve attachment references, then the depth
-        // stencil attachment reference.
-        let attachment_references = description
+        if multiview {
+            if view_masks.contains(&0) {
+                return Err(RenderPassCreationError::InconsistentViewMasks);
+            }
+
+            if !device.enabled_features().multiview {
+                return Err(RenderPassCreationError::MultiviewFeatureNotEnabled);
+            }
+
+            let max_view_count = device
+                .physical_device()
+                .max_multiview_view_count()
+                .unwrap_or(0);
+            if view_masks
+                .iter()
+                .any(|&m| 32 - m.leading_zeros() > max_view_count)
+            {
+                return Err(RenderPassCreationError::MultiviewViewCountLimitExceeded);
+            }
+        }
+
+        if !correlation_masks.is_empty() {
+            let mut union = 0u32;
+            for &mask in correlation_masks.iter() {
+                if !multiview || union & mask != 0 {
+                    return Err(RenderPassCreationError::InvalidCorrelationMasks);
+                }
+                union |= mask;
+            }
+        }
+
+        let attachments = description.attachment_descs().collect::<SmallVec<[_; 16]>>();
+        let use_depth_stencil_resolve = description
             .subpass_descs()
-            .flat_map(|pass| {
-                // Performing some validation with debug asserts.
-                debug_assert!(pass.resolve_attachments.is_empty() ||
-                                  pass.resolve_attachments.len() == pass.color_attachments.len());
-                debug_assert!(pass.resolve_attachments
+            .any(|pass| pass.depth_stencil_resolve.is_some());
+
+        if use_depth_stencil_resolve &&
+            !(device.api_version() >= Version::V1_2 ||
+                  device.loaded_extensions().khr_depth_stencil_resolve)
+        {
+            return Err(RenderPassCreationError::DepthStencilResolveExtensionNotEnabled);
+        }
+
+        for pass in description.subpass_descs() {
+            if pass.color_attachments.len() as u32 >
+                device.physical_device().limits().max_color_attachments()
+            {
+                return Err(RenderPassCreationError::ColorAttachmentsLimitExceeded);
+            }
+
+            // Performing some validation with debug asserts.
+            debug_assert!(pass.resolve_attachments.is_empty() ||
+                              pass.resolve_attachments.len() == pass.color_attachments.len());
+            debug_assert!(pass.resolve_attachments
+                              .iter()
+                              .all(|a| attachments[a.0].samples == 1));
+            debug_assert!(pass.resolve_attachments.is_empty() ||
+                              pass.color_attachments
                                   .iter()
-                                  .all(|a| attachments[a.0].samples == 1));
-                debug_assert!(pass.resolve_attachments.is_empty() ||
-                                  pass.color_attachments
-                                      .iter()
-                                      .all(|a| attachments[a.0].samples > 1));
-                debug_assert!(pass.resolve_attachments.is_empty() ||
-                                  pass.resolve_attachments
-                                      .iter()
-                                      .zip(pass.color_attachments.iter())
-                                      .all(|(r, c)| {
-                                               attachments[r.0].format == attachments[c.0].format
-                                           }));
-                debug_assert!(pass.color_attachments
+                                  .all(|a| attachments[a.0].samples > 1));
+            debug_assert!(pass.resolve_attachments.is_empty() ||
+                              pass.resolve_attachments
                                   .iter()
-                                  .cloned()
-                                  .chain(pass.depth_stencil.clone().into_iter())
-                                  .chain(pass.input_attachments.iter().cloned())
-                                  .chain(pass.resolve_attachments.iter().cloned())
-                                  .all(|(a, _)| {
-                                           pass.preserve_attachments
-                                               .iter()
-                                               .find(|&&b| a == b)
-                                               .is_none()
+                                  .zip(pass.color_attachments.iter())
+                                  .all(|(r, c)| {
+                                           attachments[r.0].format == attachments[c.0].format
                                        }));
-                debug_assert!(
-                    pass.color_attachments
-                        .iter()
-                        .cloned()
-                        .chain(pass.depth_stencil.clone().into_iter())
-                        .all(|(atch, layout)| if let Some(r) =
-                            pass.input_attachments.iter().find(|r| r.0 == atch)
-                        {
-                            r.1 == layout
-                        } else {
-                            true
-                        })
-                );
-
-                let resolve = pass.resolve_attachments
-                    .into_iter()
-                    .map(|(offset, img_la)| {
-                             debug_assert!(offset < attachments.len());
-                             vk::AttachmentReference {
-                                 attachment: offset as u32,
-                                 layout: img_la as u32,
-                             }
-                         });
-
-                let color = pass.color_attachments.into_iter().map(|(offset, img_la)| {
-                    debug_assert!(offset < attachments.len());
-                    vk::AttachmentReference {
-                        attachment: offset as u32,
-                        layout: img_la as u32,
-                    }
-                });
+            debug_assert!(pass.color_attachments
+                              .iter()
+                              .cloned()
+                              .chain(pass.depth_stencil.clone().into_iter())
+                              .chain(pass.input_attachments.iter().cloned())
+                              .chain(pass.resolve_attachments.iter().cloned())
+                              .chain(pass.depth_stencil_resolve.into_iter())
+                              .all(|(a, _)| {
+                                       pass.preserve_attachments
+                                           .iter()
+                                           .find(|&&b| a == b)
+                                           .is_none()
+                                   }));
+            debug_assert!(
+                pass.color_attachments
+                    .iter()
+                    .cloned()
+                    .chain(pass.depth_stencil.clone().into_iter())
+                    .all(|(atch, layout)| if let Some(r) =
+                        pass.input_attachments.iter().find(|r| r.0 == atch)
+                    {
+                        r.1 == layout
+                    } else {
+                        true
+                    })
+            );
+
+            if let Some((resolve, _)) = pass.depth_stencil_resolve {
+                let depth_stencil = match pass.depth_stencil {
+                    Some((d, _)) => &attachments[d],
+                    None => return Err(RenderPassCreationError::InvalidDepthStencilResolve),
+                };
+                let resolve = &attachments[resolve];
+                if depth_stencil.samples == 1 || resolve.samples != 1 ||
+                    depth_stencil.format != resolve.format
+                {
+                    return Err(RenderPassCreationError::InvalidDepthStencilResolve);
+                }
 
-                let input = pass.input_attachments.into_iter().map(|(offset, img_la)| {
-                    debug_assert!(offset < attachments.len());
-                    vk::AttachmentReference {
-                        attachment: offset as u32,
-                        layout: img_la as u32,
-                    }
-                });
+                check_depth_stencil_resolve_modes(&device,
+                                                  resolve.format.ty(),
+                                                  pass.depth_resolve_mode,
+                                                  pass.stencil_resolve_mode)?;
+            }
+        }
 
-                let depthstencil = if let Some((offset, img_la)) = pass.depth_stencil {
-                    Some(vk::AttachmentReference {
-                             attachment: offset as u32,
-                             layout: img_la as u32,
-                         })
-                } else {
-                    None
-                }.into_iter();
+        let render_pass = unsafe {
+            if device.api_version() >= Version::V1_2 ||
+                device.loaded_extensions().khr_create_renderpass2
+            {
+                create_render_pass2(&device, &description, multiview, &correlation_masks)?
+            } else {
+                create_render_pass(&device, &description, multiview, &view_masks,
+                                   &correlation_masks)?
+            }
+        };
 
-                color.chain(input).chain(resolve).chain(depthstencil)
-            })
-            .collect::<SmallVec<[_; 16]>>();
+        Ok(RenderPass {
+               device: device.clone(),
+               render_pass: render_pass,
+               desc: description,
+               granularity: Mutex::new(None),
+           })
+    }
+}
 
-        // Same as `attachment_references` but only for the preserve attachments.
-        // This is separate because attachment references are u32s and not `vkAttachmentReference`
-        // structs.
-        let preserve_attachments_references = description
-            .subpass_descs()
-            .flat_map(|pass| {
-                          pass.preserve_attachments
-                              .into_iter()
-                              .map(|offset| offset as u32)
-                      })
-            .collect::<SmallVec<[_; 16]>>();
+// Creates a render pass with `vkCreateRenderPass`.
+//
+// The description must have been validated by `RenderPass::new`.
+unsafe fn create_render_pass<D>(device: &Device, description: &D, multiview: bool,
+                                view_masks: &[u32], correlation_masks: &[u32])
+                                -> Result<vk::RenderPass, RenderPassCreationError>
+    where D: RenderPassDesc
+{
+    let vk = device.pointers();
+
+    let attachments = description
+        .attachment_descs()
+        .map(|attachment| {
+            debug_assert!(attachment.samples.is_power_of_two());
+
+            vk::AttachmentDescription {
+                flags: 0, // FIXME: may alias flag
+                format: attachment.format as u32,
+                samples: attachment.samples,
+                loadOp: attachment.load as u32,
+                storeOp: attachment.store as u32,
+                stencilLoadOp: attachment.stencil_load as u32,
+                stencilStoreOp: attachment.stencil_store as u32,
+                initialLayout: attachment.initial_layout as u32,
+                finalLayout: attachment.final_layout as u32,
+            }
+        })
+        .collect::<SmallVec<[_; 16]>>();
+
+    // We need to pass pointers to vkAttachmentReference structs when creating the render pass.
+    // Therefore we need to allocate them in advance.
+    //
+    // This block allocates, for each pass, in order, all color attachment references, then all
+    // input attachment references, then all resolve attachment references, then the depth
+    // stencil attachment reference.
+    let attachment_references = description
+        .subpass_descs()
+        .flat_map(|pass| {
+            let resolve = pass.resolve_attachments
+                .into_iter()
+                .map(|(offset, img_la)| {
+                         debug_assert!(offset < attachments.len());
+                         vk::AttachmentReference {
+                             attachment: offset as u32,
+                             layout: img_la as u32,
+                         }
+                     });
+
+            let color = pass.color_attachments.into_iter().map(|(offset, img_la)| {
+                debug_assert!(offset < attachments.len());
+                vk::AttachmentReference {
+                    attachment: offset as u32,
+                    layout: img_la as u32,
+                }
+            });
 
-        // Now iterating over passes.
-        let passes = unsafe {
-            // `ref_index` and `preserve_ref_index` are increased during the loop and point to the
-            // next element to use in respectively `attachment_references` and
-            // `preserve_attachments_references`.
-            let mut ref_index = 0usize;
-            let mut preserve_ref_index = 0usize;
-            let mut out: SmallVec<[_; 16]> = SmallVec::new();
-
-            for pass in description.subpass_descs() {
-                if pass.color_attachments.len() as u32 >
-                    device.physical_device().limits().max_color_attachments()
-                {
-                    return Err(RenderPassCreationError::ColorAttachmentsLimitExceeded);
+            let input = pass.input_attachments.into_iter().map(|(offset, img_la)| {
+                debug_assert!(offset < attachments.len());
+                vk::AttachmentReference {
+                    attachment: offset as u32,
+                    layout: img_la as u32,
                 }
+            });
+
+            let depthstencil = if let Some((offset, img_la)) = pass.depth_stencil {
+                Some(vk::AttachmentReference {
+                         attachment: offset as u32,
+                         layout: img_la as u32,
+                     })
+            } else {
+                None
+            }.into_iter();
+
+            color.chain(input).chain(resolve).chain(depthstencil)
+        })
+        .collect::<SmallVec<[_; 16]>>();
+
+    // Same as `attachment_references` but only for the preserve attachments.
+    // This is separate because attachment references are u32s and not `vkAttachmentReference`
+    // structs.
+    let preserve_attachments_references = description
+        .subpass_descs()
+        .flat_map(|pass| {
+                      pass.preserve_attachments
+                          .into_iter()
+                          .map(|offset| offset as u32)
+                  })
+        .collect::<SmallVec<[_; 16]>>();
+
+    // Now iterating over passes.
+    let passes = {
+        // `ref_index` and `preserve_ref_index` are increased during the loop and point to the
+        // next element to use in respectively `attachment_references` and
+        // `preserve_attachments_references`.
+        let mut ref_index = 0usize;
+        let mut preserve_ref_index = 0usize;
+        let mut out: SmallVec<[_; 16]> = SmallVec::new();
+
+        for pass in description.subpass_descs() {
+            let color_attachments = attachment_references.as_ptr().offset(ref_index as isize);
+            ref_index += pass.color_attachments.len();
+            let input_attachments = attachment_references.as_ptr().offset(ref_index as isize);
+            ref_index += pass.input_attachments.len();
+            let resolve_attachments = attachment_references.as_ptr().offset(ref_index as isize);
+            ref_index += pass.resolve_attachments.len();
+            let depth_stencil = if pass.depth_stencil.is_some() {
+                let a = attachment_references.as_ptr().offset(ref_index as isize);
+                ref_index += 1;
+                a
+            } else {
+                ptr::null()
+            };
 
-                let color_attachments = attachment_references.as_ptr().offset(ref_index as isize);
-                ref_index += pass.color_attachments.len();
-                let input_attachments = attachment_references.as_ptr().offset(ref_index as isize);
-                ref_index += pass.input_attachments.len();
-                let resolve_attachments = attachment_references.as_ptr().offset(ref_index as isize);
-                ref_index += pass.resolve_attachments.len();
-                let depth_stencil = if pass.depth_stencil.is_some() {
-                    let a = attachment_references.as_ptr().offset(ref_index as isize);
-                    ref_index += 1;
-                    a
-                } else {
-                    ptr::null()
-                };
+            let preserve_attachments = preserve_attachments_references
+                .as_ptr()
+                .offset(preserve_ref_index as isize);
+            preserve_ref_index += pass.preserve_attachments.len();
+
+            out.push(vk::SubpassDescription {
+                         flags: 0, // reserved
+                         pipelineBindPoint: vk::PIPELINE_BIND_POINT_GRAPHICS,
+                         inputAttachmentCount: pass.input_attachments.len() as u32,
+                         pInputAttachments: if pass.input_attachments.is_empty() {
+                             ptr::null()
+                         } else {
+                             input_attachments
+                         },
+                         colorAttachmentCount: pass.color_attachments.len() as u32,
+                         pColorAttachments: if pass.color_attachments.is_empty() {
+                             ptr::null()
+                         } else {
+                             color_attachments
+                         },
+                         pResolveAttachments: if pass.resolve_attachments.is_empty() {
+                             ptr::null()
+                         } else {
+                             resolve_attachments
+                         },
+                         pDepthStencilAttachment: depth_stencil,
+                         preserveAttachmentCount: pass.preserve_attachments.len() as u32,
+                         pPreserveAttachments: if pass.preserve_attachments.is_empty() {
+                             ptr::null()
+                         } else {
+                             preserve_attachments
+                         },
+                     });
+        }
 
-                let preserve_attachments = preserve_attachments_references
-                    .as_ptr()
-                    .offset(preserve_ref_index as isize);
-                preserve_ref_index += pass.preserve_attachments.len();
-
-                out.push(vk::SubpassDescription {
-                             flags: 0, // reserved
-                             pipelineBindPoint: vk::PIPELINE_BIND_POINT_GRAPHICS,
-                             inputAttachmentCount: pass.input_attachments.len() as u32,
-                             pInputAttachments: if pass.input_attachments.is_empty() {
-                                 ptr::null()
-                             } else {
-                                 input_attachments
-                             },
-                             colorAttachmentCount: pass.color_attachments.len() as u32,
-                             pColorAttachments: if pass.color_attachments.is_empty() {
-                                 ptr::null()
-                             } else {
-                                 color_attachments
-                             },
-                             pResolveAttachments: if pass.resolve_attachments.is_empty() {
-                                 ptr::null()
-                             } else {
-                                 resolve_attachments
-                             },
-                             pDepthStencilAttachment: depth_stencil,
-                             preserveAttachmentCount: pass.preserve_attachments.len() as u32,
-                             pPreserveAttachments: if pass.preserve_attachments.is_empty() {
-                                 ptr::null()
-                             } else {
-                                 preserve_attachments
-                             },
-                         });
+        assert!(!out.is_empty());
+        // If these assertions fails, there's a serious bug in the code above ^.
+        debug_assert!(ref_index == attachment_references.len());
+        debug_assert!(preserve_ref_index == preserve_attachments_references.len());
+
+        out
+    };
+
+    let dependencies = description
+        .dependency_descs()
+        .map(|dependency| {
+            debug_assert!(dependency.source_subpass as u32 == vk::SUBPASS_EXTERNAL || dependency.source_subpass < passes.len());
+            debug_assert!(dependency.destination_subpass as u32 == vk::SUBPASS_EXTERNAL || dependency.destination_subpass < passes.len());
+
+            vk::SubpassDependency {
+                srcSubpass: dependency.source_subpass as u32,
+                dstSubpass: dependency.destination_subpass as u32,
+                srcStageMask: dependency.source_stages.into_vulkan_bits(),
+                dstStageMask: dependency.destination_stages.into_vulkan_bits(),
+                srcAccessMask: dependency.source_access.into_vulkan_bits(),
+                dstAccessMask: dependency.destination_access.into_vulkan_bits(),
+                dependencyFlags: {
+                    let mut flags = 0;
+                    if dependency.by_region {
+                        flags |= vk::DEPENDENCY_BY_REGION_BIT;
+                    }
+                    // Required by Vulkan for self-dependencies of multiview subpasses.
+                    if multiview && dependency.source_subpass == dependency.destination_subpass {
+                        flags |= vk::DEPENDENCY_VIEW_LOCAL_BIT_KHR;
+                    }
+                    flags
+                },
             }
+        })
+        .collect::<SmallVec<[_; 16]>>();
+
+    let multiview_infos = if multiview {
+        Some(vk::RenderPassMultiviewCreateInfoKHR {
+                 sType: vk::STRUCTURE_TYPE_RENDER_PASS_MULTIVIEW_CREATE_INFO_KHR,
+                 pNext: ptr::null(),
+                 subpassCount: view_masks.len() as u32,
+                 pViewMasks: view_masks.as_ptr(),
+                 dependencyCount: 0,
+                 pViewOffsets: ptr::null(),
+                 correlationMaskCount: correlation_masks.len() as u32,
+                 pCorrelationMasks: if correlation_masks.is_empty() {
+                     ptr::null()
+                 } else {
+                     correlation_masks.as_ptr()
+                 },
+             })
+    } else {
+        None
+    };
+
+    let infos = vk::RenderPassCreateInfo {
+        sType: vk::STRUCTURE_TYPE_RENDER_PASS_CREATE_INFO,
+        pNext: multiview_infos
+            .as_ref()
+            .map(|i| i as *const _ as *const _)
+            .unwrap_or(ptr::null()),
+        flags: 0, // reserved
+        attachmentCount: attachments.len() as u32,
+        pAttachments: if attachments.is_empty() {
+            ptr::null()
+        } else {
+            attachments.as_ptr()
+        },
+        subpassCount: passes.len() as u32,
+        pSubpasses: if passes.is_empty() {
+            ptr::null()
+        } else {
+            passes.as_ptr()
+        },
+        dependencyCount: dependencies.len() as u32,
+        pDependencies: if dependencies.is_empty() {
+            ptr::null()
+        } else {
+            dependencies.as_ptr()
+        },
+    };
+
+    let mut output = MaybeUninit::uninit();
+    check_errors(vk.CreateRenderPass(device.internal_object(),
+                                     &infos,
+                                     ptr::null(),
+                                     output.as_mut_ptr()))?;
+    Ok(output.assume_init())
+}
 
-            assert!(!out.is_empty());
-            // If these assertions fails, there's a serious bug in the code above ^.
-            debug_assert!(ref_index == attachment_references.len());
-            debug_assert!(preserve_ref_index == preserve_attachments_references.len());
+// Creates a render pass with `vkCreateRenderPass2`, which is required to resolve depth-stencil
+// attachments.
+//
+// The description must have been validated by `RenderPass::new`.
+unsafe fn create_render_pass2<D>(device: &Device, description: &D, multiview: bool,
+                                 correlation_masks: &[u32])
+                                 -> Result<vk::RenderPass, RenderPassCreationError>
+    where D: RenderPassDesc
+{
+    let vk = device.pointers();
 
-            out
-        };
+    let attachments = description
+        .attachment_descs()
+        .map(|attachment| {
+            debug_assert!(attachment.samples.is_power_of_two());
 
-        let dependencies = description
-            .dependency_descs()
-            .map(|dependency| {
-                debug_assert!(dependency.source_subpass as u32 == vk::SUBPASS_EXTERNAL || dependency.source_subpass < passes.len());
-                debug_assert!(dependency.destination_subpass as u32 == vk::SUBPASS_EXTERNAL || dependency.destination_subpass < passes.len());
-
-                vk::SubpassDependency {
-                    srcSubpass: dependency.source_subpass as u32,
-                    dstSubpass: dependency.destination_subpass as u32,
-                    srcStageMask: dependency.source_stages.into_vulkan_bits(),
-                    dstStageMask: dependency.destination_stages.into_vulkan_bits(),
-                    srcAccessMask: dependency.source_access.into_vulkan_bits(),
-                    dstAccessMask: dependency.destination_access.into_vulkan_bits(),
-                    dependencyFlags: if dependency.by_region {
-                        vk::DEPENDENCY_BY_REGION_BIT
-                    } else {
-                        0
+            vk::AttachmentDescription2KHR {
+                sType: vk::STRUCTURE_TYPE_ATTACHMENT_DESCRIPTION_2_KHR,
+                pNext: ptr::null(),
+                flags: 0, // FIXME: may alias flag
+                format: attachment.format as u32,
+                samples: attachment.samples,
+                loadOp: attachment.load as u32,
+                storeOp: attachment.store as u32,
+                stencilLoadOp: attachment.stencil_load as u32,
+                stencilStoreOp: attachment.stencil_store as u32,
+                initialLayout: attachment.initial_layout as u32,
+                finalLayout: attachment.final_layout as u32,
+            }
+        })
+        .collect::<SmallVec<[_; 16]>>();
+
+    let reference = |(offset, img_la): (usize, ImageLayout), aspect_mask| {
+        debug_assert!(offset < attachments.len());
+        vk::AttachmentReference2KHR {
+            sType: vk::STRUCTURE_TYPE_ATTACHMENT_REFERENCE_2_KHR,
+            pNext: ptr::null(),
+            attachment: offset as u32,
+            layout: img_la as u32,
+            aspectMask: aspect_mask,
+        }
+    };
+
+    // Same layout as in `create_render_pass`, with the depth-stencil resolve attachment
+    // reference after the depth-stencil attachment reference.
+    let attachment_references = description
+        .subpass_descs()
+        .flat_map(|pass| {
+            // The aspect mask is only used for input attachments.
+            let color = pass.color_attachments.into_iter().map(|a| reference(a, 0));
+            let input = pass.input_attachments.into_iter().map(|a| {
+                let aspect_mask = match description.attachment_desc(a.0).unwrap().format.ty() {
+                    FormatTy::Depth => vk::IMAGE_ASPECT_DEPTH_BIT,
+                    FormatTy::Stencil => vk::IMAGE_ASPECT_STENCIL_BIT,
+                    FormatTy::DepthStencil => {
+                        vk::IMAGE_ASPECT_DEPTH_BIT | vk::IMAGE_ASPECT_STENCIL_BIT
                     },
-                }
+                    _ => vk::IMAGE_ASPECT_COLOR_BIT,
+                };
+                reference(a, aspect_mask)
+            });
+            let resolve = pass.resolve_attachments.into_iter().map(|a| reference(a, 0));
+            let depthstencil = pass.depth_stencil.into_iter().map(|a| reference(a, 0));
+            let depthstencil_resolve = pass.depth_stencil_resolve
+                .into_iter()
+                .map(|a| reference(a, 0));
+
+            color
+                .chain(input)
+                .chain(resolve)
+                .chain(depthstencil)
+                .chain(depthstencil_resolve)
+                .collect::<SmallVec<[_; 8]>>()
+        })
+        .collect::<SmallVec<[_; 16]>>();
+
+    let preserve_attachments_references = description
+        .subpass_descs()
+        .flat_map(|pass| {
+                      pass.preserve_attachments
+                          .into_iter()
+                          .map(|offset| offset as u32)
+                  })
+        .collect::<SmallVec<[_; 16]>>();
+
+    // The structs that describe how depth-stencil attachments are resolved are chained to the
+    // subpass descriptions, so they must be allocated in advance as well.
+    let depth_stencil_resolves = {
+        let mut ref_index = 0usize;
+        description
+            .subpass_descs()
+            .map(|pass| {
+                ref_index += pass.color_attachments.len() + pass.input_attachments.len() +
+                    pass.resolve_attachments.len() +
+                    pass.depth_stencil.iter().count();
+
+                pass.depth_stencil_resolve.map(|_| {
+                    let resolve = attachment_references.as_ptr().add(ref_index);
+                    ref_index += 1;
+                    vk::SubpassDescriptionDepthStencilResolveKHR {
+                        sType: vk::STRUCTURE_TYPE_SUBPASS_DESCRIPTION_DEPTH_STENCIL_RESOLVE_KHR,
+                        pNext: ptr::null(),
+                        depthResolveMode: pass.depth_resolve_mode
+                            .map(|m| m as u32)
+                            .unwrap_or(vk::RESOLVE_MODE_NONE_KHR),
+                        stencilResolveMode: pass.stencil_resolve_mode
+                            .map(|m| m as u32)
+                            .unwrap_or(vk::RESOLVE_MODE_NONE_KHR),
+                        pDepthStencilResolveAttachment: resolve,
+                    }
+                })
             })
-            .collect::<SmallVec<[_; 16]>>();
+            .collect::<SmallVec<[_; 16]>>()
+    };
+
+    let passes = {
+        let mut ref_index = 0usize;
+        let mut preserve_ref_index = 0usize;
+        let mut out: SmallVec<[_; 16]> = SmallVec::new();
+
+        for (pass, depth_stencil_resolve) in
+            description.subpass_descs().zip(depth_stencil_resolves.iter())
+        {
+            let color_attachments = attachment_references.as_ptr().add(ref_index);
+            ref_index += pass.color_attachments.len();
+            let input_attachments = attachment_references.as_ptr().add(ref_index);
+            ref_index += pass.input_attachments.len();
+            let resolve_attachments = attachment_references.as_ptr().add(ref_index);
+            ref_index += pass.resolve_attachments.len();
+            let depth_stencil = if pass.depth_stencil.is_some() {
+                let a = attachment_references.as_ptr().add(ref_index);
+                ref_index += 1;
+                a
+            } else {
+                ptr::null()
+            };
+            if pass.depth_stencil_resolve.is_some() {
+                ref_index += 1;
+            }
 
-        let render_pass = unsafe {
-            let infos = vk::RenderPassCreateInfo {
-                sType: vk::STRUCTURE_TYPE_RENDER_PASS_CREATE_INFO,
+            let preserve_attachments = preserve_attachments_references
+                .as_ptr()
+                .add(preserve_ref_index);
+            preserve_ref_index += pass.preserve_attachments.len();
+
+            out.push(vk::SubpassDescription2KHR {
+                         sType: vk::STRUCTURE_TYPE_SUBPASS_DESCRIPTION_2_KHR,
+                         pNext: depth_stencil_resolve
+                             .as_ref()
+                             .map(|r| r as *const _ as *const _)
+                             .unwrap_or(ptr::null()),
+                         flags: 0, // reserved
+                         pipelineBindPoint: vk::PIPELINE_BIND_POINT_GRAPHICS,
+                         viewMask: pass.view_mask,
+                         inputAttachmentCount: pass.input_attachments.len() as u32,
+                         pInputAttachments: if pass.input_attachments.is_empty() {
+                             ptr::null()
+                         } else {
+                             input_attachments
+                         },
+                         colorAttachmentCount: pass.color_attachments.len() as u32,
+                         pColorAttachments: if pass.color_attachments.is_empty() {
+                             ptr::null()
+                         } else {
+                             color_attachments
+                         },
+                         pResolveAttachments: if pass.resolve_attachments.is_empty() {
+                             ptr::null()
+                         } else {
+                             resolve_attachments
+                         },
+                         pDepthStencilAttachment: depth_stencil,
+                         preserveAttachmentCount: pass.preserve_attachments.len() as u32,
+                         pPreserveAttachments: if pass.preserve_attachments.is_empty() {
+                             ptr::null()
+                         } else {
+                             preserve_attachments
+                         },
+                     });
+        }
+
+        assert!(!out.is_empty());
+        // If these assertions fails, there's a serious bug in the code above ^.
+        debug_assert!(ref_index == attachment_references.len());
+        debug_assert!(preserve_ref_index == preserve_attachments_references.len());
+
+        out
+    };
+
+    let dependencies = description
+        .dependency_descs()
+        .map(|dependency| {
+            debug_assert!(dependency.source_subpass as u32 == vk::SUBPASS_EXTERNAL || dependency.source_subpass < passes.len());
+            debug_assert!(dependency.destination_subpass as u32 == vk::SUBPASS_EXTERNAL || dependency.destination_subpass < passes.len());
+
+            vk::SubpassDependency2KHR {
+                sType: vk::STRUCTURE_TYPE_SUBPASS_DEPENDENCY_2_KHR,
                 pNext: ptr::null(),
-                flags: 0, // reserved
-                attachmentCount: attachments.len() as u32,
-                pAttachments: if attachments.is_empty() {
-                    ptr::null()
-                } else {
-                    attachments.as_ptr()
-                },
-                subpassCount: passes.len() as u32,
-                pSubpasses: if passes.is_empty() {
-                    ptr::null()
-                } else {
-                    passes.as_ptr()
-                },
-                dependencyCount: dependencies.len() as u32,
-                pDependencies: if dependencies.is_empty() {
-                    ptr::null()
-                } else {
-                    dependencies.as_ptr()
+                srcSubpass: dependency.source_subpass as u32,
+                dstSubpass: dependency.destination_subpass as u32,
+                srcStageMask: dependency.source_stages.into_vulkan_bits(),
+                dstStageMask: dependency.destination_stages.into_vulkan_bits(),
+                srcAccessMask: dependency.source_access.into_vulkan_bits(),
+                dstAccessMask: dependency.destination_access.into_vulkan_bits(),
+                dependencyFlags: {
+                    let mut flags = 0;
+                    if dependency.by_region {
+                        flags |= vk::DEPENDENCY_BY_REGION_BIT;
+                    }
+                    // Required by Vulkan for self-dependencies of multiview subpasses.
+                    if multiview && dependency.source_subpass == dependency.destination_subpass {
+                        flags |= vk::DEPENDENCY_VIEW_LOCAL_BIT_KHR;
+                    }
+                    flags
                 },
-            };
+                viewOffset: 0,
+            }
+        })
+        .collect::<SmallVec<[_; 16]>>();
+
+    let infos = vk::RenderPassCreateInfo2KHR {
+        sType: vk::STRUCTURE_TYPE_RENDER_PASS_CREATE_INFO_2_KHR,
+        pNext: ptr::null(),
+        flags: 0, // reserved
+        attachmentCount: attachments.len() as u32,
+        pAttachments: if attachments.is_empty() {
+            ptr::null()
+        } else {
+            attachments.as_ptr()
+        },
+        subpassCount: passes.len() as u32,
+        pSubpasses: if passes.is_empty() {
+            ptr::null()
+        } else {
+            passes.as_ptr()
+        },
+        dependencyCount: dependencies.len() as u32,
+        pDependencies: if dependencies.is_empty() {
+            ptr::null()
+        } else {
+            dependencies.as_ptr()
+        },
+        correlatedViewMaskCount: correlation_masks.len() as u32,
+        pCorrelatedViewMasks: if correlation_masks.is_empty() {
+            ptr::null()
+        } else {
+            correlation_masks.as_ptr()
+        },
+    };
+
+    let mut output = MaybeUninit::uninit();
+    check_errors(vk.CreateRenderPass2KHR(device.internal_object(),
+                                         &infos,
+                                         ptr::null(),
+                                         output.as_mut_ptr()))?;
+    Ok(output.assume_init())
+}
 
-            let mut output = MaybeUninit::uninit();
-            check_errors(vk.CreateRenderPass(device.internal_object(),
-                                             &infos,
-                                             ptr::null(),
-                                             output.as_mut_ptr()))?;
-            output.assume_init()
-        };
+// Checks that the device supports resolving a depth-stencil attachment whose format is of type
+// `ty` with the given modes.
+fn check_depth_stencil_resolve_modes(device: &Device, ty: FormatTy,
+                                     depth_mode: Option<ResolveMode>,
+                                     stencil_mode: Option<ResolveMode>)
+                                     -> Result<(), RenderPassCreationError> {
+    // The mode of an aspect that the format doesn't have is ignored.
+    let (depth_mode, stencil_mode) = match ty {
+        FormatTy::Depth => (depth_mode, None),
+        FormatTy::Stencil => (None, stencil_mode),
+        FormatTy::DepthStencil => (depth_mode, stencil_mode),
+        _ => return Err(RenderPassCreationError::InvalidDepthStencilResolve),
+    };
+
+    if depth_mode.is_none() && stencil_mode.is_none() {
+        return Err(RenderPassCreationError::InvalidDepthStencilResolve);
+    }
 
-        Ok(RenderPass {
-               device: device.clone(),
-               render_pass: render_pass,
-               desc: description,
-               granularity: Mutex::new(None),
-           })
+    // If the properties couldn't be queried, only rely on what Vulkan guarantees.
+    let properties = device
+        .physical_device()
+        .depth_stencil_resolve_properties()
+        .cloned()
+        .unwrap_or(DepthStencilResolveProperties {
+                       supported_depth_resolve_modes: ResolveModes {
+                           sample_zero: true,
+                           ..ResolveModes::default()
+                       },
+                       supported_stencil_resolve_modes: ResolveModes {
+                           sample_zero: true,
+                           ..ResolveModes::default()
+                       },
+                       independent_resolve_none: false,
+                       independent_resolve: false,
+                   });
+
+    if let Some(mode) = depth_mode {
+        if !properties.supported_depth_resolve_modes.supports(mode) {
+            return Err(RenderPassCreationError::UnsupportedResolveMode);
+        }
+    }
+    if let Some(mode) = stencil_mode {
+        if !properties.supported_stencil_resolve_modes.supports(mode) {
+            return Err(RenderPassCreationError::UnsupportedResolveMode);
+        }
     }
+
+    if ty == FormatTy::DepthStencil && depth_mode != stencil_mode && !properties.independent_resolve &&
+        !(properties.independent_resolve_none && (depth_mode.is_none() || stencil_mode.is_none()))
+    {
+        return Err(RenderPassCreationError::IncompatibleResolveModes);
+    }
+
+    Ok(())
 }
 
 impl RenderPass<EmptySinglePassRenderPassDesc> {
@@ -441,6 +879,11 @@ unsafe impl<D> RenderPassDesc for RenderPass<D>
     fn dependency_desc(&self, num: usize) -> Option<PassDependencyDescription> {
         self.desc.dependency_desc(num)
     }
+
+    #[inline]
+    fn correlation_masks(&self) -> Vec<u32> {
+        self.desc.correlation_masks()
+    }
 }
 
 unsafe impl<C, D> RenderPassDescClearValues<C> for RenderPass<D>
@@ -512,6 +955,26 @@ pub enum RenderPassCreationError {
     OomError(OomError),
     /// The maximum number of color attachments has been exceeded.
     ColorAttachmentsLimitExceeded,
+    /// Some subpasses have a non-zero view mask while others don't.
+    InconsistentViewMasks,
+    /// A subpass has a non-zero view mask but the `multiview` feature isn't enabled.
+    MultiviewFeatureNotEnabled,
+    /// A view mask contains a view whose index is greater than or equal to the maximum number
+    /// of views supported by the device.
+    MultiviewViewCountLimitExceeded,
+    /// The correlation masks overlap, or were provided while multiview is not used.
+    InvalidCorrelationMasks,
+    /// A subpass resolves its depth-stencil attachment, but neither Vulkan 1.2 nor the
+    /// `khr_depth_stencil_resolve` extension is enabled.
+    DepthStencilResolveExtensionNotEnabled,
+    /// The depth-stencil resolve attachment of a subpass is invalid. The subpass must have a
+    /// multisampled depth-stencil attachment, the resolve attachment must have one sample and
+    /// the same format, and at least one of the aspects must be resolved.
+    InvalidDepthStencilResolve,
+    /// The device doesn't support the depth or stencil resolve mode of a subpass.
+    UnsupportedResolveMode,
+    /// The device doesn't support resolving the depth and stencil aspects with different modes.
+    IncompatibleResolveModes,
 }
 
 impl error::Error for RenderPassCreationError {
@@ -522,6 +985,32 @@ impl error::Error for RenderPassCreationError {
             RenderPassCreationError::ColorAttachmentsLimitExceeded => {
                 "the maximum number of color attachments has been exceeded"
             },
+            RenderPassCreationError::InconsistentViewMasks => {
+                "some subpasses have a non-zero view mask while others don't"
+            },
+            RenderPassCreationError::MultiviewFeatureNotEnabled => {
+                "a subpass has a non-zero view mask but the `multiview` feature isn't enabled"
+            },
+            RenderPassCreationError::MultiviewViewCountLimitExceeded => {
+                "a view mask exceeds the maximum number of views supported by the device"
+            },
+            RenderPassCreationError::InvalidCorrelationMasks => {
+                "the correlation masks overlap or were provided without using multiview"
+            },
+            RenderPassCreationError::DepthStencilResolveExtensionNotEnabled => {
+                "a subpass resolves its depth-stencil attachment but the \
+                 `khr_depth_stencil_resolve` extension isn't enabled"
+            },
+            RenderPassCreationError::InvalidDepthStencilResolve => {
+                "the depth-stencil resolve attachment of a subpass is invalid"
+            },
+            RenderPassCreationError::UnsupportedResolveMode => {
+                "the device doesn't support the depth or stencil resolve mode of a subpass"
+            },
+            RenderPassCreationError::IncompatibleResolveModes => {
+                "the device doesn't support resolving the depth and stencil aspects with \
+                 different modes"
+            },
         }
     }
 
@@ -566,8 +1055,12 @@ impl From<Error> for RenderPassCreationError {
 #[cfg(test)]
 mod tests {
     use format::Format;
+    use format::FormatTy;
     use framebuffer::RenderPass;
     use framebuffer::RenderPassCreationError;
+    use framebuffer::ResolveMode;
+    use super::check_depth_stencil_resolve_modes;
+    use version::Version;
 
     #[test]
     fn empty() {
@@ -629,4 +1122,126 @@ mod tests {
         assert_ne!(granularity[0], 0);
         assert_ne!(granularity[1], 0);
     }
+
+    #[test]
+    fn multiview_feature_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let rp = single_pass_renderpass! {
+            device.clone(),
+            attachments: {
+                a: { load: Clear, store: DontCare, format: Format::R8G8B8A8Unorm, samples: 1, }
+            },
+            pass: {
+                color: [a],
+                depth_stencil: {},
+                view_mask: 0b11,
+            }
+        };
+
+        match rp {
+            Err(RenderPassCreationError::MultiviewFeatureNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn inconsistent_view_masks() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let rp = ordered_passes_renderpass! {
+            device.clone(),
+            attachments: {
+                a: { load: Clear, store: DontCare, format: Format::R8G8B8A8Unorm, samples: 1, },
+                b: { load: Clear, store: DontCare, format: Format::R8G8B8A8Unorm, samples: 1, }
+            },
+            passes: [
+                { color: [a], depth_stencil: {}, input: [], view_mask: 0b1 },
+                { color: [b], depth_stencil: {}, input: [] }
+            ]
+        };
+
+        match rp {
+            Err(RenderPassCreationError::InconsistentViewMasks) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn correlation_masks_without_multiview() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let rp = single_pass_renderpass! {
+            device.clone(),
+            attachments: {
+                a: { load: Clear, store: DontCare, format: Format::R8G8B8A8Unorm, samples: 1, }
+            },
+            pass: {
+                color: [a],
+                depth_stencil: {}
+            },
+            correlation_masks: [0b11]
+        };
+
+        match rp {
+            Err(RenderPassCreationError::InvalidCorrelationMasks) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn depth_stencil_resolve_extension_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        if device.api_version() >= Version::V1_2 {
+            return; // test ignored
+        }
+
+        let rp = single_pass_renderpass! {
+            device.clone(),
+            attachments: {
+                a: { load: Clear, store: DontCare, format: Format::D16Unorm, samples: 4, },
+                b: { load: DontCare, store: Store, format: Format::D16Unorm, samples: 1, }
+            },
+            pass: {
+                color: [],
+                depth_stencil: {a},
+                depth_stencil_resolve: {b}
+            }
+        };
+
+        match rp {
+            Err(RenderPassCreationError::DepthStencilResolveExtensionNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn invalid_depth_stencil_resolve_modes() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        match check_depth_stencil_resolve_modes(&device,
+                                                FormatTy::Float,
+                                                Some(ResolveMode::SampleZero),
+                                                None) {
+            Err(RenderPassCreationError::InvalidDepthStencilResolve) => (),
+            _ => panic!(),
+        }
+
+        // The stencil mode is ignored for depth-only formats.
+        match check_depth_stencil_resolve_modes(&device,
+                                                FormatTy::Depth,
+                                                None,
+                                                Some(ResolveMode::SampleZero)) {
+            Err(RenderPassCreationError::InvalidDepthStencilResolve) => (),
+            _ => panic!(),
+        }
+
+        // Resolving with sample zero is always supported.
+        check_depth_stencil_resolve_modes(&device,
+                                          FormatTy::Depth,
+                                          Some(ResolveMode::SampleZero),
+                                          None)
+            .unwrap();
+    }
 }
@@ -23,6 +23,8 @@ use std::mem::MaybeUninit;
 use std::ops::Range;
 use std::ptr;
 use std::sync::Arc;
+use std::sync::atomic::AtomicU32;
+use std::sync::atomic::Ordering;
 
 use device::Device;
 use format::Format;
@@ -40,6 +42,7 @@ use Error;
 use OomError;
 use VulkanObject;
 use check_errors;
+use version::Version;
 use vk;
 
 /// A storage for pixels or arbitrary data.
@@ -71,6 +74,12 @@ pub struct UnsafeImage {
     // `vkDestroyImage` is called only if `needs_destruction` is true.
     needs_destruction: bool,
     preinitialized_layout: bool,
+
+    // True if the image was created with `Sharing::Exclusive`.
+    exclusive_sharing: bool,
+    // Queue family that owns the image, or `QUEUE_FAMILY_IGNORED` if the ownership of the image
+    // has never been transferred.
+    queue_family_owner: AtomicU32,
 }
 
 impl UnsafeImage {
@@ -156,7 +165,7 @@ impl UnsafeImage {
             {
                 return Err(ImageCreationError::UnsupportedUsage);
             }
-            if device.loaded_extensions().khr_maintenance1 {
+            if device.api_version() >= Version::V1_1 || device.loaded_extensions().khr_maintenance1 {
                 if usage.transfer_source &&
                     (features & vk::FORMAT_FEATURE_TRANSFER_SRC_BIT_KHR == 0)
                 {
@@ -503,14 +512,18 @@ impl UnsafeImage {
             output.assume_init()
         };
 
-        let mem_reqs = if device.loaded_extensions().khr_get_memory_requirements2 {
+        let mem_reqs = if device.api_version() >= Version::V1_1 ||
+            device.loaded_extensions().khr_get_memory_requirements2
+        {
             let infos = vk::ImageMemoryRequirementsInfo2KHR {
                 sType: vk::STRUCTURE_TYPE_IMAGE_MEMORY_REQUIREMENTS_INFO_2_KHR,
                 pNext: ptr::null_mut(),
                 image: image,
             };
 
-            let mut output2 = if device.loaded_extensions().khr_dedicated_allocation {
+            let mut output2 = if device.api_version() >= Version::V1_1 ||
+                device.loaded_extensions().khr_dedicated_allocation
+            {
                 Some(vk::MemoryDedicatedRequirementsKHR {
                          sType: vk::STRUCTURE_TYPE_MEMORY_DEDICATED_REQUIREMENTS_KHR,
                          pNext: ptr::null(),
@@ -559,6 +572,8 @@ impl UnsafeImage {
             format_features: format_features,
             needs_destruction: true,
             preinitialized_layout,
+            exclusive_sharing: sh_mode == vk::SHARING_MODE_EXCLUSIVE,
+            queue_family_owner: AtomicU32::new(vk::QUEUE_FAMILY_IGNORED),
         };
 
         Ok((image, mem_reqs))
@@ -589,6 +604,9 @@ impl UnsafeImage {
             format_features: output.assume_init().optimalTilingFeatures,
             needs_destruction: false, // TODO: pass as parameter
             preinitialized_layout: false, // TODO: Maybe this should be passed in?
+            // The ownership of images created from a raw handle isn't tracked.
+            exclusive_sharing: false,
+            queue_family_owner: AtomicU32::new(vk::QUEUE_FAMILY_IGNORED),
         }
     }
 
@@ -795,6 +813,42 @@ impl UnsafeImage {
     pub fn preinitialized_layout(&self) -> bool {
         self.preinitialized_layout
     }
+
+    /// Returns true if the image was created with `Sharing::Exclusive`.
+    #[inline]
+    pub fn exclusive_sharing(&self) -> bool {
+        self.exclusive_sharing
+    }
+
+    /// Returns the queue family that currently owns the image.
+    ///
+    /// Returns `None` if the image was created with `Sharing::Concurrent`, or if its ownership
+    /// has never been transferred between queue families. In that situation the image can be
+    /// used from any queue family.
+    #[inline]
+    pub fn queue_family_owner(&self) -> Option<u32> {
+        if !self.exclusive_sharing {
+            return None;
+        }
+
+        match self.queue_family_owner.load(Ordering::SeqCst) {
+            vk::QUEUE_FAMILY_IGNORED => None,
+            family => Some(family),
+        }
+    }
+
+    /// Records that the ownership of the image has been transferred to the given queue family.
+    /// Has no effect if the image was created with `Sharing::Concurrent`.
+    ///
+    /// # Safety
+    ///
+    /// A command that releases the image to `queue_family` must have been submitted.
+    #[inline]
+    pub unsafe fn set_queue_family_owner(&self, queue_family: u32) {
+        if self.exclusive_sharing {
+            self.queue_family_owner.store(queue_family, Ordering::SeqCst);
+        }
+    }
 }
 
 unsafe impl VulkanObject for UnsafeImage {
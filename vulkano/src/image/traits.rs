@@ -137,6 +137,21 @@ pub unsafe trait ImageAccess {
     /// this image one after the other.
     fn final_layout_requirement(&self) -> ImageLayout;
 
+    /// Returns the queue family that currently owns the image, or `None` if the image can be used
+    /// from any queue family.
+    ///
+    /// The ownership of an image created with `Sharing::Exclusive` is tracked once it has been
+    /// released to another queue family with `release_image_ownership`. From then on, it can only
+    /// be used from the queue family it was released to.
+    ///
+    /// The owner changes once the command buffer that releases the image has been executed. Until
+    /// then, the image can only be used from the destination family by a submission that comes
+    /// after this command buffer in the same chain of futures.
+    #[inline]
+    fn queue_family_owner(&self) -> Option<u32> {
+        self.inner().image.queue_family_owner()
+    }
+
     /// Wraps around this `ImageAccess` and returns an identical `ImageAccess` but whose initial
     /// layout requirement is either `Undefined` or `Preinitialized`.
     #[inline]
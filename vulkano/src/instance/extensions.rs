@@ -17,14 +17,56 @@ use std::str;
 use check_errors;
 use instance::loader;
 use instance::loader::LoadingError;
+use extensions::ExtensionRestriction::*;
+use extensions::ExtensionRestrictionError;
 use extensions::SupportedExtensionsError;
+use version::Version;
 use vk;
 
 macro_rules! instance_extensions {
-    ($sname:ident, $rawname:ident, $($ext:ident => $s:expr,)*) => (
+    (
+        $sname:ident, $rawname:ident,
+        $(
+            $ext:ident => $s:tt {
+                promoted_to: $core:expr,
+                requires: [$($req:ident),*],
+            },
+        )*
+    ) => (
         extensions! {
             $sname, $rawname,
-            $( $ext => $s,)*
+            $(
+                $ext => $s {
+                    promoted_to: $core,
+                    requires: [$($req),*],
+                },
+            )*
+        }
+
+        impl $sname {
+            /// Checks that the extensions of this list can be enabled on an instance that uses
+            /// `api_version` of Vulkan.
+            ///
+            /// Each extension that the extensions of the list require must be in the list as
+            /// well, unless it has been promoted to `api_version`. `with_dependencies` can be used
+            /// to add the missing ones.
+            pub fn check_requirements(&self, api_version: Version)
+                                      -> Result<(), ExtensionRestrictionError> {
+                let available = self.union(&$sname::promoted_to_core(api_version));
+                $(
+                    if self.$ext {
+                        let required = $sname { $ext: true, .. $sname::none() }
+                            .required_extensions();
+                        if let Some(&missing) = required.difference(&available).names().first() {
+                            return Err(ExtensionRestrictionError {
+                                extension: str::from_utf8($s).unwrap(),
+                                restriction: RequiresInstanceExtension(missing),
+                            });
+                        }
+                    }
+                )*
+                Ok(())
+            }
         }
 
         impl $rawname {
@@ -142,25 +184,7 @@ macro_rules! instance_extensions {
     );
 }
 
-instance_extensions! {
-    InstanceExtensions,
-    RawInstanceExtensions,
-    khr_surface => b"VK_KHR_surface",
-    khr_display => b"VK_KHR_display",
-    khr_xlib_surface => b"VK_KHR_xlib_surface",
-    khr_xcb_surface => b"VK_KHR_xcb_surface",
-    khr_wayland_surface => b"VK_KHR_wayland_surface",
-    khr_android_surface => b"VK_KHR_android_surface",
-    khr_win32_surface => b"VK_KHR_win32_surface",
-    ext_debug_utils => b"VK_EXT_debug_utils",
-    mvk_ios_surface => b"VK_MVK_ios_surface",
-    mvk_macos_surface => b"VK_MVK_macos_surface",
-    mvk_moltenvk => b"VK_MVK_moltenvk",     // TODO: confirm that it's an instance extension
-    nn_vi_surface => b"VK_NN_vi_surface",
-    ext_swapchain_colorspace => b"VK_EXT_swapchain_colorspace",
-    khr_get_physical_device_properties2 => b"VK_KHR_get_physical_device_properties2",
-    khr_get_surface_capabilities2 => b"VK_KHR_get_surface_capabilities2",
-}
+include!("../autogen/instance_extensions.rs");
 
 /// This helper type can only be instantiated inside this module.
 /// See `*Extensions::_unbuildable`.
@@ -170,11 +194,32 @@ pub struct Unbuildable(());
 
 #[cfg(test)]
 mod tests {
+    use extensions::ExtensionRestriction;
     use instance::{InstanceExtensions, RawInstanceExtensions};
+    use version::Version;
 
     #[test]
     fn empty_extensions() {
         let i: RawInstanceExtensions = (&InstanceExtensions::none()).into();
         assert!(i.iter().next().is_none());
     }
+
+    #[test]
+    fn check_requirements() {
+        let extensions = InstanceExtensions {
+            khr_external_memory_capabilities: true,
+            ..InstanceExtensions::none()
+        };
+
+        let error = extensions.check_requirements(Version::V1_0).unwrap_err();
+        assert_eq!(error.extension, "VK_KHR_external_memory_capabilities");
+        assert_eq!(error.restriction,
+                   ExtensionRestriction::RequiresInstanceExtension(
+                       "VK_KHR_get_physical_device_properties2"));
+        assert!(extensions.check_requirements(Version::V1_1).is_ok());
+
+        let extensions = extensions.with_dependencies(Version::V1_0);
+        assert!(extensions.khr_get_physical_device_properties2);
+        assert!(extensions.check_requirements(Version::V1_0).is_ok());
+    }
 }
@@ -9,6 +9,7 @@
 
 use smallvec::SmallVec;
 use std::borrow::Cow;
+use std::cmp;
 use std::error;
 use std::ffi::CStr;
 use std::ffi::CString;
@@ -26,16 +27,26 @@ use Error;
 use OomError;
 use VulkanObject;
 use check_errors;
+use extensions;
+use extensions::ExtensionRestrictionError;
 use instance::limits::Limits;
 use instance::loader;
 use instance::loader::FunctionPointers;
 use instance::loader::Loader;
 use instance::loader::LoadingError;
+use instance::properties::DepthStencilResolveProperties;
+use instance::properties::DescriptorIndexingLimits;
+use instance::properties::DriverProperties;
+use instance::properties::ExtendedProperties;
+use instance::properties::IdProperties;
+use instance::properties::PropertiesFfi;
+use instance::properties::SubgroupProperties;
 use vk;
 
 use instance::{InstanceExtensions, RawInstanceExtensions};
 use version::Version;
 use features::Features;
+use features::FeaturesFfi;
 
 /// An instance of a Vulkan context. This is the main object that should be created by an
 /// application before everything else.
@@ -99,6 +110,7 @@ pub struct Instance {
     vk: vk::InstancePointers,
     extensions: RawInstanceExtensions,
     layers: SmallVec<[CString; 16]>,
+    api_version: Version,
     function_pointers: OwnedOrRef<FunctionPointers<Box<dyn Loader + Send + Sync>>>,
 }
 
@@ -126,6 +138,10 @@ impl Instance {
     /// };
     /// ```
     ///
+    /// The dependencies between the extensions that are known to vulkano are checked with
+    /// `InstanceExtensions::check_requirements`. Use `InstanceExtensions::with_dependencies` to
+    /// add the extensions that are missing.
+    ///
     /// # Panic
     ///
     /// - Panics if the version numbers passed in `ApplicationInfo` are too large can't be
@@ -180,6 +196,24 @@ impl Instance {
             None => Some(&def),
         };
 
+        // The version that is actually used is the lowest of the version requested by the
+        // application and the version supported by the loader. Vulkan 1.0 loaders refuse any
+        // other version than 1.0, while later loaders accept any version.
+        let max_api_version = app_infos
+            .and_then(|a| a.api_version)
+            .unwrap_or(Version::V1_2);
+        let loader_api_version = function_pointers.api_version()?;
+        let api_version = cmp::min(max_api_version, loader_api_version);
+        let requested_api_version = if loader_api_version >= Version::V1_1 {
+            max_api_version
+        } else {
+            Version::V1_0
+        };
+
+        // Checking the dependencies between extensions, since a missing one is often reported by
+        // the implementation as an unrelated error, or not reported at all.
+        InstanceExtensions::from(&extensions).check_requirements(api_version)?;
+
         // Building the CStrings from the `str`s within `app_infos`.
         // They need to be created ahead of time, since we pass pointers to them.
         let app_infos_strings = if let Some(app_infos) = app_infos {
@@ -222,11 +256,7 @@ impl Instance {
                     .engine_version
                     .map(|v| v.into_vulkan_version())
                     .unwrap_or(0),
-                apiVersion: Version {
-                    major: 1,
-                    minor: 1,
-                    patch: 0,
-                }.into_vulkan_version(), // TODO:
+                apiVersion: requested_api_version.into_vulkan_version(),
             })
 
         } else {
@@ -288,9 +318,11 @@ impl Instance {
         let vk_khr_get_physical_device_properties2 = CString::new(b"VK_KHR_get_physical_device_properties2".to_vec()).unwrap();
 
         // Getting the properties of all physical devices.
-        // If possible, we use VK_KHR_get_physical_device_properties2.
-        let physical_devices = if extensions.iter().any(|v| *v == vk_khr_get_physical_device_properties2) {
-            Instance::init_physical_devices2(&vk, physical_devices, &extensions)
+        // If possible, we use VK_KHR_get_physical_device_properties2 or its Vulkan 1.1 equivalent.
+        let physical_devices = if api_version >= Version::V1_1 ||
+            extensions.iter().any(|v| *v == vk_khr_get_physical_device_properties2)
+        {
+            Instance::init_physical_devices2(&vk, physical_devices, api_version, &extensions)
         } else {
             Instance::init_physical_devices(&vk, physical_devices)
         };
@@ -302,6 +334,7 @@ impl Instance {
                         vk: vk,
                         extensions: extensions,
                         layers: layers,
+                        api_version,
                         function_pointers: function_pointers,
                     }))
     }
@@ -345,17 +378,19 @@ impl Instance {
                             properties: properties,
                             memory: memory,
                             queue_families: queue_families,
-                            available_features: Features::from_vulkan_features(available_features),
+                            available_features: Features::from_vulkan_features(available_features,
+                                                                               &FeaturesFfi::for_query(|_, _| false)),
+                            extended_properties: ExtendedProperties::default(),
                         });
         }
         output
     }
 
     /// Initialize all physical devices, but use VK_KHR_get_physical_device_properties2
-    /// TODO: Query extension-specific physical device properties, once a new instance extension is supported.
     fn init_physical_devices2(vk: &vk::InstancePointers,
                               physical_devices: Vec<vk::PhysicalDevice>,
-                              extensions: &RawInstanceExtensions)
+                              api_version: Version,
+                              instance_extensions: &RawInstanceExtensions)
                               -> Vec<PhysicalDeviceInfos> {
         let mut output = Vec::with_capacity(physical_devices.len());
 
@@ -371,6 +406,28 @@ impl Instance {
                 output.properties
             };
 
+            // The properties of an extension can only be queried if the device supports it, or
+            // supports the version of Vulkan the extension was promoted to.
+            let device_api_version = cmp::min(api_version,
+                                              Version::from_vulkan_version(properties.apiVersion));
+            let device_extensions = supported_device_extensions(vk, device);
+
+            let extended_properties = unsafe {
+                let mut properties_chain = PropertiesFfi::for_query(device_api_version, |name| {
+                    device_extensions.iter().any(|ext| ext.as_slice() == name) ||
+                        instance_extensions.iter().any(|ext| ext.as_bytes() == name)
+                });
+
+                let mut output = vk::PhysicalDeviceProperties2KHR {
+                    sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_PROPERTIES_2_KHR,
+                    pNext: properties_chain.head_as_mut_ptr() as *const _,
+                    properties: mem::zeroed(),
+                };
+
+                vk.GetPhysicalDeviceProperties2KHR(device, &mut output);
+                properties_chain.into_properties()
+            };
+
             let queue_families = unsafe {
                 let mut num = 0;
                 vk.GetPhysicalDeviceQueueFamilyProperties2KHR(device, &mut num, ptr::null_mut());
@@ -404,10 +461,15 @@ impl Instance {
                 output.memoryProperties
             };
 
+            let mut features_chain = FeaturesFfi::for_query(|name, core_version| {
+                extensions::is_promoted(core_version, device_api_version) ||
+                    device_extensions.iter().any(|ext| ext.as_slice() == name)
+            });
+
             let available_features: vk::PhysicalDeviceFeatures = unsafe {
                 let mut output = vk::PhysicalDeviceFeatures2KHR {
                     sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_FEATURES_2_KHR,
-                    pNext: ptr::null_mut(),
+                    pNext: features_chain.head_as_mut_ptr() as *const _,
                     features: mem::zeroed(),
                 };
                 vk.GetPhysicalDeviceFeatures2KHR(device, &mut output);
@@ -419,7 +481,9 @@ impl Instance {
                             properties: properties,
                             memory: memory,
                             queue_families: queue_families,
-                            available_features: Features::from_vulkan_features(available_features),
+                            available_features: Features::from_vulkan_features(available_features,
+                                                                               &features_chain),
+                            extended_properties,
                         });
         }
         output
@@ -463,6 +527,15 @@ impl Instance {
         &self.extensions
     }
 
+    /// Returns the version of Vulkan that is used by the instance.
+    ///
+    /// This is the lowest of the version requested in the `ApplicationInfo` and of the version
+    /// supported by the Vulkan loader.
+    #[inline]
+    pub fn api_version(&self) -> Version {
+        self.api_version
+    }
+
     /// Returns the list of layers requested when creating this instance.
     #[doc(hidden)]
     #[inline]
@@ -471,6 +544,37 @@ impl Instance {
     }
 }
 
+/// Returns true if the physical device supports the given device extension.
+///
+/// Used during the initialization of the physical devices, before any `PhysicalDevice` can be
+/// built.
+// Returns the names of the extensions supported by a physical device, or an empty list if
+// they can't be enumerated.
+fn supported_device_extensions(vk: &vk::InstancePointers, device: vk::PhysicalDevice)
+                               -> Vec<Vec<u8>> {
+    unsafe {
+        let mut num = 0;
+        if vk.EnumerateDeviceExtensionProperties(device, ptr::null(), &mut num,
+                                                 ptr::null_mut()) != vk::SUCCESS
+        {
+            return Vec::new();
+        }
+
+        let mut properties: Vec<vk::ExtensionProperties> = Vec::with_capacity(num as usize);
+        if vk.EnumerateDeviceExtensionProperties(device, ptr::null(), &mut num,
+                                                 properties.as_mut_ptr()) != vk::SUCCESS
+        {
+            return Vec::new();
+        }
+        properties.set_len(num as usize);
+
+        properties
+            .iter()
+            .map(|p| CStr::from_ptr(p.extensionName.as_ptr()).to_bytes().to_vec())
+            .collect()
+    }
+}
+
 impl fmt::Debug for Instance {
     #[inline]
     fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
@@ -543,6 +647,12 @@ pub struct ApplicationInfo<'a> {
     pub engine_name: Option<Cow<'a, str>>,
     /// An opaque number that contains the version number of the engine.
     pub engine_version: Option<Version>,
+    /// The highest version of Vulkan that the application is designed to use. If `None`, the
+    /// highest version supported by vulkano is requested.
+    ///
+    /// The version that is used is the lowest of this value and of the version supported by the
+    /// Vulkan implementation. See `Instance::api_version` and `Device::api_version`.
+    pub api_version: Option<Version>,
 }
 
 impl<'a> ApplicationInfo<'a> {
@@ -568,6 +678,7 @@ impl<'a> ApplicationInfo<'a> {
             application_version: Some(version),
             engine_name: None,
             engine_version: None,
+            api_version: None,
         }
     }
 }
@@ -595,6 +706,7 @@ macro_rules! app_info_from_cargo_toml {
             application_version: Some(version),
             engine_name: None,
             engine_version: None,
+            api_version: None,
         }
     }}
 }
@@ -606,6 +718,7 @@ impl<'a> Default for ApplicationInfo<'a> {
             application_version: None,
             engine_name: None,
             engine_version: None,
+            api_version: None,
         }
     }
 }
@@ -623,6 +736,9 @@ pub enum InstanceCreationError {
     LayerNotPresent,
     /// One of the requested extensions is missing.
     ExtensionNotPresent,
+    /// One of the restrictions of the requested extensions isn't met. For example an extension
+    /// requires another extension that wasn't requested.
+    ExtensionRestrictionNotMet(ExtensionRestrictionError),
     /// The version requested is not supported by the implementation.
     // TODO: more info about this once the question of the version has been resolved
     IncompatibleDriver,
@@ -637,6 +753,9 @@ impl error::Error for InstanceCreationError {
             InstanceCreationError::InitializationFailed => "initialization failed",
             InstanceCreationError::LayerNotPresent => "layer not present",
             InstanceCreationError::ExtensionNotPresent => "extension not present",
+            InstanceCreationError::ExtensionRestrictionNotMet(_) => {
+                "a restriction of one of the requested extensions isn't met"
+            },
             InstanceCreationError::IncompatibleDriver => "incompatible driver",
         }
     }
@@ -646,6 +765,7 @@ impl error::Error for InstanceCreationError {
         match *self {
             InstanceCreationError::LoadingError(ref err) => Some(err),
             InstanceCreationError::OomError(ref err) => Some(err),
+            InstanceCreationError::ExtensionRestrictionNotMet(ref err) => Some(err),
             _ => None,
         }
     }
@@ -672,6 +792,13 @@ impl From<LoadingError> for InstanceCreationError {
     }
 }
 
+impl From<ExtensionRestrictionError> for InstanceCreationError {
+    #[inline]
+    fn from(err: ExtensionRestrictionError) -> InstanceCreationError {
+        InstanceCreationError::ExtensionRestrictionNotMet(err)
+    }
+}
+
 impl From<Error> for InstanceCreationError {
     #[inline]
     fn from(err: Error) -> InstanceCreationError {
@@ -693,6 +820,7 @@ struct PhysicalDeviceInfos {
     queue_families: Vec<vk::QueueFamilyProperties>,
     memory: vk::PhysicalDeviceMemoryProperties,
     available_features: Features,
+    extended_properties: ExtendedProperties,
 }
 
 /// Represents one of the available devices on this machine.
@@ -930,6 +1058,88 @@ impl<'a> PhysicalDevice<'a> {
         Limits::from_vk_limits(&self.infos().properties.limits)
     }
 
+    /// Returns the properties of the subgroups of this device.
+    ///
+    /// Returns `None` if the device doesn't support Vulkan 1.1, or if neither Vulkan 1.1 nor the
+    /// `khr_get_physical_device_properties2` extension are enabled on the instance.
+    #[inline]
+    pub fn subgroup_properties(&self) -> Option<&'a SubgroupProperties> {
+        self.infos().extended_properties.subgroup.as_ref()
+    }
+
+    /// Returns information about the driver of this device.
+    ///
+    /// Returns `None` if the device supports neither Vulkan 1.2 nor the `khr_driver_properties`
+    /// extension, or if the properties couldn't be queried through the instance.
+    #[inline]
+    pub fn driver_properties(&self) -> Option<&'a DriverProperties> {
+        self.infos().extended_properties.driver.as_ref()
+    }
+
+    /// Returns the UUIDs and LUID of this device and of its driver.
+    ///
+    /// Returns `None` if neither Vulkan 1.1 nor the `khr_external_memory_capabilities`
+    /// extension are available.
+    #[inline]
+    pub fn id_properties(&self) -> Option<&'a IdProperties> {
+        self.infos().extended_properties.id.as_ref()
+    }
+
+    /// Returns the maximum size of a single memory allocation, in bytes.
+    ///
+    /// Allocations larger than this may still succeed, but their behavior isn't guaranteed.
+    /// Returns `None` if the device supports neither Vulkan 1.1 nor the `khr_maintenance3`
+    /// extension, or if the properties couldn't be queried through the instance.
+    #[inline]
+    pub fn max_memory_allocation_size(&self) -> Option<u64> {
+        self.infos().extended_properties.max_memory_allocation_size
+    }
+
+    /// Returns the maximum number of descriptors in a single descriptor set.
+    ///
+    /// Returns `None` under the same conditions as `max_memory_allocation_size`.
+    #[inline]
+    pub fn max_per_set_descriptors(&self) -> Option<u32> {
+        self.infos().extended_properties.max_per_set_descriptors
+    }
+
+    /// Returns the maximum number of views in a subpass that uses multiview.
+    ///
+    /// Returns `None` if the device supports neither Vulkan 1.1 nor the `khr_multiview`
+    /// extension, or if the properties couldn't be queried through the instance.
+    #[inline]
+    pub fn max_multiview_view_count(&self) -> Option<u32> {
+        self.infos().extended_properties.max_multiview_view_count
+    }
+
+    /// Returns the maximum instance index of draw commands in a subpass that uses multiview.
+    ///
+    /// Returns `None` under the same conditions as `max_multiview_view_count`.
+    #[inline]
+    pub fn max_multiview_instance_index(&self) -> Option<u32> {
+        self.infos().extended_properties.max_multiview_instance_index
+    }
+
+    /// Returns the limits of this device related to descriptor indexing.
+    ///
+    /// Returns `None` if the device supports neither Vulkan 1.2 nor the
+    /// `ext_descriptor_indexing` extension, or if the properties couldn't be queried through the
+    /// instance.
+    #[inline]
+    pub fn descriptor_indexing_limits(&self) -> Option<&'a DescriptorIndexingLimits> {
+        self.infos().extended_properties.descriptor_indexing.as_ref()
+    }
+
+    /// Returns the modes that this device supports for resolving depth-stencil attachments.
+    ///
+    /// Returns `None` if the device supports neither Vulkan 1.2 nor the
+    /// `khr_depth_stencil_resolve` extension, or if the properties couldn't be queried through
+    /// the instance.
+    #[inline]
+    pub fn depth_stencil_resolve_properties(&self) -> Option<&'a DepthStencilResolveProperties> {
+        self.infos().extended_properties.depth_stencil_resolve.as_ref()
+    }
+
     /// Returns an opaque number representing the version of the driver of this device.
     ///
     /// The meaning of this number is implementation-specific. It can be used in bug reports, for
@@ -30,7 +30,10 @@ use std::os::raw::c_char;
 use std::os::raw::c_void;
 use std::path::Path;
 
+use OomError;
 use SafeDeref;
+use check_errors;
+use version::Version;
 use vk;
 
 /// Implemented on objects that grant access to a Vulkan implementation.
@@ -124,6 +127,27 @@ impl<L> FunctionPointers<L> {
         &self.entry_points
     }
 
+    /// Returns the highest Vulkan version that is supported for instances.
+    ///
+    /// Vulkan 1.0 loaders don't provide `vkEnumerateInstanceVersion`, in which case 1.0 is
+    /// returned.
+    pub fn api_version(&self) -> Result<Version, OomError>
+        where L: Loader
+    {
+        unsafe {
+            let name = b"vkEnumerateInstanceVersion\0";
+            let func = self.loader.get_instance_proc_addr(0, name.as_ptr() as *const _) as
+                *const c_void;
+            if func.is_null() {
+                return Ok(Version::V1_0);
+            }
+
+            let mut output = 0;
+            check_errors(self.entry_points.EnumerateInstanceVersion(&mut output))?;
+            Ok(Version::from_vulkan_version(output))
+        }
+    }
+
     /// Calls `get_instance_proc_addr` on the underlying loader.
     #[inline]
     pub fn get_instance_proc_addr(&self, instance: vk::Instance, name: *const c_char)
@@ -93,6 +93,15 @@
 //! # }
 //! ```
 //!
+//! # API version
+//!
+//! The `api_version` member of `ApplicationInfo` is the highest version of Vulkan that your
+//! application is designed to use. The version that is actually used, returned by
+//! `Instance::api_version` and `Device::api_version`, may be lower if the Vulkan implementation
+//! doesn't support it. When a device uses a version of Vulkan in which an extension has been
+//! promoted to core, vulkano provides the functionality of the extension even if it isn't
+//! enabled.
+//!
 //! # Enumerating physical devices and creating a device
 //!
 //! After you have created an instance, the next step is usually to enumerate the physical devices
@@ -106,6 +115,8 @@
 //! `device` module for more info.
 //!
 
+pub use extensions::ExtensionRestriction;
+pub use extensions::ExtensionRestrictionError;
 pub use self::extensions::InstanceExtensions;
 pub use self::extensions::RawInstanceExtensions;
 pub use self::instance::ApplicationInfo;
@@ -126,6 +137,23 @@ pub use self::layers::LayersListError;
 pub use self::layers::layers_list;
 pub use self::limits::Limits;
 pub use self::loader::LoadingError;
+pub use self::properties::ConformanceVersion;
+pub use self::properties::DepthStencilResolveProperties;
+pub use self::properties::DescriptorIndexingLimits;
+pub use self::properties::DriverId;
+pub use self::properties::DriverProperties;
+pub use self::properties::IdProperties;
+pub use self::properties::ResolveModes;
+pub use self::properties::SubgroupFeatures;
+pub use self::properties::SubgroupProperties;
+pub use self::selector::Candidate;
+pub use self::selector::DedicatedQueue;
+pub use self::selector::PhysicalDeviceDescription;
+pub use self::selector::PhysicalDeviceSelector;
+pub use self::selector::QueueFamilyDescription;
+pub use self::selector::Rejection;
+pub use self::selector::RejectionReason;
+pub use self::selector::Selection;
 pub use version::Version;
 
 pub mod debug;
@@ -135,3 +163,5 @@ mod extensions;
 mod instance;
 mod layers;
 mod limits;
+mod properties;
+mod selector;
@@ -0,0 +1,540 @@
+// Copyright (c) 2026 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Properties of a physical device that are queried by chaining structs to
+//! `VkPhysicalDeviceProperties2`.
+
+use std::fmt;
+use std::mem;
+use std::os::raw::c_void;
+use std::ptr;
+
+use descriptor::descriptor::ShaderStages;
+use framebuffer::ResolveMode;
+use version::Version;
+use vk;
+
+/// Properties of the subgroups of a physical device.
+///
+/// A subgroup is a set of shader invocations that execute together and that can efficiently
+/// share data with each other.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct SubgroupProperties {
+    /// Number of invocations in each subgroup.
+    pub subgroup_size: u32,
+    /// Shader stages in which subgroup operations are supported.
+    pub supported_stages: ShaderStages,
+    /// Categories of subgroup operations that are supported.
+    pub supported_operations: SubgroupFeatures,
+    /// If true, quad operations are supported in all the stages of `supported_stages`. If false,
+    /// they are only supported in the fragment and compute stages.
+    pub quad_operations_in_all_stages: bool,
+}
+
+/// Categories of subgroup operations.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct SubgroupFeatures {
+    pub basic: bool,
+    pub vote: bool,
+    pub arithmetic: bool,
+    pub ballot: bool,
+    pub shuffle: bool,
+    pub shuffle_relative: bool,
+    pub clustered: bool,
+    pub quad: bool,
+}
+
+impl SubgroupFeatures {
+    #[inline]
+    fn from_bits(val: vk::SubgroupFeatureFlags) -> SubgroupFeatures {
+        SubgroupFeatures {
+            basic: (val & vk::SUBGROUP_FEATURE_BASIC_BIT) != 0,
+            vote: (val & vk::SUBGROUP_FEATURE_VOTE_BIT) != 0,
+            arithmetic: (val & vk::SUBGROUP_FEATURE_ARITHMETIC_BIT) != 0,
+            ballot: (val & vk::SUBGROUP_FEATURE_BALLOT_BIT) != 0,
+            shuffle: (val & vk::SUBGROUP_FEATURE_SHUFFLE_BIT) != 0,
+            shuffle_relative: (val & vk::SUBGROUP_FEATURE_SHUFFLE_RELATIVE_BIT) != 0,
+            clustered: (val & vk::SUBGROUP_FEATURE_CLUSTERED_BIT) != 0,
+            quad: (val & vk::SUBGROUP_FEATURE_QUAD_BIT) != 0,
+        }
+    }
+}
+
+/// Identifies the driver of a physical device.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum DriverId {
+    AmdProprietary,
+    AmdOpenSource,
+    MesaRadv,
+    NvidiaProprietary,
+    IntelProprietaryWindows,
+    IntelOpenSourceMesa,
+    ImaginationProprietary,
+    QualcommProprietary,
+    ArmProprietary,
+    GoogleSwiftshader,
+    GgpProprietary,
+    BroadcomProprietary,
+    /// A driver that vulkano doesn't know about. Contains the raw value of the identifier.
+    Other(u32),
+}
+
+impl DriverId {
+    #[inline]
+    fn from_vulkan_id(id: vk::DriverIdKHR) -> DriverId {
+        match id {
+            vk::DRIVER_ID_AMD_PROPRIETARY_KHR => DriverId::AmdProprietary,
+            vk::DRIVER_ID_AMD_OPEN_SOURCE_KHR => DriverId::AmdOpenSource,
+            vk::DRIVER_ID_MESA_RADV_KHR => DriverId::MesaRadv,
+            vk::DRIVER_ID_NVIDIA_PROPRIETARY_KHR => DriverId::NvidiaProprietary,
+            vk::DRIVER_ID_INTEL_PROPRIETARY_WINDOWS_KHR => DriverId::IntelProprietaryWindows,
+            vk::DRIVER_ID_INTEL_OPEN_SOURCE_MESA_KHR => DriverId::IntelOpenSourceMesa,
+            vk::DRIVER_ID_IMAGINATION_PROPRIETARY_KHR => DriverId::ImaginationProprietary,
+            vk::DRIVER_ID_QUALCOMM_PROPRIETARY_KHR => DriverId::QualcommProprietary,
+            vk::DRIVER_ID_ARM_PROPRIETARY_KHR => DriverId::ArmProprietary,
+            vk::DRIVER_ID_GOOGLE_SWIFTSHADER_KHR => DriverId::GoogleSwiftshader,
+            vk::DRIVER_ID_GGP_PROPRIETARY_KHR => DriverId::GgpProprietary,
+            vk::DRIVER_ID_BROADCOM_PROPRIETARY_KHR => DriverId::BroadcomProprietary,
+            id => DriverId::Other(id),
+        }
+    }
+}
+
+/// Version of the Vulkan conformance test suite that a driver has passed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ConformanceVersion {
+    pub major: u8,
+    pub minor: u8,
+    pub subminor: u8,
+    pub patch: u8,
+}
+
+impl fmt::Display for ConformanceVersion {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}.{}.{}.{}", self.major, self.minor, self.subminor, self.patch)
+    }
+}
+
+/// Information about the driver of a physical device.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DriverProperties {
+    /// Identifier of the driver.
+    pub driver_id: DriverId,
+    /// Name of the driver.
+    pub driver_name: String,
+    /// Additional information about the driver, such as its version.
+    pub driver_info: String,
+    /// Version of the conformance test suite that the driver has passed.
+    pub conformance_version: ConformanceVersion,
+}
+
+/// Identifiers of a physical device and of its driver.
+///
+/// These are mostly useful to share resources with other APIs or processes.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct IdProperties {
+    /// Identifier of the device, which is the same across instances and processes.
+    pub device_uuid: [u8; 16],
+    /// Identifier of the driver, which is the same across instances and processes.
+    pub driver_uuid: [u8; 16],
+    /// Locally unique identifier of the device, if the driver provides one. Only available on
+    /// Windows.
+    pub device_luid: Option<[u8; 8]>,
+    /// Bitmask of the node of the device within its linked device adapter. Only meaningful if
+    /// `device_luid` is `Some`.
+    pub device_node_mask: u32,
+}
+
+/// Limits of a physical device related to descriptor indexing.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct DescriptorIndexingLimits {
+    pub max_update_after_bind_descriptors_in_all_pools: u32,
+    pub shader_uniform_buffer_array_non_uniform_indexing_native: bool,
+    pub shader_sampled_image_array_non_uniform_indexing_native: bool,
+    pub shader_storage_buffer_array_non_uniform_indexing_native: bool,
+    pub shader_storage_image_array_non_uniform_indexing_native: bool,
+    pub shader_input_attachment_array_non_uniform_indexing_native: bool,
+    pub robust_buffer_access_update_after_bind: bool,
+    pub quad_divergent_implicit_lod: bool,
+    pub max_per_stage_descriptor_update_after_bind_samplers: u32,
+    pub max_per_stage_descriptor_update_after_bind_uniform_buffers: u32,
+    pub max_per_stage_descriptor_update_after_bind_storage_buffers: u32,
+    pub max_per_stage_descriptor_update_after_bind_sampled_images: u32,
+    pub max_per_stage_descriptor_update_after_bind_storage_images: u32,
+    pub max_per_stage_descriptor_update_after_bind_input_attachments: u32,
+    pub max_per_stage_update_after_bind_resources: u32,
+    pub max_descriptor_set_update_after_bind_samplers: u32,
+    pub max_descriptor_set_update_after_bind_uniform_buffers: u32,
+    pub max_descriptor_set_update_after_bind_uniform_buffers_dynamic: u32,
+    pub max_descriptor_set_update_after_bind_storage_buffers: u32,
+    pub max_descriptor_set_update_after_bind_storage_buffers_dynamic: u32,
+    pub max_descriptor_set_update_after_bind_sampled_images: u32,
+    pub max_descriptor_set_update_after_bind_storage_images: u32,
+    pub max_descriptor_set_update_after_bind_input_attachments: u32,
+}
+
+/// Resolve modes that a physical device supports for depth-stencil attachments.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct DepthStencilResolveProperties {
+    /// Modes supported for the depth aspect.
+    pub supported_depth_resolve_modes: ResolveModes,
+    /// Modes supported for the stencil aspect.
+    pub supported_stencil_resolve_modes: ResolveModes,
+    /// If true, one of the aspects of a depth-stencil attachment can be resolved while the other
+    /// isn't. Otherwise both aspects must use the same mode.
+    pub independent_resolve_none: bool,
+    /// If true, the two aspects of a depth-stencil attachment can be resolved with any
+    /// combination of modes, including not being resolved. Implies `independent_resolve_none`.
+    pub independent_resolve: bool,
+}
+
+/// Set of resolve modes.
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+pub struct ResolveModes {
+    pub sample_zero: bool,
+    pub average: bool,
+    pub min: bool,
+    pub max: bool,
+}
+
+impl ResolveModes {
+    /// Returns true if `mode` is part of the set.
+    #[inline]
+    pub fn supports(&self, mode: ResolveMode) -> bool {
+        match mode {
+            ResolveMode::SampleZero => self.sample_zero,
+            ResolveMode::Average => self.average,
+            ResolveMode::Min => self.min,
+            ResolveMode::Max => self.max,
+        }
+    }
+
+    #[inline]
+    fn from_bits(val: vk::ResolveModeFlagsKHR) -> ResolveModes {
+        ResolveModes {
+            sample_zero: (val & vk::RESOLVE_MODE_SAMPLE_ZERO_BIT_KHR) != 0,
+            average: (val & vk::RESOLVE_MODE_AVERAGE_BIT_KHR) != 0,
+            min: (val & vk::RESOLVE_MODE_MIN_BIT_KHR) != 0,
+            max: (val & vk::RESOLVE_MODE_MAX_BIT_KHR) != 0,
+        }
+    }
+}
+
+/// The properties of a physical device that aren't part of `VkPhysicalDeviceProperties`.
+///
+/// Each member is `None` if the device doesn't support the corresponding struct.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct ExtendedProperties {
+    pub subgroup: Option<SubgroupProperties>,
+    pub driver: Option<DriverProperties>,
+    pub id: Option<IdProperties>,
+    pub max_per_set_descriptors: Option<u32>,
+    pub max_memory_allocation_size: Option<u64>,
+    pub max_multiview_view_count: Option<u32>,
+    pub max_multiview_instance_index: Option<u32>,
+    pub descriptor_indexing: Option<DescriptorIndexingLimits>,
+    pub depth_stencil_resolve: Option<DepthStencilResolveProperties>,
+}
+
+/// The structs that are chained to `VkPhysicalDeviceProperties2` to query the properties of
+/// `ExtendedProperties`.
+///
+/// Each member is `None` if the corresponding struct isn't part of the chain.
+pub(crate) struct PropertiesFfi {
+    subgroup: Option<vk::PhysicalDeviceSubgroupProperties>,
+    driver: Option<vk::PhysicalDeviceDriverPropertiesKHR>,
+    id: Option<vk::PhysicalDeviceIDPropertiesKHR>,
+    maintenance3: Option<vk::PhysicalDeviceMaintenance3PropertiesKHR>,
+    multiview: Option<vk::PhysicalDeviceMultiviewPropertiesKHR>,
+    descriptor_indexing: Option<vk::PhysicalDeviceDescriptorIndexingPropertiesEXT>,
+    depth_stencil_resolve: Option<vk::PhysicalDeviceDepthStencilResolvePropertiesKHR>,
+}
+
+impl PropertiesFfi {
+    /// Builds the structs to query the properties of a physical device.
+    ///
+    /// `api_version` is the version of Vulkan supported by the device, and `is_supported` is
+    /// called with the names of the extensions that the structs belong to. The instance
+    /// extensions enabled on the instance and the device extensions supported by the device are
+    /// expected to be reported as supported.
+    pub(crate) fn for_query<F>(api_version: Version, is_supported: F) -> PropertiesFfi
+        where F: Fn(&[u8]) -> bool
+    {
+        unsafe {
+            PropertiesFfi {
+                subgroup: if api_version >= Version::V1_1 {
+                    Some(vk::PhysicalDeviceSubgroupProperties {
+                        sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_SUBGROUP_PROPERTIES,
+                        .. mem::zeroed()
+                    })
+                } else {
+                    None
+                },
+                driver: if api_version >= Version::V1_2 ||
+                    is_supported(b"VK_KHR_driver_properties")
+                {
+                    Some(vk::PhysicalDeviceDriverPropertiesKHR {
+                        sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_DRIVER_PROPERTIES_KHR,
+                        .. mem::zeroed()
+                    })
+                } else {
+                    None
+                },
+                id: if api_version >= Version::V1_1 ||
+                    is_supported(b"VK_KHR_external_memory_capabilities")
+                {
+                    Some(vk::PhysicalDeviceIDPropertiesKHR {
+                        sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_ID_PROPERTIES_KHR,
+                        .. mem::zeroed()
+                    })
+                } else {
+                    None
+                },
+                maintenance3: if api_version >= Version::V1_1 ||
+                    is_supported(b"VK_KHR_maintenance3")
+                {
+                    Some(vk::PhysicalDeviceMaintenance3PropertiesKHR {
+                        sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_MAINTENANCE_3_PROPERTIES_KHR,
+                        .. mem::zeroed()
+                    })
+                } else {
+                    None
+                },
+                multiview: if api_version >= Version::V1_1 ||
+                    is_supported(b"VK_KHR_multiview")
+                {
+                    Some(vk::PhysicalDeviceMultiviewPropertiesKHR {
+                        sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_MULTIVIEW_PROPERTIES_KHR,
+                        .. mem::zeroed()
+                    })
+                } else {
+                    None
+                },
+                descriptor_indexing: if api_version >= Version::V1_2 ||
+                    is_supported(b"VK_EXT_descriptor_indexing")
+                {
+                    Some(vk::PhysicalDeviceDescriptorIndexingPropertiesEXT {
+                        sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_DESCRIPTOR_INDEXING_PROPERTIES_EXT,
+                        .. mem::zeroed()
+                    })
+                } else {
+                    None
+                },
+                depth_stencil_resolve: if api_version >= Version::V1_2 ||
+                    is_supported(b"VK_KHR_depth_stencil_resolve")
+                {
+                    Some(vk::PhysicalDeviceDepthStencilResolvePropertiesKHR {
+                        sType: vk::STRUCTURE_TYPE_PHYSICAL_DEVICE_DEPTH_STENCIL_RESOLVE_PROPERTIES_KHR,
+                        .. mem::zeroed()
+                    })
+                } else {
+                    None
+                },
+            }
+        }
+    }
+
+    /// Links the structs together and returns a pointer to the first one, or a null pointer if
+    /// there is none.
+    ///
+    /// The pointer is only valid as long as `self` isn't moved or dropped.
+    pub(crate) fn head_as_mut_ptr(&mut self) -> *mut c_void {
+        let mut p_next: *mut c_void = ptr::null_mut();
+        macro_rules! link {
+            ($($member:ident),+) => {
+                $(
+                    if let Some(ref mut s) = self.$member {
+                        s.pNext = p_next;
+                        p_next = s as *mut _ as *mut c_void;
+                    }
+                )+
+            };
+        }
+        link!(subgroup, driver, id, maintenance3, multiview, descriptor_indexing,
+              depth_stencil_resolve);
+        p_next
+    }
+
+    /// Converts the queried structs into `ExtendedProperties`.
+    pub(crate) fn into_properties(self) -> ExtendedProperties {
+        ExtendedProperties {
+            subgroup: self.subgroup.map(|p| {
+                SubgroupProperties {
+                    subgroup_size: p.subgroupSize,
+                    supported_stages: shader_stages_from_bits(p.supportedStages),
+                    supported_operations: SubgroupFeatures::from_bits(p.supportedOperations),
+                    quad_operations_in_all_stages: p.quadOperationsInAllStages != 0,
+                }
+            }),
+            driver: self.driver.map(|p| {
+                DriverProperties {
+                    driver_id: DriverId::from_vulkan_id(p.driverID),
+                    driver_name: c_string(&p.driverName),
+                    driver_info: c_string(&p.driverInfo),
+                    conformance_version: ConformanceVersion {
+                        major: p.conformanceVersion.major,
+                        minor: p.conformanceVersion.minor,
+                        subminor: p.conformanceVersion.subminor,
+                        patch: p.conformanceVersion.patch,
+                    },
+                }
+            }),
+            id: self.id.map(|p| {
+                IdProperties {
+                    device_uuid: p.deviceUUID,
+                    driver_uuid: p.driverUUID,
+                    device_luid: if p.deviceLUIDValid != 0 {
+                        Some(p.deviceLUID)
+                    } else {
+                        None
+                    },
+                    device_node_mask: p.deviceNodeMask,
+                }
+            }),
+            max_per_set_descriptors: self.maintenance3.as_ref().map(|p| p.maxPerSetDescriptors),
+            max_memory_allocation_size: self.maintenance3
+                .as_ref()
+                .map(|p| p.maxMemoryAllocationSize),
+            max_multiview_view_count: self.multiview.as_ref().map(|p| p.maxMultiviewViewCount),
+            max_multiview_instance_index: self.multiview
+                .as_ref()
+                .map(|p| p.maxMultiviewInstanceIndex),
+            descriptor_indexing: self.descriptor_indexing.map(|p| {
+                DescriptorIndexingLimits {
+                    max_update_after_bind_descriptors_in_all_pools:
+                        p.maxUpdateAfterBindDescriptorsInAllPools,
+                    shader_uniform_buffer_array_non_uniform_indexing_native:
+                        p.shaderUniformBufferArrayNonUniformIndexingNative != 0,
+                    shader_sampled_image_array_non_uniform_indexing_native:
+                        p.shaderSampledImageArrayNonUniformIndexingNative != 0,
+                    shader_storage_buffer_array_non_uniform_indexing_native:
+                        p.shaderStorageBufferArrayNonUniformIndexingNative != 0,
+                    shader_storage_image_array_non_uniform_indexing_native:
+                        p.shaderStorageImageArrayNonUniformIndexingNative != 0,
+                    shader_input_attachment_array_non_uniform_indexing_native:
+                        p.shaderInputAttachmentArrayNonUniformIndexingNative != 0,
+                    robust_buffer_access_update_after_bind: p.robustBufferAccessUpdateAfterBind !=
+                        0,
+                    quad_divergent_implicit_lod: p.quadDivergentImplicitLod != 0,
+                    max_per_stage_descriptor_update_after_bind_samplers:
+                        p.maxPerStageDescriptorUpdateAfterBindSamplers,
+                    max_per_stage_descriptor_update_after_bind_uniform_buffers:
+                        p.maxPerStageDescriptorUpdateAfterBindUniformBuffers,
+                    max_per_stage_descriptor_update_after_bind_storage_buffers:
+                        p.maxPerStageDescriptorUpdateAfterBindStorageBuffers,
+                    max_per_stage_descriptor_update_after_bind_sampled_images:
+                        p.maxPerStageDescriptorUpdateAfterBindSampledImages,
+                    max_per_stage_descriptor_update_after_bind_storage_images:
+                        p.maxPerStageDescriptorUpdateAfterBindStorageImages,
+                    max_per_stage_descriptor_update_after_bind_input_attachments:
+                        p.maxPerStageDescriptorUpdateAfterBindInputAttachments,
+                    max_per_stage_update_after_bind_resources:
+                        p.maxPerStageUpdateAfterBindResources,
+                    max_descriptor_set_update_after_bind_samplers:
+                        p.maxDescriptorSetUpdateAfterBindSamplers,
+                    max_descriptor_set_update_after_bind_uniform_buffers:
+                        p.maxDescriptorSetUpdateAfterBindUniformBuffers,
+                    max_descriptor_set_update_after_bind_uniform_buffers_dynamic:
+                        p.maxDescriptorSetUpdateAfterBindUniformBuffersDynamic,
+                    max_descriptor_set_update_after_bind_storage_buffers:
+                        p.maxDescriptorSetUpdateAfterBindStorageBuffers,
+                    max_descriptor_set_update_after_bind_storage_buffers_dynamic:
+                        p.maxDescriptorSetUpdateAfterBindStorageBuffersDynamic,
+                    max_descriptor_set_update_after_bind_sampled_images:
+                        p.maxDescriptorSetUpdateAfterBindSampledImages,
+                    max_descriptor_set_update_after_bind_storage_images:
+                        p.maxDescriptorSetUpdateAfterBindStorageImages,
+                    max_descriptor_set_update_after_bind_input_attachments:
+                        p.maxDescriptorSetUpdateAfterBindInputAttachments,
+                }
+            }),
+            depth_stencil_resolve: self.depth_stencil_resolve.map(|p| {
+                DepthStencilResolveProperties {
+                    supported_depth_resolve_modes: ResolveModes::from_bits(
+                        p.supportedDepthResolveModes),
+                    supported_stencil_resolve_modes: ResolveModes::from_bits(
+                        p.supportedStencilResolveModes),
+                    independent_resolve_none: p.independentResolveNone != 0,
+                    independent_resolve: p.independentResolve != 0,
+                }
+            }),
+        }
+    }
+}
+
+#[inline]
+fn shader_stages_from_bits(val: vk::ShaderStageFlags) -> ShaderStages {
+    ShaderStages {
+        vertex: (val & vk::SHADER_STAGE_VERTEX_BIT) != 0,
+        tessellation_control: (val & vk::SHADER_STAGE_TESSELLATION_CONTROL_BIT) != 0,
+        tessellation_evaluation: (val & vk::SHADER_STAGE_TESSELLATION_EVALUATION_BIT) != 0,
+        geometry: (val & vk::SHADER_STAGE_GEOMETRY_BIT) != 0,
+        fragment: (val & vk::SHADER_STAGE_FRAGMENT_BIT) != 0,
+        compute: (val & vk::SHADER_STAGE_COMPUTE_BIT) != 0,
+    }
+}
+
+// Converts a null-terminated array of characters returned by the driver into a `String`.
+#[inline]
+fn c_string(chars: &[::std::os::raw::c_char]) -> String {
+    let bytes = unsafe { &*(chars as *const [_] as *const [u8]) };
+    let len = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[.. len]).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use instance::properties::DriverId;
+    use instance::properties::PropertiesFfi;
+    use instance::properties::SubgroupFeatures;
+    use version::Version;
+    use vk;
+
+    #[test]
+    fn query_chain_follows_version_and_extensions() {
+        let mut chain = PropertiesFfi::for_query(Version::V1_0, |_| false);
+        assert!(chain.head_as_mut_ptr().is_null());
+
+        let mut chain = PropertiesFfi::for_query(Version::V1_0,
+                                                 |name| name == b"VK_KHR_driver_properties");
+        assert!(!chain.head_as_mut_ptr().is_null());
+        let properties = chain.into_properties();
+        assert!(properties.driver.is_some());
+        assert!(properties.subgroup.is_none());
+        assert!(properties.max_memory_allocation_size.is_none());
+
+        let properties = PropertiesFfi::for_query(Version::V1_1, |_| false).into_properties();
+        assert!(properties.subgroup.is_some());
+        assert!(properties.id.is_some());
+        assert!(properties.max_per_set_descriptors.is_some());
+        assert!(properties.max_multiview_view_count.is_some());
+        assert!(properties.driver.is_none());
+        assert!(properties.descriptor_indexing.is_none());
+        assert!(properties.depth_stencil_resolve.is_none());
+
+        let properties = PropertiesFfi::for_query(Version::V1_2, |_| false).into_properties();
+        assert!(properties.depth_stencil_resolve.is_some());
+    }
+
+    #[test]
+    fn conversions() {
+        assert_eq!(DriverId::from_vulkan_id(vk::DRIVER_ID_MESA_RADV_KHR), DriverId::MesaRadv);
+        assert_eq!(DriverId::from_vulkan_id(1000), DriverId::Other(1000));
+
+        let features = SubgroupFeatures::from_bits(vk::SUBGROUP_FEATURE_BASIC_BIT |
+                                                       vk::SUBGROUP_FEATURE_QUAD_BIT);
+        assert_eq!(features,
+                   SubgroupFeatures {
+                       basic: true,
+                       quad: true,
+                       ..SubgroupFeatures::default()
+                   });
+    }
+}
@@ -0,0 +1,885 @@
+// Copyright (c) 2026 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Choosing a physical device according to the requirements of the application.
+//!
+//! A `PhysicalDeviceSelector` holds the extensions, features, queues, limits and formats that the
+//! application needs. It describes each physical device of an instance with a
+//! `PhysicalDeviceDescription`, rejects the devices that don't meet the requirements and ranks
+//! the others.
+//!
+//! The ranking only looks at the descriptions, which can be built by hand in order to test how a
+//! set of requirements behaves on hardware that isn't available.
+
+use std::cmp;
+use std::collections::HashMap;
+use std::error;
+use std::fmt;
+use std::mem::MaybeUninit;
+use std::sync::Arc;
+
+use device::DeviceExtensions;
+use features::Features;
+use format::Format;
+use image::ImageUsage;
+use instance::Instance;
+use instance::Limits;
+use instance::PhysicalDevice;
+use instance::PhysicalDeviceType;
+use instance::QueueFamily;
+use swapchain::Surface;
+use version::Version;
+use vk;
+
+use VulkanObject;
+
+/// Whether a queue family that is dedicated to some kind of operations is needed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DedicatedQueue {
+    /// The queue family isn't looked for.
+    NotNeeded,
+    /// Devices that have such a queue family are ranked higher.
+    Preferred,
+    /// Devices that don't have such a queue family are rejected.
+    Required,
+}
+
+/// Requirements of the application on the physical device, used to choose one.
+///
+/// # Example
+///
+/// ```no_run
+/// # use vulkano::instance::Instance;
+/// # use vulkano::instance::InstanceExtensions;
+/// use vulkano::device::Device;
+/// use vulkano::device::DeviceExtensions;
+/// use vulkano::device::Features;
+/// use vulkano::instance::DedicatedQueue;
+/// use vulkano::instance::PhysicalDeviceSelector;
+///
+/// # let instance = Instance::new(None, &InstanceExtensions::none(), None).unwrap();
+/// let extensions = DeviceExtensions {
+///     khr_storage_buffer_storage_class: true,
+///     .. DeviceExtensions::none()
+/// };
+///
+/// let selection = PhysicalDeviceSelector::new()
+///     .extensions(&extensions)
+///     .graphics()
+///     .dedicated_transfer(DedicatedQueue::Preferred)
+///     .select(&instance);
+///
+/// for rejection in selection.rejections.iter() {
+///     println!("{} isn't suitable: {:?}", rejection.name, rejection.reasons);
+/// }
+///
+/// let candidate = selection.best().expect("no suitable physical device");
+/// let physical = candidate.physical_device(&instance);
+/// let (device, queues) = Device::new(physical, &Features::none(), &extensions,
+///                                    candidate.queue_families(physical)).unwrap();
+/// ```
+pub struct PhysicalDeviceSelector {
+    api_version: Version,
+    extensions: DeviceExtensions,
+    features: Features,
+    graphics: bool,
+    present: Option<PresentSupport>,
+    compute: DedicatedQueue,
+    transfer: DedicatedQueue,
+    limits: Vec<LimitRequirement>,
+    formats: Vec<(Format, ImageUsage)>,
+}
+
+// Returns true if a queue family can present to the surface of the selector.
+type PresentSupport = Box<dyn Fn(QueueFamily) -> bool>;
+
+// The name of a limit, the function that returns its value and its minimum.
+type LimitRequirement = (&'static str, fn(&Limits) -> u64, u64);
+
+impl PhysicalDeviceSelector {
+    /// Builds a selector that accepts every physical device.
+    #[inline]
+    pub fn new() -> PhysicalDeviceSelector {
+        PhysicalDeviceSelector {
+            api_version: Version::V1_0,
+            extensions: DeviceExtensions::none(),
+            features: Features::none(),
+            graphics: false,
+            present: None,
+            compute: DedicatedQueue::NotNeeded,
+            transfer: DedicatedQueue::NotNeeded,
+            limits: Vec::new(),
+            formats: Vec::new(),
+        }
+    }
+
+    /// Requires the device to use at least `version` of Vulkan with the instance.
+    #[inline]
+    pub fn api_version(mut self, version: Version) -> PhysicalDeviceSelector {
+        self.api_version = version;
+        self
+    }
+
+    /// Requires the device to support these extensions, along with the extensions they depend
+    /// on that aren't part of the version of Vulkan of the device.
+    #[inline]
+    pub fn extensions(mut self, extensions: &DeviceExtensions) -> PhysicalDeviceSelector {
+        self.extensions = self.extensions.union(extensions);
+        self
+    }
+
+    /// Requires the device to support these features.
+    #[inline]
+    pub fn features(mut self, features: &Features) -> PhysicalDeviceSelector {
+        self.features = self.features.union(features);
+        self
+    }
+
+    /// Requires a queue family that supports graphics operations.
+    #[inline]
+    pub fn graphics(mut self) -> PhysicalDeviceSelector {
+        self.graphics = true;
+        self
+    }
+
+    /// Requires a queue family that supports graphics operations and a queue family that can
+    /// present to `surface`. Devices where a single family can do both are ranked higher.
+    #[inline]
+    pub fn surface<W>(mut self, surface: &Arc<Surface<W>>) -> PhysicalDeviceSelector
+        where W: 'static
+    {
+        let surface = surface.clone();
+        self.graphics = true;
+        self.present = Some(Box::new(move |family| surface.is_supported(family).unwrap_or(false)));
+        self
+    }
+
+    /// Sets whether a queue family that supports compute but not graphics operations is needed,
+    /// in order to run compute work asynchronously.
+    ///
+    /// If a dedicated family is only preferred and there's none, a family that supports both
+    /// is chosen instead.
+    #[inline]
+    pub fn dedicated_compute(mut self, requirement: DedicatedQueue) -> PhysicalDeviceSelector {
+        self.compute = requirement;
+        self
+    }
+
+    /// Sets whether a queue family that supports transfers but not graphics operations is
+    /// needed, in order to upload data asynchronously.
+    ///
+    /// If a dedicated family is only preferred and there's none, a family that supports both
+    /// is chosen instead.
+    #[inline]
+    pub fn dedicated_transfer(mut self, requirement: DedicatedQueue) -> PhysicalDeviceSelector {
+        self.transfer = requirement;
+        self
+    }
+
+    /// Requires a limit of the device to be at least `minimum`. `name` identifies the limit in
+    /// the descriptions and in the rejection reasons.
+    ///
+    /// ```
+    /// use vulkano::instance::PhysicalDeviceSelector;
+    ///
+    /// let selector = PhysicalDeviceSelector::new()
+    ///     .min_limit("max_push_constants_size", |l| l.max_push_constants_size() as u64, 256);
+    /// ```
+    #[inline]
+    pub fn min_limit(mut self, name: &'static str, limit: fn(&Limits) -> u64, minimum: u64)
+                     -> PhysicalDeviceSelector {
+        self.limits.push((name, limit, minimum));
+        self
+    }
+
+    /// Requires images of `format` with optimal tiling to support `usage`.
+    #[inline]
+    pub fn format(mut self, format: Format, usage: ImageUsage) -> PhysicalDeviceSelector {
+        self.formats.push((format, usage));
+        self
+    }
+
+    /// Describes each physical device of `instance` and ranks them.
+    pub fn select(&self, instance: &Arc<Instance>) -> Selection {
+        let descriptions = PhysicalDevice::enumerate(instance)
+            .map(|physical_device| self.describe(physical_device))
+            .collect::<Vec<_>>();
+        self.rank(descriptions.iter())
+    }
+
+    /// Describes a physical device. Only the limits and the formats that the selector requires
+    /// are included in the description.
+    pub fn describe(&self, physical_device: PhysicalDevice) -> PhysicalDeviceDescription {
+        let instance = physical_device.instance();
+        let api_version = cmp::min(instance.api_version(), physical_device.api_version());
+
+        let queue_families = physical_device
+            .queue_families()
+            .map(|family| {
+                QueueFamilyDescription {
+                    id: family.id(),
+                    queues_count: family.queues_count(),
+                    graphics: family.supports_graphics(),
+                    compute: family.supports_compute(),
+                    transfer: family.explicitly_supports_transfers(),
+                    present: self.present.as_ref().map(|present| present(family)).unwrap_or(false),
+                }
+            })
+            .collect();
+
+        let limits = self.limits
+            .iter()
+            .map(|&(name, limit, _)| (name, limit(&physical_device.limits())))
+            .collect();
+
+        // Before Vulkan 1.1 and `VK_KHR_maintenance1`, any supported format can be transferred.
+        let extensions = DeviceExtensions::supported_by_device(physical_device);
+        let transfer_bits = api_version >= Version::V1_1 || extensions.khr_maintenance1;
+        let formats = self.formats
+            .iter()
+            .map(|&(format, _)| {
+                let features = unsafe {
+                    let mut output = MaybeUninit::uninit();
+                    instance.pointers().GetPhysicalDeviceFormatProperties(
+                        physical_device.internal_object(), format as u32, output.as_mut_ptr());
+                    output.assume_init().optimalTilingFeatures
+                };
+                (format, image_usage_from_format_features(features, transfer_bits))
+            })
+            .collect();
+
+        let device_local_memory = physical_device
+            .memory_heaps()
+            .filter(|heap| heap.is_device_local())
+            .map(|heap| heap.size() as u64)
+            .sum();
+
+        PhysicalDeviceDescription {
+            index: physical_device.index(),
+            name: physical_device.name(),
+            ty: physical_device.ty(),
+            api_version,
+            extensions,
+            features: physical_device.supported_features().clone(),
+            queue_families,
+            limits,
+            formats,
+            device_local_memory,
+        }
+    }
+
+    /// Checks each description against the requirements and ranks the devices that meet them,
+    /// the best one first.
+    pub fn rank<'d, I>(&self, descriptions: I) -> Selection
+        where I: IntoIterator<Item = &'d PhysicalDeviceDescription>
+    {
+        let mut candidates = Vec::new();
+        let mut rejections = Vec::new();
+
+        for description in descriptions {
+            match self.evaluate(description) {
+                Ok(candidate) => candidates.push(candidate),
+                Err(reasons) => {
+                    rejections.push(Rejection {
+                                        index: description.index,
+                                        name: description.name.clone(),
+                                        reasons,
+                                    })
+                },
+            }
+        }
+
+        // The sort is stable, so devices with the same score keep the order of enumeration.
+        candidates.sort_by_key(|c| cmp::Reverse(c.score));
+
+        Selection {
+            candidates,
+            rejections,
+        }
+    }
+
+    // Returns the candidate corresponding to a description, or all the reasons why it's
+    // rejected.
+    fn evaluate(&self, description: &PhysicalDeviceDescription)
+                -> Result<Candidate, Vec<RejectionReason>> {
+        let mut reasons = Vec::new();
+
+        if description.api_version < self.api_version {
+            reasons.push(RejectionReason::ApiVersionTooLow {
+                             required: self.api_version,
+                             supported: description.api_version,
+                         });
+        }
+
+        let required_extensions = self.extensions
+            .with_dependencies(description.api_version)
+            .difference(&DeviceExtensions::promoted_to_core(description.api_version));
+        let missing_extensions = required_extensions.difference(&description.extensions);
+        if missing_extensions != DeviceExtensions::none() {
+            reasons.push(RejectionReason::MissingExtensions(missing_extensions));
+        }
+
+        if !description.features.superset_of(&self.features) {
+            let missing_features = self.features.difference(&description.features);
+            reasons.push(RejectionReason::MissingFeatures(missing_features));
+        }
+
+        let families = &description.queue_families;
+
+        // A graphics family that can also present is preferred.
+        let graphics_family = if self.graphics {
+            let family = families
+                .iter()
+                .find(|f| f.graphics && (self.present.is_none() || f.present))
+                .or_else(|| families.iter().find(|f| f.graphics));
+            if family.is_none() {
+                reasons.push(RejectionReason::NoGraphicsQueue);
+            }
+            family.map(|f| f.id)
+        } else {
+            None
+        };
+
+        let present_family = if self.present.is_some() {
+            let family = match graphics_family {
+                Some(id) if families.iter().any(|f| f.id == id && f.present) => Some(id),
+                _ => families.iter().find(|f| f.present).map(|f| f.id),
+            };
+            if family.is_none() {
+                reasons.push(RejectionReason::NoPresentQueue);
+            }
+            family
+        } else {
+            None
+        };
+
+        // When there's no dedicated family, the compute and transfer operations fall back to
+        // a family that also supports graphics.
+        let (compute_family, dedicated_compute) = if self.compute != DedicatedQueue::NotNeeded {
+            let dedicated = families.iter().find(|f| f.compute && !f.graphics).map(|f| f.id);
+            if dedicated.is_none() && self.compute == DedicatedQueue::Required {
+                reasons.push(RejectionReason::NoDedicatedComputeQueue);
+            }
+            let family = dedicated.or_else(|| families.iter().find(|f| f.compute).map(|f| f.id));
+            (family, dedicated.is_some())
+        } else {
+            (None, false)
+        };
+
+        // A family that only does transfers is preferred. Otherwise any family that doesn't
+        // support graphics and isn't already used for compute will do.
+        let (transfer_family, dedicated_transfer) = if self.transfer != DedicatedQueue::NotNeeded {
+            let dedicated = families
+                .iter()
+                .find(|f| f.transfer && !f.graphics && !f.compute)
+                .or_else(|| {
+                    families.iter().find(|f| {
+                        (f.transfer || f.compute) && !f.graphics && Some(f.id) != compute_family
+                    })
+                })
+                .map(|f| f.id);
+            if dedicated.is_none() && self.transfer == DedicatedQueue::Required {
+                reasons.push(RejectionReason::NoDedicatedTransferQueue);
+            }
+            let family = dedicated.or_else(|| {
+                families.iter().find(|f| f.transfer || f.compute || f.graphics).map(|f| f.id)
+            });
+            (family, dedicated.is_some())
+        } else {
+            (None, false)
+        };
+
+        for &(name, _, minimum) in self.limits.iter() {
+            let value = description.limits.get(name).cloned().unwrap_or(0);
+            if value < minimum {
+                reasons.push(RejectionReason::LimitTooLow {
+                                 name,
+                                 minimum,
+                                 value,
+                             });
+            }
+        }
+
+        for &(format, usage) in self.formats.iter() {
+            let supported = match description.formats.get(&format) {
+                Some(supported) => usage_supported(supported, &usage),
+                None => false,
+            };
+            if !supported {
+                reasons.push(RejectionReason::UnsupportedFormat { format, usage });
+            }
+        }
+
+        if !reasons.is_empty() {
+            return Err(reasons);
+        }
+
+        let mut score = match description.ty {
+            PhysicalDeviceType::DiscreteGpu => 1000,
+            PhysicalDeviceType::IntegratedGpu => 500,
+            PhysicalDeviceType::VirtualGpu => 250,
+            PhysicalDeviceType::Cpu => 100,
+            PhysicalDeviceType::Other => 0,
+        };
+        if dedicated_compute {
+            score += 100;
+        }
+        if dedicated_transfer {
+            score += 100;
+        }
+        if present_family.is_some() && present_family == graphics_family {
+            score += 50;
+        }
+        // Breaks ties between devices of the same kind, one point per GiB.
+        score += cmp::min(description.device_local_memory >> 30, 64) as u32;
+
+        Ok(Candidate {
+               index: description.index,
+               name: description.name.clone(),
+               score,
+               graphics_family,
+               present_family,
+               compute_family,
+               transfer_family,
+           })
+    }
+}
+
+impl Default for PhysicalDeviceSelector {
+    #[inline]
+    fn default() -> PhysicalDeviceSelector {
+        PhysicalDeviceSelector::new()
+    }
+}
+
+impl fmt::Debug for PhysicalDeviceSelector {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        fmt.debug_struct("PhysicalDeviceSelector")
+            .field("api_version", &self.api_version)
+            .field("extensions", &self.extensions)
+            .field("features", &self.features)
+            .field("graphics", &self.graphics)
+            .field("present", &self.present.is_some())
+            .field("compute", &self.compute)
+            .field("transfer", &self.transfer)
+            .field("limits", &self.limits.iter().map(|l| (l.0, l.2)).collect::<Vec<_>>())
+            .field("formats", &self.formats)
+            .finish()
+    }
+}
+
+/// Description of a physical device, as seen by a `PhysicalDeviceSelector`.
+#[derive(Debug, Clone)]
+pub struct PhysicalDeviceDescription {
+    /// Index of the physical device in the physical devices list of the instance.
+    pub index: usize,
+    /// Name of the device.
+    pub name: String,
+    /// Type of the device.
+    pub ty: PhysicalDeviceType,
+    /// The version of Vulkan that a device created from this physical device uses.
+    pub api_version: Version,
+    /// The supported device extensions.
+    pub extensions: DeviceExtensions,
+    /// The supported features.
+    pub features: Features,
+    /// The queue families of the device.
+    pub queue_families: Vec<QueueFamilyDescription>,
+    /// Values of the limits, by the names given to `PhysicalDeviceSelector::min_limit`. A limit
+    /// that is absent is considered to be 0.
+    pub limits: HashMap<&'static str, u64>,
+    /// The usages supported by images of each format with optimal tiling. A format that is
+    /// absent isn't supported.
+    pub formats: HashMap<Format, ImageUsage>,
+    /// Total size in bytes of the heaps that are local to the device.
+    pub device_local_memory: u64,
+}
+
+/// Description of a queue family of a physical device.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct QueueFamilyDescription {
+    /// Identifier of the queue family.
+    pub id: u32,
+    /// Number of queues in the family.
+    pub queues_count: usize,
+    /// The family supports graphics operations.
+    pub graphics: bool,
+    /// The family supports compute operations.
+    pub compute: bool,
+    /// The family explicitly supports transfers. Graphics and compute families always support
+    /// them.
+    pub transfer: bool,
+    /// The family can present to the surface of the selector.
+    pub present: bool,
+}
+
+/// Result of the ranking of physical devices.
+#[derive(Debug, Clone)]
+pub struct Selection {
+    /// The devices that meet the requirements, the best one first.
+    pub candidates: Vec<Candidate>,
+    /// The devices that don't meet the requirements.
+    pub rejections: Vec<Rejection>,
+}
+
+impl Selection {
+    /// Returns the best candidate, or `None` if no device meets the requirements.
+    #[inline]
+    pub fn best(&self) -> Option<&Candidate> {
+        self.candidates.first()
+    }
+}
+
+/// A physical device that meets the requirements of a `PhysicalDeviceSelector`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Candidate {
+    /// Index of the physical device in the physical devices list of the instance.
+    pub index: usize,
+    /// Name of the device.
+    pub name: String,
+    /// Score of the device. Devices with a higher score are more suitable.
+    pub score: u32,
+    /// The queue family to use for graphics operations, if graphics were requested.
+    pub graphics_family: Option<u32>,
+    /// The queue family to use for presentation, if a surface was given.
+    pub present_family: Option<u32>,
+    /// The queue family to use for compute operations, if a dedicated compute family was
+    /// requested. This family doesn't support graphics operations if possible.
+    pub compute_family: Option<u32>,
+    /// The queue family to use for transfers, if a dedicated transfer family was requested. This
+    /// family doesn't support graphics operations if possible.
+    pub transfer_family: Option<u32>,
+}
+
+impl Candidate {
+    /// Returns the physical device of this candidate.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `instance` isn't the instance the candidate was selected from.
+    ///
+    #[inline]
+    pub fn physical_device<'a>(&self, instance: &'a Arc<Instance>) -> PhysicalDevice<'a> {
+        PhysicalDevice::from_index(instance, self.index)
+            .expect("the candidate doesn't belong to this instance")
+    }
+
+    /// Returns the queue families chosen for this candidate, each one once and with a priority
+    /// of 0.5, in the form expected by `Device::new`.
+    ///
+    /// The queues returned by `Device::new` are in the order of the graphics, present, compute
+    /// and transfer families, skipping the families that were already listed.
+    pub fn queue_families<'a>(&self, physical_device: PhysicalDevice<'a>)
+                              -> Vec<(QueueFamily<'a>, f32)> {
+        let mut ids: Vec<u32> = Vec::with_capacity(4);
+        for &id in [self.graphics_family,
+                    self.present_family,
+                    self.compute_family,
+                    self.transfer_family]
+            .iter()
+            .flatten()
+        {
+            if !ids.contains(&id) {
+                ids.push(id);
+            }
+        }
+
+        ids.into_iter()
+            .map(|id| {
+                     let family = physical_device
+                         .queue_family_by_id(id)
+                         .expect("the candidate doesn't belong to this physical device");
+                     (family, 0.5)
+                 })
+            .collect()
+    }
+}
+
+/// A physical device that doesn't meet the requirements of a `PhysicalDeviceSelector`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rejection {
+    /// Index of the physical device in the physical devices list of the instance.
+    pub index: usize,
+    /// Name of the device.
+    pub name: String,
+    /// Every requirement that the device doesn't meet.
+    pub reasons: Vec<RejectionReason>,
+}
+
+/// A requirement that a physical device doesn't meet.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RejectionReason {
+    /// The device uses a version of Vulkan that is too old.
+    ApiVersionTooLow {
+        required: Version,
+        supported: Version,
+    },
+    /// Some of the extensions aren't supported.
+    MissingExtensions(DeviceExtensions),
+    /// Some of the features aren't supported.
+    MissingFeatures(Features),
+    /// No queue family supports graphics operations.
+    NoGraphicsQueue,
+    /// No queue family can present to the surface.
+    NoPresentQueue,
+    /// No queue family supports compute operations without graphics operations.
+    NoDedicatedComputeQueue,
+    /// No queue family supports transfers without graphics operations.
+    NoDedicatedTransferQueue,
+    /// A limit is lower than the minimum.
+    LimitTooLow {
+        name: &'static str,
+        minimum: u64,
+        value: u64,
+    },
+    /// Images of a format don't support a usage with optimal tiling.
+    UnsupportedFormat {
+        format: Format,
+        usage: ImageUsage,
+    },
+}
+
+impl error::Error for RejectionReason {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            RejectionReason::ApiVersionTooLow { .. } => {
+                "the device uses a version of Vulkan that is too old"
+            },
+            RejectionReason::MissingExtensions(_) => "some of the extensions aren't supported",
+            RejectionReason::MissingFeatures(_) => "some of the features aren't supported",
+            RejectionReason::NoGraphicsQueue => "no queue family supports graphics operations",
+            RejectionReason::NoPresentQueue => "no queue family can present to the surface",
+            RejectionReason::NoDedicatedComputeQueue => {
+                "no queue family supports compute operations without graphics operations"
+            },
+            RejectionReason::NoDedicatedTransferQueue => {
+                "no queue family supports transfers without graphics operations"
+            },
+            RejectionReason::LimitTooLow { .. } => "a limit is lower than the minimum",
+            RejectionReason::UnsupportedFormat { .. } => {
+                "images of a format don't support a usage with optimal tiling"
+            },
+        }
+    }
+}
+
+impl fmt::Display for RejectionReason {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            RejectionReason::ApiVersionTooLow { required, supported } => {
+                write!(fmt, "the device uses Vulkan {}, but {} is required", supported, required)
+            },
+            RejectionReason::MissingExtensions(ref extensions) => {
+                write!(fmt, "the extensions {:?} aren't supported", extensions)
+            },
+            RejectionReason::LimitTooLow { name, minimum, value } => {
+                write!(fmt, "the limit {} is {}, but at least {} is required", name, value,
+                       minimum)
+            },
+            RejectionReason::UnsupportedFormat { format, ref usage } => {
+                write!(fmt, "images of format {:?} don't support {:?}", format, usage)
+            },
+            _ => write!(fmt, "{}", error::Error::description(self)),
+        }
+    }
+}
+
+// Returns the usages of images that are allowed by the features of a format.
+fn image_usage_from_format_features(features: u32, transfer_bits: bool) -> ImageUsage {
+    let has = |bit| features & bit != 0;
+    let attachment = has(vk::FORMAT_FEATURE_COLOR_ATTACHMENT_BIT) ||
+        has(vk::FORMAT_FEATURE_DEPTH_STENCIL_ATTACHMENT_BIT);
+
+    ImageUsage {
+        transfer_source: if transfer_bits {
+            has(vk::FORMAT_FEATURE_TRANSFER_SRC_BIT_KHR)
+        } else {
+            features != 0
+        },
+        transfer_destination: if transfer_bits {
+            has(vk::FORMAT_FEATURE_TRANSFER_DST_BIT_KHR)
+        } else {
+            features != 0
+        },
+        sampled: has(vk::FORMAT_FEATURE_SAMPLED_IMAGE_BIT),
+        storage: has(vk::FORMAT_FEATURE_STORAGE_IMAGE_BIT),
+        color_attachment: has(vk::FORMAT_FEATURE_COLOR_ATTACHMENT_BIT),
+        depth_stencil_attachment: has(vk::FORMAT_FEATURE_DEPTH_STENCIL_ATTACHMENT_BIT),
+        transient_attachment: attachment,
+        input_attachment: attachment,
+    }
+}
+
+// Returns true if every usage of `required` is in `supported`.
+#[inline]
+fn usage_supported(supported: &ImageUsage, required: &ImageUsage) -> bool {
+    let required = required.to_usage_bits();
+    supported.to_usage_bits() & required == required
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use device::DeviceExtensions;
+    use features::Features;
+    use format::Format;
+    use image::ImageUsage;
+    use instance::DedicatedQueue;
+    use instance::PhysicalDeviceDescription;
+    use instance::PhysicalDeviceSelector;
+    use instance::PhysicalDeviceType;
+    use instance::QueueFamilyDescription;
+    use instance::RejectionReason;
+    use version::Version;
+
+    fn family(id: u32, graphics: bool, compute: bool, transfer: bool)
+              -> QueueFamilyDescription {
+        QueueFamilyDescription {
+            id,
+            queues_count: 1,
+            graphics,
+            compute,
+            transfer,
+            present: graphics,
+        }
+    }
+
+    fn fake_device(index: usize, ty: PhysicalDeviceType) -> PhysicalDeviceDescription {
+        PhysicalDeviceDescription {
+            index,
+            name: format!("device {}", index),
+            ty,
+            api_version: Version::V1_1,
+            extensions: DeviceExtensions::none(),
+            features: Features::none(),
+            queue_families: vec![family(0, true, true, true)],
+            limits: HashMap::new(),
+            formats: HashMap::new(),
+            device_local_memory: 1 << 30,
+        }
+    }
+
+    #[test]
+    fn ranking_by_type() {
+        let devices = vec![fake_device(0, PhysicalDeviceType::Cpu),
+                           fake_device(1, PhysicalDeviceType::IntegratedGpu),
+                           fake_device(2, PhysicalDeviceType::DiscreteGpu)];
+
+        let selection = PhysicalDeviceSelector::new().graphics().rank(&devices);
+        assert!(selection.rejections.is_empty());
+        let order = selection.candidates.iter().map(|c| c.index).collect::<Vec<_>>();
+        assert_eq!(order, vec![2, 1, 0]);
+        assert_eq!(selection.best().unwrap().graphics_family, Some(0));
+    }
+
+    #[test]
+    fn dedicated_queues() {
+        let mut integrated = fake_device(0, PhysicalDeviceType::IntegratedGpu);
+        integrated.queue_families.push(family(1, false, true, true));
+        integrated.queue_families.push(family(2, false, false, true));
+        let discrete = fake_device(1, PhysicalDeviceType::DiscreteGpu);
+
+        let selector = PhysicalDeviceSelector::new()
+            .dedicated_compute(DedicatedQueue::Preferred)
+            .dedicated_transfer(DedicatedQueue::Required);
+        let selection = selector.rank(vec![&integrated, &discrete]);
+
+        assert_eq!(selection.candidates.len(), 1);
+        assert_eq!(selection.candidates[0].compute_family, Some(1));
+        assert_eq!(selection.candidates[0].transfer_family, Some(2));
+        assert_eq!(selection.rejections[0].index, 1);
+        assert_eq!(selection.rejections[0].reasons,
+                   vec![RejectionReason::NoDedicatedTransferQueue]);
+
+        // When the dedicated families are only preferred, the graphics family is used instead.
+        // The type of the device still matters more than its queue families.
+        let selector = PhysicalDeviceSelector::new()
+            .dedicated_compute(DedicatedQueue::Preferred)
+            .dedicated_transfer(DedicatedQueue::Preferred);
+        let selection = selector.rank(vec![&integrated, &discrete]);
+        assert_eq!(selection.candidates[0].index, 1);
+        assert_eq!(selection.candidates[0].compute_family, Some(0));
+        assert_eq!(selection.candidates[0].transfer_family, Some(0));
+        assert_eq!(selection.candidates[1].compute_family, Some(1));
+
+        // Without a transfer-only family, the compute family isn't used for both.
+        let selector = selector.dedicated_transfer(DedicatedQueue::Required);
+        integrated.queue_families.pop();
+        let selection = selector.rank(Some(&integrated));
+        assert_eq!(selection.rejections[0].reasons,
+                   vec![RejectionReason::NoDedicatedTransferQueue]);
+    }
+
+    #[test]
+    fn rejection_reasons() {
+        let mut device = fake_device(0, PhysicalDeviceType::DiscreteGpu);
+        device.api_version = Version::V1_0;
+        device.limits.insert("max_push_constants_size", 128);
+        device.formats.insert(Format::R8G8B8A8Unorm,
+                              ImageUsage {
+                                  sampled: true,
+                                  ..ImageUsage::none()
+                              });
+
+        let extensions = DeviceExtensions {
+            khr_dedicated_allocation: true,
+            ..DeviceExtensions::none()
+        };
+        let features = Features {
+            geometry_shader: true,
+            ..Features::none()
+        };
+        let color_attachment = ImageUsage {
+            color_attachment: true,
+            ..ImageUsage::none()
+        };
+        let selector = PhysicalDeviceSelector::new()
+            .extensions(&extensions)
+            .features(&features)
+            .min_limit("max_push_constants_size", |l| l.max_push_constants_size() as u64, 256)
+            .format(Format::R8G8B8A8Unorm, color_attachment);
+
+        let selection = selector.rank(Some(&device));
+        assert!(selection.candidates.is_empty());
+        assert_eq!(selection.rejections[0].reasons,
+                   vec![RejectionReason::MissingExtensions(DeviceExtensions {
+                                                                khr_dedicated_allocation: true,
+                                                                khr_get_memory_requirements2:
+                                                                    true,
+                                                                ..DeviceExtensions::none()
+                                                            }),
+                        RejectionReason::MissingFeatures(features.clone()),
+                        RejectionReason::LimitTooLow {
+                            name: "max_push_constants_size",
+                            minimum: 256,
+                            value: 128,
+                        },
+                        RejectionReason::UnsupportedFormat {
+                            format: Format::R8G8B8A8Unorm,
+                            usage: color_attachment,
+                        }]);
+
+        // The dependencies that are part of Vulkan 1.1 aren't required.
+        device.api_version = Version::V1_1;
+        device.extensions.khr_dedicated_allocation = true;
+        device.features.geometry_shader = true;
+        device.limits.insert("max_push_constants_size", 256);
+        device.formats.insert(Format::R8G8B8A8Unorm, ImageUsage::all());
+        assert_eq!(selector.rank(Some(&device)).candidates.len(), 1);
+
+        let selector = selector.api_version(Version::V1_2);
+        assert_eq!(selector.rank(Some(&device)).rejections[0].reasons,
+                   vec![RejectionReason::ApiVersionTooLow {
+                            required: Version::V1_2,
+                            supported: Version::V1_1,
+                        }]);
+    }
+}
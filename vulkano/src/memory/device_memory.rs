@@ -26,6 +26,7 @@ use device::DeviceOwned;
 use instance::MemoryType;
 use memory::Content;
 use memory::DedicatedAlloc;
+use version::Version;
 use vk;
 
 /// Represents memory that has been allocated.
@@ -98,7 +99,9 @@ impl DeviceMemory {
             let vk = device.pointers();
 
             // Decide whether we are going to pass a `vkMemoryDedicatedAllocateInfoKHR`.
-            let dedicated_alloc_info = if device.loaded_extensions().khr_dedicated_allocation {
+            let dedicated_alloc_info = if device.api_version() >= Version::V1_1 ||
+                device.loaded_extensions().khr_dedicated_allocation
+            {
                 match resource {
                     DedicatedAlloc::Buffer(buffer) => {
                         Some(vk::MemoryDedicatedAllocateInfoKHR {
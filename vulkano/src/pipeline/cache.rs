@@ -13,21 +13,30 @@
 //! pipelines on the disk.
 //!
 //! You can create either an empty cache or a cache from some initial data. Whenever you create a
-//! graphics or compute pipeline, you have the possibility to pass a reference to that cache.
-//! TODO: ^ that's not the case yet
+//! graphics or compute pipeline, you have the possibility to pass a reference to that cache with
+//! `GraphicsPipelineBuilder::build_with_cache` or `ComputePipeline::with_cache`.
 //! The Vulkan implementation will then look in the cache for an existing entry, or add one if it
 //! doesn't exist.
 //!
 //! Once that is done, you can extract the data from the cache and store it. See the documentation
 //! of [`get_data`](struct.PipelineCache.html#method.get_data) for example of how to store the data
-//! on the disk, and [`with_data`](struct.PipelineCache.html#method.with_data) for how to reload it.
+//! on the disk, and [`with_checked_data`](struct.PipelineCache.html#method.with_checked_data) for
+//! how to reload it.
+//!
+//! The data starts with a header that identifies the physical device and the driver that produced
+//! it. Data produced by another device or by another version of the driver is usually useless, and
+//! can be detected with [`PipelineCacheHeader`](struct.PipelineCacheHeader.html).
 //!
 
+use std::error;
+use std::fmt;
 use std::mem::MaybeUninit;
 use std::ptr;
 use std::sync::Arc;
 
 use device::Device;
+use device::DeviceOwned;
+use instance::PhysicalDevice;
 
 use OomError;
 use VulkanObject;
@@ -50,11 +59,14 @@ impl PipelineCache {
     /// implementation. Therefore you can easily crash your application or the system by passing
     /// wrong data. Hence why this function is unsafe.
     ///
+    /// The header of the data isn't checked. Prefer
+    /// [`with_checked_data`](#method.with_checked_data) if the data comes from a file that may
+    /// have been produced by another device or driver.
+    ///
     /// # Example
     ///
     /// This example loads a cache from a file, if it exists.
     /// See [`get_data`](#method.get_data) for how to store the data in a file.
-    /// TODO: there's a header in the cached data that must be checked ; talk about this
     ///
     /// ```
     /// # use std::sync::Arc;
@@ -87,6 +99,36 @@ impl PipelineCache {
         PipelineCache::new_impl(device, Some(initial_data))
     }
 
+    /// Same as [`with_data`](#method.with_data), but first checks that the header of the data
+    /// matches the physical device of `device`.
+    ///
+    /// Returns an error if the data was produced by another physical device or by another version
+    /// of the driver. In that situation the data should be discarded and replaced with an empty
+    /// cache.
+    ///
+    /// This function is still unsafe, as only the header is checked and not the rest of the data.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use std::sync::Arc;
+    /// # use vulkano::device::Device;
+    /// use std::fs;
+    /// use vulkano::pipeline::cache::PipelineCache;
+    /// # let device: Arc<Device> = return;
+    ///
+    /// let cache = fs::read("pipeline_cache.bin").ok()
+    ///     .and_then(|data| unsafe { PipelineCache::with_checked_data(device.clone(), &data).ok() })
+    ///     .unwrap_or_else(|| PipelineCache::empty(device.clone()).unwrap());
+    /// ```
+    #[inline]
+    pub unsafe fn with_checked_data(device: Arc<Device>, initial_data: &[u8])
+                                    -> Result<Arc<PipelineCache>, PipelineCacheDataError> {
+        let header = PipelineCacheHeader::from_data(initial_data)?;
+        header.check_compatible(device.physical_device())?;
+        Ok(PipelineCache::new_impl(device, Some(initial_data))?)
+    }
+
     /// Builds a new empty pipeline cache.
     ///
     /// # Example
@@ -216,6 +258,13 @@ impl PipelineCache {
     }
 }
 
+unsafe impl DeviceOwned for PipelineCache {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
 unsafe impl VulkanObject for PipelineCache {
     type Object = vk::PipelineCache;
 
@@ -237,9 +286,234 @@ impl Drop for PipelineCache {
     }
 }
 
+/// Header found at the start of the data of a pipeline cache.
+///
+/// The header identifies the physical device and the driver that produced the data.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PipelineCacheHeader {
+    /// Version of the header. Only version 1 is known.
+    pub header_version: u32,
+    /// PCI ID of the vendor of the physical device.
+    pub vendor_id: u32,
+    /// PCI ID of the physical device.
+    pub device_id: u32,
+    /// Identifier of the physical device and driver version that are compatible with the data.
+    pub pipeline_cache_uuid: [u8; 16],
+}
+
+impl PipelineCacheHeader {
+    // Size of version one of the header.
+    const SIZE: usize = 32;
+
+    /// Reads the header at the start of some pipeline cache data.
+    ///
+    /// Returns an error if the data is too short or if the header version is unknown.
+    pub fn from_data(data: &[u8]) -> Result<PipelineCacheHeader, PipelineCacheDataError> {
+        // The fields of the header are always stored with the least significant byte first.
+        let read_u32 = |offset: usize| {
+            (data[offset] as u32) | (data[offset + 1] as u32) << 8 |
+                (data[offset + 2] as u32) << 16 | (data[offset + 3] as u32) << 24
+        };
+
+        if data.len() < 8 {
+            return Err(PipelineCacheDataError::HeaderTooShort);
+        }
+
+        let header_length = read_u32(0) as usize;
+        let header_version = read_u32(4);
+
+        if header_version != vk::PIPELINE_CACHE_HEADER_VERSION_ONE {
+            return Err(PipelineCacheDataError::UnsupportedHeaderVersion(header_version));
+        }
+
+        if header_length < PipelineCacheHeader::SIZE || data.len() < header_length {
+            return Err(PipelineCacheDataError::HeaderTooShort);
+        }
+
+        let mut pipeline_cache_uuid = [0; 16];
+        pipeline_cache_uuid.copy_from_slice(&data[16 .. 32]);
+
+        Ok(PipelineCacheHeader {
+               header_version,
+               vendor_id: read_u32(8),
+               device_id: read_u32(12),
+               pipeline_cache_uuid,
+           })
+    }
+
+    /// Checks whether the data was produced by the given physical device with the same version
+    /// of the driver.
+    #[inline]
+    pub fn check_compatible(&self, physical_device: PhysicalDevice)
+                            -> Result<(), PipelineCacheDataError> {
+        self.check_ids(physical_device.pci_vendor_id(),
+                       physical_device.pci_device_id(),
+                       physical_device.uuid())
+    }
+
+    fn check_ids(&self, vendor_id: u32, device_id: u32, pipeline_cache_uuid: &[u8; 16])
+                 -> Result<(), PipelineCacheDataError> {
+        if self.vendor_id != vendor_id {
+            return Err(PipelineCacheDataError::VendorIdMismatch {
+                           expected: vendor_id,
+                           obtained: self.vendor_id,
+                       });
+        }
+
+        if self.device_id != device_id {
+            return Err(PipelineCacheDataError::DeviceIdMismatch {
+                           expected: device_id,
+                           obtained: self.device_id,
+                       });
+        }
+
+        if &self.pipeline_cache_uuid != pipeline_cache_uuid {
+            return Err(PipelineCacheDataError::UuidMismatch);
+        }
+
+        Ok(())
+    }
+}
+
+/// Error that can happen when loading pipeline cache data.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PipelineCacheDataError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// The data is too short to contain a header.
+    HeaderTooShort,
+
+    /// The version of the header is unknown.
+    UnsupportedHeaderVersion(u32),
+
+    /// The data was produced by a physical device of another vendor.
+    VendorIdMismatch {
+        /// Vendor ID of the physical device.
+        expected: u32,
+        /// Vendor ID found in the header.
+        obtained: u32,
+    },
+
+    /// The data was produced by another physical device.
+    DeviceIdMismatch {
+        /// Device ID of the physical device.
+        expected: u32,
+        /// Device ID found in the header.
+        obtained: u32,
+    },
+
+    /// The data was produced by another physical device or by another version of the driver.
+    UuidMismatch,
+}
+
+impl error::Error for PipelineCacheDataError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            PipelineCacheDataError::OomError(_) => "not enough memory available",
+            PipelineCacheDataError::HeaderTooShort => {
+                "the data is too short to contain a header"
+            },
+            PipelineCacheDataError::UnsupportedHeaderVersion(_) => {
+                "the version of the header is unknown"
+            },
+            PipelineCacheDataError::VendorIdMismatch { .. } => {
+                "the data was produced by a physical device of another vendor"
+            },
+            PipelineCacheDataError::DeviceIdMismatch { .. } => {
+                "the data was produced by another physical device"
+            },
+            PipelineCacheDataError::UuidMismatch => {
+                "the data was produced by another physical device or by another version of the \
+                 driver"
+            },
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&dyn error::Error> {
+        match *self {
+            PipelineCacheDataError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for PipelineCacheDataError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
+
+impl From<OomError> for PipelineCacheDataError {
+    #[inline]
+    fn from(err: OomError) -> PipelineCacheDataError {
+        PipelineCacheDataError::OomError(err)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use pipeline::cache::PipelineCache;
+    use pipeline::cache::PipelineCacheDataError;
+    use pipeline::cache::PipelineCacheHeader;
+
+    fn header_data(vendor_id: u32, device_id: u32, uuid: [u8; 16]) -> Vec<u8> {
+        let mut data = Vec::new();
+        for &value in [32u32, 1, vendor_id, device_id].iter() {
+            data.extend_from_slice(&[value as u8, (value >> 8) as u8, (value >> 16) as u8,
+                                     (value >> 24) as u8]);
+        }
+        data.extend_from_slice(&uuid);
+        data.extend_from_slice(&[0xde, 0xad, 0xbe, 0xef]);
+        data
+    }
+
+    #[test]
+    fn header_parse() {
+        let data = header_data(0x10de, 0x1b80, [7; 16]);
+        let header = PipelineCacheHeader::from_data(&data).unwrap();
+        assert_eq!(header.header_version, 1);
+        assert_eq!(header.vendor_id, 0x10de);
+        assert_eq!(header.device_id, 0x1b80);
+        assert_eq!(header.pipeline_cache_uuid, [7; 16]);
+    }
+
+    #[test]
+    fn header_invalid() {
+        let data = header_data(0x10de, 0x1b80, [7; 16]);
+        assert_eq!(PipelineCacheHeader::from_data(&data[.. 20]),
+                   Err(PipelineCacheDataError::HeaderTooShort));
+        assert_eq!(PipelineCacheHeader::from_data(&[]),
+                   Err(PipelineCacheDataError::HeaderTooShort));
+
+        let mut data = data;
+        data[4] = 2;
+        assert_eq!(PipelineCacheHeader::from_data(&data),
+                   Err(PipelineCacheDataError::UnsupportedHeaderVersion(2)));
+    }
+
+    #[test]
+    fn header_check_ids() {
+        let data = header_data(0x10de, 0x1b80, [7; 16]);
+        let header = PipelineCacheHeader::from_data(&data).unwrap();
+
+        assert_eq!(header.check_ids(0x10de, 0x1b80, &[7; 16]), Ok(()));
+        assert_eq!(header.check_ids(0x1002, 0x1b80, &[7; 16]),
+                   Err(PipelineCacheDataError::VendorIdMismatch {
+                           expected: 0x1002,
+                           obtained: 0x10de,
+                       }));
+        assert_eq!(header.check_ids(0x10de, 0x1b81, &[7; 16]),
+                   Err(PipelineCacheDataError::DeviceIdMismatch {
+                           expected: 0x1b81,
+                           obtained: 0x1b80,
+                       }));
+        assert_eq!(header.check_ids(0x10de, 0x1b80, &[8; 16]),
+                   Err(PipelineCacheDataError::UuidMismatch));
+    }
 
     #[test]
     fn merge_self_forbidden() {
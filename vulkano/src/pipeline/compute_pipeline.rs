@@ -25,7 +25,8 @@ use descriptor::pipeline_layout::PipelineLayoutDescPcRange;
 use descriptor::pipeline_layout::PipelineLayoutNotSupersetError;
 use descriptor::pipeline_layout::PipelineLayoutSuperset;
 use descriptor::pipeline_layout::PipelineLayoutSys;
-use pipeline::shader::EntryPointAbstract;
+use pipeline::cache::PipelineCache;
+use pipeline::shader::ComputeEntryPointAbstract;
 use pipeline::shader::SpecializationConstants;
 
 use Error;
@@ -47,6 +48,8 @@ use vk;
 pub struct ComputePipeline<Pl> {
     inner: Inner,
     pipeline_layout: Pl,
+    workgroup_size: Option<[u32; 3]>,
+    allow_derivatives: bool,
 }
 
 struct Inner {
@@ -60,7 +63,7 @@ impl ComputePipeline<()> {
         device: Arc<Device>, shader: &Cs, specialization: &Cs::SpecializationConstants)
         -> Result<ComputePipeline<PipelineLayout<Cs::PipelineLayout>>, ComputePipelineCreationError>
         where Cs::PipelineLayout: Clone,
-              Cs: EntryPointAbstract
+              Cs: ComputeEntryPointAbstract
     {
         unsafe {
             let pipeline_layout = shader.layout().clone().build(device.clone())?;
@@ -70,6 +73,87 @@ impl ComputePipeline<()> {
                                                             pipeline_layout)
         }
     }
+
+    /// Same as `new`, but passes a pipeline cache to the Vulkan implementation.
+    ///
+    /// The implementation will look for an existing entry in the cache or add a new one, which can
+    /// considerably speed up the creation of the pipeline.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the cache doesn't belong to `device`.
+    ///
+    pub fn with_cache<Cs>(
+        device: Arc<Device>, shader: &Cs, specialization: &Cs::SpecializationConstants,
+        cache: &PipelineCache)
+        -> Result<ComputePipeline<PipelineLayout<Cs::PipelineLayout>>, ComputePipelineCreationError>
+        where Cs::PipelineLayout: Clone,
+              Cs: ComputeEntryPointAbstract
+    {
+        assert_eq!(device.internal_object(), cache.device().internal_object());
+
+        unsafe {
+            let pipeline_layout = shader.layout().clone().build(device.clone())?;
+            ComputePipeline::new_impl(device,
+                                      shader,
+                                      specialization,
+                                      pipeline_layout,
+                                      Some(cache),
+                                      false,
+                                      None)
+        }
+    }
+
+    /// Same as `new`, but allows the pipeline to be used as the base of derivative pipelines.
+    ///
+    /// Creating derivatives of a pipeline can be faster than creating unrelated pipelines, and
+    /// switching between a pipeline and its derivatives can be cheaper on some implementations.
+    pub fn allowing_derivatives<Cs>(
+        device: Arc<Device>, shader: &Cs, specialization: &Cs::SpecializationConstants)
+        -> Result<ComputePipeline<PipelineLayout<Cs::PipelineLayout>>, ComputePipelineCreationError>
+        where Cs::PipelineLayout: Clone,
+              Cs: ComputeEntryPointAbstract
+    {
+        unsafe {
+            let pipeline_layout = shader.layout().clone().build(device.clone())?;
+            ComputePipeline::new_impl(device,
+                                      shader,
+                                      specialization,
+                                      pipeline_layout,
+                                      None,
+                                      true,
+                                      None)
+        }
+    }
+
+    /// Builds a new `ComputePipeline` that is a derivative of `base`, on the same device.
+    ///
+    /// The base pipeline must have been created with `allowing_derivatives`, otherwise an error
+    /// is returned.
+    pub fn derivative_of<Cs, Cp>(
+        base: &Cp, shader: &Cs, specialization: &Cs::SpecializationConstants)
+        -> Result<ComputePipeline<PipelineLayout<Cs::PipelineLayout>>, ComputePipelineCreationError>
+        where Cs::PipelineLayout: Clone,
+              Cs: ComputeEntryPointAbstract,
+              Cp: ComputePipelineAbstract + DeviceOwned
+    {
+        if !base.allows_derivatives() {
+            return Err(ComputePipelineCreationError::BasePipelineDerivativesNotAllowed);
+        }
+
+        let device = base.device().clone();
+
+        unsafe {
+            let pipeline_layout = shader.layout().clone().build(device.clone())?;
+            ComputePipeline::new_impl(device,
+                                      shader,
+                                      specialization,
+                                      pipeline_layout,
+                                      None,
+                                      false,
+                                      Some(base.inner().internal_object()))
+        }
+    }
 }
 
 impl<Pl> ComputePipeline<Pl> {
@@ -82,7 +166,7 @@ impl<Pl> ComputePipeline<Pl> {
                                     pipeline_layout: Pl)
                                     -> Result<ComputePipeline<Pl>, ComputePipelineCreationError>
         where Cs::PipelineLayout: Clone,
-              Cs: EntryPointAbstract,
+              Cs: ComputeEntryPointAbstract,
               Pl: PipelineLayoutAbstract
     {
         unsafe {
@@ -101,11 +185,46 @@ impl<Pl> ComputePipeline<Pl> {
         pipeline_layout: Pl)
         -> Result<ComputePipeline<Pl>, ComputePipelineCreationError>
         where Cs::PipelineLayout: Clone,
-              Cs: EntryPointAbstract,
+              Cs: ComputeEntryPointAbstract,
+              Pl: PipelineLayoutAbstract
+    {
+        ComputePipeline::new_impl(device, shader, specialization, pipeline_layout, None, false,
+                                  None)
+    }
+
+    unsafe fn new_impl<Cs>(
+        device: Arc<Device>, shader: &Cs, specialization: &Cs::SpecializationConstants,
+        pipeline_layout: Pl, cache: Option<&PipelineCache>, allow_derivatives: bool,
+        base_pipeline: Option<vk::Pipeline>)
+        -> Result<ComputePipeline<Pl>, ComputePipelineCreationError>
+        where Cs::PipelineLayout: Clone,
+              Cs: ComputeEntryPointAbstract,
               Pl: PipelineLayoutAbstract
     {
         let vk = device.pointers();
 
+        let workgroup_size = shader.workgroup_size().map(|size| size.resolve(specialization));
+        if let Some(size) = workgroup_size {
+            let limits = device.physical_device().limits();
+            let max_size = limits.max_compute_work_group_size();
+            let invocations = size[0] as u64 * size[1] as u64 * size[2] as u64;
+
+            if size[0] > max_size[0] || size[1] > max_size[1] || size[2] > max_size[2] {
+                return Err(ComputePipelineCreationError::WorkgroupSizeExceedsLimit {
+                    requested: size,
+                    max_supported: max_size,
+                });
+            }
+
+            let max_invocations = limits.max_compute_work_group_invocations();
+            if invocations > max_invocations as u64 {
+                return Err(ComputePipelineCreationError::WorkgroupInvocationsExceedLimit {
+                    requested: invocations,
+                    max_supported: max_invocations,
+                });
+            }
+        }
+
         let pipeline = {
             let spec_descriptors = Cs::SpecializationConstants::descriptors();
             let specialization = vk::SpecializationInfo {
@@ -129,19 +248,32 @@ impl<Pl> ComputePipeline<Pl> {
                 },
             };
 
+            let flags = {
+                let mut flags = 0;
+                if allow_derivatives {
+                    flags |= vk::PIPELINE_CREATE_ALLOW_DERIVATIVES_BIT;
+                }
+                if base_pipeline.is_some() {
+                    flags |= vk::PIPELINE_CREATE_DERIVATIVE_BIT;
+                }
+                flags
+            };
+
             let infos = vk::ComputePipelineCreateInfo {
                 sType: vk::STRUCTURE_TYPE_COMPUTE_PIPELINE_CREATE_INFO,
                 pNext: ptr::null(),
-                flags: 0,
+                flags,
                 stage: stage,
                 layout: PipelineLayoutAbstract::sys(&pipeline_layout).internal_object(),
-                basePipelineHandle: 0,
-                basePipelineIndex: 0,
+                basePipelineHandle: base_pipeline.unwrap_or(0),
+                basePipelineIndex: -1,
             };
 
             let mut output = MaybeUninit::uninit();
             check_errors(vk.CreateComputePipelines(device.internal_object(),
-                                                   0,
+                                                   cache
+                                                       .map(|c| c.internal_object())
+                                                       .unwrap_or(0),
                                                    1,
                                                    &infos,
                                                    ptr::null(),
@@ -155,6 +287,8 @@ impl<Pl> ComputePipeline<Pl> {
                    pipeline: pipeline,
                },
                pipeline_layout: pipeline_layout,
+               workgroup_size: workgroup_size,
+               allow_derivatives,
            })
     }
 }
@@ -178,12 +312,31 @@ impl<Pl> ComputePipeline<Pl> {
     pub fn layout(&self) -> &Pl {
         &self.pipeline_layout
     }
+
+    /// Returns the size of the local workgroup of the shader, with the specialization constants
+    /// applied, or `None` if the entry point didn't provide it.
+    #[inline]
+    pub fn workgroup_size(&self) -> Option<[u32; 3]> {
+        self.workgroup_size
+    }
+
+    /// Returns true if the pipeline can be used as the base of derivative pipelines.
+    #[inline]
+    pub fn allows_derivatives(&self) -> bool {
+        self.allow_derivatives
+    }
 }
 
 /// Trait implemented on all compute pipelines.
 pub unsafe trait ComputePipelineAbstract: PipelineLayoutAbstract {
     /// Returns an opaque object that represents the inside of the compute pipeline.
     fn inner(&self) -> ComputePipelineSys;
+
+    /// Returns the size of the local workgroup of the shader, or `None` if it is unknown.
+    fn workgroup_size(&self) -> Option<[u32; 3]>;
+
+    /// Returns true if the pipeline can be used as the base of derivative pipelines.
+    fn allows_derivatives(&self) -> bool;
 }
 
 unsafe impl<Pl> ComputePipelineAbstract for ComputePipeline<Pl>
@@ -193,6 +346,16 @@ unsafe impl<Pl> ComputePipelineAbstract for ComputePipeline<Pl>
     fn inner(&self) -> ComputePipelineSys {
         ComputePipelineSys(self.inner.pipeline, PhantomData)
     }
+
+    #[inline]
+    fn workgroup_size(&self) -> Option<[u32; 3]> {
+        self.workgroup_size
+    }
+
+    #[inline]
+    fn allows_derivatives(&self) -> bool {
+        self.allow_derivatives
+    }
 }
 
 unsafe impl<T> ComputePipelineAbstract for T
@@ -203,6 +366,16 @@ unsafe impl<T> ComputePipelineAbstract for T
     fn inner(&self) -> ComputePipelineSys {
         (**self).inner()
     }
+
+    #[inline]
+    fn workgroup_size(&self) -> Option<[u32; 3]> {
+        (**self).workgroup_size()
+    }
+
+    #[inline]
+    fn allows_derivatives(&self) -> bool {
+        (**self).allows_derivatives()
+    }
 }
 
 /// Opaque object that represents the inside of the compute pipeline. Can be made into a trait
@@ -253,6 +426,11 @@ unsafe impl<Pl> PipelineLayoutDesc for ComputePipeline<Pl>
         self.pipeline_layout.descriptor(set, binding)
     }
 
+    #[inline]
+    fn provided_set_layout(&self, set: usize) -> Option<Arc<UnsafeDescriptorSetLayout>> {
+        self.pipeline_layout.provided_set_layout(set)
+    }
+
     #[inline]
     fn num_push_constants_ranges(&self) -> usize {
         self.pipeline_layout.num_push_constants_ranges()
@@ -302,6 +480,22 @@ pub enum ComputePipelineCreationError {
     PipelineLayoutCreationError(PipelineLayoutCreationError),
     /// The pipeline layout is not compatible with what the shader expects.
     IncompatiblePipelineLayout(PipelineLayoutNotSupersetError),
+    /// One of the dimensions of the workgroup size is too large for the device's limits.
+    WorkgroupSizeExceedsLimit {
+        /// The workgroup size of the shader.
+        requested: [u32; 3],
+        /// The maximum supported workgroup size.
+        max_supported: [u32; 3],
+    },
+    /// The total number of invocations in a workgroup is too large for the device's limits.
+    WorkgroupInvocationsExceedLimit {
+        /// The number of invocations in a workgroup of the shader.
+        requested: u64,
+        /// The maximum supported number of invocations.
+        max_supported: u32,
+    },
+    /// The base pipeline of a derivative pipeline wasn't created with `allowing_derivatives`.
+    BasePipelineDerivativesNotAllowed,
 }
 
 impl error::Error for ComputePipelineCreationError {
@@ -313,6 +507,12 @@ impl error::Error for ComputePipelineCreationError {
                 "error while creating the pipeline layout object",
             ComputePipelineCreationError::IncompatiblePipelineLayout(_) =>
                 "the pipeline layout is not compatible with what the shader expects",
+            ComputePipelineCreationError::WorkgroupSizeExceedsLimit { .. } =>
+                "the workgroup size of the shader is too large for the device's limits",
+            ComputePipelineCreationError::WorkgroupInvocationsExceedLimit { .. } =>
+                "the number of invocations in a workgroup is too large for the device's limits",
+            ComputePipelineCreationError::BasePipelineDerivativesNotAllowed =>
+                "the base pipeline of a derivative pipeline doesn't allow derivatives",
         }
     }
 
@@ -322,6 +522,9 @@ impl error::Error for ComputePipelineCreationError {
             ComputePipelineCreationError::OomError(ref err) => Some(err),
             ComputePipelineCreationError::PipelineLayoutCreationError(ref err) => Some(err),
             ComputePipelineCreationError::IncompatiblePipelineLayout(ref err) => Some(err),
+            ComputePipelineCreationError::WorkgroupSizeExceedsLimit { .. } => None,
+            ComputePipelineCreationError::WorkgroupInvocationsExceedLimit { .. } => None,
+            ComputePipelineCreationError::BasePipelineDerivativesNotAllowed => None,
         }
     }
 }
@@ -379,10 +582,13 @@ mod tests {
     use descriptor::descriptor::DescriptorDescTy;
     use descriptor::descriptor::ShaderStages;
     use descriptor::descriptor_set::PersistentDescriptorSet;
+    use descriptor::pipeline_layout::EmptyPipelineDesc;
     use descriptor::pipeline_layout::PipelineLayoutAbstract;
     use descriptor::pipeline_layout::PipelineLayoutDesc;
     use descriptor::pipeline_layout::PipelineLayoutDescPcRange;
     use pipeline::ComputePipeline;
+    use pipeline::ComputePipelineCreationError;
+    use pipeline::cache::PipelineCache;
     use pipeline::shader::ShaderModule;
     use pipeline::shader::SpecializationConstants;
     use pipeline::shader::SpecializationMapEntry;
@@ -394,6 +600,58 @@ mod tests {
     // TODO: test for basic creation
     // TODO: test for pipeline layout error
 
+    // Compute shader with an empty `main` function and a workgroup size of 1.
+    const EMPTY_MODULE: [u32; 35] = [
+        0x07230203, 0x00010000, 0, 5, 0,
+        0x00020011, 1,
+        0x0003000e, 0, 1,
+        0x0005000f, 5, 1, 0x6e69616d, 0,
+        0x00060010, 1, 17, 1, 1, 1,
+        0x00020013, 2,
+        0x00030021, 3, 2,
+        0x00050036, 2, 1, 0, 3,
+        0x000200f8, 4,
+        0x000100fd,
+        0x00010038,
+    ];
+
+    #[test]
+    fn derivatives() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let module = unsafe { ShaderModule::from_words(device.clone(), &EMPTY_MODULE).unwrap() };
+        let shader = unsafe {
+            module.compute_entry_point::<(), _>(CStr::from_bytes_with_nul(b"main\0").unwrap(),
+                                                EmptyPipelineDesc)
+        };
+
+        let base = ComputePipeline::allowing_derivatives(device.clone(), &shader, &()).unwrap();
+        assert!(base.allows_derivatives());
+
+        let derivative = ComputePipeline::derivative_of(&base, &shader, &()).unwrap();
+        assert!(!derivative.allows_derivatives());
+
+        match ComputePipeline::derivative_of(&derivative, &shader, &()) {
+            Err(ComputePipelineCreationError::BasePipelineDerivativesNotAllowed) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn with_cache() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let module = unsafe { ShaderModule::from_words(device.clone(), &EMPTY_MODULE).unwrap() };
+        let shader = unsafe {
+            module.compute_entry_point::<(), _>(CStr::from_bytes_with_nul(b"main\0").unwrap(),
+                                                EmptyPipelineDesc)
+        };
+
+        let cache = PipelineCache::empty(device.clone()).unwrap();
+        let _pipeline = ComputePipeline::with_cache(device, &shader, &(), &cache).unwrap();
+        assert!(!cache.get_data().unwrap().is_empty());
+    }
+
     #[test]
     fn spec_constants() {
         // This test checks whether specialization constants work.
@@ -188,6 +188,18 @@ pub struct DynamicStencilValue {
     pub value: u32,
 }
 
+/// Container for dynamic StencilFaceFlags and stencil operations.
+///
+/// Used with the `VK_EXT_extended_dynamic_state` extension.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct DynamicStencilOps {
+    pub face: StencilFaceFlags,
+    pub fail_op: StencilOp,
+    pub pass_op: StencilOp,
+    pub depth_fail_op: StencilOp,
+    pub compare: Compare,
+}
+
 /// Allows you to ask the GPU to exclude fragments that are outside of a certain range.
 #[derive(Debug, Clone, PartialEq)]
 pub enum DepthBounds {
@@ -29,7 +29,9 @@ use pipeline::blend::LogicOp;
 use pipeline::depth_stencil::Compare;
 use pipeline::depth_stencil::DepthBounds;
 use pipeline::depth_stencil::DepthStencil;
+use pipeline::cache::PipelineCache;
 use pipeline::graphics_pipeline::GraphicsPipeline;
+use pipeline::graphics_pipeline::GraphicsPipelineAbstract;
 use pipeline::graphics_pipeline::GraphicsPipelineCreationError;
 use pipeline::graphics_pipeline::Inner as GraphicsPipelineInner;
 use pipeline::input_assembly::PrimitiveTopology;
@@ -43,6 +45,7 @@ use pipeline::shader::GraphicsEntryPointAbstract;
 use pipeline::shader::GraphicsShaderType;
 use pipeline::shader::ShaderInterfaceDefMatch;
 use pipeline::shader::SpecializationConstants;
+use pipeline::shader::non_flat_fragment_input;
 use pipeline::vertex::BufferlessDefinition;
 use pipeline::vertex::SingleBufferDefinition;
 use pipeline::vertex::VertexDefinition;
@@ -55,6 +58,7 @@ use check_errors;
 use descriptor::pipeline_layout::PipelineLayoutDesc;
 use descriptor::pipeline_layout::PipelineLayoutDescTweaks;
 use descriptor::pipeline_layout::PipelineLayoutSuperset;
+use device::DeviceOwned;
 use framebuffer::RenderPassSubpassInterface;
 use vk;
 
@@ -76,6 +80,22 @@ pub struct GraphicsPipelineBuilder<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss,
     depth_stencil: DepthStencil,
     blend: Blend,
     render_pass: Option<Subpass<Rp>>,
+    extended_dynamic_state: ExtendedDynamicState,
+    allow_derivatives: bool,
+    base_pipeline: Option<Arc<dyn GraphicsPipelineAbstract + Send + Sync>>,
+}
+
+// States of `VK_EXT_extended_dynamic_state` that are dynamic.
+#[derive(Debug, Copy, Clone, Default)]
+struct ExtendedDynamicState {
+    cull_mode: bool,
+    front_face: bool,
+    primitive_topology: bool,
+    depth_test_enable: bool,
+    depth_write_enable: bool,
+    depth_compare_op: bool,
+    stencil_test_enable: bool,
+    stencil_op: bool,
 }
 
 // Additional parameters if tessellation is used.
@@ -122,6 +142,9 @@ impl
                 depth_stencil: DepthStencil::disabled(),
                 blend: Blend::pass_through(),
                 render_pass: None,
+                extended_dynamic_state: Default::default(),
+                allow_derivatives: false,
+                base_pipeline: None,
             }
         }
     }
@@ -155,6 +178,11 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
           Rp: RenderPassAbstract + RenderPassSubpassInterface<Fs::OutputDefinition>
 {
     /// Builds the graphics pipeline, using an inferred a pipeline layout.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the base pipeline set with `derivative_of` doesn't belong to `device`.
+    ///
     // TODO: replace Box<PipelineLayoutAbstract> with a PipelineUnion struct without template params
     pub fn build(self, device: Arc<Device>)
                  -> Result<GraphicsPipeline<Vdef, Box<dyn PipelineLayoutAbstract + Send + Sync>, Rp>,
@@ -166,9 +194,55 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
     ///
     /// Configures the inferred layout for each descriptor `(set, binding)` in `dynamic_buffers` to accept dynamic
     /// buffers.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the base pipeline set with `derivative_of` doesn't belong to `device`.
+    ///
     pub fn with_auto_layout(self, device: Arc<Device>, dynamic_buffers: &[(usize, usize)])
                             -> Result<GraphicsPipeline<Vdef, Box<dyn PipelineLayoutAbstract + Send + Sync>, Rp>,
                                       GraphicsPipelineCreationError>
+    {
+        self.with_auto_layout_impl(device, dynamic_buffers, None)
+    }
+
+    /// Builds the graphics pipeline, using an inferred pipeline layout and a pipeline cache.
+    ///
+    /// The Vulkan implementation will look for an existing entry in the cache or add a new one,
+    /// which can considerably speed up the creation of the pipeline.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the cache doesn't belong to `device`.
+    /// - Panics if the base pipeline set with `derivative_of` doesn't belong to `device`.
+    ///
+    pub fn build_with_cache(self, device: Arc<Device>, cache: &PipelineCache)
+                            -> Result<GraphicsPipeline<Vdef, Box<dyn PipelineLayoutAbstract + Send + Sync>, Rp>,
+                                      GraphicsPipelineCreationError>
+    {
+        self.with_auto_layout_impl(device, &[], Some(cache))
+    }
+
+    /// Same as `with_auto_layout`, but also passes a pipeline cache to the Vulkan
+    /// implementation.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the cache doesn't belong to `device`.
+    /// - Panics if the base pipeline set with `derivative_of` doesn't belong to `device`.
+    ///
+    pub fn with_auto_layout_and_cache(self, device: Arc<Device>,
+                                      dynamic_buffers: &[(usize, usize)], cache: &PipelineCache)
+                                      -> Result<GraphicsPipeline<Vdef, Box<dyn PipelineLayoutAbstract + Send + Sync>, Rp>,
+                                                GraphicsPipelineCreationError>
+    {
+        self.with_auto_layout_impl(device, dynamic_buffers, Some(cache))
+    }
+
+    fn with_auto_layout_impl(self, device: Arc<Device>, dynamic_buffers: &[(usize, usize)],
+                             cache: Option<&PipelineCache>)
+                             -> Result<GraphicsPipeline<Vdef, Box<dyn PipelineLayoutAbstract + Send + Sync>, Rp>,
+                                       GraphicsPipelineCreationError>
     {
         let pipeline_layout;
 
@@ -298,7 +372,7 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
             }
         }
 
-        self.with_pipeline_layout(device, pipeline_layout)
+        self.with_pipeline_layout_impl(device, pipeline_layout, cache)
     }
 
     /// Builds the graphics pipeline.
@@ -306,15 +380,66 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
     /// Does the same as `build`, except that `build` automatically builds the pipeline layout
     /// object corresponding to the union of your shaders while this function allows you to specify
     /// the pipeline layout.
-    pub fn with_pipeline_layout<Pl>(mut self, device: Arc<Device>, pipeline_layout: Pl)
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the base pipeline set with `derivative_of` doesn't belong to `device`.
+    ///
+    pub fn with_pipeline_layout<Pl>(self, device: Arc<Device>, pipeline_layout: Pl)
                                     -> Result<GraphicsPipeline<Vdef, Pl, Rp>,
                                               GraphicsPipelineCreationError>
         where Pl: PipelineLayoutAbstract
+    {
+        self.with_pipeline_layout_impl(device, pipeline_layout, None)
+    }
+
+    /// Same as `with_pipeline_layout`, but also passes a pipeline cache to the Vulkan
+    /// implementation.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the cache doesn't belong to `device`.
+    /// - Panics if the base pipeline set with `derivative_of` doesn't belong to `device`.
+    ///
+    pub fn with_pipeline_layout_and_cache<Pl>(self, device: Arc<Device>, pipeline_layout: Pl,
+                                              cache: &PipelineCache)
+                                              -> Result<GraphicsPipeline<Vdef, Pl, Rp>,
+                                                        GraphicsPipelineCreationError>
+        where Pl: PipelineLayoutAbstract
+    {
+        self.with_pipeline_layout_impl(device, pipeline_layout, Some(cache))
+    }
+
+    fn with_pipeline_layout_impl<Pl>(mut self, device: Arc<Device>, pipeline_layout: Pl,
+                                     cache: Option<&PipelineCache>)
+                                     -> Result<GraphicsPipeline<Vdef, Pl, Rp>,
+                                               GraphicsPipelineCreationError>
+        where Pl: PipelineLayoutAbstract
     {
         // TODO: return errors instead of panicking if missing param
 
         let vk = device.pointers();
 
+        if let Some(cache) = cache {
+            assert_eq!(device.internal_object(), cache.device().internal_object());
+        }
+
+        // Checking that the base pipeline of a derivative pipeline can be used.
+        if let Some(ref base) = self.base_pipeline {
+            assert_eq!(device.internal_object(), base.device().internal_object());
+
+            if !base.allows_derivatives() {
+                return Err(GraphicsPipelineCreationError::BasePipelineDerivativesNotAllowed);
+            }
+        }
+
+        // Checking the interpolation of the inputs of the fragment shader.
+        if let Some(location) =
+            non_flat_fragment_input(self.fragment_shader.as_ref().unwrap().0.input())
+        {
+            return Err(GraphicsPipelineCreationError::FragmentInputNotFlat { location });
+        }
+
         // Checking that the pipeline layout matches the shader stages.
         // TODO: more details in the errors
         PipelineLayoutSuperset::ensure_superset_of(&pipeline_layout,
@@ -1027,6 +1152,34 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
             },
         };
 
+        {
+            let ext = &self.extended_dynamic_state;
+            let states = [
+                (ext.cull_mode, vk::DYNAMIC_STATE_CULL_MODE_EXT),
+                (ext.front_face, vk::DYNAMIC_STATE_FRONT_FACE_EXT),
+                (ext.primitive_topology, vk::DYNAMIC_STATE_PRIMITIVE_TOPOLOGY_EXT),
+                (ext.depth_test_enable, vk::DYNAMIC_STATE_DEPTH_TEST_ENABLE_EXT),
+                (ext.depth_write_enable, vk::DYNAMIC_STATE_DEPTH_WRITE_ENABLE_EXT),
+                (ext.depth_compare_op, vk::DYNAMIC_STATE_DEPTH_COMPARE_OP_EXT),
+                (ext.stencil_test_enable, vk::DYNAMIC_STATE_STENCIL_TEST_ENABLE_EXT),
+                (ext.stencil_op, vk::DYNAMIC_STATE_STENCIL_OP_EXT),
+            ];
+
+            for &(dynamic, state) in states.iter() {
+                if !dynamic {
+                    continue;
+                }
+
+                if !device.loaded_extensions().ext_extended_dynamic_state ||
+                    !device.enabled_features().extended_dynamic_state
+                {
+                    return Err(GraphicsPipelineCreationError::ExtendedDynamicStateExtensionNotEnabled);
+                }
+
+                dynamic_states.push(state);
+            }
+        }
+
         let dynamic_states = if !dynamic_states.is_empty() {
             Some(vk::PipelineDynamicStateCreateInfo {
                      sType: vk::STRUCTURE_TYPE_PIPELINE_DYNAMIC_STATE_CREATE_INFO,
@@ -1039,11 +1192,22 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
             None
         };
 
+        let flags = {
+            let mut flags = 0;
+            if self.allow_derivatives {
+                flags |= vk::PIPELINE_CREATE_ALLOW_DERIVATIVES_BIT;
+            }
+            if self.base_pipeline.is_some() {
+                flags |= vk::PIPELINE_CREATE_DERIVATIVE_BIT;
+            }
+            flags
+        };
+
         let pipeline = unsafe {
             let infos = vk::GraphicsPipelineCreateInfo {
                 sType: vk::STRUCTURE_TYPE_GRAPHICS_PIPELINE_CREATE_INFO,
                 pNext: ptr::null(),
-                flags: 0, // TODO: some flags are available but none are critical
+                flags,
                 stageCount: stages.len() as u32,
                 pStages: stages.as_ptr(),
                 pVertexInputState: &vertex_input_state,
@@ -1069,13 +1233,18 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
                     .inner()
                     .internal_object(),
                 subpass: self.render_pass.as_ref().unwrap().index(),
-                basePipelineHandle: 0, // TODO:
-                basePipelineIndex: -1, // TODO:
+                basePipelineHandle: self.base_pipeline
+                    .as_ref()
+                    .map(|base| GraphicsPipelineAbstract::inner(base).internal_object())
+                    .unwrap_or(0),
+                basePipelineIndex: -1,
             };
 
             let mut output = MaybeUninit::uninit();
             check_errors(vk.CreateGraphicsPipelines(device.internal_object(),
-                                                    0,
+                                                    cache
+                                                        .map(|c| c.internal_object())
+                                                        .unwrap_or(0),
                                                     1,
                                                     &infos,
                                                     ptr::null(),
@@ -1113,17 +1282,48 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
                dynamic_stencil_write_mask: self.depth_stencil.stencil_back.write_mask.is_none(),
                dynamic_stencil_reference: self.depth_stencil.stencil_back.reference.is_none(),
                dynamic_blend_constants: self.blend.blend_constants.is_none(),
+               dynamic_cull_mode: self.extended_dynamic_state.cull_mode,
+               dynamic_front_face: self.extended_dynamic_state.front_face,
+               dynamic_primitive_topology: self.extended_dynamic_state.primitive_topology,
+               dynamic_depth_test_enable: self.extended_dynamic_state.depth_test_enable,
+               dynamic_depth_write_enable: self.extended_dynamic_state.depth_write_enable,
+               dynamic_depth_compare_op: self.extended_dynamic_state.depth_compare_op,
+               dynamic_stencil_test_enable: self.extended_dynamic_state.stencil_test_enable,
+               dynamic_stencil_op: self.extended_dynamic_state.stencil_op,
 
                num_viewports: self.viewport.as_ref().unwrap().num_viewports(),
+
+               primitive_topology: self.input_assembly_topology,
+
+               allow_derivatives: self.allow_derivatives,
            })
     }
-
-    // TODO: add build_with_cache method
 }
 
 impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
     GraphicsPipelineBuilder<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp> {
-    // TODO: add pipeline derivate system
+    /// Allows the pipeline to be used as the base of derivative pipelines.
+    ///
+    /// Creating derivatives of a pipeline can be faster than creating unrelated pipelines, and
+    /// switching between a pipeline and its derivatives can be cheaper on some implementations.
+    #[inline]
+    pub fn allow_derivatives(mut self) -> Self {
+        self.allow_derivatives = true;
+        self
+    }
+
+    /// Makes the pipeline a derivative of `base`.
+    ///
+    /// The base pipeline must have been created with `allow_derivatives`, otherwise building
+    /// will return an error. Building panics if it doesn't belong to the same device as the
+    /// pipeline.
+    #[inline]
+    pub fn derivative_of<Gp>(mut self, base: Gp) -> Self
+        where Gp: GraphicsPipelineAbstract + Send + Sync + 'static
+    {
+        self.base_pipeline = Some(Arc::new(base) as Arc<_>);
+        self
+    }
 
     /// Sets the vertex input.
     #[inline]
@@ -1144,6 +1344,9 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
             depth_stencil: self.depth_stencil,
             blend: self.blend,
             render_pass: self.render_pass,
+            extended_dynamic_state: self.extended_dynamic_state,
+            allow_derivatives: self.allow_derivatives,
+            base_pipeline: self.base_pipeline,
         }
     }
 
@@ -1191,6 +1394,9 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
             depth_stencil: self.depth_stencil,
             blend: self.blend,
             render_pass: self.render_pass,
+            extended_dynamic_state: self.extended_dynamic_state,
+            allow_derivatives: self.allow_derivatives,
+            base_pipeline: self.base_pipeline,
         }
     }
 
@@ -1214,6 +1420,18 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
         self
     }
 
+    /// Sets the topology of the primitives as dynamic. It will need to be set before drawing.
+    ///
+    /// The topology passed when drawing must belong to the same class (points, lines, triangles
+    /// or patches) as the one set with `primitive_topology`.
+    ///
+    /// Requires the `VK_EXT_extended_dynamic_state` extension.
+    #[inline]
+    pub fn primitive_topology_dynamic(mut self) -> Self {
+        self.extended_dynamic_state.primitive_topology = true;
+        self
+    }
+
     /// Sets the topology of the primitives to a list of points.
     ///
     /// > **Note**: This is equivalent to
@@ -1348,6 +1566,9 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
             depth_stencil: self.depth_stencil,
             blend: self.blend,
             render_pass: self.render_pass,
+            extended_dynamic_state: self.extended_dynamic_state,
+            allow_derivatives: self.allow_derivatives,
+            base_pipeline: self.base_pipeline,
         }
     }
 
@@ -1381,6 +1602,9 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
             depth_stencil: self.depth_stencil,
             blend: self.blend,
             render_pass: self.render_pass,
+            extended_dynamic_state: self.extended_dynamic_state,
+            allow_derivatives: self.allow_derivatives,
+            base_pipeline: self.base_pipeline,
         }
     }
 
@@ -1486,6 +1710,15 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
         self
     }
 
+    /// Sets the front-facing faces as dynamic. They will need to be set before drawing.
+    ///
+    /// Requires the `VK_EXT_extended_dynamic_state` extension.
+    #[inline]
+    pub fn front_face_dynamic(mut self) -> Self {
+        self.extended_dynamic_state.front_face = true;
+        self
+    }
+
     /// Sets backface culling as disabled. This is the default.
     #[inline]
     pub fn cull_mode_disabled(mut self) -> Self {
@@ -1519,6 +1752,15 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
         self
     }
 
+    /// Sets the backface culling as dynamic. It will need to be set before drawing.
+    ///
+    /// Requires the `VK_EXT_extended_dynamic_state` extension.
+    #[inline]
+    pub fn cull_mode_dynamic(mut self) -> Self {
+        self.extended_dynamic_state.cull_mode = true;
+        self
+    }
+
     /// Sets the polygon mode to "fill". This is the default.
     #[inline]
     pub fn polygon_mode_fill(mut self) -> Self {
@@ -1652,6 +1894,9 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
             depth_stencil: self.depth_stencil,
             blend: self.blend,
             render_pass: self.render_pass,
+            extended_dynamic_state: self.extended_dynamic_state,
+            allow_derivatives: self.allow_derivatives,
+            base_pipeline: self.base_pipeline,
         }
     }
 
@@ -1689,6 +1934,54 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
         self
     }
 
+    /// Sets whether the depth test is enabled as dynamic. It will need to be set before drawing.
+    ///
+    /// Requires the `VK_EXT_extended_dynamic_state` extension.
+    #[inline]
+    pub fn depth_test_enable_dynamic(mut self) -> Self {
+        self.extended_dynamic_state.depth_test_enable = true;
+        self
+    }
+
+    /// Sets whether the depth buffer will be written as dynamic. It will need to be set before
+    /// drawing.
+    ///
+    /// Requires the `VK_EXT_extended_dynamic_state` extension.
+    #[inline]
+    pub fn depth_write_dynamic(mut self) -> Self {
+        self.extended_dynamic_state.depth_write_enable = true;
+        self
+    }
+
+    /// Sets the comparison operator of the depth test as dynamic. It will need to be set before
+    /// drawing.
+    ///
+    /// Requires the `VK_EXT_extended_dynamic_state` extension.
+    #[inline]
+    pub fn depth_compare_dynamic(mut self) -> Self {
+        self.extended_dynamic_state.depth_compare_op = true;
+        self
+    }
+
+    /// Sets whether the stencil test is enabled as dynamic. It will need to be set before drawing.
+    ///
+    /// Requires the `VK_EXT_extended_dynamic_state` extension.
+    #[inline]
+    pub fn stencil_test_enable_dynamic(mut self) -> Self {
+        self.extended_dynamic_state.stencil_test_enable = true;
+        self
+    }
+
+    /// Sets the operations and the comparison operator of the stencil test as dynamic. They will
+    /// need to be set before drawing.
+    ///
+    /// Requires the `VK_EXT_extended_dynamic_state` extension.
+    #[inline]
+    pub fn stencil_op_dynamic(mut self) -> Self {
+        self.extended_dynamic_state.stencil_op = true;
+        self
+    }
+
     // TODO: missing tons of depth-stencil stuff
 
 
@@ -1768,6 +2061,9 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp>
             depth_stencil: self.depth_stencil,
             blend: self.blend,
             render_pass: Some(subpass),
+            extended_dynamic_state: self.extended_dynamic_state,
+            allow_derivatives: self.allow_derivatives,
+            base_pipeline: self.base_pipeline,
         }
     }
 }
@@ -1812,6 +2108,219 @@ impl<Vdef, Vs, Vss, Tcs, Tcss, Tes, Tess, Gs, Gss, Fs, Fss, Rp> Clone
             depth_stencil: self.depth_stencil.clone(),
             blend: self.blend.clone(),
             render_pass: self.render_pass.clone(),
+            extended_dynamic_state: self.extended_dynamic_state,
+            allow_derivatives: self.allow_derivatives,
+            base_pipeline: self.base_pipeline.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use descriptor::descriptor::DescriptorBufferDesc;
+    use descriptor::descriptor::DescriptorDesc;
+    use descriptor::descriptor::DescriptorDescTy;
+    use descriptor::descriptor::ShaderStages;
+    use descriptor::pipeline_layout::EmptyPipelineDesc;
+    use descriptor::pipeline_layout::PipelineLayoutDesc;
+    use descriptor::pipeline_layout::PipelineLayoutDescPcRange;
+    use format::Format;
+    use framebuffer::Subpass;
+    use pipeline::GraphicsPipeline;
+    use pipeline::GraphicsPipelineCreationError;
+    use pipeline::cache::PipelineCache;
+    use pipeline::shader::EmptyShaderInterfaceDef;
+    use pipeline::shader::GraphicsShaderType;
+    use pipeline::shader::ShaderModule;
+    use std::ffi::CStr;
+    use std::sync::Arc;
+
+    // Vertex shader with an empty `main` function.
+    const EMPTY_VS: [u32; 29] = [
+        0x07230203, 0x00010000, 0, 5, 0,
+        0x00020011, 1,
+        0x0003000e, 0, 1,
+        0x0005000f, 0, 1, 0x6e69616d, 0,
+        0x00020013, 2,
+        0x00030021, 3, 2,
+        0x00050036, 2, 1, 0, 3,
+        0x000200f8, 4,
+        0x000100fd,
+        0x00010038,
+    ];
+
+    // Fragment shader with an empty `main` function.
+    const EMPTY_FS: [u32; 32] = [
+        0x07230203, 0x00010000, 0, 5, 0,
+        0x00020011, 1,
+        0x0003000e, 0, 1,
+        0x0005000f, 4, 1, 0x6e69616d, 0,
+        0x00030010, 1, 7,
+        0x00020013, 2,
+        0x00030021, 3, 2,
+        0x00050036, 2, 1, 0, 3,
+        0x000200f8, 4,
+        0x000100fd,
+        0x00010038,
+    ];
+
+    // Layout with a single uniform buffer at set 0, binding 0.
+    #[derive(Debug, Copy, Clone)]
+    struct UniformLayout;
+
+    unsafe impl PipelineLayoutDesc for UniformLayout {
+        fn num_sets(&self) -> usize {
+            1
+        }
+        fn num_bindings_in_set(&self, set: usize) -> Option<usize> {
+            match set {
+                0 => Some(1),
+                _ => None,
+            }
+        }
+        fn descriptor(&self, set: usize, binding: usize) -> Option<DescriptorDesc> {
+            match (set, binding) {
+                (0, 0) => Some(DescriptorDesc {
+                                   ty: DescriptorDescTy::Buffer(DescriptorBufferDesc {
+                                                                    dynamic: None,
+                                                                    storage: false,
+                                                                }),
+                                   array_count: 1,
+                                   stages: ShaderStages {
+                                       vertex: true,
+                                       ..ShaderStages::none()
+                                   },
+                                   readonly: true,
+                               }),
+                _ => None,
+            }
+        }
+        fn num_push_constants_ranges(&self) -> usize {
+            0
+        }
+        fn push_constants_range(&self, _: usize) -> Option<PipelineLayoutDescPcRange> {
+            None
+        }
+    }
+
+    #[test]
+    fn derivatives() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let vs = unsafe { ShaderModule::from_words(device.clone(), &EMPTY_VS).unwrap() };
+        let fs = unsafe { ShaderModule::from_words(device.clone(), &EMPTY_FS).unwrap() };
+        let name = CStr::from_bytes_with_nul(b"main\0").unwrap();
+        let (vs, fs) = unsafe {
+            (vs.graphics_entry_point::<(), _, _, _>(name,
+                                                    EmptyShaderInterfaceDef,
+                                                    EmptyShaderInterfaceDef,
+                                                    EmptyPipelineDesc,
+                                                    GraphicsShaderType::Vertex),
+             fs.graphics_entry_point::<(), _, _, _>(name,
+                                                    EmptyShaderInterfaceDef,
+                                                    EmptyShaderInterfaceDef,
+                                                    EmptyPipelineDesc,
+                                                    GraphicsShaderType::Fragment))
+        };
+
+        let rp = Arc::new(single_pass_renderpass!(device.clone(),
+            attachments: {
+                color: {
+                    load: Clear,
+                    store: Store,
+                    format: Format::R8G8B8A8Unorm,
+                    samples: 1,
+                }
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {}
+            }
+        ).unwrap());
+
+        let base = GraphicsPipeline::start()
+            .vertex_shader(vs, ())
+            .viewports_dynamic_scissors_irrelevant(1)
+            .fragment_shader(fs, ())
+            .render_pass(Subpass::from(rp.clone(), 0).unwrap())
+            .allow_derivatives()
+            .build(device.clone())
+            .unwrap();
+        assert!(base.allows_derivatives());
+
+        let derivative = GraphicsPipeline::start()
+            .vertex_shader(vs, ())
+            .viewports_dynamic_scissors_irrelevant(1)
+            .fragment_shader(fs, ())
+            .render_pass(Subpass::from(rp.clone(), 0).unwrap())
+            .derivative_of(base)
+            .build(device.clone())
+            .unwrap();
+        assert!(!derivative.allows_derivatives());
+
+        let result = GraphicsPipeline::start()
+            .vertex_shader(vs, ())
+            .viewports_dynamic_scissors_irrelevant(1)
+            .fragment_shader(fs, ())
+            .render_pass(Subpass::from(rp, 0).unwrap())
+            .derivative_of(derivative)
+            .build(device);
+
+        match result {
+            Err(GraphicsPipelineCreationError::BasePipelineDerivativesNotAllowed) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn cache_and_dynamic_buffers() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let vs = unsafe { ShaderModule::from_words(device.clone(), &EMPTY_VS).unwrap() };
+        let fs = unsafe { ShaderModule::from_words(device.clone(), &EMPTY_FS).unwrap() };
+        let name = CStr::from_bytes_with_nul(b"main\0").unwrap();
+        let (vs, fs) = unsafe {
+            (vs.graphics_entry_point::<(), _, _, _>(name,
+                                                    EmptyShaderInterfaceDef,
+                                                    EmptyShaderInterfaceDef,
+                                                    UniformLayout,
+                                                    GraphicsShaderType::Vertex),
+             fs.graphics_entry_point::<(), _, _, _>(name,
+                                                    EmptyShaderInterfaceDef,
+                                                    EmptyShaderInterfaceDef,
+                                                    EmptyPipelineDesc,
+                                                    GraphicsShaderType::Fragment))
+        };
+
+        let rp = Arc::new(single_pass_renderpass!(device.clone(),
+            attachments: {
+                color: {
+                    load: Clear,
+                    store: Store,
+                    format: Format::R8G8B8A8Unorm,
+                    samples: 1,
+                }
+            },
+            pass: {
+                color: [color],
+                depth_stencil: {}
+            }
+        ).unwrap());
+
+        let cache = PipelineCache::empty(device.clone()).unwrap();
+
+        let pipeline = GraphicsPipeline::start()
+            .vertex_shader(vs, ())
+            .viewports_dynamic_scissors_irrelevant(1)
+            .fragment_shader(fs, ())
+            .render_pass(Subpass::from(rp, 0).unwrap())
+            .with_auto_layout_and_cache(device, &[(0, 0)], &cache)
+            .unwrap();
+
+        match pipeline.descriptor(0, 0).unwrap().ty {
+            DescriptorDescTy::Buffer(DescriptorBufferDesc { dynamic: Some(true), .. }) => (),
+            _ => panic!(),
         }
+        assert!(!cache.get_data().unwrap().is_empty());
     }
 }
@@ -55,6 +55,13 @@ pub enum GraphicsPipelineCreationError {
     /// expects.
     FragmentShaderRenderPassIncompatible,
 
+    /// An input of the fragment shader has an integer or double-precision format but isn't
+    /// decorated with `Flat`.
+    FragmentInputNotFlat {
+        /// Location of the input.
+        location: u32,
+    },
+
     /// The vertex definition is not compatible with the input of the vertex shader.
     IncompatibleVertexDefinition(IncompatibleVertexDefinitionError),
 
@@ -182,6 +189,14 @@ pub enum GraphicsPipelineCreationError {
 
     /// The `alpha_to_one` feature must be enabled in order to use alpha-to-one.
     AlphaToOneFeatureNotEnabled,
+
+    /// The `VK_EXT_extended_dynamic_state` extension and the `extended_dynamic_state` feature
+    /// must be enabled in order to make the cull mode, front face, primitive topology, depth test
+    /// or stencil operations dynamic.
+    ExtendedDynamicStateExtensionNotEnabled,
+
+    /// The base pipeline of a derivative pipeline wasn't created with `allow_derivatives`.
+    BasePipelineDerivativesNotAllowed,
 }
 
 impl error::Error for GraphicsPipelineCreationError {
@@ -222,6 +237,10 @@ impl error::Error for GraphicsPipelineCreationError {
                 "the output of the fragment shader is not compatible with what the render pass \
                  subpass expects"
             },
+            GraphicsPipelineCreationError::FragmentInputNotFlat { .. } => {
+                "an input of the fragment shader has an integer or double-precision format but \
+                 isn't flat"
+            },
             GraphicsPipelineCreationError::IncompatibleVertexDefinition(_) => {
                 "the vertex definition is not compatible with the input of the vertex shader"
             },
@@ -319,6 +338,13 @@ impl error::Error for GraphicsPipelineCreationError {
             GraphicsPipelineCreationError::AlphaToOneFeatureNotEnabled => {
                 "the `alpha_to_one` feature must be enabled in order to use alpha-to-one"
             },
+            GraphicsPipelineCreationError::ExtendedDynamicStateExtensionNotEnabled => {
+                "the `VK_EXT_extended_dynamic_state` extension and the `extended_dynamic_state` \
+                 feature must be enabled in order to use extended dynamic states"
+            },
+            GraphicsPipelineCreationError::BasePipelineDerivativesNotAllowed => {
+                "the base pipeline of a derivative pipeline wasn't created with `allow_derivatives`"
+            },
         }
     }
 
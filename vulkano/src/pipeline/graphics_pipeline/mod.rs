@@ -35,6 +35,7 @@ use framebuffer::RenderPassDesc;
 use framebuffer::RenderPassDescClearValues;
 use framebuffer::RenderPassSys;
 use framebuffer::Subpass;
+use pipeline::input_assembly::PrimitiveTopology;
 use pipeline::shader::EmptyEntryPointDummy;
 use pipeline::vertex::BufferlessDefinition;
 use pipeline::vertex::IncompatibleVertexDefinitionError;
@@ -72,8 +73,20 @@ pub struct GraphicsPipeline<VertexDefinition, Layout, RenderP> {
     dynamic_stencil_write_mask: bool,
     dynamic_stencil_reference: bool,
     dynamic_blend_constants: bool,
+    dynamic_cull_mode: bool,
+    dynamic_front_face: bool,
+    dynamic_primitive_topology: bool,
+    dynamic_depth_test_enable: bool,
+    dynamic_depth_write_enable: bool,
+    dynamic_depth_compare_op: bool,
+    dynamic_stencil_test_enable: bool,
+    dynamic_stencil_op: bool,
 
     num_viewports: u32,
+
+    primitive_topology: PrimitiveTopology,
+
+    allow_derivatives: bool,
 }
 
 #[derive(PartialEq, Eq, Hash)]
@@ -168,12 +181,24 @@ impl<Mv, L, Rp> GraphicsPipeline<Mv, L, Rp> {
         self.dynamic_scissor
     }
 
+    /// Returns true if the depth bias used by this pipeline is dynamic.
+    #[inline]
+    pub fn has_dynamic_depth_bias(&self) -> bool {
+        self.dynamic_depth_bias
+    }
+
     /// Returns true if the depth bounds used by this pipeline are dynamic.
     #[inline]
     pub fn has_dynamic_depth_bounds(&self) -> bool {
         self.dynamic_depth_bounds
     }
 
+    /// Returns true if the blend constants used by this pipeline are dynamic.
+    #[inline]
+    pub fn has_dynamic_blend_constants(&self) -> bool {
+        self.dynamic_blend_constants
+    }
+
     /// Returns true if the stencil compare masks used by this pipeline are dynamic.
     #[inline]
     pub fn has_dynamic_stencil_compare_mask(&self) -> bool {
@@ -191,6 +216,67 @@ impl<Mv, L, Rp> GraphicsPipeline<Mv, L, Rp> {
     pub fn has_dynamic_stencil_reference(&self) -> bool {
         self.dynamic_stencil_reference
     }
+
+    /// Returns true if the cull mode used by this pipeline is dynamic.
+    #[inline]
+    pub fn has_dynamic_cull_mode(&self) -> bool {
+        self.dynamic_cull_mode
+    }
+
+    /// Returns true if the front face used by this pipeline is dynamic.
+    #[inline]
+    pub fn has_dynamic_front_face(&self) -> bool {
+        self.dynamic_front_face
+    }
+
+    /// Returns true if the primitive topology used by this pipeline is dynamic.
+    #[inline]
+    pub fn has_dynamic_primitive_topology(&self) -> bool {
+        self.dynamic_primitive_topology
+    }
+
+    /// Returns the primitive topology the pipeline was created with. If the primitive topology
+    /// is dynamic, it can be changed to any topology of the same class.
+    #[inline]
+    pub fn primitive_topology(&self) -> PrimitiveTopology {
+        self.primitive_topology
+    }
+
+    /// Returns true if the depth test enable state used by this pipeline is dynamic.
+    #[inline]
+    pub fn has_dynamic_depth_test_enable(&self) -> bool {
+        self.dynamic_depth_test_enable
+    }
+
+    /// Returns true if the depth write enable state used by this pipeline is dynamic.
+    #[inline]
+    pub fn has_dynamic_depth_write_enable(&self) -> bool {
+        self.dynamic_depth_write_enable
+    }
+
+    /// Returns true if the depth comparison operator used by this pipeline is dynamic.
+    #[inline]
+    pub fn has_dynamic_depth_compare_op(&self) -> bool {
+        self.dynamic_depth_compare_op
+    }
+
+    /// Returns true if the stencil test enable state used by this pipeline is dynamic.
+    #[inline]
+    pub fn has_dynamic_stencil_test_enable(&self) -> bool {
+        self.dynamic_stencil_test_enable
+    }
+
+    /// Returns true if the stencil operations used by this pipeline are dynamic.
+    #[inline]
+    pub fn has_dynamic_stencil_op(&self) -> bool {
+        self.dynamic_stencil_op
+    }
+
+    /// Returns true if this pipeline can be used as the base of derivative pipelines.
+    #[inline]
+    pub fn allows_derivatives(&self) -> bool {
+        self.allow_derivatives
+    }
 }
 
 unsafe impl<Mv, L, Rp> PipelineLayoutAbstract for GraphicsPipeline<Mv, L, Rp>
@@ -225,6 +311,11 @@ unsafe impl<Mv, L, Rp> PipelineLayoutDesc for GraphicsPipeline<Mv, L, Rp>
         self.layout.descriptor(set, binding)
     }
 
+    #[inline]
+    fn provided_set_layout(&self, set: usize) -> Option<Arc<UnsafeDescriptorSetLayout>> {
+        self.layout.provided_set_layout(set)
+    }
+
     #[inline]
     fn num_push_constants_ranges(&self) -> usize {
         self.layout.num_push_constants_ranges()
@@ -291,6 +382,11 @@ unsafe impl<Mv, L, Rp> RenderPassDesc for GraphicsPipeline<Mv, L, Rp>
     fn dependency_desc(&self, num: usize) -> Option<PassDependencyDescription> {
         self.render_pass.dependency_desc(num)
     }
+
+    #[inline]
+    fn correlation_masks(&self) -> Vec<u32> {
+        self.render_pass.correlation_masks()
+    }
 }
 
 unsafe impl<C, Mv, L, Rp> RenderPassDescClearValues<C> for GraphicsPipeline<Mv, L, Rp>
@@ -353,9 +449,15 @@ pub unsafe trait GraphicsPipelineAbstract: PipelineLayoutAbstract + RenderPassAb
     /// Returns true if the scissors used by this pipeline are dynamic.
     fn has_dynamic_scissors(&self) -> bool;
 
+    /// Returns true if the depth bias used by this pipeline is dynamic.
+    fn has_dynamic_depth_bias(&self) -> bool;
+
     /// Returns true if the depth bounds used by this pipeline are dynamic.
     fn has_dynamic_depth_bounds(&self) -> bool;
 
+    /// Returns true if the blend constants used by this pipeline are dynamic.
+    fn has_dynamic_blend_constants(&self) -> bool;
+
     /// Returns true if the stencil compare masks used by this pipeline are dynamic.
     fn has_dynamic_stencil_compare_mask(&self) -> bool;
 
@@ -364,6 +466,37 @@ pub unsafe trait GraphicsPipelineAbstract: PipelineLayoutAbstract + RenderPassAb
 
     /// Returns true if the stencil references used by this pipeline are dynamic.
     fn has_dynamic_stencil_reference(&self) -> bool;
+
+    /// Returns true if the cull mode used by this pipeline is dynamic.
+    fn has_dynamic_cull_mode(&self) -> bool;
+
+    /// Returns true if the front face used by this pipeline is dynamic.
+    fn has_dynamic_front_face(&self) -> bool;
+
+    /// Returns true if the primitive topology used by this pipeline is dynamic.
+    fn has_dynamic_primitive_topology(&self) -> bool;
+
+    /// Returns the primitive topology the pipeline was created with. If the primitive topology
+    /// is dynamic, it can be changed to any topology of the same class.
+    fn primitive_topology(&self) -> PrimitiveTopology;
+
+    /// Returns true if the depth test enable state used by this pipeline is dynamic.
+    fn has_dynamic_depth_test_enable(&self) -> bool;
+
+    /// Returns true if the depth write enable state used by this pipeline is dynamic.
+    fn has_dynamic_depth_write_enable(&self) -> bool;
+
+    /// Returns true if the depth comparison operator used by this pipeline is dynamic.
+    fn has_dynamic_depth_compare_op(&self) -> bool;
+
+    /// Returns true if the stencil test enable state used by this pipeline is dynamic.
+    fn has_dynamic_stencil_test_enable(&self) -> bool;
+
+    /// Returns true if the stencil operations used by this pipeline are dynamic.
+    fn has_dynamic_stencil_op(&self) -> bool;
+
+    /// Returns true if this pipeline can be used as the base of derivative pipelines.
+    fn allows_derivatives(&self) -> bool;
 }
 
 unsafe impl<Mv, L, Rp> GraphicsPipelineAbstract for GraphicsPipeline<Mv, L, Rp>
@@ -401,11 +534,21 @@ unsafe impl<Mv, L, Rp> GraphicsPipelineAbstract for GraphicsPipeline<Mv, L, Rp>
         self.dynamic_scissor
     }
 
+    #[inline]
+    fn has_dynamic_depth_bias(&self) -> bool {
+        self.dynamic_depth_bias
+    }
+
     #[inline]
     fn has_dynamic_depth_bounds(&self) -> bool {
         self.dynamic_depth_bounds
     }
 
+    #[inline]
+    fn has_dynamic_blend_constants(&self) -> bool {
+        self.dynamic_blend_constants
+    }
+
     #[inline]
     fn has_dynamic_stencil_compare_mask(&self) -> bool {
         self.dynamic_stencil_compare_mask
@@ -420,6 +563,56 @@ unsafe impl<Mv, L, Rp> GraphicsPipelineAbstract for GraphicsPipeline<Mv, L, Rp>
     fn has_dynamic_stencil_reference(&self) -> bool {
         self.dynamic_stencil_reference
     }
+
+    #[inline]
+    fn has_dynamic_cull_mode(&self) -> bool {
+        self.dynamic_cull_mode
+    }
+
+    #[inline]
+    fn has_dynamic_front_face(&self) -> bool {
+        self.dynamic_front_face
+    }
+
+    #[inline]
+    fn has_dynamic_primitive_topology(&self) -> bool {
+        self.dynamic_primitive_topology
+    }
+
+    #[inline]
+    fn primitive_topology(&self) -> PrimitiveTopology {
+        self.primitive_topology
+    }
+
+    #[inline]
+    fn has_dynamic_depth_test_enable(&self) -> bool {
+        self.dynamic_depth_test_enable
+    }
+
+    #[inline]
+    fn has_dynamic_depth_write_enable(&self) -> bool {
+        self.dynamic_depth_write_enable
+    }
+
+    #[inline]
+    fn has_dynamic_depth_compare_op(&self) -> bool {
+        self.dynamic_depth_compare_op
+    }
+
+    #[inline]
+    fn has_dynamic_stencil_test_enable(&self) -> bool {
+        self.dynamic_stencil_test_enable
+    }
+
+    #[inline]
+    fn has_dynamic_stencil_op(&self) -> bool {
+        self.dynamic_stencil_op
+    }
+
+    #[inline]
+    fn allows_derivatives(&self) -> bool {
+        self.allow_derivatives
+    }
 }
 
 unsafe impl<T> GraphicsPipelineAbstract for T
@@ -456,11 +649,21 @@ unsafe impl<T> GraphicsPipelineAbstract for T
         (**self).has_dynamic_scissors()
     }
 
+    #[inline]
+    fn has_dynamic_depth_bias(&self) -> bool {
+        (**self).has_dynamic_depth_bias()
+    }
+
     #[inline]
     fn has_dynamic_depth_bounds(&self) -> bool {
         (**self).has_dynamic_depth_bounds()
     }
 
+    #[inline]
+    fn has_dynamic_blend_constants(&self) -> bool {
+        (**self).has_dynamic_blend_constants()
+    }
+
     #[inline]
     fn has_dynamic_stencil_compare_mask(&self) -> bool {
         (**self).has_dynamic_stencil_compare_mask()
@@ -475,6 +678,56 @@ unsafe impl<T> GraphicsPipelineAbstract for T
     fn has_dynamic_stencil_reference(&self) -> bool {
         (**self).has_dynamic_stencil_reference()
     }
+
+    #[inline]
+    fn has_dynamic_cull_mode(&self) -> bool {
+        (**self).has_dynamic_cull_mode()
+    }
+
+    #[inline]
+    fn has_dynamic_front_face(&self) -> bool {
+        (**self).has_dynamic_front_face()
+    }
+
+    #[inline]
+    fn has_dynamic_primitive_topology(&self) -> bool {
+        (**self).has_dynamic_primitive_topology()
+    }
+
+    #[inline]
+    fn primitive_topology(&self) -> PrimitiveTopology {
+        (**self).primitive_topology()
+    }
+
+    #[inline]
+    fn has_dynamic_depth_test_enable(&self) -> bool {
+        (**self).has_dynamic_depth_test_enable()
+    }
+
+    #[inline]
+    fn has_dynamic_depth_write_enable(&self) -> bool {
+        (**self).has_dynamic_depth_write_enable()
+    }
+
+    #[inline]
+    fn has_dynamic_depth_compare_op(&self) -> bool {
+        (**self).has_dynamic_depth_compare_op()
+    }
+
+    #[inline]
+    fn has_dynamic_stencil_test_enable(&self) -> bool {
+        (**self).has_dynamic_stencil_test_enable()
+    }
+
+    #[inline]
+    fn has_dynamic_stencil_op(&self) -> bool {
+        (**self).has_dynamic_stencil_op()
+    }
+
+    #[inline]
+    fn allows_derivatives(&self) -> bool {
+        (**self).allows_derivatives()
+    }
 }
 
 impl<Mv, L, Rp> PartialEq for GraphicsPipeline<Mv, L, Rp>
@@ -83,6 +83,24 @@ impl Into<vk::PrimitiveTopology> for PrimitiveTopology {
 }
 
 impl PrimitiveTopology {
+    /// Returns the class of this primitive topology.
+    #[inline]
+    pub fn class(&self) -> PrimitiveTopologyClass {
+        match *self {
+            PrimitiveTopology::PointList => PrimitiveTopologyClass::Point,
+            PrimitiveTopology::LineList |
+            PrimitiveTopology::LineStrip |
+            PrimitiveTopology::LineListWithAdjacency |
+            PrimitiveTopology::LineStripWithAdjacency => PrimitiveTopologyClass::Line,
+            PrimitiveTopology::TriangleList |
+            PrimitiveTopology::TriangleStrip |
+            PrimitiveTopology::TriangleFan |
+            PrimitiveTopology::TriangleListWithAdjacency |
+            PrimitiveTopology::TriangleStripWithAdjacency => PrimitiveTopologyClass::Triangle,
+            PrimitiveTopology::PatchList { .. } => PrimitiveTopologyClass::Patch,
+        }
+    }
+
     /// Returns true if this primitive topology supports using primitives restart.
     #[inline]
     pub fn supports_primitive_restart(&self) -> bool {
@@ -97,6 +115,18 @@ impl PrimitiveTopology {
     }
 }
 
+/// Kind of primitives that a `PrimitiveTopology` produces.
+///
+/// When the primitive topology of a pipeline is dynamic, it can only be changed to a topology of
+/// the same class as the one the pipeline was created with.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum PrimitiveTopologyClass {
+    Point,
+    Line,
+    Triangle,
+    Patch,
+}
+
 /// Trait for types that can be used as indices by the GPU.
 pub unsafe trait Index {
     /// Returns the type of data.
@@ -125,3 +155,22 @@ pub enum IndexType {
     U16 = vk::INDEX_TYPE_UINT16,
     U32 = vk::INDEX_TYPE_UINT32,
 }
+
+#[cfg(test)]
+mod tests {
+    use pipeline::input_assembly::PrimitiveTopology;
+    use pipeline::input_assembly::PrimitiveTopologyClass;
+
+    #[test]
+    fn topology_class() {
+        assert_eq!(PrimitiveTopology::PointList.class(), PrimitiveTopologyClass::Point);
+        assert_eq!(PrimitiveTopology::LineStrip.class(),
+                   PrimitiveTopology::LineListWithAdjacency.class());
+        assert_eq!(PrimitiveTopology::TriangleFan.class(), PrimitiveTopologyClass::Triangle);
+        assert_eq!(PrimitiveTopology::TriangleStripWithAdjacency.class(),
+                   PrimitiveTopologyClass::Triangle);
+        assert_eq!(PrimitiveTopology::PatchList { vertices_per_patch: 3 }.class(),
+                   PrimitiveTopologyClass::Patch);
+        assert_ne!(PrimitiveTopology::LineList.class(), PrimitiveTopology::TriangleList.class());
+    }
+}
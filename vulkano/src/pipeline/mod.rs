@@ -95,5 +95,6 @@ pub mod input_assembly;
 pub mod multisample;
 pub mod raster;
 pub mod shader;
+pub mod shader_reload;
 pub mod vertex;
 pub mod viewport;
@@ -77,7 +77,7 @@ impl DepthBiasControl {
     }
 }
 
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq)]
 pub struct DepthBias {
     pub constant_factor: f32,
     /// Requires the `depth_bias_clamp` feature to be enabled.
@@ -91,7 +91,7 @@ pub struct DepthBias {
 /// clockwise or counter-clockwise correspond to the front and the back of each triangle. Then
 /// `cull_mode` lets you specify whether front faces should be discarded, back faces should be
 /// discarded, or none, or both.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[repr(u32)]
 pub enum CullMode {
     /// No culling.
@@ -112,7 +112,7 @@ impl Default for CullMode {
 }
 
 /// Specifies which triangle orientation corresponds to the front or the triangle.
-#[derive(Copy, Clone, Debug)]
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
 #[repr(u32)]
 pub enum FrontFace {
     /// Triangles whose vertices are oriented counter-clockwise on the screen will be considered
@@ -33,6 +33,7 @@ use std::sync::Arc;
 use descriptor::pipeline_layout::EmptyPipelineDesc;
 use descriptor::pipeline_layout::PipelineLayoutDesc;
 use format::Format;
+use format::FormatTy;
 use pipeline::input_assembly::PrimitiveTopology;
 
 use OomError;
@@ -159,6 +160,7 @@ impl ShaderModule {
             module: self,
             name: name,
             layout: layout,
+            workgroup_size: None,
             marker: PhantomData,
         }
     }
@@ -320,6 +322,15 @@ pub unsafe trait EntryPointAbstract {
     fn layout(&self) -> &Self::PipelineLayout;
 }
 
+/// Trait for types that contain the entry point of a compute shader.
+///
+/// This trait is unsafe because the implementation must correctly describe the workgroup size
+/// declared in the shader.
+pub unsafe trait ComputeEntryPointAbstract: EntryPointAbstract {
+    /// Returns the size of the local workgroup of the shader, or `None` if it is unknown.
+    fn workgroup_size(&self) -> Option<WorkgroupSize>;
+}
+
 /// Represents the entry point of a compute shader in a shader module.
 ///
 /// Can be obtained by calling `compute_shader_entry_point()` on the shader module.
@@ -328,9 +339,31 @@ pub struct ComputeEntryPoint<'a, S, L> {
     module: &'a ShaderModule,
     name: &'a CStr,
     layout: L,
+    workgroup_size: Option<WorkgroupSize>,
     marker: PhantomData<S>,
 }
 
+impl<'a, S, L> ComputeEntryPoint<'a, S, L> {
+    /// Sets the size of the local workgroup of the entry point.
+    ///
+    /// # Safety
+    ///
+    /// - The workgroup size must correctly describe the one declared in the shader, including
+    ///   the dimensions that come from specialization constants.
+    ///
+    #[inline]
+    pub unsafe fn with_workgroup_size(mut self, workgroup_size: WorkgroupSize) -> Self {
+        self.workgroup_size = Some(workgroup_size);
+        self
+    }
+
+    /// Returns the size of the local workgroup of the entry point, or `None` if it is unknown.
+    #[inline]
+    pub fn workgroup_size(&self) -> Option<WorkgroupSize> {
+        self.workgroup_size
+    }
+}
+
 unsafe impl<'a, S, L> EntryPointAbstract for ComputeEntryPoint<'a, S, L>
     where L: PipelineLayoutDesc,
           S: SpecializationConstants
@@ -354,7 +387,93 @@ unsafe impl<'a, S, L> EntryPointAbstract for ComputeEntryPoint<'a, S, L>
     }
 }
 
-/// A dummy that implements `GraphicsEntryPointAbstract` and `EntryPointAbstract`.
+unsafe impl<'a, S, L> ComputeEntryPointAbstract for ComputeEntryPoint<'a, S, L>
+    where L: PipelineLayoutDesc,
+          S: SpecializationConstants
+{
+    #[inline]
+    fn workgroup_size(&self) -> Option<WorkgroupSize> {
+        self.workgroup_size
+    }
+}
+
+/// Size of the local workgroup of a compute shader.
+///
+/// Each dimension is either a constant or the value of a specialization constant, in which case
+/// the actual size is only known once the pipeline is created.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct WorkgroupSize {
+    pub x: WorkgroupSizeDimension,
+    pub y: WorkgroupSizeDimension,
+    pub z: WorkgroupSizeDimension,
+}
+
+/// One dimension of a `WorkgroupSize`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WorkgroupSizeDimension {
+    /// The dimension is a constant.
+    Constant(u32),
+    /// The dimension is the value of a specialization constant.
+    SpecializationConstant {
+        /// Identifier of the specialization constant.
+        constant_id: u32,
+        /// Value used if the specialization constants don't provide one.
+        default_value: u32,
+    },
+}
+
+impl WorkgroupSize {
+    /// Builds a `WorkgroupSize` whose dimensions are all constants.
+    #[inline]
+    pub fn constant(size: [u32; 3]) -> WorkgroupSize {
+        WorkgroupSize {
+            x: WorkgroupSizeDimension::Constant(size[0]),
+            y: WorkgroupSizeDimension::Constant(size[1]),
+            z: WorkgroupSizeDimension::Constant(size[2]),
+        }
+    }
+
+    /// Returns the actual size of the workgroup when the shader is specialized with the given
+    /// specialization constants.
+    #[inline]
+    pub fn resolve<S>(&self, specialization: &S) -> [u32; 3]
+        where S: SpecializationConstants
+    {
+        [
+            self.x.resolve(specialization),
+            self.y.resolve(specialization),
+            self.z.resolve(specialization),
+        ]
+    }
+}
+
+impl WorkgroupSizeDimension {
+    /// Returns the value of the dimension when the shader is specialized with the given
+    /// specialization constants.
+    pub fn resolve<S>(&self, specialization: &S) -> u32
+        where S: SpecializationConstants
+    {
+        match *self {
+            WorkgroupSizeDimension::Constant(value) => value,
+            WorkgroupSizeDimension::SpecializationConstant { constant_id, default_value } => {
+                let entry = S::descriptors().iter().find(|e| e.constant_id == constant_id);
+                match entry {
+                    Some(entry) if entry.size == mem::size_of::<u32>() => unsafe {
+                        // Safe because the `SpecializationConstants` trait guarantees that the
+                        // offset and size of the entry are valid.
+                        let ptr = (specialization as *const S as *const u8)
+                            .offset(entry.offset as isize);
+                        ptr::read_unaligned(ptr as *const u32)
+                    },
+                    _ => default_value,
+                }
+            },
+        }
+    }
+}
+
+/// A dummy that implements `GraphicsEntryPointAbstract`, `ComputeEntryPointAbstract` and
+/// `EntryPointAbstract`.
 ///
 /// When a function has a signature like: `fn foo<S: EntryPointAbstract>(shader: Option<S>)`, you
 /// can pass `None::<EmptyEntryPointDummy>`.
@@ -385,6 +504,13 @@ unsafe impl EntryPointAbstract for EmptyEntryPointDummy {
     }
 }
 
+unsafe impl ComputeEntryPointAbstract for EmptyEntryPointDummy {
+    #[inline]
+    fn workgroup_size(&self) -> Option<WorkgroupSize> {
+        unreachable!()
+    }
+}
+
 unsafe impl GraphicsEntryPointAbstract for EmptyEntryPointDummy {
     type InputDefinition = EmptyShaderInterfaceDef;
     type OutputDefinition = EmptyShaderInterfaceDef;
@@ -424,12 +550,50 @@ pub unsafe trait ShaderInterfaceDef {
 /// Entry of a shader interface definition.
 #[derive(Debug, Clone)]
 pub struct ShaderInterfaceDefEntry {
-    /// Range of locations covered by the element.
+    /// Range of locations covered by the element. Empty if the element is a built-in.
     pub location: Range<u32>,
     /// Format of a each location of the element.
     pub format: Format,
     /// Name of the element, or `None` if the name is unknown.
     pub name: Option<Cow<'static, str>>,
+    /// Index of the first component of each location used by the element, as set with the
+    /// `Component` decoration. `0` if the decoration is absent.
+    pub component: u32,
+    /// How the element is interpolated when it is passed to a fragment shader.
+    pub interpolation: ShaderInterfaceInterpolation,
+    /// True if the element is per-patch rather than per-vertex. Only relevant to tessellation
+    /// shaders.
+    pub is_patch: bool,
+    /// If the element is a built-in, which one. Built-ins don't have a location.
+    pub builtin: Option<ShaderInterfaceBuiltIn>,
+}
+
+/// Interpolation decoration of an element of a shader interface.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ShaderInterfaceInterpolation {
+    /// Perspective-correct interpolation. This is the default.
+    Smooth,
+    /// No interpolation, the value of the provoking vertex is used.
+    Flat,
+    /// Linear interpolation in screen space.
+    NoPerspective,
+}
+
+/// Built-in variable that can be passed from one shader stage to the next one, or that is
+/// provided by the implementation.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ShaderInterfaceBuiltIn {
+    /// `gl_Position`.
+    Position,
+    /// `gl_PointSize`.
+    PointSize,
+    /// `gl_ClipDistance`.
+    ClipDistance,
+    /// `gl_CullDistance`.
+    CullDistance,
+    /// `gl_ViewIndex`, the index of the view being rendered when using multiview. It is provided
+    /// by the implementation and is never written by a previous stage.
+    ViewIndex,
 }
 
 /// Description of an empty shader interface.
@@ -447,6 +611,13 @@ unsafe impl ShaderInterfaceDef for EmptyShaderInterfaceDef {
 
 /// Extension trait for `ShaderInterfaceDef` that specifies that the interface is potentially
 /// compatible with another one.
+///
+/// `self` is the input of a shader stage and `other` is the output of the previous stage. Each
+/// element must match on its location, component, format and per-patch-ness. Built-ins read by
+/// `self` must be written by `other`.
+///
+/// Interpolation decorations are not compared, as Vulkan ignores them on outputs. See
+/// `non_flat_fragment_input` for the requirements on the inputs of a fragment shader.
 pub unsafe trait ShaderInterfaceDefMatch<I>: ShaderInterfaceDef
     where I: ShaderInterfaceDef
 {
@@ -460,22 +631,59 @@ unsafe impl<T, I> ShaderInterfaceDefMatch<I> for T
           I: ShaderInterfaceDef
 {
     fn matches(&self, other: &I) -> Result<(), ShaderInterfaceMismatchError> {
-        if self.elements().len() != other.elements().len() {
+        let self_elements = self.elements().filter(|e| e.builtin.is_none()).count();
+        let other_elements = other.elements().filter(|e| e.builtin.is_none()).count();
+        if self_elements != other_elements {
             return Err(ShaderInterfaceMismatchError::ElementsCountMismatch {
-                self_elements: self.elements().len() as u32,
-                other_elements: other.elements().len() as u32,
+                self_elements: self_elements as u32,
+                other_elements: other_elements as u32,
             });
         }
 
         for a in self.elements() {
+            if let Some(builtin) = a.builtin {
+                if builtin == ShaderInterfaceBuiltIn::ViewIndex {
+                    continue;
+                }
+
+                // Built-ins read by a stage must have been written by the previous stage.
+                let b = match other.elements().find(|e| e.builtin == Some(builtin)) {
+                    None => return Err(ShaderInterfaceMismatchError::MissingBuiltIn {
+                                           builtin,
+                                       }),
+                    Some(b) => b,
+                };
+
+                if a.format != b.format {
+                    return Err(ShaderInterfaceMismatchError::BuiltInFormatMismatch {
+                        builtin,
+                        self_format: a.format,
+                        other_format: b.format,
+                    });
+                }
+
+                continue;
+            }
+
             for loc in a.location.clone() {
+                let at_location = |e: &ShaderInterfaceDefEntry| {
+                    loc >= e.location.start && loc < e.location.end
+                };
+
                 let b = match other
                     .elements()
-                    .find(|e| loc >= e.location.start && loc < e.location.end) {
-                    None => return Err(ShaderInterfaceMismatchError::MissingElement {
-                                           location: loc,
-                                       }),
+                    .find(|e| at_location(e) && e.component == a.component) {
                     Some(b) => b,
+                    None => match other.elements().find(|e| at_location(e)) {
+                        None => return Err(ShaderInterfaceMismatchError::MissingElement {
+                                               location: loc,
+                                           }),
+                        Some(b) => return Err(ShaderInterfaceMismatchError::ComponentMismatch {
+                                                  location: loc,
+                                                  self_component: a.component,
+                                                  other_component: b.component,
+                                              }),
+                    },
                 };
 
                 if a.format != b.format {
@@ -486,6 +694,12 @@ unsafe impl<T, I> ShaderInterfaceDefMatch<I> for T
                     });
                 }
 
+                if a.is_patch != b.is_patch {
+                    return Err(ShaderInterfaceMismatchError::PatchMismatch {
+                        location: loc,
+                    });
+                }
+
                 // TODO: enforce this?
                 /*match (a.name, b.name) {
                     (Some(ref an), Some(ref bn)) => if an != bn { return false },
@@ -501,6 +715,25 @@ unsafe impl<T, I> ShaderInterfaceDefMatch<I> for T
     }
 }
 
+/// Returns the location of the first input of a fragment shader that has an integer or
+/// double-precision format but isn't decorated with `Flat`, which Vulkan doesn't allow.
+pub fn non_flat_fragment_input<I>(input: &I) -> Option<u32>
+    where I: ShaderInterfaceDef
+{
+    input
+        .elements()
+        .filter(|e| e.builtin.is_none())
+        .filter(|e| e.interpolation != ShaderInterfaceInterpolation::Flat)
+        .find(|e| {
+                  let ty = e.format.ty();
+                  ty == FormatTy::Uint || ty == FormatTy::Sint ||
+                      e.format == Format::R64Sfloat || e.format == Format::R64G64Sfloat ||
+                      e.format == Format::R64G64B64Sfloat ||
+                      e.format == Format::R64G64B64A64Sfloat
+              })
+        .map(|e| e.location.start)
+}
+
 /// Error that can happen when the interface mismatches between two shader stages.
 #[derive(Clone, Debug, PartialEq, Eq)]
 pub enum ShaderInterfaceMismatchError {
@@ -527,6 +760,39 @@ pub enum ShaderInterfaceMismatchError {
         /// Format in the second interface.
         other_format: Format
     },
+
+    /// An element exists at the same location in both interfaces, but starts at a different
+    /// component.
+    ComponentMismatch {
+        /// Location of the element that mismatches.
+        location: u32,
+        /// First component in the first interface.
+        self_component: u32,
+        /// First component in the second interface.
+        other_component: u32,
+    },
+
+    /// An element is per-patch in one interface and per-vertex in the other.
+    PatchMismatch {
+        /// Location of the element that mismatches.
+        location: u32,
+    },
+
+    /// A built-in read by the first interface is not written by the second one.
+    MissingBuiltIn {
+        /// The missing built-in.
+        builtin: ShaderInterfaceBuiltIn,
+    },
+
+    /// The format of a built-in does not match.
+    BuiltInFormatMismatch {
+        /// The built-in that mismatches.
+        builtin: ShaderInterfaceBuiltIn,
+        /// Format in the first interface.
+        self_format: Format,
+        /// Format in the second interface.
+        other_format: Format,
+    },
 }
 
 impl error::Error for ShaderInterfaceMismatchError {
@@ -542,6 +808,18 @@ impl error::Error for ShaderInterfaceMismatchError {
             ShaderInterfaceMismatchError::FormatMismatch { .. } => {
                 "the format of an element does not match"
             },
+            ShaderInterfaceMismatchError::ComponentMismatch { .. } => {
+                "an element does not start at the same component"
+            },
+            ShaderInterfaceMismatchError::PatchMismatch { .. } => {
+                "an element is per-patch in one interface and per-vertex in the other"
+            },
+            ShaderInterfaceMismatchError::MissingBuiltIn { .. } => {
+                "a built-in is read but not written by the previous stage"
+            },
+            ShaderInterfaceMismatchError::BuiltInFormatMismatch { .. } => {
+                "the format of a built-in does not match"
+            },
         }
     }
 }
@@ -647,3 +925,175 @@ pub struct SpecializationMapEntry {
     /// Size of the data in bytes. Must match the size of the constant (`4` for booleans).
     pub size: usize,
 }
+
+#[cfg(test)]
+mod tests {
+    use format::Format;
+    use pipeline::shader::ShaderInterfaceBuiltIn;
+    use pipeline::shader::ShaderInterfaceDef;
+    use pipeline::shader::ShaderInterfaceDefEntry;
+    use pipeline::shader::ShaderInterfaceDefMatch;
+    use pipeline::shader::ShaderInterfaceInterpolation;
+    use pipeline::shader::ShaderInterfaceMismatchError;
+    use pipeline::shader::SpecializationConstants;
+    use pipeline::shader::SpecializationMapEntry;
+    use pipeline::shader::WorkgroupSize;
+    use pipeline::shader::WorkgroupSizeDimension;
+    use pipeline::shader::non_flat_fragment_input;
+    use std::vec::IntoIter as VecIntoIter;
+
+    struct Interface(Vec<ShaderInterfaceDefEntry>);
+
+    unsafe impl ShaderInterfaceDef for Interface {
+        type Iter = VecIntoIter<ShaderInterfaceDefEntry>;
+
+        fn elements(&self) -> Self::Iter {
+            self.0.clone().into_iter()
+        }
+    }
+
+    fn entry(location: u32, component: u32, format: Format) -> ShaderInterfaceDefEntry {
+        ShaderInterfaceDefEntry {
+            location: location .. location + 1,
+            format: format,
+            name: None,
+            component: component,
+            interpolation: ShaderInterfaceInterpolation::Smooth,
+            is_patch: false,
+            builtin: None,
+        }
+    }
+
+    fn builtin(builtin: ShaderInterfaceBuiltIn, format: Format) -> ShaderInterfaceDefEntry {
+        ShaderInterfaceDefEntry {
+            location: 0 .. 0,
+            format: format,
+            name: None,
+            component: 0,
+            interpolation: ShaderInterfaceInterpolation::Smooth,
+            is_patch: false,
+            builtin: Some(builtin),
+        }
+    }
+
+    #[test]
+    fn interface_components() {
+        let output = Interface(vec![entry(0, 0, Format::R32G32Sfloat),
+                                    entry(0, 2, Format::R32Sfloat)]);
+        let input = Interface(vec![entry(0, 2, Format::R32Sfloat),
+                                   entry(0, 0, Format::R32G32Sfloat)]);
+        assert!(input.matches(&output).is_ok());
+
+        let input = Interface(vec![entry(0, 1, Format::R32Sfloat),
+                                   entry(0, 0, Format::R32G32Sfloat)]);
+        match input.matches(&output) {
+            Err(ShaderInterfaceMismatchError::ComponentMismatch { location: 0, .. }) => (),
+            r => panic!("{:?}", r),
+        }
+    }
+
+    #[test]
+    fn interface_interpolation() {
+        // Interpolation decorations on outputs are ignored.
+        let mut flat = entry(0, 0, Format::R32Sint);
+        flat.interpolation = ShaderInterfaceInterpolation::Flat;
+
+        let output = Interface(vec![flat.clone()]);
+        let input = Interface(vec![entry(0, 0, Format::R32Sint)]);
+        assert!(input.matches(&output).is_ok());
+
+        let output = Interface(vec![entry(0, 0, Format::R32Sint)]);
+        let input = Interface(vec![flat.clone()]);
+        assert!(input.matches(&output).is_ok());
+
+        // Integer inputs of a fragment shader must be flat.
+        let input = Interface(vec![entry(0, 0, Format::R32G32B32A32Sfloat),
+                                   entry(1, 0, Format::R32Sint)]);
+        assert_eq!(non_flat_fragment_input(&input), Some(1));
+
+        let input = Interface(vec![entry(0, 0, Format::R32G32B32A32Sfloat), flat]);
+        assert_eq!(non_flat_fragment_input(&input), None);
+    }
+
+    #[test]
+    fn interface_patch() {
+        let output = Interface(vec![entry(3, 0, Format::R32G32B32A32Sfloat)]);
+        let mut patch = entry(3, 0, Format::R32G32B32A32Sfloat);
+        patch.is_patch = true;
+        let input = Interface(vec![patch]);
+
+        match input.matches(&output) {
+            Err(ShaderInterfaceMismatchError::PatchMismatch { location: 3 }) => (),
+            r => panic!("{:?}", r),
+        }
+    }
+
+    #[test]
+    fn interface_builtins() {
+        let output = Interface(vec![builtin(ShaderInterfaceBuiltIn::Position,
+                                            Format::R32G32B32A32Sfloat),
+                                    entry(0, 0, Format::R32Sfloat)]);
+        let input = Interface(vec![entry(0, 0, Format::R32Sfloat)]);
+        assert!(input.matches(&output).is_ok());
+
+        let input = Interface(vec![builtin(ShaderInterfaceBuiltIn::ClipDistance,
+                                           Format::R32Sfloat),
+                                   entry(0, 0, Format::R32Sfloat)]);
+        match input.matches(&output) {
+            Err(ShaderInterfaceMismatchError::MissingBuiltIn {
+                    builtin: ShaderInterfaceBuiltIn::ClipDistance,
+                }) => (),
+            r => panic!("{:?}", r),
+        }
+
+        // The view index is provided by the implementation.
+        let input = Interface(vec![builtin(ShaderInterfaceBuiltIn::ViewIndex, Format::R32Uint),
+                                   entry(0, 0, Format::R32Sfloat)]);
+        assert!(input.matches(&output).is_ok());
+    }
+
+    #[repr(C)]
+    struct SpecConsts {
+        a: u32,
+        b: u32,
+    }
+
+    unsafe impl SpecializationConstants for SpecConsts {
+        fn descriptors() -> &'static [SpecializationMapEntry] {
+            static DESCRIPTORS: [SpecializationMapEntry; 2] = [
+                SpecializationMapEntry {
+                    constant_id: 0,
+                    offset: 0,
+                    size: 4,
+                },
+                SpecializationMapEntry {
+                    constant_id: 5,
+                    offset: 4,
+                    size: 4,
+                },
+            ];
+
+            &DESCRIPTORS
+        }
+    }
+
+    #[test]
+    fn workgroup_size_resolve() {
+        let size = WorkgroupSize {
+            x: WorkgroupSizeDimension::SpecializationConstant {
+                constant_id: 5,
+                default_value: 1,
+            },
+            y: WorkgroupSizeDimension::Constant(4),
+            z: WorkgroupSizeDimension::SpecializationConstant {
+                constant_id: 7,
+                default_value: 2,
+            },
+        };
+
+        let spec = SpecConsts { a: 16, b: 64 };
+        assert_eq!(size.resolve(&spec), [64, 4, 2]);
+        assert_eq!(size.resolve(&()), [1, 4, 2]);
+        assert_eq!(WorkgroupSize::constant([8, 8, 1]).resolve(&spec), [8, 8, 1]);
+    }
+}
@@ -0,0 +1,640 @@
+// Copyright (c) 2026 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Support for reloading shaders while the application is running.
+//!
+//! The `vulkano-shaders` crate generates Rust types that describe the layout of a shader
+//! (descriptor sets, push constants, inputs and outputs). When the GLSL source is recompiled at
+//! runtime, the new SPIR-V must still match these types, otherwise pipelines created from the
+//! reloaded module would be unsound.
+//!
+//! This module provides a `ShaderLayoutSignature`, which is a summary of everything in a SPIR-V
+//! module that the generated types depend on. Two modules whose signatures are equal can be used
+//! interchangeably with the same generated types.
+//!
+//! You normally don't need to use this module directly. Instead pass `hot_reload: true` to the
+//! `shader!` macro, which generates a `reload` method that uses it.
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::error;
+use std::fmt;
+use std::io::Error as IoError;
+
+use OomError;
+
+/// Resource of a shader module whose layout is part of a `ShaderLayoutSignature`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ShaderResource {
+    /// Capability declared by the module, as the value of the SPIR-V `Capability` enum.
+    Capability(u32),
+    /// Descriptor at the given set and binding.
+    Descriptor {
+        set: u32,
+        binding: u32,
+    },
+    /// The push constants block.
+    PushConstants,
+    /// Input of the shader at the given location and component.
+    Input {
+        location: u32,
+        component: u32,
+    },
+    /// Output of the shader at the given location and component.
+    Output {
+        location: u32,
+        component: u32,
+    },
+    /// Specialization constant with the given `constant_id`.
+    SpecializationConstant {
+        constant_id: u32,
+    },
+}
+
+/// Summary of the parts of a SPIR-V module that the code generated by `vulkano-shaders` depends
+/// on.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShaderLayoutSignature {
+    // Canonical description of the type of each resource.
+    resources: BTreeMap<ShaderResource, String>,
+}
+
+impl ShaderLayoutSignature {
+    /// Builds the signature of a SPIR-V module.
+    pub fn from_words(spirv: &[u32]) -> Result<ShaderLayoutSignature, ShaderReloadError> {
+        let module = Module::parse(spirv)?;
+        let mut resources = BTreeMap::new();
+
+        for &cap in &module.capabilities {
+            resources.insert(ShaderResource::Capability(cap), String::new());
+        }
+
+        for &(result_type, id, storage_class) in &module.variables {
+            let decorations = module.decorations.get(&id);
+            let decoration = |dec: u32| {
+                decorations.and_then(|d| d.iter().find(|&&(d, _)| d == dec).map(|&(_, v)| v))
+            };
+
+            // Built-ins are not part of the generated types.
+            if decoration(DECORATION_BUILT_IN).is_some() {
+                continue;
+            }
+
+            let resource = match storage_class {
+                STORAGE_CLASS_UNIFORM_CONSTANT |
+                STORAGE_CLASS_UNIFORM |
+                STORAGE_CLASS_STORAGE_BUFFER => {
+                    ShaderResource::Descriptor {
+                        set: decoration(DECORATION_DESCRIPTOR_SET).unwrap_or(0),
+                        binding: decoration(DECORATION_BINDING).unwrap_or(0),
+                    }
+                },
+                STORAGE_CLASS_PUSH_CONSTANT => ShaderResource::PushConstants,
+                STORAGE_CLASS_INPUT | STORAGE_CLASS_OUTPUT => {
+                    let location = match decoration(DECORATION_LOCATION) {
+                        Some(l) => l,
+                        None => continue,
+                    };
+                    // Variables packed in the same location are distinguished by their
+                    // component.
+                    let component = decoration(DECORATION_COMPONENT).unwrap_or(0);
+
+                    if storage_class == STORAGE_CLASS_INPUT {
+                        ShaderResource::Input { location, component }
+                    } else {
+                        ShaderResource::Output { location, component }
+                    }
+                },
+                _ => continue,
+            };
+
+            let mut description = String::new();
+            module.describe_type(result_type, &mut description, 0)?;
+            resources.insert(resource, description);
+        }
+
+        // The generated `SpecializationConstants` struct has one member per constant.
+        for &(result_type, id) in &module.spec_constants {
+            let constant_id = match module.decorations
+                .get(&id)
+                .and_then(|d| d.iter().find(|&&(d, _)| d == DECORATION_SPEC_ID))
+            {
+                Some(&(_, constant_id)) => constant_id,
+                None => continue,
+            };
+
+            let mut description = String::new();
+            module.describe_type(result_type, &mut description, 0)?;
+            resources.insert(ShaderResource::SpecializationConstant { constant_id },
+                             description);
+        }
+
+        Ok(ShaderLayoutSignature { resources })
+    }
+
+    /// Returns an iterator to the list of resources of the module.
+    #[inline]
+    pub fn resources<'a>(&'a self) -> impl Iterator<Item = ShaderResource> + 'a {
+        self.resources.keys().cloned()
+    }
+
+    /// Checks whether a module with the signature `other` can be used in place of a module with
+    /// the signature `self`.
+    ///
+    /// `other` is not allowed to declare capabilities that `self` doesn't declare, but it is
+    /// allowed to stop using some of them.
+    pub fn check_compatible(&self, other: &ShaderLayoutSignature)
+                            -> Result<(), ShaderReloadError> {
+        for (resource, description) in &self.resources {
+            if let ShaderResource::Capability(_) = *resource {
+                continue;
+            }
+
+            match other.resources.get(resource) {
+                None => return Err(ShaderReloadError::MissingResource(*resource)),
+                Some(d) if d != description => {
+                    return Err(ShaderReloadError::ResourceMismatch(*resource));
+                },
+                Some(_) => (),
+            }
+        }
+
+        for resource in other.resources.keys() {
+            if !self.resources.contains_key(resource) {
+                return Err(ShaderReloadError::UnexpectedResource(*resource));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Error that can happen when reloading a shader.
+#[derive(Debug)]
+pub enum ShaderReloadError {
+    /// Error while reading the source of the shader.
+    IoError(IoError),
+    /// Error while compiling the source of the shader. Contains the compiler's output.
+    CompilationError(String),
+    /// The SPIR-V code is malformed.
+    InvalidSpirv,
+    /// A resource that the generated types expect is missing from the new module.
+    MissingResource(ShaderResource),
+    /// The new module uses a resource that the generated types don't know about.
+    UnexpectedResource(ShaderResource),
+    /// The layout of a resource is different in the new module.
+    ResourceMismatch(ShaderResource),
+    /// Not enough memory.
+    OomError(OomError),
+}
+
+impl error::Error for ShaderReloadError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            ShaderReloadError::IoError(_) => "error while reading the source of the shader",
+            ShaderReloadError::CompilationError(_) => "error while compiling the shader",
+            ShaderReloadError::InvalidSpirv => "the SPIR-V code is malformed",
+            ShaderReloadError::MissingResource(_) => {
+                "a resource expected by the generated types is missing from the new module"
+            },
+            ShaderReloadError::UnexpectedResource(_) => {
+                "the new module uses a resource unknown to the generated types"
+            },
+            ShaderReloadError::ResourceMismatch(_) => {
+                "the layout of a resource is different in the new module"
+            },
+            ShaderReloadError::OomError(_) => "not enough memory available",
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&dyn error::Error> {
+        match *self {
+            ShaderReloadError::IoError(ref err) => Some(err),
+            ShaderReloadError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ShaderReloadError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        match *self {
+            ShaderReloadError::CompilationError(ref msg) => {
+                write!(fmt, "{}: {}", error::Error::description(self), msg)
+            },
+            ShaderReloadError::MissingResource(r) |
+            ShaderReloadError::UnexpectedResource(r) |
+            ShaderReloadError::ResourceMismatch(r) => {
+                write!(fmt, "{} ({:?})", error::Error::description(self), r)
+            },
+            _ => write!(fmt, "{}", error::Error::description(self)),
+        }
+    }
+}
+
+impl From<IoError> for ShaderReloadError {
+    #[inline]
+    fn from(err: IoError) -> ShaderReloadError {
+        ShaderReloadError::IoError(err)
+    }
+}
+
+impl From<OomError> for ShaderReloadError {
+    #[inline]
+    fn from(err: OomError) -> ShaderReloadError {
+        ShaderReloadError::OomError(err)
+    }
+}
+
+const SPIRV_MAGIC: u32 = 0x07230203;
+
+const OP_CAPABILITY: u32 = 17;
+const OP_TYPE_VOID: u32 = 19;
+const OP_TYPE_BOOL: u32 = 20;
+const OP_TYPE_INT: u32 = 21;
+const OP_TYPE_FLOAT: u32 = 22;
+const OP_TYPE_VECTOR: u32 = 23;
+const OP_TYPE_MATRIX: u32 = 24;
+const OP_TYPE_IMAGE: u32 = 25;
+const OP_TYPE_SAMPLER: u32 = 26;
+const OP_TYPE_SAMPLED_IMAGE: u32 = 27;
+const OP_TYPE_ARRAY: u32 = 28;
+const OP_TYPE_RUNTIME_ARRAY: u32 = 29;
+const OP_TYPE_STRUCT: u32 = 30;
+const OP_TYPE_POINTER: u32 = 32;
+const OP_CONSTANT: u32 = 43;
+const OP_SPEC_CONSTANT_TRUE: u32 = 48;
+const OP_SPEC_CONSTANT_FALSE: u32 = 49;
+const OP_SPEC_CONSTANT: u32 = 50;
+const OP_VARIABLE: u32 = 59;
+const OP_DECORATE: u32 = 71;
+const OP_MEMBER_DECORATE: u32 = 72;
+
+const DECORATION_BLOCK: u32 = 2;
+const DECORATION_BUFFER_BLOCK: u32 = 3;
+const DECORATION_ROW_MAJOR: u32 = 4;
+const DECORATION_COL_MAJOR: u32 = 5;
+const DECORATION_ARRAY_STRIDE: u32 = 6;
+const DECORATION_MATRIX_STRIDE: u32 = 7;
+const DECORATION_BUILT_IN: u32 = 11;
+const DECORATION_SPEC_ID: u32 = 1;
+const DECORATION_LOCATION: u32 = 30;
+const DECORATION_COMPONENT: u32 = 31;
+const DECORATION_BINDING: u32 = 33;
+const DECORATION_DESCRIPTOR_SET: u32 = 34;
+const DECORATION_OFFSET: u32 = 35;
+
+const STORAGE_CLASS_UNIFORM_CONSTANT: u32 = 0;
+const STORAGE_CLASS_INPUT: u32 = 1;
+const STORAGE_CLASS_UNIFORM: u32 = 2;
+const STORAGE_CLASS_OUTPUT: u32 = 3;
+const STORAGE_CLASS_PUSH_CONSTANT: u32 = 9;
+const STORAGE_CLASS_STORAGE_BUFFER: u32 = 12;
+
+// Maximum nesting of types, to protect against malformed modules with cyclic definitions.
+const MAX_TYPE_DEPTH: u32 = 64;
+
+// The subset of a SPIR-V module that is needed to build a signature.
+struct Module<'a> {
+    capabilities: Vec<u32>,
+    // Type, constant and spec constant instructions, indexed by result id.
+    definitions: HashMap<u32, (u32, &'a [u32])>,
+    // `(result_type, result_id, storage_class)` of each global variable.
+    variables: Vec<(u32, u32, u32)>,
+    // `(result_type, result_id)` of each scalar specialization constant.
+    spec_constants: Vec<(u32, u32)>,
+    // `(decoration, first operand)` for each decorated id.
+    decorations: HashMap<u32, Vec<(u32, u32)>>,
+    // `(member, decoration, first operand)` for each decorated struct.
+    member_decorations: HashMap<u32, Vec<(u32, u32, u32)>>,
+}
+
+impl<'a> Module<'a> {
+    fn parse(spirv: &'a [u32]) -> Result<Module<'a>, ShaderReloadError> {
+        if spirv.len() < 5 || spirv[0] != SPIRV_MAGIC {
+            return Err(ShaderReloadError::InvalidSpirv);
+        }
+
+        let mut module = Module {
+            capabilities: Vec::new(),
+            definitions: HashMap::new(),
+            variables: Vec::new(),
+            spec_constants: Vec::new(),
+            decorations: HashMap::new(),
+            member_decorations: HashMap::new(),
+        };
+
+        let mut words = &spirv[5 ..];
+        while !words.is_empty() {
+            let opcode = words[0] & 0xffff;
+            let word_count = (words[0] >> 16) as usize;
+            if word_count == 0 || word_count > words.len() {
+                return Err(ShaderReloadError::InvalidSpirv);
+            }
+            let operands = &words[1 .. word_count];
+            words = &words[word_count ..];
+
+            match opcode {
+                OP_CAPABILITY if !operands.is_empty() => {
+                    module.capabilities.push(operands[0]);
+                },
+                OP_TYPE_VOID ..= OP_TYPE_POINTER if !operands.is_empty() => {
+                    module.definitions.insert(operands[0], (opcode, &operands[1 ..]));
+                },
+                OP_CONSTANT | OP_SPEC_CONSTANT if operands.len() >= 2 => {
+                    module.definitions.insert(operands[1], (opcode, &operands[2 ..]));
+                    if opcode == OP_SPEC_CONSTANT {
+                        module.spec_constants.push((operands[0], operands[1]));
+                    }
+                },
+                OP_SPEC_CONSTANT_TRUE | OP_SPEC_CONSTANT_FALSE if operands.len() >= 2 => {
+                    module.spec_constants.push((operands[0], operands[1]));
+                },
+                OP_VARIABLE if operands.len() >= 3 => {
+                    module.variables.push((operands[0], operands[1], operands[2]));
+                },
+                OP_DECORATE if operands.len() >= 2 => {
+                    module.decorations
+                        .entry(operands[0])
+                        .or_insert_with(Vec::new)
+                        .push((operands[1], operands.get(2).cloned().unwrap_or(0)));
+                },
+                OP_MEMBER_DECORATE if operands.len() >= 3 => {
+                    module.member_decorations
+                        .entry(operands[0])
+                        .or_insert_with(Vec::new)
+                        .push((operands[1], operands[2], operands.get(3).cloned().unwrap_or(0)));
+                },
+                _ => (),
+            }
+        }
+
+        Ok(module)
+    }
+
+    // Writes a description of the type `id` in `out` that doesn't depend on the ids chosen by the
+    // compiler.
+    fn describe_type(&self, id: u32, out: &mut String, depth: u32)
+                     -> Result<(), ShaderReloadError> {
+        use std::fmt::Write;
+
+        if depth > MAX_TYPE_DEPTH {
+            return Err(ShaderReloadError::InvalidSpirv);
+        }
+
+        let &(opcode, operands) = self.definitions
+            .get(&id)
+            .ok_or(ShaderReloadError::InvalidSpirv)?;
+        let operand = |n: usize| operands.get(n).cloned().ok_or(ShaderReloadError::InvalidSpirv);
+
+        match opcode {
+            OP_TYPE_VOID => out.push_str("void"),
+            OP_TYPE_BOOL => out.push_str("bool"),
+            OP_TYPE_INT => {
+                let prefix = if operand(1)? != 0 { 'i' } else { 'u' };
+                write!(out, "{}{}", prefix, operand(0)?).unwrap();
+            },
+            OP_TYPE_FLOAT => write!(out, "f{}", operand(0)?).unwrap(),
+            OP_TYPE_VECTOR | OP_TYPE_MATRIX => {
+                let prefix = if opcode == OP_TYPE_VECTOR { "vec" } else { "mat" };
+                write!(out, "{}{}<", prefix, operand(1)?).unwrap();
+                self.describe_type(operand(0)?, out, depth + 1)?;
+                out.push('>');
+            },
+            OP_TYPE_IMAGE => {
+                // Dim, depth, arrayed, multisampled, sampled, format.
+                out.push_str("image<");
+                self.describe_type(operand(0)?, out, depth + 1)?;
+                for n in 1 .. 7 {
+                    write!(out, ",{}", operand(n)?).unwrap();
+                }
+                out.push('>');
+            },
+            OP_TYPE_SAMPLER => out.push_str("sampler"),
+            OP_TYPE_SAMPLED_IMAGE => {
+                out.push_str("sampled<");
+                self.describe_type(operand(0)?, out, depth + 1)?;
+                out.push('>');
+            },
+            OP_TYPE_ARRAY | OP_TYPE_RUNTIME_ARRAY => {
+                out.push('[');
+                self.describe_type(operand(0)?, out, depth + 1)?;
+                if opcode == OP_TYPE_ARRAY {
+                    out.push(';');
+                    self.describe_constant(operand(1)?, out)?;
+                }
+                self.describe_decorations(id, out);
+                out.push(']');
+            },
+            OP_TYPE_STRUCT => {
+                out.push_str("struct");
+                self.describe_decorations(id, out);
+                out.push('{');
+                for (member, &member_ty) in operands.iter().enumerate() {
+                    if member != 0 {
+                        out.push(',');
+                    }
+                    self.describe_type(member_ty, out, depth + 1)?;
+                    if let Some(decorations) = self.member_decorations.get(&id) {
+                        let mut decorations = decorations
+                            .iter()
+                            .filter(|&&(m, d, _)| m == member as u32 && is_layout_decoration(d))
+                            .map(|&(_, d, v)| (d, v))
+                            .collect::<Vec<_>>();
+                        decorations.sort();
+                        for (d, v) in decorations {
+                            write!(out, "@{}={}", d, v).unwrap();
+                        }
+                    }
+                }
+                out.push('}');
+            },
+            OP_TYPE_POINTER => {
+                write!(out, "ptr{}<", operand(0)?).unwrap();
+                self.describe_type(operand(1)?, out, depth + 1)?;
+                out.push('>');
+            },
+            _ => return Err(ShaderReloadError::InvalidSpirv),
+        }
+
+        Ok(())
+    }
+
+    // Writes the value of a constant used as an array length.
+    fn describe_constant(&self, id: u32, out: &mut String) -> Result<(), ShaderReloadError> {
+        use std::fmt::Write;
+
+        match self.definitions.get(&id) {
+            Some(&(OP_CONSTANT, operands)) => {
+                write!(out, "{}", operands.get(0).cloned().unwrap_or(0)).unwrap();
+            },
+            Some(&(OP_SPEC_CONSTANT, _)) => {
+                let spec_id = self.decorations
+                    .get(&id)
+                    .and_then(|d| d.iter().find(|&&(d, _)| d == DECORATION_SPEC_ID))
+                    .map(|&(_, v)| v)
+                    .ok_or(ShaderReloadError::InvalidSpirv)?;
+                write!(out, "spec{}", spec_id).unwrap();
+            },
+            _ => return Err(ShaderReloadError::InvalidSpirv),
+        }
+
+        Ok(())
+    }
+
+    // Writes the layout-related decorations of a type.
+    fn describe_decorations(&self, id: u32, out: &mut String) {
+        use std::fmt::Write;
+
+        if let Some(decorations) = self.decorations.get(&id) {
+            let mut decorations = decorations
+                .iter()
+                .filter(|&&(d, _)| is_layout_decoration(d))
+                .cloned()
+                .collect::<Vec<_>>();
+            decorations.sort();
+            for (d, v) in decorations {
+                write!(out, "@{}={}", d, v).unwrap();
+            }
+        }
+    }
+}
+
+// Returns true if the decoration changes the memory layout or the descriptor type of a type.
+#[inline]
+fn is_layout_decoration(decoration: u32) -> bool {
+    match decoration {
+        DECORATION_BLOCK |
+        DECORATION_BUFFER_BLOCK |
+        DECORATION_ROW_MAJOR |
+        DECORATION_COL_MAJOR |
+        DECORATION_ARRAY_STRIDE |
+        DECORATION_MATRIX_STRIDE |
+        DECORATION_OFFSET => true,
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pipeline::shader_reload::ShaderLayoutSignature;
+    use pipeline::shader_reload::ShaderReloadError;
+    use pipeline::shader_reload::ShaderResource;
+
+    // Builds a module with a uniform buffer containing a single member of type `member_ty`
+    // (`0` for `float`, `1` for `vec4`) at the given set and binding.
+    fn uniform_module(set: u32, binding: u32, member_ty: u32) -> Vec<u32> {
+        let mut words = vec![0x07230203, 0x00010000, 0, 20, 0];
+        let mut inst = |opcode: u32, operands: &[u32]| {
+            words.push(((operands.len() as u32 + 1) << 16) | opcode);
+            words.extend_from_slice(operands);
+        };
+
+        inst(17, &[1]); // OpCapability Shader
+        inst(71, &[10, 34, set]); // OpDecorate %var DescriptorSet
+        inst(71, &[10, 33, binding]); // OpDecorate %var Binding
+        inst(71, &[5, 2]); // OpDecorate %block Block
+        inst(72, &[5, 0, 35, 0]); // OpMemberDecorate %block 0 Offset 0
+        inst(22, &[2, 32]); // %2 = OpTypeFloat 32
+        inst(23, &[3, 2, 4]); // %3 = OpTypeVector %2 4
+        inst(30, &[5, 2 + member_ty]); // %5 = OpTypeStruct
+        inst(32, &[6, 2, 5]); // %6 = OpTypePointer Uniform %5
+        inst(59, &[6, 10, 2]); // %10 = OpVariable %6 Uniform
+        words
+    }
+
+    #[test]
+    fn identical_modules_compatible() {
+        let a = ShaderLayoutSignature::from_words(&uniform_module(0, 1, 1)).unwrap();
+        let b = ShaderLayoutSignature::from_words(&uniform_module(0, 1, 1)).unwrap();
+        assert!(a.check_compatible(&b).is_ok());
+        assert_eq!(a.resources().collect::<Vec<_>>(),
+                   vec![ShaderResource::Capability(1),
+                        ShaderResource::Descriptor { set: 0, binding: 1 }]);
+    }
+
+    #[test]
+    fn binding_moved() {
+        let a = ShaderLayoutSignature::from_words(&uniform_module(0, 1, 1)).unwrap();
+        let b = ShaderLayoutSignature::from_words(&uniform_module(1, 1, 1)).unwrap();
+        match a.check_compatible(&b) {
+            Err(ShaderReloadError::MissingResource(ShaderResource::Descriptor {
+                                                       set: 0,
+                                                       binding: 1,
+                                                   })) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn member_type_changed() {
+        let a = ShaderLayoutSignature::from_words(&uniform_module(0, 1, 1)).unwrap();
+        let b = ShaderLayoutSignature::from_words(&uniform_module(0, 1, 0)).unwrap();
+        match a.check_compatible(&b) {
+            Err(ShaderReloadError::ResourceMismatch(ShaderResource::Descriptor { .. })) => (),
+            _ => panic!(),
+        }
+    }
+
+    // Builds a module with two `float` inputs packed in location 0, at components 0 and 1, and
+    // a specialization constant of type `spec_ty` (`2` for `float`, `7` for `int`).
+    fn packed_inputs_module(spec_ty: u32) -> Vec<u32> {
+        let mut words = vec![0x07230203, 0x00010000, 0, 20, 0];
+        let mut inst = |opcode: u32, operands: &[u32]| {
+            words.push(((operands.len() as u32 + 1) << 16) | opcode);
+            words.extend_from_slice(operands);
+        };
+
+        inst(17, &[1]); // OpCapability Shader
+        inst(71, &[10, 30, 0]); // OpDecorate %10 Location 0
+        inst(71, &[11, 30, 0]); // OpDecorate %11 Location 0
+        inst(71, &[11, 31, 1]); // OpDecorate %11 Component 1
+        inst(71, &[12, 1, 3]); // OpDecorate %12 SpecId 3
+        inst(22, &[2, 32]); // %2 = OpTypeFloat 32
+        inst(21, &[7, 32, 1]); // %7 = OpTypeInt 32 1
+        inst(32, &[6, 1, 2]); // %6 = OpTypePointer Input %2
+        inst(50, &[spec_ty, 12, 0]); // %12 = OpSpecConstant
+        inst(59, &[6, 10, 1]); // %10 = OpVariable %6 Input
+        inst(59, &[6, 11, 1]); // %11 = OpVariable %6 Input
+        words
+    }
+
+    #[test]
+    fn packed_inputs() {
+        let a = ShaderLayoutSignature::from_words(&packed_inputs_module(2)).unwrap();
+        assert_eq!(a.resources().collect::<Vec<_>>(),
+                   vec![ShaderResource::Capability(1),
+                        ShaderResource::Input { location: 0, component: 0 },
+                        ShaderResource::Input { location: 0, component: 1 },
+                        ShaderResource::SpecializationConstant { constant_id: 3 }]);
+    }
+
+    #[test]
+    fn spec_constant_type_changed() {
+        let a = ShaderLayoutSignature::from_words(&packed_inputs_module(2)).unwrap();
+        let b = ShaderLayoutSignature::from_words(&packed_inputs_module(7)).unwrap();
+        match a.check_compatible(&b) {
+            Err(ShaderReloadError::ResourceMismatch(ShaderResource::SpecializationConstant {
+                                                        constant_id: 3,
+                                                    })) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn invalid_spirv() {
+        match ShaderLayoutSignature::from_words(&[1, 2, 3]) {
+            Err(ShaderReloadError::InvalidSpirv) => (),
+            _ => panic!(),
+        }
+    }
+}
@@ -48,6 +48,11 @@ where
         let attrib = {
             let mut attribs = Vec::with_capacity(interface.elements().len());
             for e in interface.elements() {
+                // Built-ins such as `gl_ViewIndex` are provided by the implementation.
+                if e.builtin.is_some() {
+                    continue;
+                }
+
                 let name = e.name.as_ref().unwrap();
 
                 let infos = match <T as Vertex>::member(name) {
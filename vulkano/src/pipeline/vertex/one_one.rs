@@ -47,6 +47,11 @@ unsafe impl<T, U, I> VertexDefinition<I> for OneVertexOneInstanceDefinition<T, U
         let attrib = {
             let mut attribs = Vec::with_capacity(interface.elements().len());
             for e in interface.elements() {
+                // Built-ins such as `gl_ViewIndex` are provided by the implementation.
+                if e.builtin.is_some() {
+                    continue;
+                }
+
                 let name = e.name.as_ref().unwrap();
 
                 let (infos, buf_offset) = if let Some(infos) = <T as Vertex>::member(name) {
@@ -46,6 +46,11 @@ unsafe impl<T, I> VertexDefinition<I> for SingleBufferDefinition<T>
         let attrib = {
             let mut attribs = Vec::with_capacity(interface.elements().len());
             for e in interface.elements() {
+                // Built-ins such as `gl_ViewIndex` are provided by the implementation.
+                if e.builtin.is_some() {
+                    continue;
+                }
+
                 let name = e.name.as_ref().unwrap();
 
                 let infos = match <T as Vertex>::member(name) {
@@ -0,0 +1,626 @@
+// Copyright (c) 2026 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::error;
+use std::fmt;
+use std::sync::Arc;
+
+use command_buffer::submit::SubmitCommandBufferBuilder;
+use device::DeviceOwned;
+use device::Queue;
+use image::sys::ImageCreationError;
+use image::swapchain::SwapchainImage;
+use swapchain::AcquireError;
+use swapchain::PresentFuture;
+use swapchain::Swapchain;
+use swapchain::SwapchainAcquireFuture;
+use swapchain::SwapchainCreationError;
+use swapchain::VirtualSwapchain;
+use swapchain::VirtualSwapchainAcquireFuture;
+use swapchain::VirtualSwapchainImage;
+use swapchain::VirtualSwapchainPresentFuture;
+use swapchain::acquire_next_image;
+use swapchain::acquire_next_virtual_image;
+use swapchain::present;
+use swapchain::present_virtual;
+use sync::Fence;
+use sync::FenceSignalFuture;
+use sync::FlushError;
+use sync::GpuFuture;
+
+// A recreated swapchain and its images.
+type Recreated<S> = (Arc<S>, Vec<Arc<<S as FrameSwapchain>::Image>>);
+
+/// A swapchain that can be driven by a `FrameScheduler`.
+///
+/// Implemented on `Swapchain` and on `VirtualSwapchain`.
+pub trait FrameSwapchain: Sized {
+    /// Type of the images of the swapchain.
+    type Image;
+    /// Future returned when acquiring an image.
+    type AcquireFuture: GpuFuture + 'static;
+    /// Future returned when presenting an image.
+    type PresentFuture: GpuFuture + 'static;
+
+    /// Returns the dimensions of the images of the swapchain.
+    fn dimensions(&self) -> [u32; 2];
+
+    /// Builds a new swapchain with the same parameters but with different dimensions.
+    fn recreate_with_dimensions(&self, dimensions: [u32; 2])
+                                -> Result<Recreated<Self>, FrameError>;
+
+    /// Acquires an image of the swapchain. Blocks until an image is available.
+    fn acquire(swapchain: Arc<Self>)
+               -> Result<(usize, bool, Self::AcquireFuture), AcquireError>;
+
+    /// Presents an image of the swapchain after `before`.
+    fn present(swapchain: Arc<Self>, before: Box<dyn GpuFuture>, queue: Arc<Queue>,
+               image_num: usize)
+               -> Self::PresentFuture;
+}
+
+impl<W> FrameSwapchain for Swapchain<W>
+    where W: 'static
+{
+    type Image = SwapchainImage<W>;
+    type AcquireFuture = SwapchainAcquireFuture<W>;
+    type PresentFuture = PresentFuture<Box<dyn GpuFuture>, W>;
+
+    #[inline]
+    fn dimensions(&self) -> [u32; 2] {
+        self.dimensions()
+    }
+
+    #[inline]
+    fn recreate_with_dimensions(&self, dimensions: [u32; 2])
+                                -> Result<Recreated<Self>, FrameError> {
+        Ok(self.recreate_with_dimensions(dimensions)?)
+    }
+
+    #[inline]
+    fn acquire(swapchain: Arc<Self>)
+               -> Result<(usize, bool, Self::AcquireFuture), AcquireError> {
+        acquire_next_image(swapchain, None)
+    }
+
+    #[inline]
+    fn present(swapchain: Arc<Self>, before: Box<dyn GpuFuture>, queue: Arc<Queue>,
+               image_num: usize)
+               -> Self::PresentFuture {
+        present(swapchain, before, queue, image_num)
+    }
+}
+
+impl FrameSwapchain for VirtualSwapchain {
+    type Image = VirtualSwapchainImage;
+    type AcquireFuture = VirtualSwapchainAcquireFuture;
+    type PresentFuture = VirtualSwapchainPresentFuture<Box<dyn GpuFuture>>;
+
+    #[inline]
+    fn dimensions(&self) -> [u32; 2] {
+        self.dimensions()
+    }
+
+    #[inline]
+    fn recreate_with_dimensions(&self, dimensions: [u32; 2])
+                                -> Result<Recreated<Self>, FrameError> {
+        Ok(self.recreate_with_dimensions(dimensions)?)
+    }
+
+    #[inline]
+    fn acquire(swapchain: Arc<Self>)
+               -> Result<(usize, bool, Self::AcquireFuture), AcquireError> {
+        acquire_next_virtual_image(swapchain, None)
+    }
+
+    #[inline]
+    fn present(swapchain: Arc<Self>, before: Box<dyn GpuFuture>, queue: Arc<Queue>,
+               image_num: usize)
+               -> Self::PresentFuture {
+        present_virtual(swapchain, before, queue, image_num)
+    }
+}
+
+// What the GPU must have finished before a slot can be reused.
+enum SubmittedFrame<S>
+    where S: FrameSwapchain
+{
+    // A frame that has been presented.
+    Presented(Arc<FenceSignalFuture<S::PresentFuture>>),
+    // A frame whose submission has failed. The fence is signaled once the commands that have
+    // been submitted to the queue before the failure are finished.
+    Failed(Fence),
+}
+
+impl<S> SubmittedFrame<S>
+    where S: FrameSwapchain
+{
+    fn wait(&self) -> Result<(), FlushError> {
+        match *self {
+            SubmittedFrame::Presented(ref future) => future.wait(None),
+            SubmittedFrame::Failed(ref fence) => Ok(fence.wait(None)?),
+        }
+    }
+
+    fn cleanup_finished(&mut self) {
+        if let SubmittedFrame::Presented(ref mut future) = *self {
+            future.cleanup_finished();
+        }
+    }
+}
+
+/// Drives the acquire, render and present loop of a swapchain.
+///
+/// The scheduler keeps up to `frames_in_flight` frames being processed by the GPU at the same
+/// time. Each frame is assigned a slot between 0 and `frames_in_flight`, and a slot is only
+/// reused once the GPU has finished processing the frame that previously used it. Resources that
+/// are written by the CPU every frame, such as the buffers of a `CpuBufferPool`, can therefore be
+/// duplicated per slot.
+///
+/// The swapchain is recreated when it is out of date, when it is suboptimal, or when the
+/// dimensions passed to `begin_frame` change. The images of the new swapchain are passed to a
+/// callback so that the framebuffers can be rebuilt.
+///
+/// Both `Swapchain`s and `VirtualSwapchain`s can be driven by a `FrameScheduler`.
+///
+/// # Example
+///
+/// ```
+/// use std::sync::Arc;
+/// use vulkano::swapchain::FrameScheduler;
+/// use vulkano::sync::GpuFuture;
+/// # use vulkano::swapchain::Swapchain;
+/// # use vulkano::image::SwapchainImage;
+/// # let swapchain: Arc<Swapchain<()>> = return;
+/// # let images: Vec<Arc<SwapchainImage<()>>> = return;
+/// # let queue: Arc<vulkano::device::Queue> = return;
+/// # let window_dimensions: [u32; 2] = return;
+/// # let rebuild_framebuffers = |_: &[Arc<SwapchainImage<()>>]| ();
+///
+/// let mut scheduler = FrameScheduler::new(swapchain, images, queue.clone(), 2);
+///
+/// loop {
+///     let frame = match scheduler.begin_frame(window_dimensions, rebuild_framebuffers).unwrap() {
+///         Some(frame) => frame,
+///         // The frame must be skipped, for example because the window is minimized.
+///         None => continue,
+///     };
+///
+///     // Build a command buffer that draws to `frame.image()`, and execute it after the
+///     // future of the frame.
+///     let future = frame.into_future();
+///
+///     scheduler.end_frame(future).unwrap();
+/// }
+/// ```
+pub struct FrameScheduler<S>
+    where S: FrameSwapchain
+{
+    swapchain: Arc<S>,
+    images: Vec<Arc<S::Image>>,
+    queue: Arc<Queue>,
+    // The last frame submitted in each slot.
+    frames: Vec<Option<SubmittedFrame<S>>>,
+    // The last frame that was presented, which the next frame is executed after.
+    previous_frame: Option<Arc<FenceSignalFuture<S::PresentFuture>>>,
+    // Slot of the current or next frame.
+    slot: usize,
+    // Index of the image acquired by `begin_frame`, if `end_frame` hasn't been called yet.
+    acquired_image: Option<usize>,
+    // True if the swapchain must be recreated before the next frame.
+    recreate: bool,
+}
+
+impl<S> FrameScheduler<S>
+    where S: FrameSwapchain
+{
+    /// Builds a new `FrameScheduler` from a swapchain and its images.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `frames_in_flight` is 0.
+    ///
+    pub fn new(swapchain: Arc<S>, images: Vec<Arc<S::Image>>, queue: Arc<Queue>,
+               frames_in_flight: usize)
+               -> FrameScheduler<S> {
+        assert!(frames_in_flight >= 1);
+
+        FrameScheduler {
+            swapchain,
+            images,
+            queue,
+            frames: (0 .. frames_in_flight).map(|_| None).collect(),
+            previous_frame: None,
+            slot: 0,
+            acquired_image: None,
+            recreate: false,
+        }
+    }
+
+    /// Returns the current swapchain.
+    #[inline]
+    pub fn swapchain(&self) -> &Arc<S> {
+        &self.swapchain
+    }
+
+    /// Returns the images of the current swapchain.
+    #[inline]
+    pub fn images(&self) -> &[Arc<S::Image>] {
+        &self.images
+    }
+
+    /// Returns the maximum number of frames that can be processed by the GPU at the same time,
+    /// which is also the number of slots.
+    #[inline]
+    pub fn frames_in_flight(&self) -> usize {
+        self.frames.len()
+    }
+
+    /// Requests the swapchain to be recreated at the start of the next frame.
+    #[inline]
+    pub fn recreate_swapchain(&mut self) {
+        self.recreate = true;
+    }
+
+    /// Starts a new frame.
+    ///
+    /// Blocks until the GPU has finished processing the frame that last used the slot of the new
+    /// frame, then recreates the swapchain if needed and acquires an image. `on_recreate` is
+    /// called with the new images if the swapchain has been recreated.
+    ///
+    /// `dimensions` should be the current dimensions of the surface. The swapchain is recreated
+    /// if they are different from the dimensions of the swapchain.
+    ///
+    /// Returns `None` if no image can be drawn to for now, for example because the window is
+    /// minimized or because the swapchain had to be recreated. The frame should be skipped in
+    /// that case.
+    ///
+    /// If the previous frame has been dropped instead of being passed to `end_frame`, its image
+    /// can't be presented anymore and the swapchain is recreated in order to get it back.
+    pub fn begin_frame<R>(&mut self, dimensions: [u32; 2], on_recreate: R)
+                          -> Result<Option<Frame<S>>, FrameError>
+        where R: FnOnce(&[Arc<S::Image>])
+    {
+        if self.acquired_image.take().is_some() {
+            self.recreate = true;
+        }
+
+        if let Some(frame) = self.frames[self.slot].take() {
+            frame.wait()?;
+        }
+
+        for frame in self.frames.iter_mut().flatten() {
+            frame.cleanup_finished();
+        }
+
+        if self.recreate || dimensions != self.swapchain.dimensions() {
+            let (swapchain, images) = match self.swapchain.recreate_with_dimensions(dimensions) {
+                Ok(r) => r,
+                Err(FrameError::SwapchainCreationError(
+                        SwapchainCreationError::UnsupportedDimensions)) => return Ok(None),
+                Err(err) => return Err(err),
+            };
+
+            on_recreate(&images);
+            self.swapchain = swapchain;
+            self.images = images;
+            self.recreate = false;
+        }
+
+        let (image_num, suboptimal, acquire_future) =
+            match S::acquire(self.swapchain.clone()) {
+                Ok(r) => r,
+                Err(AcquireError::OutOfDate) => {
+                    self.recreate = true;
+                    return Ok(None);
+                },
+                Err(err) => return Err(err.into()),
+            };
+
+        if suboptimal {
+            self.recreate = true;
+        }
+
+        let future: Box<dyn GpuFuture> = match self.previous_frame {
+            Some(ref previous) => Box::new(previous.clone().join(acquire_future)),
+            None => Box::new(acquire_future),
+        };
+
+        self.acquired_image = Some(image_num);
+
+        Ok(Some(Frame {
+                    image_num,
+                    image: self.images[image_num].clone(),
+                    slot: self.slot,
+                    future,
+                }))
+    }
+
+    /// Presents the image acquired by `begin_frame` after `future`, and flushes.
+    ///
+    /// `future` must have been built from the future of the frame returned by `begin_frame`.
+    ///
+    /// If the swapchain is out of date, the frame is discarded and the swapchain is recreated at
+    /// the start of the next frame.
+    ///
+    /// If presenting fails, the slot of the frame is still only reused once the GPU has finished
+    /// executing the commands that have been submitted.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `begin_frame` hasn't returned a frame since the last call to `end_frame`.
+    ///
+    pub fn end_frame<F>(&mut self, future: F) -> Result<(), FrameError>
+        where F: GpuFuture + 'static
+    {
+        let image_num = self.acquired_image
+            .take()
+            .expect("begin_frame must return a frame before end_frame is called");
+
+        let future = S::present(self.swapchain.clone(),
+                                Box::new(future),
+                                self.queue.clone(),
+                                image_num)
+            .then_signal_fence_and_flush();
+
+        let result = match future {
+            Ok(future) => {
+                let future = Arc::new(future);
+                self.frames[self.slot] = Some(SubmittedFrame::Presented(future.clone()));
+                self.previous_frame = Some(future);
+                Ok(())
+            },
+            Err(err) => {
+                self.previous_frame = None;
+
+                // Some of the commands of the frame may have been submitted before the error.
+                let fence = signal_fence(&self.queue);
+                let fence_err = match fence {
+                    Ok(fence) => {
+                        self.frames[self.slot] = Some(SubmittedFrame::Failed(fence));
+                        None
+                    },
+                    Err(err) => Some(err),
+                };
+
+                match (err, fence_err) {
+                    (FlushError::OutOfDate, None) => {
+                        self.recreate = true;
+                        Ok(())
+                    },
+                    (FlushError::OutOfDate, Some(err)) => Err(err.into()),
+                    (err, _) => Err(err.into()),
+                }
+            },
+        };
+
+        self.slot = (self.slot + 1) % self.frames.len();
+        result
+    }
+}
+
+// Submits a fence that is signaled once all the commands that have previously been submitted to
+// the queue are finished.
+fn signal_fence(queue: &Arc<Queue>) -> Result<Fence, FlushError> {
+    let fence = Fence::from_pool(queue.device().clone()).map_err(FlushError::OomError)?;
+
+    unsafe {
+        let mut builder = SubmitCommandBufferBuilder::new();
+        builder.set_fence_signal(&fence);
+        builder.submit(queue)?;
+    }
+
+    Ok(fence)
+}
+
+/// A frame started with `FrameScheduler::begin_frame`.
+pub struct Frame<S>
+    where S: FrameSwapchain
+{
+    image_num: usize,
+    image: Arc<S::Image>,
+    slot: usize,
+    future: Box<dyn GpuFuture>,
+}
+
+impl<S> Frame<S>
+    where S: FrameSwapchain
+{
+    /// Returns the index of the acquired image within the images of the swapchain.
+    #[inline]
+    pub fn image_num(&self) -> usize {
+        self.image_num
+    }
+
+    /// Returns the acquired image.
+    #[inline]
+    pub fn image(&self) -> &Arc<S::Image> {
+        &self.image
+    }
+
+    /// Returns the slot of the frame, between 0 and `frames_in_flight`.
+    ///
+    /// Resources associated with this slot aren't used by the GPU anymore.
+    #[inline]
+    pub fn slot(&self) -> usize {
+        self.slot
+    }
+
+    /// Returns the future that the commands of the frame must be executed after.
+    #[inline]
+    pub fn into_future(self) -> Box<dyn GpuFuture> {
+        self.future
+    }
+}
+
+/// Error that can happen when starting or ending a frame.
+#[derive(Debug, Clone)]
+pub enum FrameError {
+    /// Error while recreating the swapchain.
+    SwapchainCreationError(SwapchainCreationError),
+
+    /// Error while creating the images of a recreated virtual swapchain.
+    ImageCreationError(ImageCreationError),
+
+    /// Error while acquiring an image.
+    AcquireError(AcquireError),
+
+    /// Error while flushing or waiting for a frame.
+    FlushError(FlushError),
+}
+
+impl error::Error for FrameError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            FrameError::SwapchainCreationError(_) => "error while recreating the swapchain",
+            FrameError::ImageCreationError(_) => {
+                "error while creating the images of a recreated virtual swapchain"
+            },
+            FrameError::AcquireError(_) => "error while acquiring an image",
+            FrameError::FlushError(_) => "error while flushing or waiting for a frame",
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&dyn error::Error> {
+        match *self {
+            FrameError::SwapchainCreationError(ref err) => Some(err),
+            FrameError::ImageCreationError(ref err) => Some(err),
+            FrameError::AcquireError(ref err) => Some(err),
+            FrameError::FlushError(ref err) => Some(err),
+        }
+    }
+}
+
+impl fmt::Display for FrameError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
+
+impl From<SwapchainCreationError> for FrameError {
+    #[inline]
+    fn from(err: SwapchainCreationError) -> FrameError {
+        FrameError::SwapchainCreationError(err)
+    }
+}
+
+impl From<ImageCreationError> for FrameError {
+    #[inline]
+    fn from(err: ImageCreationError) -> FrameError {
+        FrameError::ImageCreationError(err)
+    }
+}
+
+impl From<AcquireError> for FrameError {
+    #[inline]
+    fn from(err: AcquireError) -> FrameError {
+        FrameError::AcquireError(err)
+    }
+}
+
+impl From<FlushError> for FrameError {
+    #[inline]
+    fn from(err: FlushError) -> FrameError {
+        FrameError::FlushError(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use format::Format;
+    use image::ImageUsage;
+    use swapchain::FrameScheduler;
+    use swapchain::VirtualSwapchain;
+
+    #[test]
+    fn slot_rotation() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let (swapchain, images) =
+            VirtualSwapchain::new(device, 3, Format::R8G8B8A8Unorm, [32, 32], ImageUsage::none())
+                .unwrap();
+        let mut scheduler = FrameScheduler::new(swapchain, images, queue, 2);
+
+        for num in 0 .. 5 {
+            let frame = scheduler
+                .begin_frame([32, 32], |_| panic!("the swapchain shouldn't be recreated"))
+                .unwrap()
+                .unwrap();
+            assert_eq!(frame.slot(), num % 2);
+            scheduler.end_frame(frame.into_future()).unwrap();
+        }
+    }
+
+    #[test]
+    fn recreate() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let (swapchain, images) =
+            VirtualSwapchain::new(device, 2, Format::R8G8B8A8Unorm, [32, 32], ImageUsage::none())
+                .unwrap();
+        let mut scheduler = FrameScheduler::new(swapchain, images, queue, 2);
+
+        // Resized.
+        let mut recreated = false;
+        let frame = scheduler
+            .begin_frame([64, 48], |images| {
+                assert_eq!(images.len(), 2);
+                assert_eq!(images[0].dimensions(), [64, 48]);
+                recreated = true;
+            })
+            .unwrap()
+            .unwrap();
+        assert!(recreated);
+        assert_eq!(scheduler.swapchain().dimensions(), [64, 48]);
+        scheduler.end_frame(frame.into_future()).unwrap();
+
+        // Explicitly requested.
+        scheduler.recreate_swapchain();
+        let mut recreated = false;
+        let frame = scheduler
+            .begin_frame([64, 48], |_| recreated = true)
+            .unwrap()
+            .unwrap();
+        assert!(recreated);
+        scheduler.end_frame(frame.into_future()).unwrap();
+
+        // Not needed.
+        let frame = scheduler
+            .begin_frame([64, 48], |_| panic!("the swapchain shouldn't be recreated"))
+            .unwrap()
+            .unwrap();
+        scheduler.end_frame(frame.into_future()).unwrap();
+    }
+
+    #[test]
+    fn dropped_frame() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let (swapchain, images) =
+            VirtualSwapchain::new(device, 1, Format::R8G8B8A8Unorm, [32, 32], ImageUsage::none())
+                .unwrap();
+        let mut scheduler = FrameScheduler::new(swapchain, images, queue, 1);
+
+        let frame = scheduler.begin_frame([32, 32], |_| ()).unwrap().unwrap();
+        drop(frame);
+
+        // The only image of the swapchain is still acquired, so the swapchain must be recreated
+        // in order to start a new frame.
+        let mut recreated = false;
+        let frame = scheduler
+            .begin_frame([32, 32], |_| recreated = true)
+            .unwrap()
+            .unwrap();
+        assert!(recreated);
+        scheduler.end_frame(frame.into_future()).unwrap();
+    }
+}
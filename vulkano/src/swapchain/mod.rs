@@ -317,6 +317,10 @@ pub use self::capabilities::SupportedPresentModesIter;
 pub use self::capabilities::SupportedSurfaceTransforms;
 pub use self::capabilities::SupportedSurfaceTransformsIter;
 pub use self::capabilities::SurfaceTransform;
+pub use self::frame_scheduler::Frame;
+pub use self::frame_scheduler::FrameError;
+pub use self::frame_scheduler::FrameScheduler;
+pub use self::frame_scheduler::FrameSwapchain;
 pub use self::present_region::PresentRegion;
 pub use self::present_region::RectangleLayer;
 pub use self::surface::CapabilitiesError;
@@ -334,12 +338,20 @@ pub use self::swapchain::present;
 pub use self::swapchain::present_incremental;
 pub use self::swapchain::FullscreenExclusive;
 pub use self::swapchain::FullscreenExclusiveError;
+pub use self::virtual_swapchain::VirtualSwapchain;
+pub use self::virtual_swapchain::VirtualSwapchainAcquireFuture;
+pub use self::virtual_swapchain::VirtualSwapchainImage;
+pub use self::virtual_swapchain::VirtualSwapchainPresentFuture;
+pub use self::virtual_swapchain::acquire_next_virtual_image;
+pub use self::virtual_swapchain::present_virtual;
 
 mod capabilities;
 pub mod display;
+mod frame_scheduler;
 mod present_region;
 mod surface;
 mod swapchain;
+mod virtual_swapchain;
 
 /// Internal trait so that creating/destroying a swapchain can access the surface's "has_swapchain"
 /// flag.
@@ -121,6 +121,46 @@ impl<W> Surface<W> {
                     }))
     }
 
+    /// Creates a `Surface` that isn't associated with any window or display.
+    ///
+    /// Presenting to a headless surface has no visible effect, but swapchains can be created and
+    /// images can be acquired and presented as usual. This is mostly useful to run render loops
+    /// in environments without a window system, such as automated tests.
+    ///
+    /// The `win` object is stored in the surface and can be retrieved with `window()`.
+    pub fn headless(instance: Arc<Instance>, win: W)
+                    -> Result<Arc<Surface<W>>, SurfaceCreationError> {
+        if !instance.loaded_extensions().ext_headless_surface {
+            return Err(SurfaceCreationError::MissingExtension {
+                           name: "VK_EXT_headless_surface",
+                       });
+        }
+
+        let vk = instance.pointers();
+
+        let surface = unsafe {
+            let infos = vk::HeadlessSurfaceCreateInfoEXT {
+                sType: vk::STRUCTURE_TYPE_HEADLESS_SURFACE_CREATE_INFO_EXT,
+                pNext: ptr::null(),
+                flags: 0, // reserved
+            };
+
+            let mut output = MaybeUninit::uninit();
+            check_errors(vk.CreateHeadlessSurfaceEXT(instance.internal_object(),
+                                                     &infos,
+                                                     ptr::null(),
+                                                     output.as_mut_ptr()))?;
+            output.assume_init()
+        };
+
+        Ok(Arc::new(Surface {
+                        window: win,
+                        instance: instance.clone(),
+                        surface,
+                        has_swapchain: AtomicBool::new(false),
+                    }))
+    }
+
     /// Creates a `Surface` from a Win32 window.
     ///
     /// The surface's min, max and current extent will always match the window's dimensions.
@@ -767,4 +807,13 @@ mod tests {
             _ => panic!(),
         }
     }
+
+    #[test]
+    fn ext_headless_surface_ext_missing() {
+        let instance = instance!();
+        match Surface::headless(instance, ()) {
+            Err(SurfaceCreationError::MissingExtension { .. }) => (),
+            _ => panic!(),
+        }
+    }
 }
@@ -58,6 +58,7 @@ use OomError;
 use Success;
 use VulkanObject;
 use check_errors;
+use version::Version;
 use vk;
 
 /// The way fullscreen exclusivity is handled.
@@ -442,7 +443,8 @@ impl <W> Swapchain<W> {
         let mut surface_full_screen_exclusive_info = None;
 
         if device.loaded_extensions().ext_full_screen_exclusive
-            && surface.instance().loaded_extensions().khr_get_physical_device_properties2
+            && (surface.instance().api_version() >= Version::V1_1
+                || surface.instance().loaded_extensions().khr_get_physical_device_properties2)
             && surface.instance().loaded_extensions().khr_get_surface_capabilities2
         {
             surface_full_screen_exclusive_info = Some(vk::SurfaceFullScreenExclusiveInfoEXT {
@@ -0,0 +1,970 @@
+// Copyright (c) 2026 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::collections::VecDeque;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::iter::Empty;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::AtomicUsize;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use buffer::BufferAccess;
+use command_buffer::submit::SubmitAnyBuilder;
+use command_buffer::submit::SubmitCommandBufferBuilder;
+use device::Device;
+use device::DeviceOwned;
+use device::Queue;
+use format::ClearValue;
+use format::Format;
+use format::FormatDesc;
+use format::FormatTy;
+use image::Dimensions;
+use image::ImageDimensions;
+use image::ImageInner;
+use image::ImageLayout;
+use image::ImageUsage;
+use image::ViewType;
+use image::sys::ImageCreationError;
+use image::sys::UnsafeImage;
+use image::sys::UnsafeImageView;
+use image::traits::ImageAccess;
+use image::traits::ImageClearValue;
+use image::traits::ImageContent;
+use image::traits::ImageViewAccess;
+use memory::DedicatedAlloc;
+use memory::pool::AllocFromRequirementsFilter;
+use memory::pool::AllocLayout;
+use memory::pool::MappingRequirement;
+use memory::pool::MemoryPool;
+use memory::pool::MemoryPoolAlloc;
+use memory::pool::PotentialDedicatedAllocation;
+use memory::pool::StdMemoryPoolAlloc;
+use swapchain::AcquireError;
+use sync::AccessCheckError;
+use sync::AccessError;
+use sync::AccessFlagBits;
+use sync::Fence;
+use sync::FenceWaitError;
+use sync::FlushError;
+use sync::GpuFuture;
+use sync::PipelineStages;
+use sync::Sharing;
+
+/// An offscreen swapchain whose images are ordinary images.
+///
+/// A `VirtualSwapchain` mimics the behavior of a `Swapchain` without requiring a surface. Images
+/// are acquired with `acquire_next_virtual_image` and presented with `present_virtual`, and the
+/// futures returned by these functions can be used in exactly the same way as the ones of a
+/// real swapchain. This makes it possible to run a render loop in an environment without a
+/// window system, for example to take screenshots in automated tests.
+///
+/// Images are handed out in the order in which they have been presented, and an image can only
+/// be acquired again once the GPU has finished executing the commands that were submitted
+/// before it was presented.
+///
+/// Contrary to the images of a real swapchain, the images of a virtual swapchain can be accessed
+/// when they are not acquired. They are in the `PresentSrc` layout after having been presented.
+/// The image that has been presented last is returned by `presented_image()`.
+pub struct VirtualSwapchain {
+    device: Arc<Device>,
+    format: Format,
+    dimensions: [u32; 2],
+    usage: ImageUsage,
+    images: Vec<Arc<VirtualSwapchainImage>>,
+    state: Mutex<VirtualSwapchainState>,
+}
+
+struct VirtualSwapchainState {
+    // Images that aren't acquired, in the order in which they must be acquired.
+    available: VecDeque<usize>,
+    // For each image, the fence that is signaled when the commands submitted before it was
+    // presented are finished.
+    present_fences: Vec<Option<Arc<Fence>>>,
+    // The image that has been presented last.
+    presented_image: Option<usize>,
+}
+
+impl VirtualSwapchain {
+    /// Builds a new virtual swapchain with `num_images` images.
+    ///
+    /// The `color_attachment` usage is always enabled in addition to `usage`.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if `num_images` is 0.
+    /// - Panics if `format` isn't a color format.
+    ///
+    pub fn new<F>(device: Arc<Device>, num_images: u32, format: F, dimensions: [u32; 2],
+                  usage: ImageUsage)
+                  -> Result<(Arc<VirtualSwapchain>, Vec<Arc<VirtualSwapchainImage>>),
+                            ImageCreationError>
+        where F: FormatDesc
+    {
+        assert!(num_images >= 1);
+        match format.format().ty() {
+            FormatTy::Float | FormatTy::Uint | FormatTy::Sint => (),
+            _ => panic!("the format of a virtual swapchain must be a color format"),
+        }
+
+        let usage = ImageUsage {
+            color_attachment: true,
+            ..usage
+        };
+
+        let images = (0 .. num_images)
+            .map(|_| {
+                     VirtualSwapchainImage::new(device.clone(),
+                                                format.format(),
+                                                dimensions,
+                                                usage)
+                 })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let swapchain = Arc::new(VirtualSwapchain {
+                                     device,
+                                     format: format.format(),
+                                     dimensions,
+                                     usage,
+                                     images: images.clone(),
+                                     state: Mutex::new(VirtualSwapchainState {
+                                                           available: (0 .. num_images as usize)
+                                                               .collect(),
+                                                           present_fences: (0 .. num_images)
+                                                               .map(|_| None)
+                                                               .collect(),
+                                                           presented_image: None,
+                                                       }),
+                                 });
+
+        Ok((swapchain, images))
+    }
+
+    /// Builds a new virtual swapchain with the same parameters as this one but with different
+    /// dimensions.
+    pub fn recreate_with_dimensions(
+        &self, dimensions: [u32; 2])
+        -> Result<(Arc<VirtualSwapchain>, Vec<Arc<VirtualSwapchainImage>>), ImageCreationError> {
+        VirtualSwapchain::new(self.device.clone(),
+                              self.images.len() as u32,
+                              self.format,
+                              dimensions,
+                              self.usage)
+    }
+
+    /// Returns the number of images of the swapchain.
+    #[inline]
+    pub fn num_images(&self) -> u32 {
+        self.images.len() as u32
+    }
+
+    /// Returns the format of the images of the swapchain.
+    #[inline]
+    pub fn format(&self) -> Format {
+        self.format
+    }
+
+    /// Returns the dimensions of the images of the swapchain.
+    #[inline]
+    pub fn dimensions(&self) -> [u32; 2] {
+        self.dimensions
+    }
+
+    /// Returns the images of the swapchain.
+    #[inline]
+    pub fn images(&self) -> &[Arc<VirtualSwapchainImage>] {
+        &self.images
+    }
+
+    /// Returns the image that has been presented last, or `None` if no image has been presented
+    /// yet.
+    ///
+    /// > **Note**: The commands that draw to the image may still be executing. Wait for the
+    /// > present future to be finished before reading the image.
+    #[inline]
+    pub fn presented_image(&self) -> Option<Arc<VirtualSwapchainImage>> {
+        let state = self.state.lock().unwrap();
+        state.presented_image.map(|id| self.images[id].clone())
+    }
+
+    // Called when the present of an image has been submitted.
+    fn image_presented(&self, image_id: usize, fence: Arc<Fence>) {
+        let mut state = self.state.lock().unwrap();
+        state.present_fences[image_id] = Some(fence);
+        state.available.push_back(image_id);
+        state.presented_image = Some(image_id);
+        self.images[image_id].acquired.store(false, Ordering::SeqCst);
+    }
+}
+
+unsafe impl DeviceOwned for VirtualSwapchain {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
+/// Acquires an image of a virtual swapchain.
+///
+/// Same as `acquire_next_image`, except for a `VirtualSwapchain`. The image is never suboptimal.
+///
+/// Returns `AcquireError::Timeout` immediately if all the images are already acquired.
+pub fn acquire_next_virtual_image(swapchain: Arc<VirtualSwapchain>, timeout: Option<Duration>)
+                                  -> Result<(usize, bool, VirtualSwapchainAcquireFuture),
+                                            AcquireError> {
+    loop {
+        let wait_fence = {
+            let mut state = swapchain.state.lock().unwrap();
+
+            // Looking for the first image whose present is finished.
+            let mut found = None;
+            let mut wait_fence = None;
+            for (pos, &image_id) in state.available.iter().enumerate() {
+                match state.present_fences[image_id] {
+                    Some(ref fence) => {
+                        if fence.ready()? {
+                            found = Some(pos);
+                            break;
+                        }
+                        if wait_fence.is_none() {
+                            wait_fence = Some(fence.clone());
+                        }
+                    },
+                    None => {
+                        found = Some(pos);
+                        break;
+                    },
+                }
+            }
+
+            if let Some(pos) = found {
+                let image_id = state.available.remove(pos).unwrap();
+                state.present_fences[image_id] = None;
+                swapchain.images[image_id].acquired.store(true, Ordering::SeqCst);
+
+                return Ok((image_id,
+                           false,
+                           VirtualSwapchainAcquireFuture {
+                               swapchain: swapchain.clone(),
+                               image_id,
+                           }));
+            }
+
+            match wait_fence {
+                Some(fence) => fence,
+                None => return Err(AcquireError::Timeout),
+            }
+        };
+
+        match wait_fence.wait(timeout) {
+            Ok(()) => (),
+            Err(FenceWaitError::OomError(err)) => return Err(AcquireError::OomError(err)),
+            Err(FenceWaitError::Timeout) => return Err(AcquireError::Timeout),
+            Err(FenceWaitError::DeviceLostError) => return Err(AcquireError::DeviceLost),
+        }
+    }
+}
+
+/// Presents an image of a virtual swapchain after `before`.
+///
+/// Same as `present`, except for a `VirtualSwapchain`. Presenting submits the commands of
+/// `before` if necessary, and makes the image available to be acquired again once they are
+/// finished.
+///
+/// # Panic
+///
+/// - Panics if `index` is out of range.
+/// - Panics if the image isn't acquired.
+///
+pub fn present_virtual<F>(swapchain: Arc<VirtualSwapchain>, before: F, queue: Arc<Queue>,
+                          index: usize)
+                          -> VirtualSwapchainPresentFuture<F>
+    where F: GpuFuture
+{
+    assert!(index < swapchain.images.len());
+    assert!(swapchain.images[index].acquired.load(Ordering::SeqCst),
+            "the image must be acquired before being presented");
+
+    let fence = Arc::new(Fence::from_pool(swapchain.device.clone()).unwrap());
+
+    VirtualSwapchainPresentFuture {
+        previous: before,
+        queue,
+        swapchain,
+        image_id: index,
+        fence,
+        flushed: Mutex::new(false),
+        finished: AtomicBool::new(false),
+    }
+}
+
+/// An image of a `VirtualSwapchain`.
+pub struct VirtualSwapchainImage {
+    image: UnsafeImage,
+    view: UnsafeImageView,
+    memory: PotentialDedicatedAllocation<StdMemoryPoolAlloc>,
+    format: Format,
+    // True if the image is acquired. The image can only be accessed through the acquire future
+    // in that case.
+    acquired: AtomicBool,
+    // If false, the image is still in the `Undefined` layout.
+    initialized: AtomicBool,
+    // Number of times this image is locked on the GPU side.
+    gpu_lock: AtomicUsize,
+}
+
+impl VirtualSwapchainImage {
+    fn new(device: Arc<Device>, format: Format, dimensions: [u32; 2], usage: ImageUsage)
+           -> Result<Arc<VirtualSwapchainImage>, ImageCreationError> {
+        let (image, mem_reqs) = unsafe {
+            let dims = ImageDimensions::Dim2d {
+                width: dimensions[0],
+                height: dimensions[1],
+                array_layers: 1,
+                cubemap_compatible: false,
+            };
+
+            UnsafeImage::new(device.clone(),
+                             usage,
+                             format,
+                             dims,
+                             1,
+                             1,
+                             Sharing::Exclusive::<Empty<u32>>,
+                             false,
+                             false)?
+        };
+
+        let memory = MemoryPool::alloc_from_requirements(&Device::standard_pool(&device),
+                                    &mem_reqs,
+                                    AllocLayout::Optimal,
+                                    MappingRequirement::DoNotMap,
+                                    DedicatedAlloc::Image(&image),
+                                    |t| if t.is_device_local() {
+                                        AllocFromRequirementsFilter::Preferred
+                                    } else {
+                                        AllocFromRequirementsFilter::Allowed
+                                    })?;
+        debug_assert!((memory.offset() % mem_reqs.alignment) == 0);
+        unsafe {
+            image.bind_memory(memory.memory(), memory.offset())?;
+        }
+
+        let view = unsafe { UnsafeImageView::raw(&image, ViewType::Dim2d, 0 .. 1, 0 .. 1)? };
+
+        Ok(Arc::new(VirtualSwapchainImage {
+                        image,
+                        view,
+                        memory,
+                        format,
+                        acquired: AtomicBool::new(false),
+                        initialized: AtomicBool::new(false),
+                        gpu_lock: AtomicUsize::new(0),
+                    }))
+    }
+
+    /// Returns the dimensions of the image.
+    #[inline]
+    pub fn dimensions(&self) -> [u32; 2] {
+        let dims = self.image.dimensions();
+        [dims.width(), dims.height()]
+    }
+}
+
+unsafe impl ImageAccess for VirtualSwapchainImage {
+    #[inline]
+    fn inner(&self) -> ImageInner {
+        ImageInner {
+            image: &self.image,
+            first_layer: 0,
+            num_layers: 1,
+            first_mipmap_level: 0,
+            num_mipmap_levels: 1,
+        }
+    }
+
+    #[inline]
+    fn initial_layout_requirement(&self) -> ImageLayout {
+        ImageLayout::PresentSrc
+    }
+
+    #[inline]
+    fn final_layout_requirement(&self) -> ImageLayout {
+        ImageLayout::PresentSrc
+    }
+
+    #[inline]
+    fn conflicts_buffer(&self, _: &dyn BufferAccess) -> bool {
+        false
+    }
+
+    #[inline]
+    fn conflicts_image(&self, other: &dyn ImageAccess) -> bool {
+        self.conflict_key() == other.conflict_key()
+    }
+
+    #[inline]
+    fn conflict_key(&self) -> u64 {
+        self.image.key()
+    }
+
+    #[inline]
+    fn try_gpu_lock(&self, _: bool, expected_layout: ImageLayout) -> Result<(), AccessError> {
+        // Same as swapchain images, acquired images are only accessible through the acquire
+        // future.
+        if self.acquired.load(Ordering::SeqCst) {
+            return Err(AccessError::SwapchainImageAcquireOnly);
+        }
+
+        if expected_layout != ImageLayout::PresentSrc && expected_layout != ImageLayout::Undefined {
+            return Err(AccessError::UnexpectedImageLayout {
+                           requested: expected_layout,
+                           allowed: ImageLayout::PresentSrc,
+                       });
+        }
+
+        if expected_layout != ImageLayout::Undefined && !self.initialized.load(Ordering::SeqCst) {
+            return Err(AccessError::ImageNotInitialized { requested: expected_layout });
+        }
+
+        if self.gpu_lock
+            .compare_exchange(0, 1, Ordering::SeqCst, Ordering::SeqCst)
+            .is_ok()
+        {
+            Ok(())
+        } else {
+            Err(AccessError::AlreadyInUse)
+        }
+    }
+
+    #[inline]
+    unsafe fn increase_gpu_lock(&self) {
+        let val = self.gpu_lock.fetch_add(1, Ordering::SeqCst);
+        debug_assert!(val >= 1);
+    }
+
+    #[inline]
+    unsafe fn unlock(&self, new_layout: Option<ImageLayout>) {
+        if let Some(new_layout) = new_layout {
+            debug_assert_eq!(new_layout, ImageLayout::PresentSrc);
+            self.initialized.store(true, Ordering::SeqCst);
+        }
+
+        let prev_val = self.gpu_lock.fetch_sub(1, Ordering::SeqCst);
+        debug_assert!(prev_val >= 1);
+    }
+
+    #[inline]
+    unsafe fn layout_initialized(&self) {
+        self.initialized.store(true, Ordering::SeqCst);
+    }
+
+    #[inline]
+    fn is_layout_initialized(&self) -> bool {
+        self.initialized.load(Ordering::SeqCst)
+    }
+}
+
+unsafe impl ImageClearValue<<Format as FormatDesc>::ClearValue> for VirtualSwapchainImage {
+    #[inline]
+    fn decode(&self, value: <Format as FormatDesc>::ClearValue) -> Option<ClearValue> {
+        Some(self.format.decode_clear_value(value))
+    }
+}
+
+unsafe impl<P> ImageContent<P> for VirtualSwapchainImage {
+    #[inline]
+    fn matches_format(&self) -> bool {
+        true // FIXME:
+    }
+}
+
+unsafe impl ImageViewAccess for VirtualSwapchainImage {
+    #[inline]
+    fn parent(&self) -> &dyn ImageAccess {
+        self
+    }
+
+    #[inline]
+    fn dimensions(&self) -> Dimensions {
+        let dims = self.image.dimensions();
+        Dimensions::Dim2d {
+            width: dims.width(),
+            height: dims.height(),
+        }
+    }
+
+    #[inline]
+    fn inner(&self) -> &UnsafeImageView {
+        &self.view
+    }
+
+    #[inline]
+    fn descriptor_set_storage_image_layout(&self) -> ImageLayout {
+        ImageLayout::General
+    }
+
+    #[inline]
+    fn descriptor_set_combined_image_sampler_layout(&self) -> ImageLayout {
+        ImageLayout::ShaderReadOnlyOptimal
+    }
+
+    #[inline]
+    fn descriptor_set_sampled_image_layout(&self) -> ImageLayout {
+        ImageLayout::ShaderReadOnlyOptimal
+    }
+
+    #[inline]
+    fn descriptor_set_input_attachment_layout(&self) -> ImageLayout {
+        ImageLayout::ShaderReadOnlyOptimal
+    }
+
+    #[inline]
+    fn identity_swizzle(&self) -> bool {
+        true
+    }
+}
+
+impl PartialEq for VirtualSwapchainImage {
+    #[inline]
+    fn eq(&self, other: &Self) -> bool {
+        ImageAccess::inner(self) == ImageAccess::inner(other)
+    }
+}
+
+impl Eq for VirtualSwapchainImage {}
+
+impl Hash for VirtualSwapchainImage {
+    #[inline]
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        ImageAccess::inner(self).hash(state);
+    }
+}
+
+/// Represents the moment when the GPU will have access to an image of a virtual swapchain.
+#[must_use]
+pub struct VirtualSwapchainAcquireFuture {
+    swapchain: Arc<VirtualSwapchain>,
+    image_id: usize,
+}
+
+impl VirtualSwapchainAcquireFuture {
+    /// Returns the index of the image in the list of images of the swapchain.
+    #[inline]
+    pub fn image_id(&self) -> usize {
+        self.image_id
+    }
+
+    /// Returns the corresponding swapchain.
+    #[inline]
+    pub fn swapchain(&self) -> &Arc<VirtualSwapchain> {
+        &self.swapchain
+    }
+}
+
+unsafe impl GpuFuture for VirtualSwapchainAcquireFuture {
+    #[inline]
+    fn cleanup_finished(&mut self) {
+    }
+
+    #[inline]
+    unsafe fn build_submission(&self) -> Result<SubmitAnyBuilder, FlushError> {
+        // The image was available when it was acquired, so there is nothing to wait for.
+        Ok(SubmitAnyBuilder::Empty)
+    }
+
+    #[inline]
+    fn flush(&self) -> Result<(), FlushError> {
+        Ok(())
+    }
+
+    #[inline]
+    unsafe fn signal_finished(&self) {
+    }
+
+    #[inline]
+    fn queue_change_allowed(&self) -> bool {
+        true
+    }
+
+    #[inline]
+    fn queue(&self) -> Option<Arc<Queue>> {
+        None
+    }
+
+    #[inline]
+    fn check_buffer_access(
+        &self, _: &dyn BufferAccess, _: bool, _: &Queue)
+        -> Result<Option<(PipelineStages, AccessFlagBits)>, AccessCheckError> {
+        Err(AccessCheckError::Unknown)
+    }
+
+    #[inline]
+    fn check_image_access(&self, image: &dyn ImageAccess, layout: ImageLayout, _: bool, _: &Queue)
+                          -> Result<Option<(PipelineStages, AccessFlagBits)>, AccessCheckError> {
+        let my_image = &self.swapchain.images[self.image_id];
+        if my_image.conflict_key() != image.conflict_key() {
+            return Err(AccessCheckError::Unknown);
+        }
+
+        if !my_image.initialized.load(Ordering::SeqCst) && layout != ImageLayout::Undefined {
+            return Err(AccessCheckError::Denied(AccessError::ImageNotInitialized {
+                                                    requested: layout,
+                                                }));
+        }
+
+        if layout != ImageLayout::Undefined && layout != ImageLayout::PresentSrc {
+            return Err(AccessCheckError::Denied(AccessError::UnexpectedImageLayout {
+                                                    allowed: ImageLayout::PresentSrc,
+                                                    requested: layout,
+                                                }));
+        }
+
+        Ok(None)
+    }
+}
+
+unsafe impl DeviceOwned for VirtualSwapchainAcquireFuture {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.swapchain.device
+    }
+}
+
+/// Represents an image of a virtual swapchain being presented after a previous event.
+#[must_use = "Dropping this object will immediately block the thread until the GPU has finished \
+              processing the submission"]
+pub struct VirtualSwapchainPresentFuture<P>
+    where P: GpuFuture
+{
+    previous: P,
+    queue: Arc<Queue>,
+    swapchain: Arc<VirtualSwapchain>,
+    image_id: usize,
+    // Fence signaled when the commands of `previous` are finished.
+    fence: Arc<Fence>,
+    // True if the present has already been submitted.
+    flushed: Mutex<bool>,
+    finished: AtomicBool,
+}
+
+impl<P> VirtualSwapchainPresentFuture<P>
+    where P: GpuFuture
+{
+    /// Returns the index of the image in the list of images of the swapchain.
+    #[inline]
+    pub fn image_id(&self) -> usize {
+        self.image_id
+    }
+
+    /// Returns the corresponding swapchain.
+    #[inline]
+    pub fn swapchain(&self) -> &Arc<VirtualSwapchain> {
+        &self.swapchain
+    }
+}
+
+unsafe impl<P> GpuFuture for VirtualSwapchainPresentFuture<P>
+    where P: GpuFuture
+{
+    #[inline]
+    fn cleanup_finished(&mut self) {
+        self.previous.cleanup_finished();
+
+        if *self.flushed.get_mut().unwrap() && !*self.finished.get_mut() {
+            if let Ok(true) = self.fence.ready() {
+                unsafe {
+                    self.previous.signal_finished();
+                }
+                *self.finished.get_mut() = true;
+            }
+        }
+    }
+
+    #[inline]
+    unsafe fn build_submission(&self) -> Result<SubmitAnyBuilder, FlushError> {
+        // Submissions that come after this one on the same queue are executed after it, so
+        // flushing is enough.
+        self.flush()?;
+        Ok(SubmitAnyBuilder::Empty)
+    }
+
+    fn flush(&self) -> Result<(), FlushError> {
+        unsafe {
+            let mut flushed = self.flushed.lock().unwrap();
+
+            if *flushed {
+                return Ok(());
+            }
+
+            match self.previous.build_submission()? {
+                SubmitAnyBuilder::Empty => {
+                    let mut builder = SubmitCommandBufferBuilder::new();
+                    builder.set_fence_signal(&self.fence);
+                    builder.submit(&self.queue)?;
+                },
+                SubmitAnyBuilder::SemaphoresWait(sem) => {
+                    let mut builder: SubmitCommandBufferBuilder = sem.into();
+                    builder.set_fence_signal(&self.fence);
+                    builder.submit(&self.queue)?;
+                },
+                SubmitAnyBuilder::CommandBuffer(mut builder) => {
+                    if builder.has_fence() {
+                        builder.submit(&self.queue)?;
+                        let mut builder = SubmitCommandBufferBuilder::new();
+                        builder.set_fence_signal(&self.fence);
+                        builder.submit(&self.queue)?;
+                    } else {
+                        builder.set_fence_signal(&self.fence);
+                        builder.submit(&self.queue)?;
+                    }
+                },
+                SubmitAnyBuilder::BindSparse(mut sparse) => {
+                    if sparse.has_fence() {
+                        sparse.submit(&self.queue)?;
+                        let mut builder = SubmitCommandBufferBuilder::new();
+                        builder.set_fence_signal(&self.fence);
+                        builder.submit(&self.queue)?;
+                    } else {
+                        sparse.set_fence_signal(&self.fence);
+                        sparse.submit(&self.queue)?;
+                    }
+                },
+                SubmitAnyBuilder::QueuePresent(present) => {
+                    present.submit(&self.queue)?;
+                    let mut builder = SubmitCommandBufferBuilder::new();
+                    builder.set_fence_signal(&self.fence);
+                    builder.submit(&self.queue)?; // FIXME: problematic because if we return an error and flush() is called again, then we'll submit the present twice
+                },
+            };
+
+            self.swapchain.image_presented(self.image_id, self.fence.clone());
+
+            // Only write `true` here in order to try again next time if an error occurs.
+            *flushed = true;
+            Ok(())
+        }
+    }
+
+    #[inline]
+    unsafe fn signal_finished(&self) {
+        debug_assert!(*self.flushed.lock().unwrap());
+        self.finished.store(true, Ordering::SeqCst);
+        self.previous.signal_finished();
+    }
+
+    #[inline]
+    fn queue_change_allowed(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn queue(&self) -> Option<Arc<Queue>> {
+        Some(self.queue.clone())
+    }
+
+    #[inline]
+    fn check_buffer_access(
+        &self, buffer: &dyn BufferAccess, exclusive: bool, queue: &Queue)
+        -> Result<Option<(PipelineStages, AccessFlagBits)>, AccessCheckError> {
+        self.previous.check_buffer_access(buffer, exclusive, queue)
+    }
+
+    #[inline]
+    fn check_image_access(&self, image: &dyn ImageAccess, layout: ImageLayout, exclusive: bool,
+                          queue: &Queue)
+                          -> Result<Option<(PipelineStages, AccessFlagBits)>, AccessCheckError> {
+        self.previous
+            .check_image_access(image, layout, exclusive, queue)
+    }
+}
+
+unsafe impl<P> DeviceOwned for VirtualSwapchainPresentFuture<P>
+    where P: GpuFuture
+{
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.swapchain.device
+    }
+}
+
+impl<P> Drop for VirtualSwapchainPresentFuture<P>
+    where P: GpuFuture
+{
+    fn drop(&mut self) {
+        unsafe {
+            if !*self.finished.get_mut() {
+                // TODO: handle errors?
+                self.flush().unwrap();
+                // Block until the commands are finished.
+                self.fence.wait(None).unwrap();
+                self.previous.signal_finished();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use format::Format;
+    use image::ImageLayout;
+    use image::ImageUsage;
+    use swapchain::AcquireError;
+    use swapchain::VirtualSwapchain;
+    use swapchain::acquire_next_virtual_image;
+    use swapchain::present_virtual;
+    use sync::AccessError;
+    use sync::GpuFuture;
+
+    #[test]
+    fn acquire_present_cycle() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let (swapchain, _) = VirtualSwapchain::new(device.clone(),
+                                                   2,
+                                                   Format::R8G8B8A8Unorm,
+                                                   [32, 32],
+                                                   ImageUsage::none())
+            .unwrap();
+
+        let (first, suboptimal, first_future) =
+            acquire_next_virtual_image(swapchain.clone(), None).unwrap();
+        assert!(!suboptimal);
+        let (second, _, _second_future) =
+            acquire_next_virtual_image(swapchain.clone(), None).unwrap();
+        assert_ne!(first, second);
+
+        match acquire_next_virtual_image(swapchain.clone(), None) {
+            Err(AcquireError::Timeout) => (),
+            _ => panic!(),
+        }
+
+        present_virtual(swapchain.clone(), first_future, queue, first)
+            .then_signal_fence_and_flush()
+            .unwrap()
+            .wait(None)
+            .unwrap();
+        assert_eq!(swapchain.presented_image().unwrap().dimensions(), [32, 32]);
+
+        let (again, _, _) = acquire_next_virtual_image(swapchain.clone(), None).unwrap();
+        assert_eq!(again, first);
+    }
+
+    #[test]
+    fn timeout_when_all_acquired() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let (swapchain, _) = VirtualSwapchain::new(device,
+                                                   2,
+                                                   Format::R8G8B8A8Unorm,
+                                                   [32, 32],
+                                                   ImageUsage::none())
+            .unwrap();
+
+        let _first = acquire_next_virtual_image(swapchain.clone(), None).unwrap();
+        let _second = acquire_next_virtual_image(swapchain.clone(), None).unwrap();
+
+        // Nothing can make an image available, so the timeout isn't waited for.
+        for &timeout in &[None, Some(Duration::from_secs(0)), Some(Duration::from_secs(60))] {
+            match acquire_next_virtual_image(swapchain.clone(), timeout) {
+                Err(AcquireError::Timeout) => (),
+                _ => panic!(),
+            }
+        }
+    }
+
+    #[test]
+    fn present_order() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let (swapchain, images) = VirtualSwapchain::new(device,
+                                                        3,
+                                                        Format::R8G8B8A8Unorm,
+                                                        [32, 32],
+                                                        ImageUsage::none())
+            .unwrap();
+
+        let mut acquired = (0 .. 3)
+            .map(|num| {
+                     let (id, _, future) =
+                         acquire_next_virtual_image(swapchain.clone(), None).unwrap();
+                     assert_eq!(id, num);
+                     Some(future)
+                 })
+            .collect::<Vec<_>>();
+
+        // Present in a different order than the images have been acquired.
+        let presented = [2, 0, 1];
+        for &id in &presented {
+            let future = acquired[id].take().unwrap();
+            present_virtual(swapchain.clone(), future, queue.clone(), id)
+                .then_signal_fence_and_flush()
+                .unwrap()
+                .wait(None)
+                .unwrap();
+            assert!(swapchain.presented_image().unwrap() == images[id]);
+        }
+
+        // Images are acquired again in the order in which they have been presented.
+        for &id in &presented {
+            let (again, _, _future) = acquire_next_virtual_image(swapchain.clone(), None).unwrap();
+            assert_eq!(again, id);
+        }
+    }
+
+    #[test]
+    fn image_reuse() {
+        use image::ImageAccess;
+
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let (swapchain, images) = VirtualSwapchain::new(device,
+                                                        1,
+                                                        Format::R8G8B8A8Unorm,
+                                                        [32, 32],
+                                                        ImageUsage::none())
+            .unwrap();
+
+        let (id, _, future) = acquire_next_virtual_image(swapchain.clone(), None).unwrap();
+
+        // An acquired image can only be accessed through the acquire future.
+        assert_eq!(ImageAccess::try_gpu_lock(&*images[id], false, ImageLayout::Undefined),
+                   Err(AccessError::SwapchainImageAcquireOnly));
+
+        // The present isn't waited for, acquiring the image again blocks until it is finished.
+        let present = present_virtual(swapchain.clone(), future, queue, id)
+            .then_signal_fence_and_flush()
+            .unwrap();
+        let (again, _, _future) = acquire_next_virtual_image(swapchain.clone(), None).unwrap();
+        assert_eq!(again, id);
+        present.wait(Some(Duration::from_secs(0))).unwrap();
+    }
+
+    #[test]
+    fn storage_image_layout() {
+        use image::ImageViewAccess;
+
+        let (device, _) = gfx_dev_and_queue!();
+
+        let usage = ImageUsage {
+            storage: true,
+            ..ImageUsage::none()
+        };
+        let (_, images) =
+            VirtualSwapchain::new(device, 1, Format::R8G8B8A8Unorm, [32, 32], usage).unwrap();
+
+        assert_eq!(ImageViewAccess::descriptor_set_storage_image_layout(&*images[0]),
+                   ImageLayout::General);
+    }
+}
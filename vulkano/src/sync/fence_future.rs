@@ -0,0 +1,360 @@
+// Copyright (c) 2026 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+//! Integration of fences with `std::future::Future`.
+//!
+//! The fences of pending futures are handed to background threads owned by their device. Each
+//! thread blocks on a batch of fences with a single `vkWaitForFences` call and wakes the
+//! corresponding tasks once they are signaled. Since a blocked thread can't be interrupted,
+//! fences registered in the meantime are picked up by another thread, which is spawned if none
+//! is idle. Threads stop after having been idle for a while.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::sync::Condvar;
+use std::sync::Mutex;
+use std::task::Context;
+use std::task::Poll;
+use std::task::Waker;
+use std::thread;
+use std::time::Duration;
+
+use smallvec::SmallVec;
+
+use VulkanObject;
+use check_errors;
+use device::Device;
+use device::DeviceOwned;
+use sync::Fence;
+use sync::FenceWaitError;
+use vk;
+
+// Maximum duration of a single `vkWaitForFences` call. When it is reached, the fences that have
+// been handed to other threads in the meantime are merged into the batch of the thread.
+const WAIT_TIMEOUT: Duration = Duration::from_secs(1);
+
+// Duration after which a thread that has no fence to wait upon stops.
+const IDLE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Wraps around a fence and implements `std::future::Future`. The future completes when the
+/// fence is signaled.
+///
+/// Requires the `async` feature.
+///
+/// # Example
+///
+/// ```ignore
+/// let fence = Arc::new(Fence::alloc(device.clone()).unwrap());
+/// // Submit some work that signals `fence`.
+/// FenceFuture::new(fence).await.unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct FenceFuture {
+    fence: Arc<Fence>,
+}
+
+impl FenceFuture {
+    /// Builds a new `FenceFuture` that completes when `fence` is signaled.
+    ///
+    /// > **Note**: Awaiting a fence that nothing is going to signal never completes.
+    #[inline]
+    pub fn new(fence: Arc<Fence>) -> FenceFuture {
+        FenceFuture { fence }
+    }
+
+    /// Returns the wrapped fence.
+    #[inline]
+    pub fn fence(&self) -> &Arc<Fence> {
+        &self.fence
+    }
+}
+
+impl Future for FenceFuture {
+    type Output = Result<(), FenceWaitError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        match self.fence.ready() {
+            Ok(true) => Poll::Ready(Ok(())),
+            Ok(false) => {
+                wake_when_signaled(self.fence.clone(), cx.waker().clone());
+                Poll::Pending
+            },
+            Err(err) => Poll::Ready(Err(FenceWaitError::OomError(err))),
+        }
+    }
+}
+
+// A fence awaited by a task.
+struct Registration {
+    fence: Arc<Fence>,
+    // The task to wake when the fence is signaled.
+    waker: Mutex<Waker>,
+}
+
+/// Wakes the tasks awaiting the fences of a device. Returned by `Device::fence_waiter`.
+pub(crate) struct FenceWaiter {
+    state: Mutex<WaiterState>,
+    // Notified when an element is added to `unassigned`.
+    condvar: Condvar,
+}
+
+struct WaiterState {
+    // All the fences that haven't been signaled yet.
+    registered: Vec<Arc<Registration>>,
+    // Fences that no thread is waiting upon yet.
+    unassigned: Vec<Arc<Registration>>,
+    // Number of threads that are waiting for `unassigned` to be non-empty, including the ones
+    // that have been spawned but haven't started yet.
+    idle_threads: usize,
+}
+
+impl FenceWaiter {
+    pub(crate) fn new() -> FenceWaiter {
+        FenceWaiter {
+            state: Mutex::new(WaiterState {
+                                  registered: Vec::new(),
+                                  unassigned: Vec::new(),
+                                  idle_threads: 0,
+                              }),
+            condvar: Condvar::new(),
+        }
+    }
+}
+
+// Asks the waiter of the device of `fence` to wake `waker` once `fence` is signaled.
+//
+// The fence is kept alive by the waiter until then. If the same fence is already registered,
+// only its waker is replaced.
+pub(crate) fn wake_when_signaled(fence: Arc<Fence>, waker: Waker) {
+    let waiter = Device::fence_waiter(fence.device());
+    let mut state = waiter.state.lock().unwrap();
+
+    if let Some(registration) = state
+        .registered
+        .iter()
+        .find(|registration| Arc::ptr_eq(&registration.fence, &fence))
+    {
+        *registration.waker.lock().unwrap() = waker;
+        return;
+    }
+
+    let registration = Arc::new(Registration {
+                                    fence,
+                                    waker: Mutex::new(waker),
+                                });
+    state.registered.push(registration.clone());
+    state.unassigned.push(registration);
+
+    if state.idle_threads == 0 {
+        state.idle_threads += 1;
+        let thread_waiter = waiter.clone();
+        thread::Builder::new()
+            .name("vulkano fence waiter".to_owned())
+            .spawn(move || run_waiter(&thread_waiter))
+            .expect("failed to spawn a fence waiter thread");
+    } else {
+        waiter.condvar.notify_one();
+    }
+}
+
+fn run_waiter(waiter: &FenceWaiter) {
+    let mut batch: Vec<Arc<Registration>> = Vec::new();
+
+    loop {
+        {
+            let mut state = waiter.state.lock().unwrap();
+
+            if batch.is_empty() {
+                // The thread is counted in `idle_threads` while it has nothing to wait upon.
+                while state.unassigned.is_empty() {
+                    let (new_state, result) =
+                        waiter.condvar.wait_timeout(state, IDLE_TIMEOUT).unwrap();
+                    state = new_state;
+                    if result.timed_out() && state.unassigned.is_empty() {
+                        state.idle_threads -= 1;
+                        return;
+                    }
+                }
+                state.idle_threads -= 1;
+            }
+
+            batch.append(&mut state.unassigned);
+        }
+
+        // Errors are reported by the futures themselves when they are polled again, so the
+        // tasks are woken in that case as well.
+        let result = wait_any(&batch, WAIT_TIMEOUT);
+
+        let mut wakers = Vec::new();
+        {
+            let mut state = waiter.state.lock().unwrap();
+            batch.retain(|registration| {
+                if matches!((&result, registration.fence.ready()), (&Ok(()), Ok(false))) {
+                    return true;
+                }
+
+                wakers.push(registration.waker.lock().unwrap().clone());
+                state
+                    .registered
+                    .retain(|other| !Arc::ptr_eq(other, registration));
+                false
+            });
+
+            if batch.is_empty() {
+                state.idle_threads += 1;
+            }
+        }
+
+        // The wakers are called after releasing the lock, as they may poll the future
+        // immediately and register a fence again.
+        for waker in wakers {
+            waker.wake();
+        }
+    }
+}
+
+// Blocks until at least one of the fences is signaled or the timeout is reached. All the fences
+// must belong to the same device.
+fn wait_any(batch: &[Arc<Registration>], timeout: Duration) -> Result<(), FenceWaitError> {
+    let device = batch[0].fence.device();
+    let fences: SmallVec<[vk::Fence; 8]> = batch
+        .iter()
+        .map(|registration| registration.fence.internal_object())
+        .collect();
+
+    let timeout_ns = timeout
+        .as_secs()
+        .saturating_mul(1_000_000_000)
+        .saturating_add(timeout.subsec_nanos() as u64);
+
+    unsafe {
+        let vk = device.pointers();
+        check_errors(vk.WaitForFences(device.internal_object(),
+                                      fences.len() as u32,
+                                      fences.as_ptr(),
+                                      vk::FALSE,
+                                      timeout_ns))?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::future::Future;
+    use std::pin::Pin;
+    use std::sync::Arc;
+    use std::sync::Mutex;
+    use std::sync::atomic::AtomicBool;
+    use std::sync::atomic::Ordering;
+    use std::sync::mpsc;
+    use std::task::Context;
+    use std::task::Poll;
+    use std::task::Wake;
+    use std::thread;
+    use std::thread::Thread;
+    use std::time::Duration;
+
+    use command_buffer::submit::SubmitCommandBufferBuilder;
+    use sync::Fence;
+    use sync::FenceFuture;
+    use super::wake_when_signaled;
+
+    struct ThreadWaker(Thread);
+
+    impl Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    fn block_on<F: Future>(mut future: F) -> F::Output {
+        let waker = Arc::new(ThreadWaker(thread::current())).into();
+        let mut cx = Context::from_waker(&waker);
+        loop {
+            let future = unsafe { Pin::new_unchecked(&mut future) };
+            match future.poll(&mut cx) {
+                Poll::Ready(output) => return output,
+                Poll::Pending => thread::park(),
+            }
+        }
+    }
+
+    #[test]
+    fn signaled_fence() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let fence = Arc::new(Fence::alloc_signaled(device.clone()).unwrap());
+        block_on(FenceFuture::new(fence)).unwrap();
+    }
+
+    #[test]
+    fn fences_signaled_later() {
+        let (device, queue) = gfx_dev_and_queue!();
+
+        let first = Arc::new(Fence::alloc(device.clone()).unwrap());
+        let second = Arc::new(Fence::alloc(device.clone()).unwrap());
+
+        // Signal the fences one after the other, after the tasks have started awaiting them.
+        let signaler = {
+            let first = first.clone();
+            let second = second.clone();
+            thread::spawn(move || for fence in &[second, first] {
+                              thread::sleep(Duration::from_millis(50));
+                              unsafe {
+                                  let mut builder = SubmitCommandBufferBuilder::new();
+                                  builder.set_fence_signal(fence);
+                                  builder.submit(&queue).unwrap();
+                              }
+                          })
+        };
+
+        // Both fences are awaited at the same time, so that they end up in separate batches.
+        let second_waiter = thread::spawn(move || block_on(FenceFuture::new(second)).unwrap());
+        block_on(FenceFuture::new(first)).unwrap();
+        second_waiter.join().unwrap();
+        signaler.join().unwrap();
+    }
+
+    // Waker that registers its fence again the first time it is woken, like an executor that
+    // polls the future from within `wake`.
+    struct InlineWaker {
+        fence: Arc<Fence>,
+        registered_again: AtomicBool,
+        woken: Mutex<mpsc::Sender<()>>,
+    }
+
+    impl Wake for InlineWaker {
+        fn wake(self: Arc<Self>) {
+            if self.registered_again.swap(true, Ordering::SeqCst) {
+                self.woken.lock().unwrap().send(()).unwrap();
+            } else {
+                let fence = self.fence.clone();
+                wake_when_signaled(fence, self.into());
+            }
+        }
+    }
+
+    #[test]
+    fn register_from_waker() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        let fence = Arc::new(Fence::alloc_signaled(device.clone()).unwrap());
+        let (sender, receiver) = mpsc::channel();
+        let waker = Arc::new(InlineWaker {
+                                 fence: fence.clone(),
+                                 registered_again: AtomicBool::new(false),
+                                 woken: Mutex::new(sender),
+                             });
+
+        wake_when_signaled(fence, waker.into());
+        receiver.recv_timeout(Duration::from_secs(5)).unwrap();
+    }
+}
@@ -12,6 +12,14 @@ use std::sync::Arc;
 use std::sync::Mutex;
 use std::sync::MutexGuard;
 use std::time::Duration;
+#[cfg(feature = "async")]
+use std::future::Future;
+#[cfg(feature = "async")]
+use std::pin::Pin;
+#[cfg(feature = "async")]
+use std::task::Context;
+#[cfg(feature = "async")]
+use std::task::Poll;
 
 use buffer::BufferAccess;
 use command_buffer::submit::SubmitAnyBuilder;
@@ -27,6 +35,8 @@ use sync::Fence;
 use sync::FlushError;
 use sync::GpuFuture;
 use sync::PipelineStages;
+#[cfg(feature = "async")]
+use sync::fence_future::wake_when_signaled;
 
 /// Builds a new fence signal future.
 #[inline]
@@ -37,7 +47,7 @@ pub fn then_signal_fence<F>(future: F, behavior: FenceSignalFutureBehavior) -> F
 
     assert!(future.queue().is_some()); // TODO: document
 
-    let fence = Fence::from_pool(device.clone()).unwrap();
+    let fence = Arc::new(Fence::from_pool(device.clone()).unwrap());
     FenceSignalFuture {
         device: device,
         state: Mutex::new(FenceSignalFutureState::Pending(future, fence)),
@@ -87,6 +97,10 @@ pub enum FenceSignalFutureBehavior {
 /// // Later you can wait until you reach the point of `fence_signal`:
 /// fence_signal.wait(None).unwrap();
 /// ```
+///
+/// With the `async` feature, this type also implements `std::future::Future`. Awaiting it flushes
+/// the future if necessary and completes once the fence is signaled, without blocking the
+/// current thread. The fence is waited upon by a background thread.
 #[must_use = "Dropping this object will immediately block the thread until the GPU has finished \
               processing the submission"]
 pub struct FenceSignalFuture<F>
@@ -104,17 +118,17 @@ pub struct FenceSignalFuture<F>
 // been dropped).
 enum FenceSignalFutureState<F> {
     // Newly-created. Not submitted yet.
-    Pending(F, Fence),
+    Pending(F, Arc<Fence>),
 
     // Partially submitted to the queue. Only happens in situations where submitting requires two
     // steps, and when the first step succeeded while the second step failed.
     //
     // Note that if there's ever a submit operation that needs three steps we will need to rework
     // this code, as it was designed for two-step operations only.
-    PartiallyFlushed(F, Fence),
+    PartiallyFlushed(F, Arc<Fence>),
 
     // Submitted to the queue.
-    Flushed(F, Fence),
+    Flushed(F, Arc<Fence>),
 
     // The submission is finished. The previous future and the fence have been cleaned.
     Cleaned,
@@ -310,6 +324,46 @@ impl<F> FenceSignalFutureState<F> {
     }
 }
 
+#[cfg(feature = "async")]
+impl<F> Future for FenceSignalFuture<F>
+    where F: GpuFuture
+{
+    type Output = Result<(), FlushError>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context) -> Poll<Self::Output> {
+        let mut state = self.state.lock().unwrap();
+
+        if let Err(err) = self.flush_impl(&mut state) {
+            return Poll::Ready(Err(err));
+        }
+
+        let fence = match *state {
+            FenceSignalFutureState::Flushed(_, ref fence) => fence.clone(),
+            FenceSignalFutureState::Cleaned => return Poll::Ready(Ok(())),
+            _ => unreachable!(),
+        };
+
+        match fence.ready() {
+            Ok(true) => {
+                // Same as in `wait`, the resources of the previous submissions are released.
+                if let FenceSignalFutureState::Flushed(previous, _) =
+                    mem::replace(&mut *state, FenceSignalFutureState::Cleaned)
+                {
+                    unsafe {
+                        previous.signal_finished();
+                    }
+                }
+                Poll::Ready(Ok(()))
+            },
+            Ok(false) => {
+                wake_when_signaled(fence, cx.waker().clone());
+                Poll::Pending
+            },
+            Err(err) => Poll::Ready(Err(FlushError::OomError(err))),
+        }
+    }
+}
+
 unsafe impl<F> GpuFuture for FenceSignalFuture<F>
     where F: GpuFuture
 {
@@ -31,16 +31,22 @@ use swapchain::Swapchain;
 use sync::AccessFlagBits;
 use sync::FenceWaitError;
 use sync::PipelineStages;
+use sync::TimelineSemaphore;
+use sync::TimelineSemaphoreError;
 
 pub use self::fence_signal::{FenceSignalFuture, FenceSignalFutureBehavior};
 pub use self::join::JoinFuture;
 pub use self::now::{NowFuture, now};
 pub use self::semaphore_signal::SemaphoreSignalFuture;
+pub use self::timeline_signal::TimelineSignalFuture;
+pub use self::timeline_wait::TimelineWaitFuture;
 
 mod now;
 mod fence_signal;
 mod join;
 mod semaphore_signal;
+mod timeline_signal;
+mod timeline_wait;
 
 /// Represents an event that will happen on the GPU in the future.
 ///
@@ -229,6 +235,46 @@ pub unsafe trait GpuFuture: DeviceOwned {
         Ok(f)
     }
 
+    /// Sets the counter of a timeline semaphore to `value` after this future. Returns another
+    /// future that represents the signal.
+    ///
+    /// Contrary to `then_signal_semaphore`, the CPU can wait for the returned future with
+    /// `TimelineSignalFuture::wait`, and the same semaphore can be reused for all submissions.
+    ///
+    /// Returns an error if `value` isn't greater than the current value of the counter and than
+    /// the values of the pending signal operations of the semaphore.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the semaphore doesn't belong to the same device as this future.
+    #[inline]
+    fn then_signal_timeline(self, semaphore: Arc<TimelineSemaphore>, value: u64)
+                            -> Result<TimelineSignalFuture<Self>, TimelineSemaphoreError>
+        where Self: Sized
+    {
+        timeline_signal::then_signal_timeline(self, semaphore, value)
+    }
+
+    /// Waits on `queue` until the counter of a timeline semaphore reaches `value` after this
+    /// future. Returns another future that represents the moment when the counter has reached
+    /// the value.
+    ///
+    /// The counter can be increased by the CPU with `TimelineSemaphore::signal`, or by another
+    /// submission with `then_signal_timeline`.
+    ///
+    /// # Panic
+    ///
+    /// - Panics if the semaphore doesn't belong to the same device as this future.
+    /// - Panics if this future doesn't allow changing queue and `queue` isn't the queue of this
+    ///   future.
+    #[inline]
+    fn then_wait_timeline(self, queue: Arc<Queue>, semaphore: Arc<TimelineSemaphore>, value: u64)
+                          -> TimelineWaitFuture<Self>
+        where Self: Sized
+    {
+        timeline_wait::then_wait_timeline(self, queue, semaphore, value)
+    }
+
     /// Presents a swapchain image after this future.
     ///
     /// You should only ever do this indirectly after a `SwapchainAcquireFuture` of the same image,
@@ -331,6 +377,15 @@ pub enum AccessError {
 
     /// Trying to use a swapchain image without depending on a corresponding acquire image future.
     SwapchainImageAcquireOnly,
+
+    /// Trying to use a resource from a queue family that doesn't own it. The ownership of the
+    /// resource must first be released to this queue family.
+    WrongQueueFamily {
+        /// The queue family that owns the resource.
+        owner: u32,
+        /// The queue family of the queue that tried to use the resource.
+        requested: u32,
+    },
 }
 
 impl error::Error for AccessError {
@@ -357,6 +412,9 @@ impl error::Error for AccessError {
                 "trying to use a swapchain image without depending on a corresponding acquire \
                  image future"
             },
+            AccessError::WrongQueueFamily { .. } => {
+                "trying to use a resource from a queue family that doesn't own it"
+            },
         }
     }
 }
@@ -504,6 +562,19 @@ impl From<SubmitBindSparseError> for FlushError {
     }
 }
 
+impl From<TimelineSemaphoreError> for FlushError {
+    #[inline]
+    fn from(err: TimelineSemaphoreError) -> FlushError {
+        match err {
+            TimelineSemaphoreError::OomError(err) => FlushError::OomError(err),
+            TimelineSemaphoreError::Timeout => FlushError::Timeout,
+            TimelineSemaphoreError::DeviceLost => FlushError::DeviceLost,
+            TimelineSemaphoreError::ValueNotIncreasing |
+            TimelineSemaphoreError::SignalPending => unreachable!(),
+        }
+    }
+}
+
 impl From<FenceWaitError> for FlushError {
     #[inline]
     fn from(err: FenceWaitError) -> FlushError {
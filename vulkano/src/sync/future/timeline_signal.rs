@@ -0,0 +1,242 @@
+// Copyright (c) 2026 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+use buffer::BufferAccess;
+use command_buffer::submit::SubmitAnyBuilder;
+use command_buffer::submit::SubmitCommandBufferBuilder;
+use device::Device;
+use device::DeviceOwned;
+use device::Queue;
+use image::ImageAccess;
+use image::ImageLayout;
+use sync::AccessCheckError;
+use sync::AccessFlagBits;
+use sync::FlushError;
+use sync::GpuFuture;
+use sync::PipelineStages;
+use sync::TimelineSemaphore;
+use sync::TimelineSemaphoreError;
+
+/// Builds a new timeline semaphore signal future.
+///
+/// Returns an error if `value` isn't greater than the current value of the counter and than the
+/// values of the pending signal operations of the semaphore.
+///
+/// # Panic
+///
+/// - Panics if the semaphore doesn't belong to the same device as the future.
+#[inline]
+pub fn then_signal_timeline<F>(future: F, semaphore: Arc<TimelineSemaphore>, value: u64)
+                               -> Result<TimelineSignalFuture<F>, TimelineSemaphoreError>
+    where F: GpuFuture
+{
+    assert!(future.queue().is_some()); // TODO: document
+    assert!(future.device() == semaphore.device());
+    semaphore.reserve_signal_value(value)?;
+
+    Ok(TimelineSignalFuture {
+           previous: future,
+           semaphore,
+           value,
+           wait_submitted: Mutex::new(false),
+           finished: AtomicBool::new(false),
+       })
+}
+
+/// Represents the counter of a timeline semaphore being set to a value after a previous event.
+///
+/// Contrary to a `SemaphoreSignalFuture`, the CPU can wait for this future to be signaled with
+/// the `wait` method.
+#[must_use = "Dropping this object will immediately block the thread until the GPU has finished \
+              processing the submission"]
+pub struct TimelineSignalFuture<F>
+    where F: GpuFuture
+{
+    previous: F,
+    semaphore: Arc<TimelineSemaphore>,
+    value: u64,
+    // True if the signaling command has already been submitted.
+    // If flush is called multiple times, we want to block so that only one flushing is executed.
+    // Therefore we use a `Mutex<bool>` and not an `AtomicBool`.
+    wait_submitted: Mutex<bool>,
+    finished: AtomicBool,
+}
+
+impl<F> TimelineSignalFuture<F>
+    where F: GpuFuture
+{
+    /// Returns the semaphore that is signaled by this future.
+    #[inline]
+    pub fn semaphore(&self) -> &Arc<TimelineSemaphore> {
+        &self.semaphore
+    }
+
+    /// Returns the value the counter of the semaphore is set to when this future is signaled.
+    #[inline]
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+
+    /// Flushes the future if necessary, then blocks the current thread until the counter of the
+    /// semaphore reaches the value of this future.
+    ///
+    /// If `timeout` is `None`, waits forever.
+    pub fn wait(&self, timeout: Option<Duration>) -> Result<(), FlushError> {
+        self.flush()?;
+        self.semaphore.wait(self.value, timeout)?;
+        Ok(())
+    }
+
+    /// Returns true if the counter of the semaphore has reached the value of this future.
+    ///
+    /// Always returns false if the future hasn't been flushed yet.
+    pub fn is_signaled(&self) -> Result<bool, TimelineSemaphoreError> {
+        if !*self.wait_submitted.lock().unwrap() {
+            return Ok(false);
+        }
+
+        Ok(self.semaphore.counter_value()? >= self.value)
+    }
+}
+
+unsafe impl<F> GpuFuture for TimelineSignalFuture<F>
+    where F: GpuFuture
+{
+    #[inline]
+    fn cleanup_finished(&mut self) {
+        self.previous.cleanup_finished();
+
+        if !*self.finished.get_mut() {
+            if let Ok(true) = self.is_signaled() {
+                unsafe {
+                    self.previous.signal_finished();
+                }
+                *self.finished.get_mut() = true;
+            }
+        }
+    }
+
+    #[inline]
+    unsafe fn build_submission(&self) -> Result<SubmitAnyBuilder, FlushError> {
+        // Submissions that come after this one on the same queue are executed after it, so
+        // flushing is enough.
+        self.flush()?;
+        Ok(SubmitAnyBuilder::Empty)
+    }
+
+    fn flush(&self) -> Result<(), FlushError> {
+        unsafe {
+            let mut wait_submitted = self.wait_submitted.lock().unwrap();
+
+            if *wait_submitted {
+                return Ok(());
+            }
+
+            let queue = self.previous.queue().unwrap().clone();
+
+            match self.previous.build_submission()? {
+                SubmitAnyBuilder::Empty => {
+                    let mut builder = SubmitCommandBufferBuilder::new();
+                    builder.add_signal_timeline_semaphore(&self.semaphore, self.value);
+                    builder.submit(&queue)?;
+                },
+                SubmitAnyBuilder::SemaphoresWait(sem) => {
+                    let mut builder: SubmitCommandBufferBuilder = sem.into();
+                    builder.add_signal_timeline_semaphore(&self.semaphore, self.value);
+                    builder.submit(&queue)?;
+                },
+                SubmitAnyBuilder::CommandBuffer(mut builder) => {
+                    builder.add_signal_timeline_semaphore(&self.semaphore, self.value);
+                    builder.submit(&queue)?;
+                },
+                SubmitAnyBuilder::BindSparse(sparse) => {
+                    // The bind sparse builder can't signal timeline semaphores, so the signal is
+                    // submitted separately afterwards, as for a present.
+                    sparse.submit(&queue)?;
+                    let mut builder = SubmitCommandBufferBuilder::new();
+                    builder.add_signal_timeline_semaphore(&self.semaphore, self.value);
+                    builder.submit(&queue)?;
+                },
+                SubmitAnyBuilder::QueuePresent(present) => {
+                    present.submit(&queue)?;
+                    let mut builder = SubmitCommandBufferBuilder::new();
+                    builder.add_signal_timeline_semaphore(&self.semaphore, self.value);
+                    builder.submit(&queue)?; // FIXME: problematic because if we return an error and flush() is called again, then we'll submit the present twice
+                },
+            };
+
+            // Only write `true` here in order to try again next time if an error occurs.
+            *wait_submitted = true;
+            Ok(())
+        }
+    }
+
+    #[inline]
+    unsafe fn signal_finished(&self) {
+        debug_assert!(*self.wait_submitted.lock().unwrap());
+        self.finished.store(true, Ordering::SeqCst);
+        self.previous.signal_finished();
+    }
+
+    #[inline]
+    fn queue_change_allowed(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn queue(&self) -> Option<Arc<Queue>> {
+        self.previous.queue()
+    }
+
+    #[inline]
+    fn check_buffer_access(
+        &self, buffer: &dyn BufferAccess, exclusive: bool, queue: &Queue)
+        -> Result<Option<(PipelineStages, AccessFlagBits)>, AccessCheckError> {
+        self.previous.check_buffer_access(buffer, exclusive, queue)
+    }
+
+    #[inline]
+    fn check_image_access(&self, image: &dyn ImageAccess, layout: ImageLayout, exclusive: bool,
+                          queue: &Queue)
+                          -> Result<Option<(PipelineStages, AccessFlagBits)>, AccessCheckError> {
+        self.previous
+            .check_image_access(image, layout, exclusive, queue)
+    }
+}
+
+unsafe impl<F> DeviceOwned for TimelineSignalFuture<F>
+    where F: GpuFuture
+{
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.semaphore.device()
+    }
+}
+
+impl<F> Drop for TimelineSignalFuture<F>
+    where F: GpuFuture
+{
+    fn drop(&mut self) {
+        unsafe {
+            if !*self.finished.get_mut() {
+                // TODO: handle errors?
+                self.flush().unwrap();
+                // Block until the semaphore is signaled.
+                self.semaphore.wait(self.value, None).unwrap();
+                self.previous.signal_finished();
+            }
+        }
+    }
+}
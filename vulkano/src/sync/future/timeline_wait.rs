@@ -0,0 +1,197 @@
+// Copyright (c) 2026 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::atomic::AtomicBool;
+use std::sync::atomic::Ordering;
+
+use buffer::BufferAccess;
+use command_buffer::submit::SubmitAnyBuilder;
+use command_buffer::submit::SubmitCommandBufferBuilder;
+use device::Device;
+use device::DeviceOwned;
+use device::Queue;
+use image::ImageAccess;
+use image::ImageLayout;
+use sync::AccessCheckError;
+use sync::AccessFlagBits;
+use sync::FlushError;
+use sync::GpuFuture;
+use sync::PipelineStages;
+use sync::TimelineSemaphore;
+
+/// Builds a new timeline semaphore wait future.
+///
+/// # Panic
+///
+/// - Panics if the semaphore doesn't belong to the same device as the future.
+/// - Panics if the future doesn't allow changing queue and `queue` isn't the queue of the future.
+#[inline]
+pub fn then_wait_timeline<F>(future: F, queue: Arc<Queue>, semaphore: Arc<TimelineSemaphore>,
+                             value: u64)
+                             -> TimelineWaitFuture<F>
+    where F: GpuFuture
+{
+    assert!(future.device() == semaphore.device());
+    if !future.queue_change_allowed() {
+        assert!(future.queue().unwrap().is_same(&queue));
+    }
+
+    TimelineWaitFuture {
+        previous: future,
+        queue,
+        semaphore,
+        value,
+        submitted: Mutex::new(false),
+        finished: AtomicBool::new(false),
+    }
+}
+
+/// Represents a queue waiting until the counter of a timeline semaphore reaches a value after a
+/// previous event.
+#[must_use = "Dropping this object will immediately block the thread until the GPU has finished \
+              processing the submission"]
+pub struct TimelineWaitFuture<F>
+    where F: GpuFuture
+{
+    previous: F,
+    queue: Arc<Queue>,
+    semaphore: Arc<TimelineSemaphore>,
+    value: u64,
+    // True if the wait has already been submitted.
+    // If flush is called multiple times, we want to block so that only one flushing is executed.
+    // Therefore we use a `Mutex<bool>` and not an `AtomicBool`.
+    submitted: Mutex<bool>,
+    finished: AtomicBool,
+}
+
+impl<F> TimelineWaitFuture<F>
+    where F: GpuFuture
+{
+    /// Returns the semaphore that is waited upon by this future.
+    #[inline]
+    pub fn semaphore(&self) -> &Arc<TimelineSemaphore> {
+        &self.semaphore
+    }
+
+    /// Returns the value the counter of the semaphore must reach for this future to be signaled.
+    #[inline]
+    pub fn value(&self) -> u64 {
+        self.value
+    }
+}
+
+unsafe impl<F> GpuFuture for TimelineWaitFuture<F>
+    where F: GpuFuture
+{
+    #[inline]
+    fn cleanup_finished(&mut self) {
+        self.previous.cleanup_finished();
+    }
+
+    unsafe fn build_submission(&self) -> Result<SubmitAnyBuilder, FlushError> {
+        let stages = PipelineStages {
+            all_commands: true,
+            ..PipelineStages::none()
+        };
+
+        // The command buffers of a previous submission must not wait on the semaphore, so they
+        // are submitted first.
+        let mut builder = match self.previous.build_submission()? {
+            SubmitAnyBuilder::Empty => SubmitCommandBufferBuilder::new(),
+            SubmitAnyBuilder::SemaphoresWait(sem) => sem.into(),
+            SubmitAnyBuilder::CommandBuffer(_) |
+            SubmitAnyBuilder::BindSparse(_) |
+            SubmitAnyBuilder::QueuePresent(_) => {
+                self.previous.flush()?;
+                SubmitCommandBufferBuilder::new()
+            },
+        };
+
+        builder.add_wait_timeline_semaphore(&self.semaphore, self.value, stages);
+        Ok(SubmitAnyBuilder::CommandBuffer(builder))
+    }
+
+    fn flush(&self) -> Result<(), FlushError> {
+        unsafe {
+            let mut submitted = self.submitted.lock().unwrap();
+            if *submitted {
+                return Ok(());
+            }
+
+            match self.build_submission()? {
+                SubmitAnyBuilder::CommandBuffer(builder) => {
+                    builder.submit(&self.queue)?;
+                },
+                _ => unreachable!(),
+            };
+
+            // Only write `true` here in order to try again next time if we failed to submit.
+            *submitted = true;
+            Ok(())
+        }
+    }
+
+    #[inline]
+    unsafe fn signal_finished(&self) {
+        self.finished.store(true, Ordering::SeqCst);
+        self.previous.signal_finished();
+    }
+
+    #[inline]
+    fn queue_change_allowed(&self) -> bool {
+        false
+    }
+
+    #[inline]
+    fn queue(&self) -> Option<Arc<Queue>> {
+        Some(self.queue.clone())
+    }
+
+    #[inline]
+    fn check_buffer_access(
+        &self, buffer: &dyn BufferAccess, exclusive: bool, queue: &Queue)
+        -> Result<Option<(PipelineStages, AccessFlagBits)>, AccessCheckError> {
+        self.previous.check_buffer_access(buffer, exclusive, queue)
+    }
+
+    #[inline]
+    fn check_image_access(&self, image: &dyn ImageAccess, layout: ImageLayout, exclusive: bool,
+                          queue: &Queue)
+                          -> Result<Option<(PipelineStages, AccessFlagBits)>, AccessCheckError> {
+        self.previous
+            .check_image_access(image, layout, exclusive, queue)
+    }
+}
+
+unsafe impl<F> DeviceOwned for TimelineWaitFuture<F>
+    where F: GpuFuture
+{
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        self.semaphore.device()
+    }
+}
+
+impl<F> Drop for TimelineWaitFuture<F>
+    where F: GpuFuture
+{
+    fn drop(&mut self) {
+        unsafe {
+            if !*self.finished.get_mut() {
+                // TODO: handle errors?
+                self.flush().unwrap();
+                // Block until the queue finished.
+                self.queue.wait().unwrap();
+                self.previous.signal_finished();
+            }
+        }
+    }
+}
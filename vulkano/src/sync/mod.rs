@@ -109,6 +109,8 @@ use std::sync::Arc;
 pub use self::event::Event;
 pub use self::fence::Fence;
 pub use self::fence::FenceWaitError;
+#[cfg(feature = "async")]
+pub use self::fence_future::FenceFuture;
 pub use self::future::now;
 pub use self::future::AccessCheckError;
 pub use self::future::AccessError;
@@ -118,15 +120,23 @@ pub use self::future::GpuFuture;
 pub use self::future::JoinFuture;
 pub use self::future::NowFuture;
 pub use self::future::SemaphoreSignalFuture;
+pub use self::future::TimelineSignalFuture;
+pub use self::future::TimelineWaitFuture;
 pub use self::pipeline::AccessFlagBits;
 pub use self::pipeline::PipelineStages;
 pub use self::semaphore::Semaphore;
+pub use self::timeline_semaphore::TimelineSemaphore;
+pub use self::timeline_semaphore::TimelineSemaphoreCreationError;
+pub use self::timeline_semaphore::TimelineSemaphoreError;
 
 mod event;
 mod fence;
+#[cfg(feature = "async")]
+pub(crate) mod fence_future;
 mod future;
 mod pipeline;
 mod semaphore;
+mod timeline_semaphore;
 
 /// Declares in which queue(s) a resource can be used.
 ///
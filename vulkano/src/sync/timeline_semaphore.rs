@@ -0,0 +1,439 @@
+// Copyright (c) 2026 The vulkano developers
+// Licensed under the Apache License, Version 2.0
+// <LICENSE-APACHE or
+// http://www.apache.org/licenses/LICENSE-2.0> or the MIT
+// license <LICENSE-MIT or http://opensource.org/licenses/MIT>,
+// at your option. All files in the project carrying such
+// notice may not be copied, modified, or distributed except
+// according to those terms.
+
+use std::error;
+use std::fmt;
+use std::mem::MaybeUninit;
+use std::ptr;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+
+use Error;
+use OomError;
+use Success;
+use VulkanObject;
+use check_errors;
+use device::Device;
+use device::DeviceOwned;
+use version::Version;
+use vk;
+
+/// A semaphore whose state is a monotonically increasing 64-bit counter.
+///
+/// Contrary to a regular `Semaphore`, a timeline semaphore can be signaled and waited upon by
+/// both the GPU and the CPU. A submission can wait until the counter reaches a given value and
+/// can set the counter to a given value when it ends. This makes it possible to use a single
+/// timeline semaphore to express the dependencies of many submissions, instead of a fence and
+/// a semaphore per submission.
+///
+/// Requires the device to support Vulkan 1.2 or to have the `khr_timeline_semaphore` extension
+/// enabled, and the `timeline_semaphore` feature to be enabled.
+///
+/// # Example
+///
+/// ```
+/// use std::time::Duration;
+/// use vulkano::sync::TimelineSemaphore;
+/// # let device: std::sync::Arc<vulkano::device::Device> = return;
+///
+/// let semaphore = TimelineSemaphore::new(device.clone(), 0).unwrap();
+/// semaphore.signal(5).unwrap();
+/// semaphore.wait(3, Some(Duration::from_secs(1))).unwrap();
+/// assert_eq!(semaphore.counter_value().unwrap(), 5);
+/// ```
+#[derive(Debug)]
+pub struct TimelineSemaphore {
+    semaphore: vk::Semaphore,
+    device: Arc<Device>,
+    // The highest value that the semaphore has been signaled with or will be signaled with by a
+    // submission that vulkano knows about.
+    last_signal_value: Mutex<u64>,
+}
+
+impl TimelineSemaphore {
+    /// Builds a new timeline semaphore whose counter starts at `initial_value`.
+    pub fn new(device: Arc<Device>, initial_value: u64)
+               -> Result<TimelineSemaphore, TimelineSemaphoreCreationError> {
+        if !(device.api_version() >= Version::V1_2 ||
+                 device.loaded_extensions().khr_timeline_semaphore)
+        {
+            return Err(TimelineSemaphoreCreationError::ExtensionNotEnabled);
+        }
+
+        if !device.enabled_features().timeline_semaphore {
+            return Err(TimelineSemaphoreCreationError::FeatureNotEnabled);
+        }
+
+        let semaphore = unsafe {
+            let type_infos = vk::SemaphoreTypeCreateInfoKHR {
+                sType: vk::STRUCTURE_TYPE_SEMAPHORE_TYPE_CREATE_INFO_KHR,
+                pNext: ptr::null(),
+                semaphoreType: vk::SEMAPHORE_TYPE_TIMELINE_KHR,
+                initialValue: initial_value,
+            };
+
+            let infos = vk::SemaphoreCreateInfo {
+                sType: vk::STRUCTURE_TYPE_SEMAPHORE_CREATE_INFO,
+                pNext: &type_infos as *const _ as *const _,
+                flags: 0, // reserved
+            };
+
+            let vk = device.pointers();
+            let mut output = MaybeUninit::uninit();
+            check_errors(vk.CreateSemaphore(device.internal_object(),
+                                            &infos,
+                                            ptr::null(),
+                                            output.as_mut_ptr()))?;
+            output.assume_init()
+        };
+
+        Ok(TimelineSemaphore {
+               semaphore,
+               device,
+               last_signal_value: Mutex::new(initial_value),
+           })
+    }
+
+    /// Returns the current value of the counter.
+    pub fn counter_value(&self) -> Result<u64, TimelineSemaphoreError> {
+        unsafe {
+            let vk = self.device.pointers();
+            let mut output = MaybeUninit::uninit();
+            check_errors(vk.GetSemaphoreCounterValueKHR(self.device.internal_object(),
+                                                        self.semaphore,
+                                                        output.as_mut_ptr()))?;
+            Ok(output.assume_init())
+        }
+    }
+
+    /// Sets the counter to `value` from the CPU.
+    ///
+    /// Returns an error if `value` isn't greater than the current value of the counter, or if a
+    /// signal operation submitted to the GPU by vulkano hasn't been executed yet.
+    pub fn signal(&self, value: u64) -> Result<(), TimelineSemaphoreError> {
+        // The lock is held until the end of the function, so that no GPU signal operation can be
+        // reserved in-between the checks and the actual signal.
+        let mut last_signal_value = self.last_signal_value.lock().unwrap();
+
+        if value <= *last_signal_value {
+            return Err(TimelineSemaphoreError::ValueNotIncreasing);
+        }
+
+        // Vulkan requires `value` to be lower than the values of the pending signal operations.
+        // Since vulkano only knows the highest of these values, refuse to signal while any of
+        // them hasn't been executed.
+        if self.counter_value()? < *last_signal_value {
+            return Err(TimelineSemaphoreError::SignalPending);
+        }
+
+        unsafe {
+            let infos = vk::SemaphoreSignalInfoKHR {
+                sType: vk::STRUCTURE_TYPE_SEMAPHORE_SIGNAL_INFO_KHR,
+                pNext: ptr::null(),
+                semaphore: self.semaphore,
+                value,
+            };
+
+            let vk = self.device.pointers();
+            check_errors(vk.SignalSemaphoreKHR(self.device.internal_object(), &infos))?;
+        }
+
+        // Only record the value once the signal has succeeded.
+        *last_signal_value = value;
+        Ok(())
+    }
+
+    /// Blocks the current thread until the counter is greater than or equal to `value`.
+    ///
+    /// If `timeout` is `None`, waits forever.
+    pub fn wait(&self, value: u64, timeout: Option<Duration>)
+                -> Result<(), TimelineSemaphoreError> {
+        unsafe {
+            let timeout_ns = if let Some(timeout) = timeout {
+                timeout
+                    .as_secs()
+                    .saturating_mul(1_000_000_000)
+                    .saturating_add(timeout.subsec_nanos() as u64)
+            } else {
+                u64::max_value()
+            };
+
+            let infos = vk::SemaphoreWaitInfoKHR {
+                sType: vk::STRUCTURE_TYPE_SEMAPHORE_WAIT_INFO_KHR,
+                pNext: ptr::null(),
+                flags: 0,
+                semaphoreCount: 1,
+                pSemaphores: &self.semaphore,
+                pValues: &value,
+            };
+
+            let vk = self.device.pointers();
+            let r = check_errors(vk.WaitSemaphoresKHR(self.device.internal_object(),
+                                                      &infos,
+                                                      timeout_ns))?;
+
+            match r {
+                Success::Success => Ok(()),
+                Success::Timeout => Err(TimelineSemaphoreError::Timeout),
+                _ => unreachable!(),
+            }
+        }
+    }
+
+    // Checks that `value` is greater than any value the semaphore has been or will be signaled
+    // with, and records it as the new highest value. Must be called before submitting a signal
+    // operation to the GPU.
+    pub(crate) fn reserve_signal_value(&self, value: u64) -> Result<(), TimelineSemaphoreError> {
+        let mut last_signal_value = self.last_signal_value.lock().unwrap();
+        if value <= *last_signal_value {
+            return Err(TimelineSemaphoreError::ValueNotIncreasing);
+        }
+        *last_signal_value = value;
+        Ok(())
+    }
+}
+
+unsafe impl DeviceOwned for TimelineSemaphore {
+    #[inline]
+    fn device(&self) -> &Arc<Device> {
+        &self.device
+    }
+}
+
+unsafe impl VulkanObject for TimelineSemaphore {
+    type Object = vk::Semaphore;
+
+    const TYPE: vk::ObjectType = vk::OBJECT_TYPE_SEMAPHORE;
+
+    #[inline]
+    fn internal_object(&self) -> vk::Semaphore {
+        self.semaphore
+    }
+}
+
+impl Drop for TimelineSemaphore {
+    #[inline]
+    fn drop(&mut self) {
+        unsafe {
+            let vk = self.device.pointers();
+            vk.DestroySemaphore(self.device.internal_object(), self.semaphore, ptr::null());
+        }
+    }
+}
+
+/// Error that can happen when creating a timeline semaphore.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TimelineSemaphoreCreationError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// The device doesn't support Vulkan 1.2 and the `khr_timeline_semaphore` extension isn't
+    /// enabled.
+    ExtensionNotEnabled,
+
+    /// The `timeline_semaphore` feature isn't enabled on the device.
+    FeatureNotEnabled,
+}
+
+impl error::Error for TimelineSemaphoreCreationError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            TimelineSemaphoreCreationError::OomError(_) => "not enough memory available",
+            TimelineSemaphoreCreationError::ExtensionNotEnabled => {
+                "the `khr_timeline_semaphore` extension isn't enabled"
+            },
+            TimelineSemaphoreCreationError::FeatureNotEnabled => {
+                "the `timeline_semaphore` feature isn't enabled"
+            },
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&dyn error::Error> {
+        match *self {
+            TimelineSemaphoreCreationError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for TimelineSemaphoreCreationError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
+
+impl From<Error> for TimelineSemaphoreCreationError {
+    #[inline]
+    fn from(err: Error) -> TimelineSemaphoreCreationError {
+        match err {
+            err @ Error::OutOfHostMemory => {
+                TimelineSemaphoreCreationError::OomError(OomError::from(err))
+            },
+            err @ Error::OutOfDeviceMemory => {
+                TimelineSemaphoreCreationError::OomError(OomError::from(err))
+            },
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
+
+/// Error that can happen when signaling, waiting upon or querying a timeline semaphore.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum TimelineSemaphoreError {
+    /// Not enough memory.
+    OomError(OomError),
+
+    /// The specified timeout wasn't long enough.
+    Timeout,
+
+    /// The device has been lost.
+    DeviceLost,
+
+    /// The value to signal isn't greater than the current value of the counter or than the
+    /// value of a pending signal operation.
+    ValueNotIncreasing,
+
+    /// Tried to signal the semaphore from the CPU while a signal operation submitted to the GPU
+    /// hasn't been executed yet.
+    SignalPending,
+}
+
+impl error::Error for TimelineSemaphoreError {
+    #[inline]
+    fn description(&self) -> &str {
+        match *self {
+            TimelineSemaphoreError::OomError(_) => "not enough memory available",
+            TimelineSemaphoreError::Timeout => "the timeout has been reached",
+            TimelineSemaphoreError::DeviceLost => "the device was lost",
+            TimelineSemaphoreError::ValueNotIncreasing => {
+                "the value to signal isn't greater than the current or pending values"
+            },
+            TimelineSemaphoreError::SignalPending => {
+                "tried to signal the semaphore from the CPU while a signal operation submitted \
+                 to the GPU is pending"
+            },
+        }
+    }
+
+    #[inline]
+    fn cause(&self) -> Option<&dyn error::Error> {
+        match *self {
+            TimelineSemaphoreError::OomError(ref err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for TimelineSemaphoreError {
+    #[inline]
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> Result<(), fmt::Error> {
+        write!(fmt, "{}", error::Error::description(self))
+    }
+}
+
+impl From<Error> for TimelineSemaphoreError {
+    #[inline]
+    fn from(err: Error) -> TimelineSemaphoreError {
+        match err {
+            err @ Error::OutOfHostMemory => TimelineSemaphoreError::OomError(OomError::from(err)),
+            err @ Error::OutOfDeviceMemory => {
+                TimelineSemaphoreError::OomError(OomError::from(err))
+            },
+            Error::DeviceLost => TimelineSemaphoreError::DeviceLost,
+            _ => panic!("unexpected error: {:?}", err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::Duration;
+    use sync::FlushError;
+    use sync::GpuFuture;
+    use sync::TimelineSemaphore;
+    use sync::TimelineSemaphoreCreationError;
+    use sync::TimelineSemaphoreError;
+    use sync::now;
+
+    #[test]
+    fn extension_not_enabled() {
+        let (device, _) = gfx_dev_and_queue!();
+
+        match TimelineSemaphore::new(device, 0) {
+            Err(TimelineSemaphoreCreationError::ExtensionNotEnabled) => (),
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn cpu_signal_increasing() {
+        let (device, _) = gfx_dev_and_queue!(extensions: [khr_timeline_semaphore],
+                                             timeline_semaphore);
+        let semaphore = TimelineSemaphore::new(device, 2).unwrap();
+
+        assert_eq!(semaphore.signal(2), Err(TimelineSemaphoreError::ValueNotIncreasing));
+        semaphore.signal(5).unwrap();
+        assert_eq!(semaphore.signal(4), Err(TimelineSemaphoreError::ValueNotIncreasing));
+        assert_eq!(semaphore.counter_value().unwrap(), 5);
+    }
+
+    #[test]
+    fn cpu_signal_while_gpu_signal_pending() {
+        let (device, queue) = gfx_dev_and_queue!(extensions: [khr_timeline_semaphore],
+                                                 timeline_semaphore);
+        let semaphore = Arc::new(TimelineSemaphore::new(device.clone(), 0).unwrap());
+
+        // The GPU signal operation is reserved, but not submitted yet.
+        let future = now(device.clone())
+            .then_wait_timeline(queue, semaphore.clone(), 0)
+            .then_signal_timeline(semaphore.clone(), 3)
+            .unwrap();
+        assert_eq!(semaphore.signal(1), Err(TimelineSemaphoreError::SignalPending));
+        assert_eq!(semaphore.signal(3), Err(TimelineSemaphoreError::ValueNotIncreasing));
+
+        match now(device).then_signal_timeline(semaphore.clone(), 2) {
+            Err(TimelineSemaphoreError::ValueNotIncreasing) => (),
+            _ => panic!(),
+        }
+
+        future.wait(Some(Duration::from_secs(5))).unwrap();
+        assert_eq!(semaphore.counter_value().unwrap(), 3);
+        semaphore.signal(4).unwrap();
+    }
+
+    #[test]
+    fn gpu_waits_for_cpu_signal() {
+        let (device, queue) = gfx_dev_and_queue!(extensions: [khr_timeline_semaphore],
+                                                 timeline_semaphore);
+        let waited = Arc::new(TimelineSemaphore::new(device.clone(), 0).unwrap());
+        let signaled = Arc::new(TimelineSemaphore::new(device.clone(), 0).unwrap());
+
+        let future = now(device)
+            .then_wait_timeline(queue, waited.clone(), 2)
+            .then_signal_timeline(signaled.clone(), 1)
+            .unwrap();
+        future.flush().unwrap();
+
+        // The queue is blocked until the counter of `waited` reaches 2.
+        waited.signal(1).unwrap();
+        assert_eq!(future.wait(Some(Duration::from_millis(50))),
+                   Err(FlushError::Timeout));
+        assert_eq!(signaled.counter_value().unwrap(), 0);
+
+        waited.signal(2).unwrap();
+        future.wait(Some(Duration::from_secs(5))).unwrap();
+        assert!(future.is_signaled().unwrap());
+        assert_eq!(signaled.counter_value().unwrap(), 1);
+    }
+}
@@ -10,11 +10,21 @@
 #![cfg(test)]
 
 /// Creates an instance or returns if initialization fails.
+///
+/// The instance uses Vulkan 1.0, so that the functionality of extensions that were promoted to
+/// core later is only available if the extension is enabled.
 macro_rules! instance {
-    () => ({
+    () => (instance!(instance::InstanceExtensions::none()));
+
+    ($extensions:expr) => ({
         use instance;
 
-        match instance::Instance::new(None, &instance::InstanceExtensions::none(), None) {
+        let app_infos = instance::ApplicationInfo {
+            api_version: Some(instance::Version::V1_0),
+            .. Default::default()
+        };
+
+        match instance::Instance::new(Some(&app_infos), &$extensions, None) {
             Ok(i) => i,
             Err(_) => return
         }
@@ -22,14 +32,35 @@ macro_rules! instance {
 }
 
 /// Creates a device and a queue for graphics operations.
+///
+/// The device extensions listed after `extensions:` are enabled, along with the device and
+/// instance extensions they require.
 macro_rules! gfx_dev_and_queue {
-    ($($feature:ident),*) => ({
+    ($($feature:ident),*) => (gfx_dev_and_queue!(extensions: [] $(, $feature)*));
+
+    (extensions: [$($extension:ident),*] $(, $feature:ident)*) => ({
         use instance;
         use device::Device;
         use device::DeviceExtensions;
         use features::Features;
 
-        let instance = instance!();
+        #[allow(unused_mut)]
+        let mut extensions = DeviceExtensions {
+            $(
+                $extension: true,
+            )*
+            .. DeviceExtensions::none()
+        };
+
+        loop {
+            let with_requirements = extensions.union(&extensions.required_extensions());
+            if with_requirements == extensions {
+                break;
+            }
+            extensions = with_requirements;
+        }
+
+        let instance = instance!(extensions.required_instance_extensions());
 
         let physical = match instance::PhysicalDevice::enumerate(&instance).next() {
             Some(p) => p,
@@ -41,7 +72,12 @@ macro_rules! gfx_dev_and_queue {
             None => return
         };
 
-        let extensions = DeviceExtensions::none();
+        // If the physical device doesn't support the requested extensions, just return.
+        if DeviceExtensions::supported_by_device(physical).intersection(&extensions) !=
+            extensions
+        {
+            return;
+        }
 
         let features = Features {
             $(
@@ -59,6 +59,27 @@ impl Ord for Version {
 }
 
 impl Version {
+    /// Vulkan 1.0.
+    pub const V1_0: Version = Version {
+        major: 1,
+        minor: 0,
+        patch: 0,
+    };
+
+    /// Vulkan 1.1.
+    pub const V1_1: Version = Version {
+        major: 1,
+        minor: 1,
+        patch: 0,
+    };
+
+    /// Vulkan 1.2.
+    pub const V1_2: Version = Version {
+        major: 1,
+        minor: 2,
+        patch: 0,
+    };
+
     /// Turns a version number given by Vulkan into a `Version` struct.
     #[inline]
     pub fn from_vulkan_version(value: u32) -> Version {